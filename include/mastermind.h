@@ -0,0 +1,62 @@
+/*
+ * C API of mastermind_solver, see src/ffi.rs. Link against the shared or static library
+ * built by `cargo build --release`.
+ *
+ * Colors are numbered from 0, a code has `mastermind_fields` of them. Functions which can
+ * fail return MASTERMIND_OK or one of the error codes.
+ */
+
+#ifndef MASTERMIND_H
+#define MASTERMIND_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define MASTERMIND_OK 0
+/* A pointer was null, or a color or the feedback was out of range. */
+#define MASTERMIND_INVALID_ARGUMENT -1
+/* No code is consistent with the feedback, which was left out. */
+#define MASTERMIND_INCONSISTENT -2
+
+typedef struct MastermindGame MastermindGame;
+
+/* Starts a game of the preset with the given name, e.g. "classic". Returns NULL if there
+ * is no such preset. */
+MastermindGame *mastermind_game_new(const char *preset);
+
+/* Frees the game, NULL is ignored. */
+void mastermind_game_free(MastermindGame *game);
+
+/* The number of fields of a code. */
+size_t mastermind_fields(const MastermindGame *game);
+
+/* Writes the colors of the best guess for the feedback so far to `colors`. */
+int mastermind_next_guess(MastermindGame *game, uint32_t *colors);
+
+/* Adds the feedback to `guess`: the number of fields with the right color and the number
+ * of further right colors in the wrong field. */
+int mastermind_push_feedback(MastermindGame *game, const uint32_t *guess, uint32_t exact,
+                             uint32_t correct_color);
+
+/* The number of codes which are consistent with the feedback so far. */
+size_t mastermind_candidates_count(MastermindGame *game);
+
+/* Scores a guess by the number of codes which give each of the `partitions` feedbacks to
+ * it, out of `total` codes. counts[fields] is the number of codes which are the guess. */
+typedef double (*MastermindScoringFn)(const uint32_t *counts, size_t partitions, size_t total,
+                                      void *user_data);
+
+/* Scores the guesses by `scoring` instead of the strategy of the solver, or by the strategy
+ * again if `scoring` is NULL. Higher scores are better, they may be negative and NaN is
+ * the worst. `scoring` is called with `user_data` from several threads at once. */
+int mastermind_set_scoring(MastermindGame *game, MastermindScoringFn scoring, void *user_data);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,48 @@
+/*
+ * Interface of solver plugins for mastermind_solver, see src/plugin.rs. A plugin is a shared
+ * library exporting the functions below, which `simulate --compare NAME` plays against the
+ * codes like the strategies of the solver. It is found if it is given with `--plugin FILE`
+ * or lies in ~/.config/mastermind_solver/plugins.
+ *
+ * Colors are numbered from 0. A solver is only used from one thread at a time.
+ */
+
+#ifndef MASTERMIND_PLUGIN_H
+#define MASTERMIND_PLUGIN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The version of this interface, which mastermind_plugin_abi returns. */
+#define MASTERMIND_PLUGIN_ABI 1
+
+/* Returns MASTERMIND_PLUGIN_ABI. */
+uint32_t mastermind_plugin_abi(void);
+
+/* The name the plugin is compared under, which can't be the one of a strategy of the
+ * solver or contain commas. */
+const char *mastermind_plugin_name(void);
+
+/* A solver for codes of `fields` fields with `colors` colors, which may repeat if
+ * `duplicates` isn't 0. Returns NULL if the plugin doesn't play the game. */
+void *mastermind_plugin_new(size_t fields, uint32_t colors, int duplicates);
+
+/* Frees the solver. */
+void mastermind_plugin_free(void *solver);
+
+/* Writes the `fields` colors of the next guess to `guess` after `turns` turns, with the
+ * guesses of the turns one after the other in `guesses` and two numbers per turn in
+ * `feedback`: the fields with the right color and the further right colors in the wrong
+ * field. Returns 0, or anything else if the solver fails. */
+int mastermind_plugin_guess(void *solver, const uint32_t *guesses, const uint32_t *feedback,
+                            size_t turns, uint32_t *guess);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
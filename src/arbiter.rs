@@ -0,0 +1,663 @@
+//! Matches between codebreaking engines, which are programs speaking a line based protocol
+//! on their standard input and output, or the solver itself. Both play the same random
+//! codes one after the other; a game is won by breaking the code in fewer turns, and lost
+//! by not breaking it within the turns allowed, guessing something illegal, taking longer
+//! than the time allowed for a guess, or leaving the protocol.
+//!
+//! The arbiter starts an engine with `sh -c COMMAND` and talks to it like this, with
+//! colors numbered from 0. Lines an engine writes starting with `info` are ignored.
+//!
+//! ```text
+//! arbiter                  engine
+//! mastermind 1             name NAME       the version of the protocol, and the name the
+//!                                          engine is recorded under
+//! new FIELDS COLORS DUP    ok              a game, with repeated colors if DUP is 1;
+//!                                          `error MESSAGE` if the engine can't play it
+//! guess                    guess C C C C   the next guess
+//! feedback EXACT WHITE                     the pegs in the right and in wrong fields
+//! quit                                     before the engine is stopped
+//! ```
+//!
+//! An engine which took too long or went away is stopped and started again for the next
+//! game. The games of every match are appended to `results.csv` in the data directory.
+
+use std::{
+    fs::OpenOptions,
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    process::{Child, ChildStdin, Command, Stdio},
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    cli::Output, csv, json::Json, preset::Preset, random::Rng, strategy::Strategy, Entry, Guess,
+    Rules, SimpleGuesser, Solver, SolverOptions,
+};
+
+/// The version of the protocol, which the arbiter announces.
+pub const PROTOCOL: u32 = 1;
+/// How long an engine may take to start and tell its name, at least.
+const STARTUP: Duration = Duration::from_secs(10);
+
+/// A contestant of a match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Engine {
+    /// The solver with a strategy.
+    Solver(Strategy),
+    /// A shell command starting an engine.
+    Command(String),
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(strategy) => Ok(Engine::Solver(strategy)),
+            Err(_) if s.trim().is_empty() => Err("an engine needs a command".to_string()),
+            Err(_) => Ok(Engine::Command(s.to_string())),
+        }
+    }
+}
+
+/// A running engine.
+struct Process {
+    name: String,
+    child: Child,
+    stdin: ChildStdin,
+    /// The lines of the engine, read on a thread of their own so they can be waited for
+    /// with a timeout.
+    lines: Receiver<io::Result<String>>,
+}
+
+impl Process {
+    fn start(command: &str, time: Duration) -> Result<Self, String> {
+        let mut child = Command::new("sh")
+            .args(["-c", command])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|err| format!("can't start {command:?}: {err}"))?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut process = Self {
+            name: command.to_string(),
+            child,
+            stdin,
+            lines,
+        };
+        process.send(&format!("mastermind {PROTOCOL}"))?;
+        let reply = process.receive(time.max(STARTUP))?;
+        match reply.strip_prefix("name ") {
+            Some(name) if !name.trim().is_empty() && !name.contains(',') => {
+                process.name = name.trim().to_string();
+                Ok(process)
+            }
+            _ => Err(format!(
+                "{command:?} answered {reply:?} instead of `name NAME` without commas"
+            )),
+        }
+    }
+
+    fn send(&mut self, line: &str) -> Result<(), String> {
+        writeln!(self.stdin, "{line}")
+            .and_then(|()| self.stdin.flush())
+            .map_err(|err| format!("{} went away: {err}", self.name))
+    }
+
+    /// The next line of the engine which isn't `info`.
+    fn receive(&mut self, time: Duration) -> Result<String, String> {
+        let deadline = Instant::now() + time;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match self.lines.recv_timeout(timeout) {
+                Ok(Ok(line)) if line.starts_with("info") => {}
+                Ok(Ok(line)) => return Ok(line.trim().to_string()),
+                Ok(Err(err)) => return Err(format!("{} can't be read: {err}", self.name)),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!("{} took more than {time:?}", self.name))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(format!("{} went away", self.name))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// An engine of a match with what it needs between games.
+enum Player<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    Solver {
+        name: String,
+        guesser: Box<SimpleGuesser<FIELDS, COLORS, PARTITIONS>>,
+    },
+    Command {
+        command: String,
+        name: String,
+        /// Started again if it failed in the last game.
+        process: Option<Process>,
+    },
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>
+    Player<FIELDS, COLORS, PARTITIONS>
+{
+    fn new(
+        engine: &Engine,
+        rules: Rules,
+        options: &SolverOptions,
+        time: Duration,
+    ) -> Result<Self, String> {
+        Ok(match engine {
+            Engine::Solver(strategy) => Player::Solver {
+                name: format!("solver-{strategy}"),
+                guesser: Box::new(SimpleGuesser {
+                    strategy: *strategy,
+                    ..SimpleGuesser::with_options(rules, options)
+                }),
+            },
+            Engine::Command(command) => {
+                let process = Process::start(command, time)?;
+                Player::Command {
+                    command: command.clone(),
+                    name: process.name.clone(),
+                    process: Some(process),
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Player::Solver { name, .. } | Player::Command { name, .. } => name,
+        }
+    }
+
+    /// The turns the player needed to break `code`, or why it lost.
+    fn play(
+        &mut self,
+        rules: Rules,
+        code: Guess<FIELDS>,
+        max_turns: usize,
+        time: Duration,
+    ) -> Result<usize, String> {
+        match self {
+            Player::Solver { name, guesser } => {
+                let mut history = vec![];
+                while history.len() < max_turns {
+                    let start = Instant::now();
+                    let guess = guesser
+                        .guess(&history)
+                        .map_err(|err| err.to_string())?
+                        .guess;
+                    if start.elapsed() > time {
+                        return Err(format!("{name} took more than {time:?}"));
+                    }
+                    history.push(Entry {
+                        guess,
+                        evaluation: rules.evaluate(code, guess),
+                    });
+                    if guess == code {
+                        return Ok(history.len());
+                    }
+                }
+                Err(format!("{name} didn't break the code in {max_turns} turns"))
+            }
+            Player::Command {
+                command,
+                name,
+                process,
+            } => {
+                let mut running = match process.take() {
+                    Some(running) => running,
+                    None => Process::start(command, time)?,
+                };
+                match Self::play_engine(&mut running, rules, code, max_turns, time) {
+                    Ok(turns) => {
+                        *process = Some(running);
+                        Ok(turns)
+                    }
+                    Err(Failure::Game(message)) => {
+                        *process = Some(running);
+                        Err(format!("{name} {message}"))
+                    }
+                    // an engine out of step with the protocol is started again
+                    Err(Failure::Protocol(message)) => Err(message),
+                }
+            }
+        }
+    }
+
+    fn play_engine(
+        process: &mut Process,
+        rules: Rules,
+        code: Guess<FIELDS>,
+        max_turns: usize,
+        time: Duration,
+    ) -> Result<usize, Failure> {
+        let name = process.name.clone();
+        let protocol = Failure::Protocol;
+        process
+            .send(&format!("new {FIELDS} {COLORS} {}", rules.duplicates as u8))
+            .map_err(protocol)?;
+        match process
+            .receive(time.max(STARTUP))
+            .map_err(protocol)?
+            .as_str()
+        {
+            "ok" => {}
+            reply => match reply.strip_prefix("error") {
+                Some(message) => {
+                    return Err(Failure::Game(format!("can't play: {}", message.trim())))
+                }
+                None => {
+                    return Err(protocol(format!(
+                        "{name} answered {reply:?} instead of `ok`"
+                    )))
+                }
+            },
+        }
+        for turn in 1..=max_turns {
+            process.send("guess").map_err(protocol)?;
+            let reply = process.receive(time).map_err(protocol)?;
+            let guess: Guess<FIELDS> = reply
+                .strip_prefix("guess ")
+                .ok_or_else(|| protocol(format!("{name} answered {reply:?} instead of a guess")))?
+                .parse()
+                .map_err(|err| protocol(format!("{name} guessed {reply:?}: {err}")))?;
+            if !rules.is_legal_turn::<FIELDS, COLORS>(&guess) {
+                return Err(protocol(format!(
+                    "{name} guessed [{guess}], which isn't legal"
+                )));
+            }
+            let evaluation = rules.evaluate(code, guess);
+            process
+                .send(&format!(
+                    "feedback {} {}",
+                    evaluation.exact, evaluation.correct_color
+                ))
+                .map_err(protocol)?;
+            if guess == code {
+                return Ok(turn);
+            }
+        }
+        Err(Failure::Game(format!(
+            "didn't break the code in {max_turns} turns"
+        )))
+    }
+}
+
+/// Why an engine lost a game.
+enum Failure {
+    /// It played badly, said without its name.
+    Game(String),
+    /// It broke the protocol or the time limit, so it can't play on.
+    Protocol(String),
+}
+
+/// One code played by both engines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game<const FIELDS: usize> {
+    pub code: Guess<FIELDS>,
+    /// The turns each engine needed, or why it lost.
+    pub results: [Result<usize, String>; 2],
+}
+
+impl<const FIELDS: usize> Game<FIELDS> {
+    pub fn points(&self) -> f64 {
+        points(
+            self.results
+                .each_ref()
+                .map(|result| result.as_ref().ok().copied()),
+        )
+    }
+}
+
+/// The points of the first engine of a game with the turns both needed, `None` for a lost
+/// game: 1 for a win, 0.5 for a draw and 0 for a loss.
+pub fn points(turns: [Option<usize>; 2]) -> f64 {
+    match turns {
+        [Some(first), Some(second)] if first == second => 0.5,
+        [Some(first), Some(second)] => (first < second) as u8 as f64,
+        [Some(_), None] => 1.,
+        [None, Some(_)] => 0.,
+        [None, None] => 0.5,
+    }
+}
+
+/// Plays `games` random codes between the engines, the second being the solver with the
+/// default strategy if only one is given.
+#[allow(clippy::too_many_arguments)]
+pub fn run<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    engines: &[Engine],
+    games: usize,
+    seed: Option<u64>,
+    time: Duration,
+    max_turns: usize,
+    results: Option<PathBuf>,
+    output: Output,
+) -> Result<(), String> {
+    let second = engines
+        .get(1)
+        .cloned()
+        .unwrap_or(Engine::Solver(Strategy::default()));
+    let mut players = [
+        Player::<FIELDS, COLORS, PARTITIONS>::new(&engines[0], rules, options, time)?,
+        Player::new(&second, rules, options, time)?,
+    ];
+    if players[0].name() == players[1].name() {
+        // the results tell the engines apart by name
+        return Err(format!("both engines are named {:?}", players[0].name()));
+    }
+    let mut rng = seed.map_or_else(Rng::from_entropy, Rng::seeded);
+    let played_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let games: Vec<_> = (0..games)
+        .map(|_| {
+            let code = rules.random_code::<FIELDS, COLORS>(&mut rng);
+            let results = [0, 1].map(|player| players[player].play(rules, code, max_turns, time));
+            for (player, result) in players.iter().zip(&results) {
+                if let Err(err) = result {
+                    debug!("{} lost [{code}]: {err}", player.name());
+                }
+            }
+            Game { code, results }
+        })
+        .collect();
+    let names = players.each_ref().map(|player| player.name().to_string());
+    print(&names, &games, output);
+    let path = results.or_else(default_results);
+    if let Some(path) = path {
+        let preset = Preset::find(FIELDS, COLORS, rules)
+            .expect("games are played with the dimensions of a preset");
+        if let Err(err) = record(&path, played_at, preset, &names, &games) {
+            info!("can't record the results in {}: {err}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn print<const FIELDS: usize>(names: &[String; 2], games: &[Game<FIELDS>], output: Output) {
+    let points: f64 = games.iter().map(Game::points).sum();
+    let turns = |result: &Result<usize, String>| match result {
+        Ok(turns) => turns.to_string(),
+        Err(_) => "-".to_string(),
+    };
+    match output {
+        Output::Text => {
+            let codes: Vec<_> = games
+                .iter()
+                .map(|game| format!("[{}]", game.code))
+                .collect();
+            let code = codes
+                .iter()
+                .map(|code| code.chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(4);
+            let [first, second] = names.each_ref().map(|name| name.chars().count().max(5));
+            println!(
+                "{:>4}  {:<code$}  {:>first$}  {:>second$}",
+                "game", "code", names[0], names[1]
+            );
+            for (index, game) in games.iter().enumerate() {
+                println!(
+                    "{:>4}  {:<code$}  {:>first$}  {:>second$}",
+                    index + 1,
+                    codes[index],
+                    turns(&game.results[0]),
+                    turns(&game.results[1])
+                );
+                for result in &game.results {
+                    if let Err(err) = result {
+                        println!("      {err}");
+                    }
+                }
+            }
+            let wins = games.iter().filter(|game| game.points() == 1.).count();
+            let losses = games.iter().filter(|game| game.points() == 0.).count();
+            println!(
+                "{} {points} : {} {} ({wins} won, {} drawn, {losses} lost)",
+                names[0],
+                games.len() as f64 - points,
+                names[1],
+                games.len() - wins - losses
+            );
+        }
+        Output::Porcelain => {
+            for game in games {
+                println!(
+                    "{}\t{}\t{}",
+                    game.code.indices(),
+                    turns(&game.results[0]),
+                    turns(&game.results[1])
+                );
+            }
+        }
+        Output::Json => {
+            let lost = games.len() as f64 - points;
+            let games: Vec<_> = games
+                .iter()
+                .map(|game| {
+                    let results: Vec<_> = game
+                        .results
+                        .iter()
+                        .map(|result| match result {
+                            Ok(turns) => Json::object([("turns", (*turns).into())]),
+                            Err(err) => Json::object([("lost", err.as_str().into())]),
+                        })
+                        .collect();
+                    Json::object([("code", game.code.into()), ("results", results.into())])
+                })
+                .collect();
+            println!(
+                "{}",
+                Json::object([
+                    ("engines", names.to_vec().into()),
+                    ("games", games.into()),
+                    ("points", vec![points, lost].into()),
+                ])
+            );
+        }
+    }
+}
+
+/// Where the results of matches are recorded, next to the database of played games.
+pub fn default_results() -> Option<PathBuf> {
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+        })?;
+    Some(data_dir.join("mastermind_solver").join("results.csv"))
+}
+
+/// The columns of the results, one line per game. The turns are empty for a lost game.
+pub const COLUMNS: [&str; 6] = [
+    "played_at",
+    "preset",
+    "first",
+    "second",
+    "first_turns",
+    "second_turns",
+];
+
+/// Appends the games to the results at `path`, with the columns first in a new file.
+fn record<const FIELDS: usize>(
+    path: &std::path::Path,
+    played_at: u64,
+    preset: Preset,
+    names: &[String; 2],
+    games: &[Game<FIELDS>],
+) -> io::Result<()> {
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let new = file.metadata()?.len() == 0;
+    let mut writer = csv::Writer::new(io::BufWriter::new(file));
+    if new {
+        writer.record(COLUMNS)?;
+    }
+    for game in games {
+        let [first, second] = game
+            .results
+            .each_ref()
+            .map(|result| result.as_ref().map_or(String::new(), usize::to_string));
+        writer.record([
+            played_at.to_string(),
+            preset.name().to_string(),
+            names[0].clone(),
+            names[1].clone(),
+            first,
+            second,
+        ])?;
+    }
+    writer.into_inner().flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::max_gauss;
+
+    /// An engine guessing the codes of the mini preset one after the other.
+    fn walker() -> String {
+        let codes: Vec<_> = Preset::Mini
+            .rules()
+            .codes::<3, 4>()
+            .map(|code| code.indices())
+            .collect();
+        format!(
+            "while read command rest; do case $command in \
+                mastermind) echo 'info starting'; echo 'name walker' ;; \
+                new) set -- {}; echo ok ;; \
+                guess) echo \"guess $1 $2 $3\"; shift 3 ;; \
+                quit) exit ;; \
+            esac; done",
+            codes.join(" ")
+        )
+    }
+
+    #[test]
+    fn parse_engines() {
+        assert_eq!("knuth".parse(), Ok(Engine::Solver(Strategy::Knuth)));
+        assert_eq!(
+            "./engine --fast".parse(),
+            Ok(Engine::Command("./engine --fast".to_string()))
+        );
+        assert!(" ".parse::<Engine>().is_err());
+    }
+
+    #[test]
+    fn referee_engines() {
+        let rules = Preset::Mini.rules();
+        let time = Duration::from_secs(10);
+        let options = SolverOptions::default();
+        let mut walker = Player::<3, 4, { max_gauss(3) }>::new(
+            &Engine::Command(walker()),
+            rules,
+            &SolverOptions::default(),
+            time,
+        )
+        .unwrap();
+        assert_eq!(walker.name(), "walker");
+        let mut solver = Player::<3, 4, { max_gauss(3) }>::new(
+            &Engine::Solver(Strategy::Entropy),
+            rules,
+            &SolverOptions::default(),
+            time,
+        )
+        .unwrap();
+        assert_eq!(solver.name(), "solver-entropy");
+        // the walker needs as many turns as the code is far into the codes
+        for (index, code) in rules.codes::<3, 4>().enumerate().step_by(5) {
+            assert_eq!(walker.play(rules, code, 30, time), Ok(index + 1));
+            assert!(solver.play(rules, code, 30, time).is_ok());
+        }
+        let last = rules.codes::<3, 4>().last().unwrap();
+        assert_eq!(
+            walker.play(rules, last, 5, time),
+            Err("walker didn't break the code in 5 turns".to_string())
+        );
+        // every game starts the walk over
+        assert_eq!(walker.play(rules, last, 30, time), Ok(24));
+
+        let sleepy = "read line; echo 'name sleepy'; read line; echo ok; sleep 10";
+        let mut sleepy = Player::<3, 4, { max_gauss(3) }>::new(
+            &Engine::Command(sleepy.into()),
+            rules,
+            &options,
+            time,
+        )
+        .unwrap();
+        let start = Instant::now();
+        assert_eq!(
+            sleepy.play(rules, last, 5, Duration::from_millis(100)),
+            Err("sleepy took more than 100ms".to_string())
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+        let Player::Command { process, .. } = &sleepy else {
+            unreachable!()
+        };
+        assert!(process.is_none());
+        assert!(Player::<3, 4, { max_gauss(3) }>::new(
+            &Engine::Command("echo nameless".into()),
+            rules,
+            &options,
+            time
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn score_and_record_games() {
+        let code = Guess::new([0, 1, 2]);
+        let game = |first, second| Game {
+            code,
+            results: [first, second],
+        };
+        let lost = || Err("lost".to_string());
+        assert_eq!(game(Ok(3), Ok(4)).points(), 1.);
+        assert_eq!(game(Ok(4), Ok(4)).points(), 0.5);
+        assert_eq!(game(lost(), Ok(6)).points(), 0.);
+        assert_eq!(game(lost(), lost()).points(), 0.5);
+
+        let path = std::env::temp_dir().join(format!("results-{}.csv", std::process::id()));
+        let names = ["walker".to_string(), "solver-entropy".to_string()];
+        let games = [game(Ok(3), Ok(4)), game(lost(), Ok(2))];
+        record(&path, 7, Preset::Mini, &names, &games).unwrap();
+        record(&path, 8, Preset::Mini, &names, &games[..1]).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            written,
+            "played_at,preset,first,second,first_turns,second_turns\r\n\
+            7,mini,walker,solver-entropy,3,4\r\n\
+            7,mini,walker,solver-entropy,,2\r\n\
+            8,mini,walker,solver-entropy,3,4\r\n"
+        );
+    }
+}
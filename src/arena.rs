@@ -0,0 +1,103 @@
+//! A bump arena for searching the game tree: the candidate sets of all nodes on the
+//! current path live in one buffer and are freed together when the search backtracks,
+//! instead of every split allocating vectors of its own.
+
+use std::ops::Range;
+
+use crate::{Guess, Rules};
+
+pub struct Arena<T> {
+    items: Vec<T>,
+}
+
+impl<T: Copy> Arena<T> {
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Appends the items and returns where they are.
+    pub fn alloc(&mut self, items: impl IntoIterator<Item = T>) -> Range<usize> {
+        let start = self.items.len();
+        self.items.extend(items);
+        start..self.items.len()
+    }
+
+    pub fn get(&self, range: Range<usize>) -> &[T] {
+        &self.items[range]
+    }
+
+    /// Everything allocated after this mark is freed by `release`.
+    pub fn mark(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn release(&mut self, mark: usize) {
+        self.items.truncate(mark);
+    }
+}
+
+impl<const FIELDS: usize> Arena<Guess<FIELDS>> {
+    /// Splits the codes in `codes` by the feedback they give to `guess`. The parts are
+    /// allocated next to each other and are indexed by `Evaluation::to_u32`, the codes keep
+    /// their order within a part.
+    pub fn split<const PARTITIONS: usize>(
+        &mut self,
+        rules: Rules,
+        codes: Range<usize>,
+        guess: Guess<FIELDS>,
+    ) -> [Range<usize>; PARTITIONS] {
+        // feedback is evaluated twice, once for counting and once for placing the codes,
+        // which is cheaper than allocating a buffer for it
+        let feedback = |code: &Guess<FIELDS>| rules.evaluate(*code, guess).to_u32() as usize;
+        let mut counts = [0; PARTITIONS];
+        for code in &self.items[codes.clone()] {
+            counts[feedback(code)] += 1;
+        }
+        let start = self.items.len();
+        let mut part_start = start;
+        let parts: [Range<usize>; PARTITIONS] = std::array::from_fn(|index| {
+            let part = part_start..part_start + counts[index];
+            part_start = part.end;
+            part
+        });
+        let mut ends = parts.clone().map(|part| part.start - start);
+        self.items.extend_from_within(codes.clone());
+        let (old, new) = self.items.split_at_mut(start);
+        for code in &old[codes] {
+            let index = feedback(code);
+            new[ends[index]] = *code;
+            ends[index] += 1;
+        }
+        parts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, CodeIterator};
+
+    #[test]
+    fn split_by_feedback() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut arena = Arena::new();
+        let codes = arena.alloc(CodeIterator::<4, 6>::default());
+        let guess = Guess::new([0, 0, 1, 1]);
+        let mark = arena.mark();
+        let parts: [_; max_gauss(4)] = arena.split(rules, codes.clone(), guess);
+        for (index, part) in parts.iter().enumerate() {
+            let expected: Vec<_> = arena
+                .get(codes.clone())
+                .iter()
+                .copied()
+                .filter(|code| rules.evaluate(*code, guess).to_u32() as usize == index)
+                .collect();
+            assert_eq!(arena.get(part.clone()), expected);
+        }
+        arena.release(mark);
+        assert_eq!(arena.mark(), codes.end);
+    }
+}
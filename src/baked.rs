@@ -0,0 +1,216 @@
+//! Decision trees compiled into the program, so that `solve` answers from them right away
+//! without reading or allocating anything. `tree --rust` writes the source of one, the one
+//! of the classic preset is `classic_book`. It has to be written again whenever the solver
+//! would choose different guesses, which `baked_book_is_up_to_date` checks.
+
+use std::time::Duration;
+
+use crate::{
+    book::Book, classic_book, compute_n_log2_n, expected_guesses, preset::Preset, tree::Node,
+    Entry, Guess, GuessReport, Rules, SolverOptions, Strategy,
+};
+
+/// A node of a compiled decision tree. The nodes are stored level by level, so the children
+/// of a node follow each other.
+#[derive(Clone, Copy, Debug)]
+pub struct BakedNode(
+    /// The packed guess, see `Guess::packed`.
+    pub u64,
+    /// The number of codes which lead to this node.
+    pub u32,
+    /// The exact matches of the feedback leading to this node.
+    pub u8,
+    /// The correct colors of the feedback leading to this node.
+    pub u8,
+    /// The index of the first child.
+    pub u32,
+    /// The number of children.
+    pub u8,
+);
+
+#[derive(Clone, Copy)]
+pub struct BakedBook<const FIELDS: usize> {
+    nodes: &'static [BakedNode],
+}
+
+impl<const FIELDS: usize> BakedBook<FIELDS> {
+    fn children(&self, node: &BakedNode) -> &'static [BakedNode] {
+        let first = node.4 as usize;
+        &self.nodes[first..first + node.5 as usize]
+    }
+}
+
+impl<const FIELDS: usize> Book<FIELDS> for BakedBook<FIELDS> {
+    fn lookup(&self, history: &[Entry<FIELDS>]) -> Option<GuessReport<FIELDS>> {
+        let mut node = self.nodes.first()?;
+        for entry in history {
+            if entry.guess.packed() != node.0 {
+                return None;
+            }
+            let feedback = (entry.evaluation.exact, entry.evaluation.correct_color);
+            node = self
+                .children(node)
+                .iter()
+                .find(|child| (child.2 as u32, child.3 as u32) == feedback)?;
+        }
+        let children = self.children(node);
+        let codes = node.1 as usize;
+        let found = codes > children.iter().map(|child| child.1 as usize).sum();
+        let partitions: f64 = children
+            .iter()
+            .map(|child| compute_n_log2_n(child.1 as usize))
+            .sum();
+        let information = (compute_n_log2_n(codes) - partitions) / codes.max(1) as f64;
+        Some(GuessReport {
+            guess: Guess::from_packed(node.0),
+            score: 0.,
+            partitions: children.len() + found as usize,
+            candidates: codes,
+            information,
+            expected_guesses: expected_guesses(
+                codes,
+                children.iter().map(|child| child.1 as usize),
+                information,
+            ),
+            scored: 0,
+            elapsed: Duration::ZERO,
+        })
+    }
+}
+
+/// The book compiled in for the game, if there is one. The guesses are the ones of the
+/// default strategy, so there is none if `laura` or missing colors restrict the guesses, or
+/// the command line sets up the solver otherwise.
+pub fn book<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    options: &SolverOptions,
+) -> Option<BakedBook<FIELDS>> {
+    if cfg!(feature = "laura")
+        || rules.missing_colors != 0
+        || options.strategy != Strategy::default()
+        || options.first_guess.is_some()
+        || options.script.is_some()
+        || options.policy.is_some()
+    {
+        return None;
+    }
+    match Preset::find(FIELDS, COLORS, rules)? {
+        Preset::Classic => Some(BakedBook {
+            nodes: &classic_book::NODES,
+        }),
+        _ => None,
+    }
+}
+
+/// The Rust source of a module with the tree as a table of `BakedNode`s.
+pub fn source<const FIELDS: usize, const COLORS: u32>(tree: &Node<FIELDS>, rules: Rules) -> String {
+    let preset = Preset::find(FIELDS, COLORS, rules)
+        .expect("trees are built with the dimensions of a preset")
+        .name();
+    let mut text = format!(
+        "//! The decision tree of the solver for the {preset} preset, written by\n\
+         //! `mastermind_solver --preset {preset} tree --rust`, see `baked`.\n\
+         \n\
+         use crate::baked::BakedNode;\n\
+         \n\
+         pub static NODES: [BakedNode; {}] = [\n",
+        tree.nodes()
+    );
+    // the nodes in the order of the table with the feedback leading to them
+    let mut nodes = vec![(0, 0, tree)];
+    let mut index = 0;
+    while let Some(&(exact, correct_color, node)) = nodes.get(index) {
+        text.push_str(&format!(
+            "    BakedNode({:#x}, {}, {exact}, {correct_color}, {}, {}),\n",
+            node.guess.packed(),
+            node.codes,
+            nodes.len(),
+            node.children.len()
+        ));
+        nodes.extend(
+            node.children
+                .iter()
+                .map(|(evaluation, child)| (evaluation.exact, evaluation.correct_color, child)),
+        );
+        index += 1;
+    }
+    text.push_str("];\n");
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, SimpleGuesser};
+
+    #[test]
+    fn look_up_a_baked_tree() {
+        let rules = Preset::Mini.rules();
+        let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::new(rules);
+        let tree = Node::build(&mut guesser, rules.codes::<3, 4>());
+        // the table of the source, parsed back
+        let nodes: Vec<_> = source::<3, 4>(&tree, rules)
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("BakedNode(")?.strip_suffix("),"))
+            .map(|fields| {
+                let fields: Vec<_> = fields.split(", ").collect();
+                let guess = u64::from_str_radix(&fields[0][2..], 16).unwrap();
+                BakedNode(
+                    guess,
+                    fields[1].parse().unwrap(),
+                    fields[2].parse().unwrap(),
+                    fields[3].parse().unwrap(),
+                    fields[4].parse().unwrap(),
+                    fields[5].parse().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(nodes.len(), tree.nodes());
+        let book = BakedBook::<3> {
+            nodes: nodes.leak(),
+        };
+        for code in rules.codes::<3, 4>() {
+            let mut history = vec![];
+            loop {
+                let report = book.lookup(&history).unwrap();
+                let expected = tree.lookup(&history).unwrap();
+                assert_eq!(report.guess, expected.guess);
+                assert_eq!(report.candidates, expected.candidates);
+                assert_eq!(report.partitions, expected.partitions);
+                assert_eq!(report.information, expected.information);
+                assert_eq!(report.expected_guesses, expected.expected_guesses);
+                if report.guess == code {
+                    break;
+                }
+                history.push(Entry {
+                    guess: report.guess,
+                    evaluation: rules.evaluate(code, report.guess),
+                });
+            }
+        }
+        let other = Guess::new([3, 3, 3]);
+        let history = [Entry {
+            guess: other,
+            evaluation: rules.evaluate(Guess::new([0, 1, 2]), other),
+        }];
+        assert!(book.lookup(&history).is_none());
+        assert!(super::book::<3, 4>(rules, &SolverOptions::default()).is_none());
+    }
+
+    #[test]
+    #[cfg(not(feature = "laura"))]
+    fn baked_book_is_up_to_date() {
+        let rules = Preset::Classic.rules();
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let tree = Node::build(&mut guesser, rules.codes::<4, 6>());
+        assert!(
+            source::<4, 6>(&tree, rules) == include_str!("classic_book.rs"),
+            "run `cargo run --release -- --preset classic tree --rust --out src/classic_book.rs`"
+        );
+        let book = book::<4, 6>(rules, &SolverOptions::default()).unwrap();
+        assert_eq!(
+            book.lookup(&[]).map(|report| report.guess),
+            Some(tree.guess)
+        );
+    }
+}
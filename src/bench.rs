@@ -0,0 +1,119 @@
+//! Benchmarks of the solver for several configurations and history depths, run them
+//! with `cargo +nightly bench`. `cargo test` runs every benchmark once as a test.
+
+extern crate test;
+
+use test::{black_box, Bencher};
+
+use crate::{max_gauss, random::Rng, Entry, Rules, SimpleGuesser, Solver};
+
+/// A history of `depth` random guesses with their feedback for a random code.
+fn history<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    depth: usize,
+) -> Vec<Entry<FIELDS>> {
+    let mut rng = Rng::seeded(7);
+    let code = rules.random_code::<FIELDS, COLORS>(&mut rng);
+    (0..depth)
+        .map(|_| {
+            let guess = rules.random_code::<FIELDS, COLORS>(&mut rng);
+            Entry {
+                guess,
+                evaluation: rules.evaluate(code, guess),
+            }
+        })
+        .collect()
+}
+
+/// Evaluates every code against one guess.
+macro_rules! bench_evaluate {
+    ($name:ident, $fields:literal, $colors:literal, $duplicates:literal) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let rules = Rules {
+                duplicates: $duplicates,
+                missing_colors: 0,
+            };
+            let codes: Vec<_> = rules.codes::<$fields, $colors>().collect();
+            let guess = rules.random_code::<$fields, $colors>(&mut Rng::seeded(1));
+            b.iter(|| {
+                codes
+                    .iter()
+                    .map(|code| rules.evaluate(*code, black_box(guess)).to_u32())
+                    .sum::<u32>()
+            });
+        }
+    };
+}
+
+/// A solver with or without the evaluation table, building it takes long without
+/// optimizations so most benchmarks go without.
+fn guesser<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    table: bool,
+) -> SimpleGuesser<FIELDS, COLORS, PARTITIONS> {
+    if table {
+        SimpleGuesser::new(rules)
+    } else {
+        SimpleGuesser {
+            rules,
+            ..Default::default()
+        }
+    }
+}
+
+/// Filters all codes by a history of the given depth.
+macro_rules! bench_filter {
+    ($name:ident, $fields:literal, $colors:literal, $duplicates:literal, $depth:literal, $table:literal) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let rules = Rules {
+                duplicates: $duplicates,
+                missing_colors: 0,
+            };
+            let history = history::<$fields, $colors>(rules, $depth);
+            let mut guesser = guesser::<$fields, $colors, { max_gauss($fields) }>(rules, $table);
+            b.iter(|| {
+                // forget the last filtering so it isn't skipped
+                guesser.filtered_by = None;
+                guesser.update_candidates(black_box(&history));
+                guesser.candidates().len()
+            });
+        }
+    };
+}
+
+/// Chooses a guess after a history of the given depth.
+macro_rules! bench_guess {
+    ($name:ident, $fields:literal, $colors:literal, $duplicates:literal, $depth:literal, $table:literal) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let rules = Rules {
+                duplicates: $duplicates,
+                missing_colors: 0,
+            };
+            let history = history::<$fields, $colors>(rules, $depth);
+            let mut guesser = guesser::<$fields, $colors, { max_gauss($fields) }>(rules, $table);
+            b.iter(|| guesser.guess(black_box(&history)).unwrap().guess);
+        }
+    };
+}
+
+bench_evaluate!(evaluate_classic, 4, 6, true);
+bench_evaluate!(evaluate_bulls, 4, 10, false);
+bench_evaluate!(evaluate_super, 5, 8, true);
+
+bench_filter!(filter_classic_1, 4, 6, true, 1, false);
+bench_filter!(filter_classic_3, 4, 6, true, 3, false);
+bench_filter!(filter_classic_table_3, 4, 6, true, 3, true);
+bench_filter!(filter_bulls_2, 4, 10, false, 2, false);
+bench_filter!(filter_super_2, 5, 8, true, 2, false);
+
+bench_guess!(guess_mini_0, 3, 4, false, 0, false);
+bench_guess!(guess_classic_0, 4, 6, true, 0, false);
+bench_guess!(guess_classic_1, 4, 6, true, 1, false);
+bench_guess!(guess_classic_2, 4, 6, true, 2, false);
+bench_guess!(guess_classic_table_2, 4, 6, true, 2, true);
+bench_guess!(guess_four_of_eight_0, 4, 8, true, 0, false);
+bench_guess!(guess_bulls_2, 4, 10, false, 2, false);
+bench_guess!(guess_bulls_3, 4, 10, false, 3, false);
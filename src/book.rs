@@ -0,0 +1,122 @@
+//! Playing from a decision tree written by the `tree` command, an opening book which covers
+//! the whole game. Guesses are looked up instead of computed, only when the game leaves the
+//! book does another solver have to guess.
+
+use std::time::{Duration, Instant};
+
+use crate::{expected_guesses, tree::Node, Entry, GuessReport, InconsistentHistory, Solver};
+
+/// A decision tree to look guesses up in.
+pub trait Book<const FIELDS: usize> {
+    /// The guess of the book for the history, if every guess of it was the one of the book.
+    /// The book has no scores, so guesses are reported with a score of 0.
+    fn lookup(&self, history: &[Entry<FIELDS>]) -> Option<GuessReport<FIELDS>>;
+}
+
+impl<const FIELDS: usize> Book<FIELDS> for Node<FIELDS> {
+    fn lookup(&self, history: &[Entry<FIELDS>]) -> Option<GuessReport<FIELDS>> {
+        let node = history.iter().try_fold(self, |node, entry| {
+            if entry.guess != node.guess {
+                return None;
+            }
+            node.children
+                .iter()
+                .find(|(evaluation, _)| *evaluation == entry.evaluation)
+                .map(|(_, child)| child)
+        })?;
+        Some(GuessReport {
+            guess: node.guess,
+            score: 0.,
+            partitions: node.children.len() + node.found() as usize,
+            candidates: node.codes,
+            information: node.information(),
+            expected_guesses: expected_guesses(
+                node.codes,
+                node.children.iter().map(|(_, child)| child.codes),
+                node.information(),
+            ),
+            scored: 0,
+            elapsed: Duration::ZERO,
+        })
+    }
+}
+
+impl<const FIELDS: usize, B: Book<FIELDS> + ?Sized> Book<FIELDS> for Box<B> {
+    fn lookup(&self, history: &[Entry<FIELDS>]) -> Option<GuessReport<FIELDS>> {
+        (**self).lookup(history)
+    }
+}
+
+pub struct BookSolver<B, S> {
+    book: B,
+    /// Guesses once the history isn't in the book.
+    pub fallback: S,
+}
+
+impl<B, S> BookSolver<B, S> {
+    pub fn new(book: B, fallback: S) -> Self {
+        Self { book, fallback }
+    }
+}
+
+impl<const FIELDS: usize, B: Book<FIELDS>, S: Solver<FIELDS>> Solver<FIELDS> for BookSolver<B, S> {
+    fn guess(
+        &mut self,
+        history: &[Entry<FIELDS>],
+    ) -> Result<GuessReport<FIELDS>, InconsistentHistory> {
+        let start = Instant::now();
+        match self.book.lookup(history) {
+            Some(report) => {
+                debug!("book guess: {}", report.guess);
+                Ok(GuessReport {
+                    elapsed: start.elapsed(),
+                    ..report
+                })
+            }
+            None => {
+                debug!("the game left the book");
+                self.fallback.guess(history)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{commands, max_gauss, Guess, Rules, SimpleGuesser};
+
+    #[test]
+    fn play_from_the_book() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::new(rules);
+        let tree = Node::build(&mut guesser, rules.codes::<3, 4>());
+        let fallback = SimpleGuesser::<3, 4, { max_gauss(3) }> {
+            rules,
+            ..Default::default()
+        };
+        let mut book = BookSolver::new(tree, fallback);
+        let code = Guess::new([3, 1, 1]);
+        let mut history = vec![];
+        for turn in commands::play(&mut guesser, code, usize::MAX, |_| {}) {
+            let report = book.guess(&history).unwrap();
+            assert_eq!(report.guess, turn.guess);
+            assert_eq!(report.scored, 0);
+            history.push(Entry {
+                guess: turn.guess,
+                evaluation: turn.evaluation,
+            });
+        }
+        // off the book the fallback guesses
+        let other = Guess::new([2, 2, 2]);
+        let history = [Entry {
+            guess: other,
+            evaluation: rules.evaluate(code, other),
+        }];
+        assert!(book.book.lookup(&history).is_none());
+        assert!(book.guess(&history).unwrap().scored > 0);
+    }
+}
@@ -0,0 +1,212 @@
+//! The best first guess and the best second guess after every first guess and feedback,
+//! kept on disk. They only depend on the configuration but are the most expensive guesses
+//! to compute, as there are the most candidates left.
+//!
+//! Every configuration has a file of its own with one line per guess:
+//! `first guess <TAB> exact correct <TAB> best guess <TAB> score`, with `-` for the first
+//! two columns of the first guess.
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use once_cell::sync::OnceCell;
+
+use crate::{strategy::Strategy, Entry, Guess, Rules};
+
+/// Part of the file name, increase it whenever the solver would choose different guesses.
+const VERSION: u32 = 2;
+
+/// The best guess and its score by the first entry of the history, if there is one.
+type Guesses<const FIELDS: usize> = HashMap<Option<Entry<FIELDS>>, (Guess<FIELDS>, f64)>;
+
+static DIRECTORY: OnceCell<Option<PathBuf>> = OnceCell::new();
+
+/// Sets where the caches are kept, `None` turns caching off. Until this is called
+/// nothing is cached.
+pub fn set_directory(directory: Option<PathBuf>) {
+    let _ = DIRECTORY.set(directory);
+}
+
+pub fn directory() -> Option<&'static Path> {
+    DIRECTORY.get()?.as_deref()
+}
+
+/// The user's cache directory, if a home directory is known.
+pub fn default_directory() -> Option<PathBuf> {
+    let cache_dir = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(cache_dir.join("mastermind_solver"))
+}
+
+pub struct OpeningCache<const FIELDS: usize> {
+    path: PathBuf,
+    guesses: Guesses<FIELDS>,
+}
+
+impl<const FIELDS: usize> OpeningCache<FIELDS> {
+    /// Loads the cache for the configuration from `directory`. A missing or unreadable
+    /// file gives an empty cache.
+    pub fn open<const COLORS: u32>(directory: &Path, rules: Rules, strategy: Strategy) -> Self {
+        let duplicates = if rules.duplicates {
+            "duplicates"
+        } else {
+            "distinct"
+        };
+        let guesses = if cfg!(feature = "laura") {
+            "-codes"
+        } else {
+            ""
+        };
+        // the default strategy and a complete set keep the names from before there were
+        // others
+        let strategy = match strategy {
+            Strategy::Entropy => String::new(),
+            strategy => format!("-{strategy}"),
+        };
+        let missing = match rules.missing_colors {
+            0 => String::new(),
+            colors => format!("-missing{colors:x}"),
+        };
+        let path = directory.join(format!(
+            "opening-v{VERSION}-{FIELDS}x{COLORS}-{duplicates}{guesses}{strategy}{missing}.txt"
+        ));
+        let guesses = match std::fs::read_to_string(&path) {
+            Ok(text) => match parse(&text) {
+                Some(guesses) => {
+                    debug!(
+                        "read {} cached guesses from {}",
+                        guesses.len(),
+                        path.display()
+                    );
+                    guesses
+                }
+                None => {
+                    info!("ignoring invalid cache {}", path.display());
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+        Self { path, guesses }
+    }
+
+    /// The cached guess for the history, only histories of up to one entry are cached.
+    pub fn get(&self, history: &[Entry<FIELDS>]) -> Option<(Guess<FIELDS>, f64)> {
+        match history {
+            [] | [_] => self.guesses.get(&history.first().copied()).copied(),
+            _ => None,
+        }
+    }
+
+    /// Remembers the guess for the history and appends it to the file, histories of more
+    /// than one entry are ignored.
+    pub fn insert(&mut self, history: &[Entry<FIELDS>], guess: Guess<FIELDS>, score: f64) {
+        let first = match history {
+            [] => None,
+            [first] => Some(*first),
+            _ => return,
+        };
+        if self.guesses.insert(first, (guess, score)).is_some() {
+            return;
+        }
+        let line = match first {
+            Some(Entry {
+                guess: first,
+                evaluation,
+            }) => format!(
+                "{}\t{} {}\t{}\t{score:?}\n",
+                first.indices(),
+                evaluation.exact,
+                evaluation.correct_color,
+                guess.indices()
+            ),
+            None => format!("-\t-\t{}\t{score:?}\n", guess.indices()),
+        };
+        let written = self
+            .path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+            })
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+        if let Err(err) = written {
+            debug!("can't write to the cache {}: {err}", self.path.display());
+        }
+    }
+}
+
+fn parse<const FIELDS: usize>(text: &str) -> Option<Guesses<FIELDS>> {
+    text.lines()
+        .map(|line| {
+            let [first, evaluation, guess, score] = line.split('\t').collect::<Vec<_>>()[..] else {
+                return None;
+            };
+            let first = match (first, evaluation) {
+                ("-", "-") => None,
+                _ => Some(Entry {
+                    guess: first.parse().ok()?,
+                    evaluation: evaluation.parse().ok()?,
+                }),
+            };
+            Some((first, (guess.parse().ok()?, score.parse().ok()?)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Evaluation;
+
+    #[test]
+    fn keeps_guesses_on_disk() {
+        let directory =
+            std::env::temp_dir().join(format!("mastermind_cache_{}", std::process::id()));
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let first = Entry {
+            guess: Guess::new([0, 0, 1, 1]),
+            evaluation: Evaluation {
+                correct_color: 1,
+                exact: 2,
+            },
+        };
+        let mut cache = OpeningCache::<4>::open::<6>(&directory, rules, Strategy::Entropy);
+        assert_eq!(cache.get(&[]), None);
+        cache.insert(&[], first.guess, 1.25);
+        cache.insert(&[first], Guess::new([1, 0, 2, 3]), 0.1 + 0.2);
+        cache.insert(&[first, first], Guess::new([1, 1, 1, 1]), 0.);
+
+        let cache = OpeningCache::<4>::open::<6>(&directory, rules, Strategy::Entropy);
+        assert_eq!(cache.get(&[]), Some((first.guess, 1.25)));
+        assert_eq!(
+            cache.get(&[first]),
+            Some((Guess::new([1, 0, 2, 3]), 0.1 + 0.2))
+        );
+        assert_eq!(cache.get(&[first, first]), None);
+        assert_eq!(cache.guesses.len(), 2);
+        // other configurations have caches of their own
+        let other = OpeningCache::<4>::open::<6>(
+            &directory,
+            Rules {
+                duplicates: false,
+                missing_colors: 0,
+            },
+            Strategy::Entropy,
+        );
+        assert_eq!(other.get(&[]), None);
+        let other = OpeningCache::<4>::open::<6>(&directory, rules, Strategy::Knuth);
+        assert_eq!(other.get(&[]), None);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,1681 @@
+//! The decision tree of the solver for the classic preset, written by
+//! `mastermind_solver --preset classic tree --rust`, see `baked`.
+
+use crate::baked::BakedNode;
+
+pub static NODES: [BakedNode; 1674] = [
+    BakedNode(0x3210, 1296, 0, 0, 1, 13),
+    BakedNode(0x5543, 16, 0, 0, 14, 8),
+    BakedNode(0x5543, 108, 1, 0, 22, 13),
+    BakedNode(0x5430, 96, 2, 0, 35, 12),
+    BakedNode(0x5430, 20, 3, 0, 47, 7),
+    BakedNode(0x5543, 152, 0, 1, 54, 12),
+    BakedNode(0x5430, 252, 1, 1, 66, 13),
+    BakedNode(0x5330, 48, 2, 1, 79, 9),
+    BakedNode(0x5431, 312, 0, 2, 88, 13),
+    BakedNode(0x5330, 132, 1, 2, 101, 9),
+    BakedNode(0x3320, 6, 2, 2, 110, 4),
+    BakedNode(0x5321, 136, 0, 3, 114, 10),
+    BakedNode(0x3321, 8, 1, 3, 124, 4),
+    BakedNode(0x2133, 9, 0, 4, 128, 4),
+    BakedNode(0x4444, 1, 1, 0, 132, 0),
+    BakedNode(0x5455, 3, 2, 0, 132, 3),
+    BakedNode(0x5555, 2, 3, 0, 135, 2),
+    BakedNode(0x4445, 1, 1, 1, 137, 0),
+    BakedNode(0x5455, 3, 2, 1, 137, 3),
+    BakedNode(0x4454, 1, 0, 2, 140, 0),
+    BakedNode(0x5455, 4, 1, 2, 140, 3),
+    BakedNode(0x4455, 1, 0, 3, 143, 0),
+    BakedNode(0x5221, 3, 0, 0, 143, 3),
+    BakedNode(0x4045, 14, 1, 0, 146, 9),
+    BakedNode(0x5005, 13, 2, 0, 155, 10),
+    BakedNode(0x5555, 2, 3, 0, 165, 2),
+    BakedNode(0x1440, 17, 0, 1, 167, 9),
+    BakedNode(0x5151, 22, 1, 1, 176, 11),
+    BakedNode(0x5551, 4, 2, 1, 187, 4),
+    BakedNode(0x2454, 14, 0, 2, 191, 10),
+    BakedNode(0x1450, 11, 1, 2, 201, 9),
+    BakedNode(0x3545, 1, 2, 2, 210, 0),
+    BakedNode(0x5442, 5, 0, 3, 210, 5),
+    BakedNode(0x3554, 1, 1, 3, 215, 0),
+    BakedNode(0x3455, 1, 0, 4, 215, 0),
+    BakedNode(0x5222, 4, 0, 0, 215, 4),
+    BakedNode(0x1100, 13, 1, 0, 219, 9),
+    BakedNode(0x5151, 11, 2, 0, 228, 8),
+    BakedNode(0x5555, 2, 3, 0, 236, 2),
+    BakedNode(0x4221, 13, 0, 1, 238, 9),
+    BakedNode(0x4020, 19, 1, 1, 247, 10),
+    BakedNode(0x5544, 4, 2, 1, 257, 4),
+    BakedNode(0x2243, 16, 0, 2, 261, 10),
+    BakedNode(0x350, 9, 1, 2, 271, 8),
+    BakedNode(0x3450, 1, 2, 2, 279, 0),
+    BakedNode(0x5552, 3, 0, 3, 279, 3),
+    BakedNode(0x3540, 1, 1, 3, 282, 0),
+    BakedNode(0x2511, 3, 1, 0, 282, 3),
+    BakedNode(0x5555, 2, 2, 0, 285, 2),
+    BakedNode(0x5332, 3, 0, 1, 287, 3),
+    BakedNode(0x3103, 6, 1, 1, 290, 6),
+    BakedNode(0x3410, 1, 2, 1, 296, 0),
+    BakedNode(0x5555, 2, 0, 2, 296, 2),
+    BakedNode(0x5552, 3, 1, 2, 298, 3),
+    BakedNode(0x4011, 14, 1, 0, 301, 9),
+    BakedNode(0x5424, 20, 2, 0, 310, 10),
+    BakedNode(0x5421, 7, 3, 0, 320, 6),
+    BakedNode(0x4440, 10, 0, 1, 326, 9),
+    BakedNode(0x4045, 30, 1, 1, 335, 12),
+    BakedNode(0x5045, 12, 2, 1, 347, 8),
+    BakedNode(0x2445, 21, 0, 2, 355, 10),
+    BakedNode(0x5452, 21, 1, 2, 365, 10),
+    BakedNode(0x5445, 4, 2, 2, 375, 4),
+    BakedNode(0x5415, 8, 0, 3, 379, 7),
+    BakedNode(0x5455, 3, 1, 3, 386, 3),
+    BakedNode(0x4355, 1, 0, 4, 389, 0),
+    BakedNode(0x5222, 6, 0, 0, 389, 6),
+    BakedNode(0x1142, 21, 1, 0, 395, 10),
+    BakedNode(0x4020, 19, 2, 0, 405, 10),
+    BakedNode(0x5300, 6, 3, 0, 415, 6),
+    BakedNode(0x2115, 31, 0, 1, 421, 12),
+    BakedNode(0x1500, 50, 1, 1, 433, 12),
+    BakedNode(0x5514, 10, 2, 1, 445, 8),
+    BakedNode(0x1314, 57, 0, 2, 453, 13),
+    BakedNode(0x3442, 21, 1, 2, 466, 9),
+    BakedNode(0x5545, 2, 2, 2, 475, 2),
+    BakedNode(0x3541, 23, 0, 3, 477, 10),
+    BakedNode(0x5554, 2, 1, 3, 487, 2),
+    BakedNode(0x5545, 3, 0, 4, 489, 3),
+    BakedNode(0x5411, 6, 1, 0, 492, 5),
+    BakedNode(0x514, 6, 2, 0, 497, 6),
+    BakedNode(0x5552, 2, 3, 0, 503, 2),
+    BakedNode(0x4223, 10, 0, 1, 505, 8),
+    BakedNode(0x5421, 9, 1, 1, 513, 7),
+    BakedNode(0x5552, 2, 2, 1, 520, 2),
+    BakedNode(0x2514, 7, 0, 2, 522, 7),
+    BakedNode(0x5552, 2, 1, 2, 529, 2),
+    BakedNode(0x5525, 4, 0, 3, 531, 4),
+    BakedNode(0x5222, 4, 0, 0, 535, 4),
+    BakedNode(0x4202, 22, 1, 0, 539, 11),
+    BakedNode(0x2443, 27, 2, 0, 550, 11),
+    BakedNode(0x3532, 8, 3, 0, 561, 7),
+    BakedNode(0x502, 28, 0, 1, 568, 11),
+    BakedNode(0x5352, 63, 1, 1, 579, 13),
+    BakedNode(0x4034, 16, 2, 1, 592, 9),
+    BakedNode(0x2443, 64, 0, 2, 601, 13),
+    BakedNode(0x2534, 34, 1, 2, 614, 10),
+    BakedNode(0x5541, 4, 2, 2, 624, 4),
+    BakedNode(0x2145, 32, 0, 3, 628, 10),
+    BakedNode(0x5453, 5, 1, 3, 638, 4),
+    BakedNode(0x4553, 4, 0, 4, 642, 4),
+    BakedNode(0x2314, 18, 1, 0, 646, 9),
+    BakedNode(0x4322, 17, 2, 0, 655, 9),
+    BakedNode(0x5552, 4, 3, 0, 664, 4),
+    BakedNode(0x221, 25, 0, 1, 668, 10),
+    BakedNode(0x2115, 14, 1, 1, 678, 9),
+    BakedNode(0x5542, 4, 2, 1, 687, 4),
+    BakedNode(0x413, 30, 0, 2, 691, 10),
+    BakedNode(0x4313, 10, 1, 2, 701, 8),
+    BakedNode(0x3105, 10, 0, 3, 709, 8),
+    BakedNode(0x3120, 1, 3, 0, 717, 0),
+    BakedNode(0x2310, 1, 2, 1, 717, 0),
+    BakedNode(0x5251, 3, 1, 2, 717, 3),
+    BakedNode(0x213, 1, 0, 3, 720, 0),
+    BakedNode(0x314, 14, 2, 0, 720, 9),
+    BakedNode(0x4223, 5, 3, 0, 729, 5),
+    BakedNode(0x4302, 26, 1, 1, 734, 10),
+    BakedNode(0x524, 11, 2, 1, 744, 9),
+    BakedNode(0x4103, 29, 0, 2, 753, 10),
+    BakedNode(0x1025, 20, 1, 2, 763, 9),
+    BakedNode(0x5553, 3, 2, 2, 772, 3),
+    BakedNode(0x1035, 20, 0, 3, 775, 10),
+    BakedNode(0x5531, 4, 1, 3, 785, 4),
+    BakedNode(0x5553, 3, 0, 4, 789, 3),
+    BakedNode(0x3021, 1, 3, 0, 792, 0),
+    BakedNode(0x1320, 1, 2, 1, 792, 0),
+    BakedNode(0x5512, 3, 1, 2, 792, 3),
+    BakedNode(0x5513, 3, 0, 3, 795, 3),
+    BakedNode(0x2103, 1, 3, 0, 798, 0),
+    BakedNode(0x5532, 3, 2, 1, 798, 3),
+    BakedNode(0x5332, 3, 1, 2, 801, 3),
+    BakedNode(0x5552, 2, 0, 3, 804, 2),
+    BakedNode(0x5444, 1, 2, 0, 806, 0),
+    BakedNode(0x5555, 1, 3, 0, 806, 0),
+    BakedNode(0x4544, 1, 0, 2, 806, 0),
+    BakedNode(0x5544, 1, 2, 0, 806, 0),
+    BakedNode(0x5545, 1, 3, 0, 806, 0),
+    BakedNode(0x5445, 1, 3, 0, 806, 0),
+    BakedNode(0x4545, 1, 1, 2, 806, 0),
+    BakedNode(0x5554, 1, 2, 2, 806, 0),
+    BakedNode(0x5454, 1, 3, 0, 806, 0),
+    BakedNode(0x4554, 1, 1, 2, 806, 0),
+    BakedNode(0x4555, 1, 2, 2, 806, 0),
+    BakedNode(0x0, 1, 0, 0, 806, 0),
+    BakedNode(0x1111, 1, 1, 0, 806, 0),
+    BakedNode(0x2222, 1, 2, 0, 806, 0),
+    BakedNode(0x3333, 1, 0, 0, 806, 0),
+    BakedNode(0x2242, 1, 1, 0, 806, 0),
+    BakedNode(0x5552, 3, 2, 0, 806, 3),
+    BakedNode(0x4040, 1, 3, 0, 809, 0),
+    BakedNode(0x5452, 3, 0, 1, 809, 3),
+    BakedNode(0x5555, 2, 1, 1, 812, 2),
+    BakedNode(0x4440, 1, 2, 1, 814, 0),
+    BakedNode(0x500, 1, 0, 2, 814, 0),
+    BakedNode(0x440, 1, 1, 2, 814, 0),
+    BakedNode(0x5544, 2, 0, 0, 814, 2),
+    BakedNode(0x5554, 2, 1, 0, 816, 2),
+    BakedNode(0x5515, 1, 2, 0, 818, 0),
+    BakedNode(0x3533, 1, 0, 1, 818, 0),
+    BakedNode(0x5555, 2, 1, 1, 818, 2),
+    BakedNode(0x5040, 1, 2, 1, 820, 0),
+    BakedNode(0x4540, 1, 0, 2, 820, 0),
+    BakedNode(0x5550, 1, 1, 2, 820, 0),
+    BakedNode(0x5500, 1, 2, 2, 820, 0),
+    BakedNode(0x540, 1, 0, 3, 820, 0),
+    BakedNode(0x3543, 1, 1, 0, 820, 0),
+    BakedNode(0x5540, 1, 2, 0, 820, 0),
+    BakedNode(0x5554, 2, 0, 0, 820, 2),
+    BakedNode(0x5554, 2, 1, 0, 822, 2),
+    BakedNode(0x5551, 2, 2, 0, 824, 2),
+    BakedNode(0x5554, 2, 0, 1, 826, 2),
+    BakedNode(0x5554, 2, 1, 1, 828, 2),
+    BakedNode(0x5554, 2, 2, 1, 830, 2),
+    BakedNode(0x5554, 2, 0, 2, 832, 2),
+    BakedNode(0x5554, 2, 1, 2, 834, 2),
+    BakedNode(0x4114, 1, 0, 3, 836, 0),
+    BakedNode(0x5533, 3, 0, 0, 836, 3),
+    BakedNode(0x5554, 3, 1, 0, 839, 3),
+    BakedNode(0x5552, 3, 2, 0, 842, 3),
+    BakedNode(0x5522, 3, 0, 1, 845, 3),
+    BakedNode(0x5553, 3, 1, 1, 848, 3),
+    BakedNode(0x5554, 2, 2, 1, 851, 2),
+    BakedNode(0x4514, 1, 0, 2, 853, 0),
+    BakedNode(0x4511, 1, 1, 2, 853, 0),
+    BakedNode(0x5115, 1, 2, 2, 853, 0),
+    BakedNode(0x1514, 1, 0, 3, 853, 0),
+    BakedNode(0x1515, 1, 0, 4, 853, 0),
+    BakedNode(0x3544, 1, 1, 0, 853, 0),
+    BakedNode(0x3553, 1, 2, 0, 853, 0),
+    BakedNode(0x5245, 1, 1, 1, 853, 0),
+    BakedNode(0x5514, 1, 2, 1, 853, 0),
+    BakedNode(0x3334, 1, 1, 0, 853, 0),
+    BakedNode(0x5555, 3, 2, 0, 853, 3),
+    BakedNode(0x2254, 1, 3, 0, 856, 0),
+    BakedNode(0x3335, 1, 0, 1, 856, 0),
+    BakedNode(0x5554, 2, 1, 1, 856, 2),
+    BakedNode(0x4450, 1, 2, 1, 858, 0),
+    BakedNode(0x4115, 1, 0, 2, 858, 0),
+    BakedNode(0x5554, 2, 1, 2, 858, 2),
+    BakedNode(0x4254, 1, 2, 2, 860, 0),
+    BakedNode(0x4225, 1, 0, 3, 860, 0),
+    BakedNode(0x3555, 1, 1, 0, 860, 0),
+    BakedNode(0x3453, 1, 2, 0, 860, 0),
+    BakedNode(0x5450, 1, 3, 0, 860, 0),
+    BakedNode(0x3535, 1, 0, 1, 860, 0),
+    BakedNode(0x5555, 2, 1, 1, 860, 2),
+    BakedNode(0x4550, 1, 2, 1, 862, 0),
+    BakedNode(0x5554, 2, 0, 2, 862, 2),
+    BakedNode(0x5415, 1, 1, 2, 864, 0),
+    BakedNode(0x4515, 1, 0, 3, 864, 0),
+    BakedNode(0x3355, 1, 0, 1, 864, 0),
+    BakedNode(0x3435, 1, 1, 1, 864, 0),
+    BakedNode(0x3354, 1, 0, 2, 864, 0),
+    BakedNode(0x3454, 1, 1, 2, 864, 0),
+    BakedNode(0x4255, 1, 0, 3, 864, 0),
+    BakedNode(0x1211, 1, 1, 0, 864, 0),
+    BakedNode(0x1212, 1, 2, 0, 864, 0),
+    BakedNode(0x2211, 1, 1, 1, 864, 0),
+    BakedNode(0x2212, 1, 2, 1, 864, 0),
+    BakedNode(0x5553, 2, 0, 0, 864, 2),
+    BakedNode(0x2220, 1, 1, 0, 866, 0),
+    BakedNode(0x5522, 2, 2, 0, 866, 2),
+    BakedNode(0x1110, 1, 3, 0, 868, 0),
+    BakedNode(0x5555, 2, 0, 1, 868, 2),
+    BakedNode(0x220, 1, 1, 1, 870, 0),
+    BakedNode(0x5211, 1, 0, 2, 870, 0),
+    BakedNode(0x10, 1, 1, 2, 870, 0),
+    BakedNode(0x5155, 2, 2, 2, 870, 2),
+    BakedNode(0x5500, 2, 0, 0, 872, 2),
+    BakedNode(0x5525, 2, 1, 0, 874, 2),
+    BakedNode(0x5250, 1, 2, 0, 876, 0),
+    BakedNode(0x5544, 2, 0, 1, 876, 2),
+    BakedNode(0x5010, 1, 1, 1, 878, 0),
+    BakedNode(0x5110, 1, 2, 1, 878, 0),
+    BakedNode(0x1410, 1, 0, 2, 878, 0),
+    BakedNode(0x5510, 1, 1, 2, 878, 0),
+    BakedNode(0x3430, 1, 0, 0, 878, 0),
+    BakedNode(0x5410, 1, 1, 0, 878, 0),
+    BakedNode(0x5533, 2, 1, 0, 878, 2),
+    BakedNode(0x5553, 2, 2, 0, 880, 2),
+    BakedNode(0x4211, 1, 3, 0, 882, 0),
+    BakedNode(0x5511, 2, 0, 1, 882, 2),
+    BakedNode(0x1215, 1, 1, 1, 884, 0),
+    BakedNode(0x4214, 1, 2, 1, 884, 0),
+    BakedNode(0x5555, 2, 1, 2, 884, 2),
+    BakedNode(0x4212, 1, 2, 2, 886, 0),
+    BakedNode(0x2214, 1, 1, 3, 886, 0),
+    BakedNode(0x1510, 1, 1, 0, 886, 0),
+    BakedNode(0x5554, 2, 2, 0, 886, 2),
+    BakedNode(0x5552, 2, 3, 0, 888, 2),
+    BakedNode(0x5443, 4, 0, 1, 890, 4),
+    BakedNode(0x5552, 3, 1, 1, 894, 3),
+    BakedNode(0x4240, 1, 2, 1, 897, 0),
+    BakedNode(0x5555, 2, 0, 2, 897, 2),
+    BakedNode(0x5555, 2, 1, 2, 899, 2),
+    BakedNode(0x4200, 1, 2, 2, 901, 0),
+    BakedNode(0x240, 1, 1, 3, 901, 0),
+    BakedNode(0x3530, 1, 1, 0, 901, 0),
+    BakedNode(0x5240, 1, 2, 0, 901, 0),
+    BakedNode(0x3400, 1, 0, 1, 901, 0),
+    BakedNode(0x3440, 1, 1, 1, 901, 0),
+    BakedNode(0x3513, 1, 1, 0, 901, 0),
+    BakedNode(0x3253, 1, 2, 0, 901, 0),
+    BakedNode(0x3243, 1, 3, 0, 901, 0),
+    BakedNode(0x5533, 4, 0, 1, 901, 4),
+    BakedNode(0x5555, 2, 1, 1, 905, 2),
+    BakedNode(0x3244, 1, 2, 1, 907, 0),
+    BakedNode(0x5533, 2, 0, 2, 907, 2),
+    BakedNode(0x5554, 2, 1, 2, 909, 2),
+    BakedNode(0x3242, 1, 2, 2, 911, 0),
+    BakedNode(0x3224, 1, 1, 3, 911, 0),
+    BakedNode(0x5555, 2, 2, 0, 911, 2),
+    BakedNode(0x3350, 1, 3, 0, 913, 0),
+    BakedNode(0x4510, 1, 1, 1, 913, 0),
+    BakedNode(0x3550, 1, 2, 1, 913, 0),
+    BakedNode(0x3415, 1, 0, 2, 913, 0),
+    BakedNode(0x3040, 1, 1, 2, 913, 0),
+    BakedNode(0x3050, 1, 2, 2, 913, 0),
+    BakedNode(0x3500, 1, 1, 3, 913, 0),
+    BakedNode(0x3514, 1, 1, 0, 913, 0),
+    BakedNode(0x3254, 1, 1, 1, 913, 0),
+    BakedNode(0x3245, 1, 0, 2, 913, 0),
+    BakedNode(0x2210, 1, 2, 0, 913, 0),
+    BakedNode(0x210, 1, 1, 1, 913, 0),
+    BakedNode(0x1210, 1, 1, 2, 913, 0),
+    BakedNode(0x3230, 1, 0, 0, 913, 0),
+    BakedNode(0x5210, 1, 1, 0, 913, 0),
+    BakedNode(0x3212, 1, 1, 1, 913, 0),
+    BakedNode(0x3211, 1, 0, 2, 913, 0),
+    BakedNode(0x3213, 1, 0, 3, 913, 0),
+    BakedNode(0x3200, 1, 2, 0, 913, 0),
+    BakedNode(0x3220, 1, 1, 1, 913, 0),
+    BakedNode(0x3110, 1, 2, 1, 913, 0),
+    BakedNode(0x4210, 1, 0, 2, 913, 0),
+    BakedNode(0x3010, 1, 1, 2, 913, 0),
+    BakedNode(0x3310, 1, 1, 3, 913, 0),
+    BakedNode(0x3214, 1, 0, 0, 913, 0),
+    BakedNode(0x3215, 1, 1, 0, 913, 0),
+    BakedNode(0x3510, 1, 1, 0, 913, 0),
+    BakedNode(0x3240, 1, 0, 1, 913, 0),
+    BakedNode(0x3250, 1, 1, 1, 913, 0),
+    BakedNode(0x2522, 1, 0, 0, 913, 0),
+    BakedNode(0x4442, 1, 1, 0, 913, 0),
+    BakedNode(0x5554, 2, 2, 0, 913, 2),
+    BakedNode(0x5554, 2, 0, 1, 915, 2),
+    BakedNode(0x5551, 2, 1, 1, 917, 2),
+    BakedNode(0x4141, 1, 2, 1, 919, 0),
+    BakedNode(0x5551, 2, 0, 2, 919, 2),
+    BakedNode(0x5544, 2, 1, 2, 921, 2),
+    BakedNode(0x1144, 1, 0, 3, 923, 0),
+    BakedNode(0x5554, 3, 1, 0, 923, 3),
+    BakedNode(0x5555, 2, 2, 0, 926, 2),
+    BakedNode(0x5555, 3, 1, 1, 928, 3),
+    BakedNode(0x5551, 3, 2, 1, 931, 3),
+    BakedNode(0x5555, 2, 0, 2, 934, 2),
+    BakedNode(0x5551, 2, 1, 2, 936, 2),
+    BakedNode(0x5442, 1, 2, 2, 938, 0),
+    BakedNode(0x5552, 2, 0, 3, 938, 2),
+    BakedNode(0x2544, 1, 1, 3, 940, 0),
+    BakedNode(0x4542, 1, 0, 4, 940, 0),
+    BakedNode(0x5555, 2, 1, 0, 940, 2),
+    BakedNode(0x5443, 1, 2, 0, 942, 0),
+    BakedNode(0x5343, 1, 1, 1, 942, 0),
+    BakedNode(0x5541, 1, 2, 1, 942, 0),
+    BakedNode(0x4543, 1, 0, 2, 942, 0),
+    BakedNode(0x5542, 1, 1, 2, 942, 0),
+    BakedNode(0x1151, 1, 0, 0, 942, 0),
+    BakedNode(0x2422, 1, 1, 0, 942, 0),
+    BakedNode(0x4422, 1, 2, 0, 942, 0),
+    BakedNode(0x5, 1, 0, 1, 942, 0),
+    BakedNode(0x2424, 1, 1, 1, 942, 0),
+    BakedNode(0x4424, 1, 2, 1, 942, 0),
+    BakedNode(0x4, 1, 0, 2, 942, 0),
+    BakedNode(0x5455, 2, 1, 2, 942, 2),
+    BakedNode(0x4404, 1, 2, 2, 944, 0),
+    BakedNode(0x5452, 5, 1, 0, 944, 5),
+    BakedNode(0x5555, 4, 2, 0, 949, 4),
+    BakedNode(0x5551, 2, 3, 0, 953, 2),
+    BakedNode(0x5452, 3, 0, 1, 955, 3),
+    BakedNode(0x5555, 4, 1, 1, 958, 4),
+    BakedNode(0x5552, 2, 2, 1, 962, 2),
+    BakedNode(0x5554, 2, 0, 2, 964, 2),
+    BakedNode(0x5552, 2, 1, 2, 966, 2),
+    BakedNode(0x445, 1, 2, 2, 968, 0),
+    BakedNode(0x5552, 2, 0, 3, 968, 2),
+    BakedNode(0x4504, 1, 1, 3, 970, 0),
+    BakedNode(0x5404, 1, 0, 4, 970, 0),
+    BakedNode(0x5555, 2, 2, 0, 970, 2),
+    BakedNode(0x5145, 1, 3, 0, 972, 0),
+    BakedNode(0x5555, 2, 1, 1, 972, 2),
+    BakedNode(0x5552, 2, 2, 1, 974, 2),
+    BakedNode(0x4533, 1, 0, 2, 976, 0),
+    BakedNode(0x5524, 1, 1, 2, 976, 0),
+    BakedNode(0x545, 1, 2, 2, 976, 0),
+    BakedNode(0x5504, 1, 1, 3, 976, 0),
+    BakedNode(0x5551, 2, 1, 0, 976, 2),
+    BakedNode(0x405, 1, 2, 0, 978, 0),
+    BakedNode(0x2425, 1, 3, 0, 978, 0),
+    BakedNode(0x5554, 3, 1, 1, 978, 3),
+    BakedNode(0x5554, 2, 2, 1, 981, 2),
+    BakedNode(0x5551, 4, 0, 2, 983, 4),
+    BakedNode(0x5551, 3, 1, 2, 987, 3),
+    BakedNode(0x5554, 2, 2, 2, 990, 2),
+    BakedNode(0x5551, 2, 0, 3, 992, 2),
+    BakedNode(0x4452, 1, 1, 3, 994, 0),
+    BakedNode(0x5554, 3, 2, 0, 994, 3),
+    BakedNode(0x5451, 1, 3, 0, 997, 0),
+    BakedNode(0x5553, 3, 1, 1, 997, 3),
+    BakedNode(0x5551, 3, 2, 1, 1000, 3),
+    BakedNode(0x5554, 2, 0, 2, 1003, 2),
+    BakedNode(0x5551, 3, 1, 2, 1005, 3),
+    BakedNode(0x5554, 2, 2, 2, 1008, 2),
+    BakedNode(0x4505, 1, 0, 3, 1010, 0),
+    BakedNode(0x2554, 1, 1, 3, 1010, 0),
+    BakedNode(0x4525, 1, 0, 4, 1010, 0),
+    BakedNode(0x5345, 1, 3, 0, 1010, 0),
+    BakedNode(0x5453, 1, 2, 1, 1010, 0),
+    BakedNode(0x5534, 1, 1, 2, 1010, 0),
+    BakedNode(0x4553, 1, 0, 3, 1010, 0),
+    BakedNode(0x4435, 1, 2, 0, 1010, 0),
+    BakedNode(0x4335, 1, 1, 1, 1010, 0),
+    BakedNode(0x5552, 2, 2, 1, 1010, 2),
+    BakedNode(0x4354, 1, 0, 2, 1012, 0),
+    BakedNode(0x4055, 1, 1, 2, 1012, 0),
+    BakedNode(0x1455, 1, 2, 2, 1012, 0),
+    BakedNode(0x4155, 1, 1, 3, 1012, 0),
+    BakedNode(0x5435, 1, 3, 0, 1012, 0),
+    BakedNode(0x5354, 1, 2, 1, 1012, 0),
+    BakedNode(0x4535, 1, 1, 2, 1012, 0),
+    BakedNode(0x1112, 1, 1, 0, 1012, 0),
+    BakedNode(0x1221, 1, 2, 0, 1012, 0),
+    BakedNode(0x1222, 1, 3, 0, 1012, 0),
+    BakedNode(0x2111, 1, 0, 1, 1012, 0),
+    BakedNode(0x2112, 1, 1, 1, 1012, 0),
+    BakedNode(0x2221, 1, 2, 1, 1012, 0),
+    BakedNode(0x3125, 5, 1, 0, 1012, 5),
+    BakedNode(0x1100, 1, 2, 0, 1017, 0),
+    BakedNode(0x5522, 5, 0, 1, 1017, 5),
+    BakedNode(0x5555, 2, 1, 1, 1022, 2),
+    BakedNode(0x5112, 1, 2, 1, 1024, 0),
+    BakedNode(0x5555, 2, 0, 2, 1024, 2),
+    BakedNode(0x5544, 2, 1, 2, 1026, 2),
+    BakedNode(0x1412, 1, 2, 2, 1028, 0),
+    BakedNode(0x2414, 1, 0, 3, 1028, 0),
+    BakedNode(0x2411, 1, 0, 4, 1028, 0),
+    BakedNode(0x5150, 1, 1, 0, 1028, 0),
+    BakedNode(0x5555, 2, 2, 0, 1028, 2),
+    BakedNode(0x5555, 2, 3, 0, 1030, 2),
+    BakedNode(0x5553, 4, 0, 1, 1032, 4),
+    BakedNode(0x5554, 3, 1, 1, 1036, 3),
+    BakedNode(0x2420, 1, 2, 1, 1039, 0),
+    BakedNode(0x5555, 2, 0, 2, 1039, 2),
+    BakedNode(0x5552, 2, 1, 2, 1041, 2),
+    BakedNode(0x420, 1, 2, 2, 1043, 0),
+    BakedNode(0x2400, 1, 1, 3, 1043, 0),
+    BakedNode(0x5420, 1, 2, 0, 1043, 0),
+    BakedNode(0x5330, 1, 3, 0, 1043, 0),
+    BakedNode(0x4430, 1, 1, 1, 1043, 0),
+    BakedNode(0x5530, 1, 2, 1, 1043, 0),
+    BakedNode(0x430, 1, 1, 2, 1043, 0),
+    BakedNode(0x5030, 1, 2, 2, 1043, 0),
+    BakedNode(0x5553, 2, 1, 0, 1043, 2),
+    BakedNode(0x5553, 2, 2, 0, 1045, 2),
+    BakedNode(0x5555, 2, 3, 0, 1047, 2),
+    BakedNode(0x5523, 4, 0, 1, 1049, 4),
+    BakedNode(0x4001, 5, 1, 1, 1053, 5),
+    BakedNode(0x5555, 2, 2, 1, 1058, 2),
+    BakedNode(0x5424, 5, 0, 2, 1060, 5),
+    BakedNode(0x5554, 3, 1, 2, 1065, 3),
+    BakedNode(0x2511, 1, 2, 2, 1068, 0),
+    BakedNode(0x5555, 2, 0, 3, 1068, 2),
+    BakedNode(0x1512, 1, 1, 3, 1070, 0),
+    BakedNode(0x1251, 1, 0, 4, 1070, 0),
+    BakedNode(0x4243, 8, 0, 0, 1070, 7),
+    BakedNode(0x5555, 2, 1, 0, 1077, 2),
+    BakedNode(0x5554, 4, 2, 0, 1079, 4),
+    BakedNode(0x5555, 2, 3, 0, 1083, 2),
+    BakedNode(0x5423, 7, 0, 1, 1085, 7),
+    BakedNode(0x5325, 7, 1, 1, 1092, 7),
+    BakedNode(0x5505, 4, 2, 1, 1099, 4),
+    BakedNode(0x5351, 9, 0, 2, 1103, 9),
+    BakedNode(0x5555, 2, 1, 2, 1112, 2),
+    BakedNode(0x1050, 1, 2, 2, 1114, 0),
+    BakedNode(0x5555, 2, 0, 3, 1114, 2),
+    BakedNode(0x150, 1, 1, 3, 1116, 0),
+    BakedNode(0x5535, 2, 1, 0, 1116, 2),
+    BakedNode(0x5234, 1, 2, 0, 1118, 0),
+    BakedNode(0x5533, 2, 0, 1, 1118, 2),
+    BakedNode(0x5350, 1, 1, 1, 1120, 0),
+    BakedNode(0x5413, 1, 2, 1, 1120, 0),
+    BakedNode(0x2450, 1, 0, 2, 1120, 0),
+    BakedNode(0x5140, 1, 1, 2, 1120, 0),
+    BakedNode(0x1450, 1, 0, 3, 1120, 0),
+    BakedNode(0x5005, 5, 0, 0, 1120, 5),
+    BakedNode(0x4400, 10, 1, 0, 1125, 8),
+    BakedNode(0x4511, 6, 2, 0, 1133, 6),
+    BakedNode(0x5553, 3, 3, 0, 1139, 3),
+    BakedNode(0x5532, 8, 0, 1, 1142, 7),
+    BakedNode(0x5521, 6, 1, 1, 1149, 6),
+    BakedNode(0x5553, 3, 2, 1, 1155, 3),
+    BakedNode(0x2143, 7, 0, 2, 1158, 7),
+    BakedNode(0x5544, 3, 1, 2, 1165, 3),
+    BakedNode(0x4311, 1, 2, 2, 1168, 0),
+    BakedNode(0x5555, 2, 0, 3, 1168, 2),
+    BakedNode(0x4113, 1, 1, 3, 1170, 0),
+    BakedNode(0x3141, 1, 0, 4, 1170, 0),
+    BakedNode(0x5554, 3, 1, 0, 1170, 3),
+    BakedNode(0x5555, 2, 2, 0, 1173, 2),
+    BakedNode(0x3452, 1, 3, 0, 1175, 0),
+    BakedNode(0x5554, 3, 0, 1, 1175, 3),
+    BakedNode(0x5554, 3, 1, 1, 1178, 3),
+    BakedNode(0x5555, 2, 2, 1, 1181, 2),
+    BakedNode(0x5534, 4, 0, 2, 1183, 4),
+    BakedNode(0x5555, 2, 1, 2, 1187, 2),
+    BakedNode(0x4235, 1, 0, 3, 1189, 0),
+    BakedNode(0x5340, 1, 2, 0, 1189, 0),
+    BakedNode(0x4530, 1, 1, 1, 1189, 0),
+    BakedNode(0x5553, 4, 2, 0, 1189, 4),
+    BakedNode(0x3542, 1, 3, 0, 1193, 0),
+    BakedNode(0x5303, 7, 1, 1, 1193, 7),
+    BakedNode(0x3524, 1, 2, 1, 1200, 0),
+    BakedNode(0x5554, 2, 0, 2, 1200, 2),
+    BakedNode(0x514, 1, 1, 2, 1202, 0),
+    BakedNode(0x3145, 1, 2, 2, 1202, 0),
+    BakedNode(0x5554, 2, 0, 3, 1202, 2),
+    BakedNode(0x5554, 2, 1, 3, 1204, 2),
+    BakedNode(0x4315, 1, 0, 4, 1206, 0),
+    BakedNode(0x4350, 1, 1, 1, 1206, 0),
+    BakedNode(0x3405, 1, 0, 2, 1206, 0),
+    BakedNode(0x3045, 1, 2, 0, 1206, 0),
+    BakedNode(0x3504, 1, 1, 1, 1206, 0),
+    BakedNode(0x3054, 1, 0, 2, 1206, 0),
+    BakedNode(0x2010, 1, 1, 0, 1206, 0),
+    BakedNode(0x2410, 1, 2, 0, 1206, 0),
+    BakedNode(0x5525, 2, 0, 1, 1206, 2),
+    BakedNode(0x2110, 1, 1, 1, 1208, 0),
+    BakedNode(0x1240, 1, 0, 2, 1208, 0),
+    BakedNode(0x230, 1, 1, 0, 1208, 0),
+    BakedNode(0x310, 1, 2, 0, 1208, 0),
+    BakedNode(0x2230, 1, 0, 1, 1208, 0),
+    BakedNode(0x1310, 1, 1, 1, 1208, 0),
+    BakedNode(0x4230, 1, 0, 2, 1208, 0),
+    BakedNode(0x4310, 1, 1, 2, 1208, 0),
+    BakedNode(0x5310, 1, 1, 0, 1208, 0),
+    BakedNode(0x5230, 1, 1, 1, 1208, 0),
+    BakedNode(0x211, 1, 1, 0, 1208, 0),
+    BakedNode(0x1213, 1, 2, 0, 1208, 0),
+    BakedNode(0x4213, 1, 3, 0, 1208, 0),
+    BakedNode(0x5554, 2, 1, 1, 1208, 2),
+    BakedNode(0x5553, 2, 2, 1, 1210, 2),
+    BakedNode(0x3112, 1, 0, 2, 1212, 0),
+    BakedNode(0x3241, 1, 1, 2, 1212, 0),
+    BakedNode(0x3412, 1, 0, 3, 1212, 0),
+    BakedNode(0x3020, 1, 1, 0, 1212, 0),
+    BakedNode(0x3420, 1, 2, 0, 1212, 0),
+    BakedNode(0x3100, 1, 0, 1, 1212, 0),
+    BakedNode(0x3231, 1, 1, 1, 1212, 0),
+    BakedNode(0x5554, 2, 0, 2, 1212, 2),
+    BakedNode(0x5213, 1, 1, 2, 1214, 0),
+    BakedNode(0x5545, 2, 0, 3, 1214, 2),
+    BakedNode(0x3130, 1, 0, 0, 1216, 0),
+    BakedNode(0x3320, 1, 0, 1, 1216, 0),
+    BakedNode(0x3011, 1, 1, 0, 1216, 0),
+    BakedNode(0x3014, 1, 2, 0, 1216, 0),
+    BakedNode(0x3202, 1, 0, 1, 1216, 0),
+    BakedNode(0x3204, 1, 1, 1, 1216, 0),
+    BakedNode(0x3512, 1, 2, 1, 1216, 0),
+    BakedNode(0x215, 1, 1, 2, 1216, 0),
+    BakedNode(0x3251, 1, 0, 3, 1216, 0),
+    BakedNode(0x3150, 1, 1, 0, 1216, 0),
+    BakedNode(0x3520, 1, 1, 1, 1216, 0),
+    BakedNode(0x3013, 1, 0, 0, 1216, 0),
+    BakedNode(0x3015, 1, 1, 0, 1216, 0),
+    BakedNode(0x3203, 1, 0, 1, 1216, 0),
+    BakedNode(0x3205, 1, 1, 1, 1216, 0),
+    BakedNode(0x2, 1, 1, 0, 1216, 0),
+    BakedNode(0x22, 1, 2, 0, 1216, 0),
+    BakedNode(0x2002, 1, 1, 1, 1216, 0),
+    BakedNode(0x2022, 1, 2, 1, 1216, 0),
+    BakedNode(0x5111, 4, 1, 0, 1216, 4),
+    BakedNode(0x5555, 2, 2, 0, 1220, 2),
+    BakedNode(0x4402, 1, 3, 0, 1222, 0),
+    BakedNode(0x5350, 4, 0, 1, 1222, 4),
+    BakedNode(0x5555, 2, 1, 1, 1226, 2),
+    BakedNode(0x402, 1, 2, 1, 1228, 0),
+    BakedNode(0x5555, 2, 0, 2, 1228, 2),
+    BakedNode(0x5552, 3, 1, 2, 1230, 3),
+    BakedNode(0x2402, 1, 2, 2, 1233, 0),
+    BakedNode(0x424, 1, 0, 3, 1233, 0),
+    BakedNode(0x422, 1, 1, 3, 1233, 0),
+    BakedNode(0x5500, 4, 0, 0, 1233, 4),
+    BakedNode(0x5411, 3, 1, 0, 1237, 3),
+    BakedNode(0x5543, 3, 2, 0, 1240, 3),
+    BakedNode(0x5553, 2, 3, 0, 1243, 2),
+    BakedNode(0x5154, 5, 0, 1, 1245, 5),
+    BakedNode(0x5552, 3, 1, 1, 1250, 3),
+    BakedNode(0x2432, 1, 2, 1, 1253, 0),
+    BakedNode(0x5555, 2, 0, 2, 1253, 2),
+    BakedNode(0x5554, 2, 1, 2, 1255, 2),
+    BakedNode(0x2434, 1, 2, 2, 1257, 0),
+    BakedNode(0x4432, 1, 1, 3, 1257, 0),
+    BakedNode(0x5544, 2, 1, 0, 1257, 2),
+    BakedNode(0x5531, 1, 2, 0, 1259, 0),
+    BakedNode(0x5401, 1, 0, 1, 1259, 0),
+    BakedNode(0x5131, 1, 1, 1, 1259, 0),
+    BakedNode(0x5432, 1, 2, 1, 1259, 0),
+    BakedNode(0x5421, 1, 0, 2, 1259, 0),
+    BakedNode(0x5331, 1, 1, 2, 1259, 0),
+    BakedNode(0x5511, 3, 1, 0, 1259, 3),
+    BakedNode(0x5533, 2, 2, 0, 1262, 2),
+    BakedNode(0x5525, 3, 3, 0, 1264, 3),
+    BakedNode(0x2323, 1, 0, 1, 1267, 0),
+    BakedNode(0x5544, 3, 1, 1, 1267, 3),
+    BakedNode(0x5505, 4, 2, 1, 1270, 4),
+    BakedNode(0x5544, 3, 0, 2, 1274, 3),
+    BakedNode(0x5525, 3, 1, 2, 1277, 3),
+    BakedNode(0x52, 1, 2, 2, 1280, 0),
+    BakedNode(0x5555, 2, 0, 3, 1280, 2),
+    BakedNode(0x5525, 2, 1, 3, 1282, 2),
+    BakedNode(0x4151, 7, 0, 0, 1284, 6),
+    BakedNode(0x2121, 7, 1, 0, 1290, 7),
+    BakedNode(0x5542, 5, 2, 0, 1297, 5),
+    BakedNode(0x5555, 2, 3, 0, 1302, 2),
+    BakedNode(0x4334, 12, 0, 1, 1304, 10),
+    BakedNode(0x5300, 7, 1, 1, 1314, 7),
+    BakedNode(0x5554, 3, 2, 1, 1321, 3),
+    BakedNode(0x2541, 9, 0, 2, 1324, 9),
+    BakedNode(0x5551, 5, 1, 2, 1333, 5),
+    BakedNode(0x5325, 1, 2, 2, 1338, 0),
+    BakedNode(0x5555, 2, 0, 3, 1338, 2),
+    BakedNode(0x5523, 1, 1, 3, 1340, 0),
+    BakedNode(0x2535, 1, 0, 4, 1340, 0),
+    BakedNode(0x5553, 3, 1, 0, 1340, 3),
+    BakedNode(0x5533, 2, 2, 0, 1343, 2),
+    BakedNode(0x5034, 1, 3, 0, 1345, 0),
+    BakedNode(0x5555, 2, 0, 1, 1345, 2),
+    BakedNode(0x5554, 3, 1, 1, 1347, 3),
+    BakedNode(0x1434, 1, 2, 1, 1350, 0),
+    BakedNode(0x5553, 2, 0, 2, 1350, 2),
+    BakedNode(0x435, 1, 1, 2, 1352, 0),
+    BakedNode(0x5403, 1, 0, 3, 1352, 0),
+    BakedNode(0x1500, 6, 0, 0, 1352, 6),
+    BakedNode(0x5350, 7, 1, 0, 1358, 7),
+    BakedNode(0x5533, 7, 2, 0, 1365, 7),
+    BakedNode(0x2343, 1, 3, 0, 1372, 0),
+    BakedNode(0x5052, 10, 0, 1, 1372, 8),
+    BakedNode(0x2325, 12, 1, 1, 1380, 8),
+    BakedNode(0x5553, 3, 2, 1, 1388, 3),
+    BakedNode(0x4452, 8, 0, 2, 1391, 8),
+    BakedNode(0x5544, 4, 1, 2, 1399, 4),
+    BakedNode(0x2344, 1, 2, 2, 1403, 0),
+    BakedNode(0x5552, 3, 0, 3, 1403, 3),
+    BakedNode(0x4342, 1, 1, 3, 1406, 0),
+    BakedNode(0x4324, 1, 0, 4, 1406, 0),
+    BakedNode(0x5151, 5, 2, 0, 1406, 5),
+    BakedNode(0x534, 1, 3, 0, 1411, 0),
+    BakedNode(0x5105, 6, 1, 1, 1411, 6),
+    BakedNode(0x2541, 1, 2, 1, 1417, 0),
+    BakedNode(0x5151, 7, 0, 2, 1417, 7),
+    BakedNode(0x5551, 4, 1, 2, 1424, 4),
+    BakedNode(0x4532, 1, 2, 2, 1428, 0),
+    BakedNode(0x5542, 5, 0, 3, 1428, 5),
+    BakedNode(0x5554, 2, 1, 3, 1433, 2),
+    BakedNode(0x5342, 1, 0, 4, 1435, 0),
+    BakedNode(0x5341, 1, 3, 0, 1435, 0),
+    BakedNode(0x4531, 1, 2, 1, 1435, 0),
+    BakedNode(0x5134, 1, 1, 2, 1435, 0),
+    BakedNode(0x1435, 1, 0, 3, 1435, 0),
+    BakedNode(0x5445, 3, 2, 0, 1435, 3),
+    BakedNode(0x5553, 2, 3, 0, 1438, 2),
+    BakedNode(0x5523, 6, 1, 1, 1440, 6),
+    BakedNode(0x5544, 3, 2, 1, 1446, 3),
+    BakedNode(0x5533, 5, 0, 2, 1449, 5),
+    BakedNode(0x5553, 3, 1, 2, 1454, 3),
+    BakedNode(0x5554, 2, 2, 2, 1457, 2),
+    BakedNode(0x5543, 4, 0, 3, 1459, 4),
+    BakedNode(0x5545, 2, 1, 3, 1463, 2),
+    BakedNode(0x1524, 1, 0, 4, 1465, 0),
+    BakedNode(0x1453, 1, 3, 0, 1465, 0),
+    BakedNode(0x5143, 1, 2, 1, 1465, 0),
+    BakedNode(0x4351, 1, 1, 2, 1465, 0),
+    BakedNode(0x5554, 2, 0, 3, 1465, 2),
+    BakedNode(0x4153, 1, 3, 0, 1467, 0),
+    BakedNode(0x1543, 1, 2, 1, 1467, 0),
+    BakedNode(0x1354, 1, 1, 2, 1467, 0),
+    BakedNode(0x1345, 1, 0, 3, 1467, 0),
+    BakedNode(0x5552, 2, 3, 0, 1467, 2),
+    BakedNode(0x5525, 2, 1, 1, 1469, 2),
+    BakedNode(0x1312, 1, 2, 1, 1471, 0),
+    BakedNode(0x5151, 3, 0, 2, 1471, 3),
+    BakedNode(0x5554, 2, 1, 2, 1474, 2),
+    BakedNode(0x4312, 1, 2, 2, 1476, 0),
+    BakedNode(0x5452, 4, 0, 3, 1476, 4),
+    BakedNode(0x1234, 1, 1, 3, 1480, 0),
+    BakedNode(0x4231, 1, 0, 4, 1480, 0),
+    BakedNode(0x5555, 2, 1, 0, 1480, 2),
+    BakedNode(0x5555, 2, 2, 0, 1482, 2),
+    BakedNode(0x4320, 1, 3, 0, 1484, 0),
+    BakedNode(0x5151, 3, 0, 1, 1484, 3),
+    BakedNode(0x5545, 3, 1, 1, 1487, 3),
+    BakedNode(0x2320, 1, 2, 1, 1490, 0),
+    BakedNode(0x5554, 3, 0, 2, 1490, 3),
+    BakedNode(0x2340, 1, 1, 2, 1493, 0),
+    BakedNode(0x2430, 1, 0, 3, 1493, 0),
+    BakedNode(0x1330, 1, 0, 0, 1493, 0),
+    BakedNode(0x5130, 1, 1, 0, 1493, 0),
+    BakedNode(0x2330, 1, 0, 1, 1493, 0),
+    BakedNode(0x5320, 1, 1, 1, 1493, 0),
+    BakedNode(0x5554, 2, 2, 0, 1493, 2),
+    BakedNode(0x5554, 2, 3, 0, 1495, 2),
+    BakedNode(0x5554, 2, 1, 1, 1497, 2),
+    BakedNode(0x5543, 3, 2, 1, 1499, 3),
+    BakedNode(0x5545, 3, 0, 2, 1502, 3),
+    BakedNode(0x5011, 6, 1, 2, 1505, 6),
+    BakedNode(0x2201, 1, 2, 2, 1511, 0),
+    BakedNode(0x5554, 3, 0, 3, 1511, 3),
+    BakedNode(0x1202, 1, 1, 3, 1514, 0),
+    BakedNode(0x2012, 1, 0, 4, 1514, 0),
+    BakedNode(0x314, 1, 1, 0, 1514, 0),
+    BakedNode(0x2313, 1, 2, 0, 1514, 0),
+    BakedNode(0x2315, 1, 3, 0, 1514, 0),
+    BakedNode(0x5554, 2, 0, 1, 1514, 2),
+    BakedNode(0x5552, 2, 1, 1, 1516, 2),
+    BakedNode(0x2150, 1, 2, 1, 1518, 0),
+    BakedNode(0x5553, 2, 0, 2, 1518, 2),
+    BakedNode(0x5554, 2, 1, 2, 1520, 2),
+    BakedNode(0x5551, 2, 0, 3, 1522, 2),
+    BakedNode(0x1530, 1, 1, 0, 1524, 0),
+    BakedNode(0x1350, 1, 0, 1, 1524, 0),
+    BakedNode(0x2530, 1, 1, 1, 1524, 0),
+    BakedNode(0x2350, 1, 0, 2, 1524, 0),
+    BakedNode(0x5501, 4, 2, 0, 1524, 4),
+    BakedNode(0x5511, 2, 3, 0, 1528, 2),
+    BakedNode(0x5551, 5, 1, 1, 1530, 5),
+    BakedNode(0x5554, 2, 2, 1, 1535, 2),
+    BakedNode(0x5321, 6, 0, 2, 1537, 6),
+    BakedNode(0x5553, 2, 1, 2, 1543, 2),
+    BakedNode(0x4013, 1, 2, 2, 1545, 0),
+    BakedNode(0x5142, 4, 0, 3, 1545, 4),
+    BakedNode(0x3401, 1, 1, 3, 1549, 0),
+    BakedNode(0x5554, 2, 0, 4, 1549, 2),
+    BakedNode(0x5203, 1, 1, 0, 1551, 0),
+    BakedNode(0x5554, 2, 2, 0, 1551, 2),
+    BakedNode(0x313, 1, 3, 0, 1553, 0),
+    BakedNode(0x235, 1, 0, 1, 1553, 0),
+    BakedNode(0x5553, 2, 1, 1, 1553, 2),
+    BakedNode(0x3032, 1, 0, 2, 1555, 0),
+    BakedNode(0x3301, 1, 1, 2, 1555, 0),
+    BakedNode(0x3031, 1, 0, 3, 1555, 0),
+    BakedNode(0x3103, 1, 3, 0, 1555, 0),
+    BakedNode(0x3023, 1, 1, 1, 1555, 0),
+    BakedNode(0x5554, 2, 2, 1, 1555, 2),
+    BakedNode(0x3052, 1, 1, 2, 1557, 0),
+    BakedNode(0x3501, 1, 2, 2, 1557, 0),
+    BakedNode(0x253, 1, 0, 3, 1557, 0),
+    BakedNode(0x3051, 1, 1, 3, 1557, 0),
+    BakedNode(0x513, 1, 0, 4, 1557, 0),
+    BakedNode(0x3201, 1, 2, 0, 1557, 0),
+    BakedNode(0x1230, 1, 1, 1, 1557, 0),
+    BakedNode(0x3012, 1, 0, 2, 1557, 0),
+    BakedNode(0x5553, 2, 2, 0, 1557, 2),
+    BakedNode(0x324, 1, 3, 0, 1559, 0),
+    BakedNode(0x5511, 2, 1, 1, 1559, 2),
+    BakedNode(0x5535, 2, 2, 1, 1561, 2),
+    BakedNode(0x5522, 2, 0, 2, 1563, 2),
+    BakedNode(0x5554, 2, 1, 2, 1565, 2),
+    BakedNode(0x341, 1, 2, 2, 1567, 0),
+    BakedNode(0x4021, 1, 0, 3, 1567, 0),
+    BakedNode(0x4301, 1, 1, 3, 1567, 0),
+    BakedNode(0x5021, 1, 1, 0, 1567, 0),
+    BakedNode(0x5301, 1, 0, 1, 1567, 0),
+    BakedNode(0x1321, 1, 1, 1, 1567, 0),
+    BakedNode(0x4321, 1, 2, 1, 1567, 0),
+    BakedNode(0x2321, 1, 1, 2, 1567, 0),
+    BakedNode(0x1303, 1, 2, 0, 1567, 0),
+    BakedNode(0x5522, 2, 3, 0, 1567, 2),
+    BakedNode(0x5122, 4, 1, 1, 1569, 4),
+    BakedNode(0x5552, 3, 2, 1, 1573, 3),
+    BakedNode(0x5151, 3, 0, 2, 1576, 3),
+    BakedNode(0x5553, 2, 1, 2, 1579, 2),
+    BakedNode(0x5554, 2, 2, 2, 1581, 2),
+    BakedNode(0x4122, 6, 0, 3, 1583, 6),
+    BakedNode(0x4023, 1, 1, 3, 1589, 0),
+    BakedNode(0x423, 1, 0, 4, 1589, 0),
+    BakedNode(0x5553, 2, 1, 0, 1589, 2),
+    BakedNode(0x1324, 1, 2, 0, 1591, 0),
+    BakedNode(0x521, 1, 3, 0, 1591, 0),
+    BakedNode(0x2331, 1, 0, 1, 1591, 0),
+    BakedNode(0x351, 1, 1, 1, 1591, 0),
+    BakedNode(0x325, 1, 2, 1, 1591, 0),
+    BakedNode(0x5555, 2, 0, 2, 1591, 2),
+    BakedNode(0x5023, 1, 1, 2, 1593, 0),
+    BakedNode(0x5302, 1, 0, 3, 1593, 0),
+    BakedNode(0x2511, 4, 2, 0, 1593, 4),
+    BakedNode(0x5311, 3, 3, 0, 1597, 3),
+    BakedNode(0x5553, 2, 1, 1, 1600, 2),
+    BakedNode(0x5504, 4, 2, 1, 1602, 4),
+    BakedNode(0x5522, 2, 0, 2, 1606, 2),
+    BakedNode(0x5432, 5, 1, 2, 1608, 5),
+    BakedNode(0x5545, 2, 2, 2, 1613, 2),
+    BakedNode(0x5542, 3, 0, 3, 1615, 3),
+    BakedNode(0x5554, 2, 1, 3, 1618, 2),
+    BakedNode(0x1034, 1, 0, 4, 1620, 0),
+    BakedNode(0x5554, 2, 2, 0, 1620, 2),
+    BakedNode(0x5542, 4, 1, 1, 1622, 4),
+    BakedNode(0x1305, 1, 2, 1, 1626, 0),
+    BakedNode(0x5554, 2, 0, 2, 1626, 2),
+    BakedNode(0x5553, 3, 1, 2, 1628, 3),
+    BakedNode(0x125, 1, 2, 2, 1631, 0),
+    BakedNode(0x5551, 3, 0, 3, 1631, 3),
+    BakedNode(0x2051, 1, 1, 3, 1634, 0),
+    BakedNode(0x5552, 2, 0, 4, 1634, 2),
+    BakedNode(0x5123, 1, 2, 0, 1636, 0),
+    BakedNode(0x2351, 1, 1, 1, 1636, 0),
+    BakedNode(0x1325, 1, 0, 2, 1636, 0),
+    BakedNode(0x5554, 2, 2, 0, 1636, 2),
+    BakedNode(0x2035, 1, 3, 0, 1638, 0),
+    BakedNode(0x5542, 4, 1, 1, 1638, 4),
+    BakedNode(0x1052, 1, 2, 1, 1642, 0),
+    BakedNode(0x2143, 1, 0, 2, 1642, 0),
+    BakedNode(0x5553, 4, 1, 2, 1642, 4),
+    BakedNode(0x5554, 2, 2, 2, 1646, 2),
+    BakedNode(0x5553, 2, 0, 3, 1648, 2),
+    BakedNode(0x1503, 1, 1, 3, 1650, 0),
+    BakedNode(0x153, 1, 0, 4, 1650, 0),
+    BakedNode(0x2531, 1, 3, 0, 1650, 0),
+    BakedNode(0x5132, 1, 2, 1, 1650, 0),
+    BakedNode(0x1523, 1, 1, 2, 1650, 0),
+    BakedNode(0x1352, 1, 0, 3, 1650, 0),
+    BakedNode(0x2153, 1, 2, 0, 1650, 0),
+    BakedNode(0x1532, 1, 1, 1, 1650, 0),
+    BakedNode(0x2135, 1, 0, 2, 1650, 0),
+    BakedNode(0x312, 1, 2, 0, 1650, 0),
+    BakedNode(0x3102, 1, 1, 1, 1650, 0),
+    BakedNode(0x231, 1, 0, 2, 1650, 0),
+    BakedNode(0x2013, 1, 2, 0, 1650, 0),
+    BakedNode(0x1203, 1, 1, 1, 1650, 0),
+    BakedNode(0x2130, 1, 0, 2, 1650, 0),
+    BakedNode(0x132, 1, 2, 0, 1650, 0),
+    BakedNode(0x2031, 1, 1, 1, 1650, 0),
+    BakedNode(0x123, 1, 0, 2, 1650, 0),
+    BakedNode(0x1032, 1, 2, 0, 1650, 0),
+    BakedNode(0x2301, 1, 1, 1, 1650, 0),
+    BakedNode(0x1023, 1, 0, 2, 1650, 0),
+    BakedNode(0x1302, 1, 1, 0, 1650, 0),
+    BakedNode(0x321, 1, 0, 1, 1650, 0),
+    BakedNode(0x40, 1, 0, 0, 1650, 0),
+    BakedNode(0x4242, 1, 1, 0, 1650, 0),
+    BakedNode(0x4244, 1, 0, 1, 1650, 0),
+    BakedNode(0x5111, 1, 1, 0, 1650, 0),
+    BakedNode(0x5222, 1, 2, 0, 1650, 0),
+    BakedNode(0x1511, 1, 0, 1, 1650, 0),
+    BakedNode(0x2244, 1, 0, 0, 1650, 0),
+    BakedNode(0x5000, 1, 1, 0, 1650, 0),
+    BakedNode(0x3343, 1, 1, 0, 1650, 0),
+    BakedNode(0x3443, 1, 1, 1, 1650, 0),
+    BakedNode(0x5244, 1, 2, 0, 1650, 0),
+    BakedNode(0x5242, 1, 1, 1, 1650, 0),
+    BakedNode(0x5440, 1, 1, 0, 1650, 0),
+    BakedNode(0x5511, 1, 2, 0, 1650, 0),
+    BakedNode(0x2252, 1, 1, 0, 1650, 0),
+    BakedNode(0x2225, 1, 0, 1, 1650, 0),
+    BakedNode(0x50, 1, 1, 0, 1650, 0),
+    BakedNode(0x1115, 1, 0, 1, 1650, 0),
+    BakedNode(0x400, 1, 0, 0, 1650, 0),
+    BakedNode(0x1411, 1, 1, 0, 1650, 0),
+    BakedNode(0x2224, 1, 1, 0, 1650, 0),
+    BakedNode(0x4222, 1, 0, 1, 1650, 0),
+    BakedNode(0x1114, 1, 1, 0, 1650, 0),
+    BakedNode(0x4000, 1, 0, 1, 1650, 0),
+    BakedNode(0x1414, 1, 1, 0, 1650, 0),
+    BakedNode(0x4400, 1, 0, 1, 1650, 0),
+    BakedNode(0x4224, 1, 1, 0, 1650, 0),
+    BakedNode(0x4111, 1, 0, 1, 1650, 0),
+    BakedNode(0x4414, 1, 1, 0, 1650, 0),
+    BakedNode(0x4411, 1, 0, 1, 1650, 0),
+    BakedNode(0x3433, 1, 2, 0, 1650, 0),
+    BakedNode(0x3444, 1, 0, 1, 1650, 0),
+    BakedNode(0x3344, 1, 0, 2, 1650, 0),
+    BakedNode(0x3353, 1, 1, 0, 1650, 0),
+    BakedNode(0x5224, 1, 2, 0, 1650, 0),
+    BakedNode(0x5400, 1, 1, 1, 1650, 0),
+    BakedNode(0x5050, 1, 2, 0, 1650, 0),
+    BakedNode(0x5252, 1, 3, 0, 1650, 0),
+    BakedNode(0x5255, 1, 2, 2, 1650, 0),
+    BakedNode(0x4500, 1, 1, 0, 1650, 0),
+    BakedNode(0x4245, 1, 0, 2, 1650, 0),
+    BakedNode(0x2245, 1, 0, 3, 1650, 0),
+    BakedNode(0x5414, 1, 1, 0, 1650, 0),
+    BakedNode(0x550, 1, 2, 0, 1650, 0),
+    BakedNode(0x5225, 1, 1, 1, 1650, 0),
+    BakedNode(0x5114, 1, 2, 0, 1650, 0),
+    BakedNode(0x5411, 1, 1, 1, 1650, 0),
+    BakedNode(0x3434, 1, 0, 0, 1650, 0),
+    BakedNode(0x450, 1, 1, 0, 1650, 0),
+    BakedNode(0x2255, 1, 2, 0, 1650, 0),
+    BakedNode(0x4050, 1, 1, 1, 1650, 0),
+    BakedNode(0x1415, 1, 0, 2, 1650, 0),
+    BakedNode(0x4252, 1, 1, 1, 1650, 0),
+    BakedNode(0x4415, 1, 0, 2, 1650, 0),
+    BakedNode(0x3445, 1, 1, 0, 1650, 0),
+    BakedNode(0x5254, 1, 2, 0, 1650, 0),
+    BakedNode(0x3534, 1, 2, 0, 1650, 0),
+    BakedNode(0x3345, 1, 0, 2, 1650, 0),
+    BakedNode(0x3233, 1, 1, 0, 1650, 0),
+    BakedNode(0x3232, 1, 0, 1, 1650, 0),
+    BakedNode(0x200, 1, 0, 1, 1650, 0),
+    BakedNode(0x2200, 1, 0, 2, 1650, 0),
+    BakedNode(0x5212, 1, 1, 0, 1650, 0),
+    BakedNode(0x5215, 1, 2, 0, 1650, 0),
+    BakedNode(0x110, 1, 1, 0, 1650, 0),
+    BakedNode(0x1010, 1, 0, 1, 1650, 0),
+    BakedNode(0x3330, 1, 1, 0, 1650, 0),
+    BakedNode(0x3030, 1, 1, 1, 1650, 0),
+    BakedNode(0x5220, 1, 2, 0, 1650, 0),
+    BakedNode(0x5200, 1, 1, 1, 1650, 0),
+    BakedNode(0x410, 1, 0, 1, 1650, 0),
+    BakedNode(0x4410, 1, 0, 2, 1650, 0),
+    BakedNode(0x3111, 1, 0, 1, 1650, 0),
+    BakedNode(0x3311, 1, 0, 2, 1650, 0),
+    BakedNode(0x3223, 1, 1, 0, 1650, 0),
+    BakedNode(0x3222, 1, 0, 1, 1650, 0),
+    BakedNode(0x3313, 1, 1, 0, 1650, 0),
+    BakedNode(0x3113, 1, 1, 1, 1650, 0),
+    BakedNode(0x1214, 1, 0, 0, 1650, 0),
+    BakedNode(0x2215, 1, 1, 0, 1650, 0),
+    BakedNode(0x3000, 1, 0, 0, 1650, 0),
+    BakedNode(0x4110, 1, 0, 1, 1650, 0),
+    BakedNode(0x4010, 1, 0, 0, 1650, 0),
+    BakedNode(0x4220, 1, 0, 1, 1650, 0),
+    BakedNode(0x3413, 1, 2, 0, 1650, 0),
+    BakedNode(0x3411, 1, 1, 1, 1650, 0),
+    BakedNode(0x3235, 1, 0, 2, 1650, 0),
+    BakedNode(0x3414, 1, 1, 2, 1650, 0),
+    BakedNode(0x3300, 1, 0, 0, 1650, 0),
+    BakedNode(0x510, 1, 1, 0, 1650, 0),
+    BakedNode(0x2250, 1, 1, 1, 1650, 0),
+    BakedNode(0x3234, 1, 0, 0, 1650, 0),
+    BakedNode(0x5214, 1, 1, 0, 1650, 0),
+    BakedNode(0x2240, 1, 0, 0, 1650, 0),
+    BakedNode(0x250, 1, 1, 0, 1650, 0),
+    BakedNode(0x3511, 1, 1, 1, 1650, 0),
+    BakedNode(0x3115, 1, 0, 2, 1650, 0),
+    BakedNode(0x3515, 1, 1, 2, 1650, 0),
+    BakedNode(0x3315, 1, 0, 3, 1650, 0),
+    BakedNode(0x4215, 1, 1, 0, 1650, 0),
+    BakedNode(0x3255, 1, 2, 0, 1650, 0),
+    BakedNode(0x3114, 1, 0, 1, 1650, 0),
+    BakedNode(0x3314, 1, 0, 2, 1650, 0),
+    BakedNode(0x3252, 1, 1, 0, 1650, 0),
+    BakedNode(0x3225, 1, 0, 1, 1650, 0),
+    BakedNode(0x3340, 1, 0, 0, 1650, 0),
+    BakedNode(0x4250, 1, 1, 0, 1650, 0),
+    BakedNode(0x4044, 1, 1, 0, 1650, 0),
+    BakedNode(0x4441, 1, 0, 1, 1650, 0),
+    BakedNode(0x2444, 1, 1, 0, 1650, 0),
+    BakedNode(0x2442, 1, 0, 1, 1650, 0),
+    BakedNode(0x44, 1, 0, 0, 1650, 0),
+    BakedNode(0x4144, 1, 0, 1, 1650, 0),
+    BakedNode(0x444, 1, 0, 0, 1650, 0),
+    BakedNode(0x1444, 1, 0, 1, 1650, 0),
+    BakedNode(0x1141, 1, 1, 0, 1650, 0),
+    BakedNode(0x1441, 1, 1, 1, 1650, 0),
+    BakedNode(0x5333, 1, 1, 0, 1650, 0),
+    BakedNode(0x5551, 1, 3, 0, 1650, 0),
+    BakedNode(0x5505, 1, 2, 1, 1650, 0),
+    BakedNode(0x5522, 1, 2, 0, 1650, 0),
+    BakedNode(0x5525, 1, 3, 0, 1650, 0),
+    BakedNode(0x4443, 1, 0, 0, 1650, 0),
+    BakedNode(0x5141, 1, 1, 0, 1650, 0),
+    BakedNode(0x5552, 1, 3, 0, 1650, 0),
+    BakedNode(0x5044, 1, 1, 0, 1650, 0),
+    BakedNode(0x5441, 1, 2, 0, 1650, 0),
+    BakedNode(0x5144, 1, 1, 1, 1650, 0),
+    BakedNode(0x4343, 1, 0, 0, 1650, 0),
+    BakedNode(0x1541, 1, 1, 0, 1650, 0),
+    BakedNode(0x544, 1, 1, 0, 1650, 0),
+    BakedNode(0x1544, 1, 1, 1, 1650, 0),
+    BakedNode(0x4541, 1, 1, 0, 1650, 0),
+    BakedNode(0x2542, 1, 2, 0, 1650, 0),
+    BakedNode(0x5533, 1, 2, 0, 1650, 0),
+    BakedNode(0x5553, 1, 3, 0, 1650, 0),
+    BakedNode(0x404, 1, 1, 0, 1650, 0),
+    BakedNode(0x4004, 1, 0, 1, 1650, 0),
+    BakedNode(0x5155, 1, 2, 0, 1650, 0),
+    BakedNode(0x4333, 1, 0, 1, 1650, 0),
+    BakedNode(0x1555, 1, 1, 1, 1650, 0),
+    BakedNode(0x2555, 1, 1, 2, 1650, 0),
+    BakedNode(0x2525, 1, 0, 3, 1650, 0),
+    BakedNode(0x4344, 1, 0, 0, 1650, 0),
+    BakedNode(0x1145, 1, 1, 0, 1650, 0),
+    BakedNode(0x5005, 1, 2, 0, 1650, 0),
+    BakedNode(0x5055, 1, 3, 0, 1650, 0),
+    BakedNode(0x45, 1, 0, 1, 1650, 0),
+    BakedNode(0x4145, 1, 0, 2, 1650, 0),
+    BakedNode(0x5151, 1, 2, 0, 1650, 0),
+    BakedNode(0x1551, 1, 1, 1, 1650, 0),
+    BakedNode(0x2552, 1, 2, 1, 1650, 0),
+    BakedNode(0x4433, 1, 0, 0, 1650, 0),
+    BakedNode(0x4522, 1, 1, 0, 1650, 0),
+    BakedNode(0x505, 1, 2, 0, 1650, 0),
+    BakedNode(0x555, 1, 3, 0, 1650, 0),
+    BakedNode(0x1445, 1, 0, 1, 1650, 0),
+    BakedNode(0x2445, 1, 0, 2, 1650, 0),
+    BakedNode(0x2524, 1, 2, 0, 1650, 0),
+    BakedNode(0x5422, 1, 1, 1, 1650, 0),
+    BakedNode(0x5004, 1, 1, 0, 1650, 0),
+    BakedNode(0x4524, 1, 1, 1, 1650, 0),
+    BakedNode(0x504, 1, 1, 0, 1650, 0),
+    BakedNode(0x5424, 1, 1, 1, 1650, 0),
+    BakedNode(0x5344, 1, 1, 0, 1650, 0),
+    BakedNode(0x5535, 1, 3, 0, 1650, 0),
+    BakedNode(0x5433, 1, 1, 0, 1650, 0),
+    BakedNode(0x5353, 1, 2, 0, 1650, 0),
+    BakedNode(0x1545, 1, 1, 1, 1650, 0),
+    BakedNode(0x2545, 1, 1, 2, 1650, 0),
+    BakedNode(0x55, 1, 1, 1, 1650, 0),
+    BakedNode(0x1155, 1, 1, 2, 1650, 0),
+    BakedNode(0x4434, 1, 1, 0, 1650, 0),
+    BakedNode(0x1451, 1, 1, 1, 1650, 0),
+    BakedNode(0x4005, 1, 0, 2, 1650, 0),
+    BakedNode(0x2452, 1, 1, 1, 1650, 0),
+    BakedNode(0x4405, 1, 0, 2, 1650, 0),
+    BakedNode(0x4334, 1, 0, 0, 1650, 0),
+    BakedNode(0x54, 1, 1, 0, 1650, 0),
+    BakedNode(0x4151, 1, 2, 0, 1650, 0),
+    BakedNode(0x1154, 1, 1, 1, 1650, 0),
+    BakedNode(0x454, 1, 1, 0, 1650, 0),
+    BakedNode(0x4451, 1, 2, 0, 1650, 0),
+    BakedNode(0x1454, 1, 1, 1, 1650, 0),
+    BakedNode(0x2454, 1, 2, 0, 1650, 0),
+    BakedNode(0x4425, 1, 0, 2, 1650, 0),
+    BakedNode(0x4054, 1, 1, 0, 1650, 0),
+    BakedNode(0x4154, 1, 1, 1, 1650, 0),
+    BakedNode(0x5434, 1, 2, 0, 1650, 0),
+    BakedNode(0x4453, 1, 1, 1, 1650, 0),
+    BakedNode(0x5355, 1, 2, 1, 1650, 0),
+    BakedNode(0x4353, 1, 2, 0, 1650, 0),
+    BakedNode(0x5334, 1, 1, 1, 1650, 0),
+    BakedNode(0x5335, 1, 1, 2, 1650, 0),
+    BakedNode(0x5054, 1, 2, 0, 1650, 0),
+    BakedNode(0x5405, 1, 1, 1, 1650, 0),
+    BakedNode(0x5154, 1, 2, 1, 1650, 0),
+    BakedNode(0x4534, 1, 2, 0, 1650, 0),
+    BakedNode(0x4345, 1, 0, 2, 1650, 0),
+    BakedNode(0x554, 1, 2, 0, 1650, 0),
+    BakedNode(0x4551, 1, 3, 0, 1650, 0),
+    BakedNode(0x1554, 1, 2, 1, 1650, 0),
+    BakedNode(0x4552, 1, 2, 1, 1650, 0),
+    BakedNode(0x5425, 1, 1, 2, 1650, 0),
+    BakedNode(0x455, 1, 1, 1, 1650, 0),
+    BakedNode(0x2455, 1, 1, 2, 1650, 0),
+    BakedNode(0x100, 1, 1, 0, 1650, 0),
+    BakedNode(0x3133, 1, 2, 0, 1650, 0),
+    BakedNode(0x1000, 1, 0, 1, 1650, 0),
+    BakedNode(0x3332, 1, 1, 1, 1650, 0),
+    BakedNode(0x2232, 1, 0, 2, 1650, 0),
+    BakedNode(0x3331, 1, 0, 0, 1650, 0),
+    BakedNode(0x20, 1, 1, 0, 1650, 0),
+    BakedNode(0x2000, 1, 0, 1, 1650, 0),
+    BakedNode(0x2020, 1, 1, 1, 1650, 0),
+    BakedNode(0x2233, 1, 0, 2, 1650, 0),
+    BakedNode(0x3131, 1, 0, 0, 1650, 0),
+    BakedNode(0x5512, 1, 2, 0, 1650, 0),
+    BakedNode(0x5221, 1, 1, 0, 1650, 0),
+    BakedNode(0x5251, 1, 2, 0, 1650, 0),
+    BakedNode(0x2412, 1, 0, 1, 1650, 0),
+    BakedNode(0x4412, 1, 0, 2, 1650, 0),
+    BakedNode(0x30, 1, 0, 0, 1650, 0),
+    BakedNode(0x5520, 1, 2, 0, 1650, 0),
+    BakedNode(0x4420, 1, 0, 0, 1650, 0),
+    BakedNode(0x5020, 1, 1, 0, 1650, 0),
+    BakedNode(0x5233, 1, 2, 0, 1650, 0),
+    BakedNode(0x3431, 1, 0, 1, 1650, 0),
+    BakedNode(0x5232, 1, 1, 1, 1650, 0),
+    BakedNode(0x5235, 1, 1, 2, 1650, 0),
+    BakedNode(0x330, 1, 0, 0, 1650, 0),
+    BakedNode(0x5100, 1, 1, 0, 1650, 0),
+    BakedNode(0x1440, 1, 0, 1, 1650, 0),
+    BakedNode(0x3432, 1, 0, 0, 1650, 0),
+    BakedNode(0x5412, 1, 1, 0, 1650, 0),
+    BakedNode(0x1400, 1, 0, 0, 1650, 0),
+    BakedNode(0x2440, 1, 0, 1, 1650, 0),
+    BakedNode(0x2202, 1, 0, 0, 1650, 0),
+    BakedNode(0x2223, 1, 1, 0, 1650, 0),
+    BakedNode(0x111, 1, 0, 0, 1650, 0),
+    BakedNode(0x1113, 1, 1, 0, 1650, 0),
+    BakedNode(0x2114, 1, 0, 0, 1650, 0),
+    BakedNode(0x2515, 1, 2, 0, 1650, 0),
+    BakedNode(0x222, 1, 1, 0, 1650, 0),
+    BakedNode(0x3323, 1, 2, 0, 1650, 0),
+    BakedNode(0x202, 1, 0, 1, 1650, 0),
+    BakedNode(0x3322, 1, 1, 1, 1650, 0),
+    BakedNode(0x1311, 1, 1, 0, 1650, 0),
+    BakedNode(0x1011, 1, 2, 0, 1650, 0),
+    BakedNode(0x1313, 1, 0, 1, 1650, 0),
+    BakedNode(0x2241, 1, 1, 1, 1650, 0),
+    BakedNode(0x11, 1, 2, 1, 1650, 0),
+    BakedNode(0x4112, 1, 0, 0, 1650, 0),
+    BakedNode(0x2512, 1, 1, 0, 1650, 0),
+    BakedNode(0x1224, 1, 2, 0, 1650, 0),
+    BakedNode(0x4221, 1, 1, 1, 1650, 0),
+    BakedNode(0x1242, 1, 0, 2, 1650, 0),
+    BakedNode(0x1244, 1, 1, 2, 1650, 0),
+    BakedNode(0x4241, 1, 0, 3, 1650, 0),
+    BakedNode(0x2251, 1, 1, 0, 1650, 0),
+    BakedNode(0x1225, 1, 0, 1, 1650, 0),
+    BakedNode(0x1255, 1, 1, 1, 1650, 0),
+    BakedNode(0x1241, 1, 0, 0, 1650, 0),
+    BakedNode(0x1252, 1, 1, 0, 1650, 0),
+    BakedNode(0x4233, 1, 3, 0, 1650, 0),
+    BakedNode(0x4232, 1, 2, 1, 1650, 0),
+    BakedNode(0x5554, 2, 1, 2, 1650, 2),
+    BakedNode(0x4234, 1, 2, 2, 1652, 0),
+    BakedNode(0x3422, 1, 0, 3, 1652, 0),
+    BakedNode(0x3442, 1, 1, 3, 1652, 0),
+    BakedNode(0x3424, 1, 0, 4, 1652, 0),
+    BakedNode(0x1413, 1, 0, 0, 1652, 0),
+    BakedNode(0x3532, 1, 1, 0, 1652, 0),
+    BakedNode(0x300, 1, 0, 0, 1652, 0),
+    BakedNode(0x2520, 1, 1, 0, 1652, 0),
+    BakedNode(0x2550, 1, 2, 0, 1652, 0),
+    BakedNode(0x1140, 1, 0, 1, 1652, 0),
+    BakedNode(0x2500, 1, 1, 0, 1652, 0),
+    BakedNode(0x1550, 1, 2, 0, 1652, 0),
+    BakedNode(0x3033, 1, 1, 0, 1652, 0),
+    BakedNode(0x4413, 1, 2, 0, 1652, 0),
+    BakedNode(0x5223, 1, 3, 0, 1652, 0),
+    BakedNode(0x3441, 1, 1, 1, 1652, 0),
+    BakedNode(0x5253, 1, 2, 1, 1652, 0),
+    BakedNode(0x3134, 1, 0, 2, 1652, 0),
+    BakedNode(0x2235, 1, 0, 3, 1652, 0),
+    BakedNode(0x4140, 1, 0, 0, 1652, 0),
+    BakedNode(0x4020, 1, 1, 0, 1652, 0),
+    BakedNode(0x2040, 1, 0, 1, 1652, 0),
+    BakedNode(0x5202, 1, 1, 1, 1652, 0),
+    BakedNode(0x5205, 1, 2, 1, 1652, 0),
+    BakedNode(0x3531, 1, 0, 2, 1652, 0),
+    BakedNode(0x5513, 1, 1, 2, 1652, 0),
+    BakedNode(0x4100, 1, 1, 0, 1652, 0),
+    BakedNode(0x1040, 1, 0, 1, 1652, 0),
+    BakedNode(0x520, 1, 1, 1, 1652, 0),
+    BakedNode(0x1150, 1, 0, 2, 1652, 0),
+    BakedNode(0x411, 1, 1, 0, 1652, 0),
+    BakedNode(0x5241, 1, 2, 0, 1652, 0),
+    BakedNode(0x5311, 1, 3, 0, 1652, 0),
+    BakedNode(0x414, 1, 0, 1, 1652, 0),
+    BakedNode(0x5313, 1, 2, 1, 1652, 0),
+    BakedNode(0x2415, 1, 0, 2, 1652, 0),
+    BakedNode(0x5113, 1, 1, 2, 1652, 0),
+    BakedNode(0x5315, 1, 2, 2, 1652, 0),
+    BakedNode(0x3135, 1, 0, 3, 1652, 0),
+    BakedNode(0x140, 1, 0, 0, 1652, 0),
+    BakedNode(0x2050, 1, 1, 0, 1652, 0),
+    BakedNode(0x5011, 1, 1, 0, 1652, 0),
+    BakedNode(0x5015, 1, 2, 0, 1652, 0),
+    BakedNode(0x530, 1, 2, 0, 1652, 0),
+    BakedNode(0x5300, 1, 1, 1, 1652, 0),
+    BakedNode(0x4030, 1, 1, 0, 1652, 0),
+    BakedNode(0x4330, 1, 1, 1, 1652, 0),
+    BakedNode(0x2205, 1, 2, 0, 1652, 0),
+    BakedNode(0x225, 1, 1, 1, 1652, 0),
+    BakedNode(0x205, 1, 2, 1, 1652, 0),
+    BakedNode(0x252, 1, 0, 2, 1652, 0),
+    BakedNode(0x255, 1, 1, 2, 1652, 0),
+    BakedNode(0x5554, 2, 0, 0, 1652, 2),
+    BakedNode(0x3303, 1, 1, 0, 1654, 0),
+    BakedNode(0x515, 1, 0, 1, 1654, 0),
+    BakedNode(0x2204, 1, 1, 1, 1654, 0),
+    BakedNode(0x4204, 1, 2, 1, 1654, 0),
+    BakedNode(0x5555, 2, 0, 2, 1654, 2),
+    BakedNode(0x204, 1, 1, 2, 1656, 0),
+    BakedNode(0x244, 1, 0, 3, 1656, 0),
+    BakedNode(0x4014, 1, 2, 0, 1656, 0),
+    BakedNode(0x3324, 1, 0, 1, 1656, 0),
+    BakedNode(0x14, 1, 1, 1, 1656, 0),
+    BakedNode(0x2514, 1, 2, 1, 1656, 0),
+    BakedNode(0x1015, 1, 1, 2, 1656, 0),
+    BakedNode(0x1254, 1, 0, 3, 1656, 0),
+    BakedNode(0x1014, 1, 0, 0, 1656, 0),
+    BakedNode(0x4314, 1, 0, 1, 1656, 0),
+    BakedNode(0x1315, 1, 0, 2, 1656, 0),
+    BakedNode(0x5545, 2, 1, 0, 1656, 2),
+    BakedNode(0x3003, 1, 0, 1, 1658, 0),
+    BakedNode(0x3522, 1, 2, 1, 1658, 0),
+    BakedNode(0x3523, 1, 1, 2, 1658, 0),
+    BakedNode(0x3552, 1, 2, 2, 1658, 0),
+    BakedNode(0x2253, 1, 0, 3, 1658, 0),
+    BakedNode(0x3525, 1, 1, 3, 1658, 0),
+    BakedNode(0x511, 1, 2, 0, 1658, 0),
+    BakedNode(0x3342, 1, 0, 1, 1658, 0),
+    BakedNode(0x3351, 1, 1, 1, 1658, 0),
+    BakedNode(0x115, 1, 0, 2, 1658, 0),
+    BakedNode(0x4512, 1, 1, 2, 1658, 0),
+    BakedNode(0x1245, 1, 0, 3, 1658, 0),
+    BakedNode(0x114, 1, 0, 0, 1658, 0),
+    BakedNode(0x4313, 1, 1, 0, 1658, 0),
+    BakedNode(0x1513, 1, 2, 0, 1658, 0),
+    BakedNode(0x3153, 1, 2, 0, 1658, 0),
+    BakedNode(0x2243, 1, 3, 0, 1658, 0),
+    BakedNode(0x3155, 1, 1, 1, 1658, 0),
+    BakedNode(0x4243, 1, 2, 1, 1658, 0),
+    BakedNode(0x3551, 1, 0, 2, 1658, 0),
+    BakedNode(0x4223, 1, 1, 2, 1658, 0),
+    BakedNode(0x4251, 1, 0, 3, 1658, 0),
+    BakedNode(0x3341, 1, 1, 0, 1658, 0),
+    BakedNode(0x3144, 1, 2, 0, 1658, 0),
+    BakedNode(0x4011, 1, 0, 1, 1658, 0),
+    BakedNode(0x3143, 1, 0, 0, 1658, 0),
+    BakedNode(0x3151, 1, 1, 0, 1658, 0),
+    BakedNode(0x3035, 1, 0, 1, 1658, 0),
+    BakedNode(0x1540, 1, 1, 1, 1658, 0),
+    BakedNode(0x415, 1, 0, 2, 1658, 0),
+    BakedNode(0x3403, 1, 0, 0, 1658, 0),
+    BakedNode(0x3451, 1, 1, 0, 1658, 0),
+    BakedNode(0x350, 1, 1, 0, 1658, 0),
+    BakedNode(0x5014, 1, 2, 0, 1658, 0),
+    BakedNode(0x4150, 1, 1, 1, 1658, 0),
+    BakedNode(0x3034, 1, 1, 0, 1658, 0),
+    BakedNode(0x340, 1, 0, 1, 1658, 0),
+    BakedNode(0x2540, 1, 1, 1, 1658, 0),
+    BakedNode(0x3404, 1, 0, 0, 1658, 0),
+    BakedNode(0x3425, 1, 1, 0, 1658, 0),
+    BakedNode(0x5204, 1, 2, 0, 1658, 0),
+    BakedNode(0x4520, 1, 1, 1, 1658, 0),
+    BakedNode(0x5314, 1, 2, 1, 1658, 0),
+    BakedNode(0x4300, 1, 0, 2, 1658, 0),
+    BakedNode(0x4340, 1, 0, 0, 1658, 0),
+    BakedNode(0x5243, 1, 1, 0, 1658, 0),
+    BakedNode(0x3043, 1, 1, 0, 1658, 0),
+    BakedNode(0x3503, 1, 2, 0, 1658, 0),
+    BakedNode(0x3044, 1, 0, 1, 1658, 0),
+    BakedNode(0x3505, 1, 1, 2, 1658, 0),
+    BakedNode(0x3004, 1, 1, 1, 1658, 0),
+    BakedNode(0x3304, 1, 2, 1, 1658, 0),
+    BakedNode(0x245, 1, 0, 2, 1658, 0),
+    BakedNode(0x3005, 1, 1, 2, 1658, 0),
+    BakedNode(0x3305, 1, 2, 2, 1658, 0),
+    BakedNode(0x3055, 1, 0, 3, 1658, 0),
+    BakedNode(0x3053, 1, 1, 3, 1658, 0),
+    BakedNode(0x254, 1, 2, 0, 1658, 0),
+    BakedNode(0x4205, 1, 0, 2, 1658, 0),
+    BakedNode(0x4253, 1, 1, 1, 1658, 0),
+    BakedNode(0x4015, 1, 0, 2, 1658, 0),
+    BakedNode(0x3154, 1, 2, 0, 1658, 0),
+    BakedNode(0x4513, 1, 1, 1, 1658, 0),
+    BakedNode(0x1220, 1, 1, 0, 1658, 0),
+    BakedNode(0x1200, 1, 0, 1, 1658, 0),
+    BakedNode(0x212, 1, 0, 0, 1658, 0),
+    BakedNode(0x214, 1, 1, 0, 1658, 0),
+    BakedNode(0x2213, 1, 1, 0, 1658, 0),
+    BakedNode(0x3221, 1, 0, 1, 1658, 0),
+    BakedNode(0x3312, 1, 0, 0, 1658, 0),
+    BakedNode(0x3140, 1, 0, 1, 1658, 0),
+    BakedNode(0x2510, 1, 1, 0, 1658, 0),
+    BakedNode(0x1250, 1, 0, 1, 1658, 0),
+    BakedNode(0x1, 1, 1, 0, 1658, 0),
+    BakedNode(0x101, 1, 2, 0, 1658, 0),
+    BakedNode(0x1001, 1, 1, 1, 1658, 0),
+    BakedNode(0x1101, 1, 2, 1, 1658, 0),
+    BakedNode(0x5002, 1, 1, 0, 1658, 0),
+    BakedNode(0x5502, 1, 2, 0, 1658, 0),
+    BakedNode(0x1121, 1, 0, 0, 1658, 0),
+    BakedNode(0x2333, 1, 1, 0, 1658, 0),
+    BakedNode(0x333, 1, 1, 1, 1658, 0),
+    BakedNode(0x33, 1, 0, 2, 1658, 0),
+    BakedNode(0x2332, 1, 0, 0, 1658, 0),
+    BakedNode(0x5052, 1, 2, 0, 1658, 0),
+    BakedNode(0x2121, 1, 0, 0, 1658, 0),
+    BakedNode(0x5025, 1, 2, 0, 1658, 0),
+    BakedNode(0x442, 1, 1, 0, 1658, 0),
+    BakedNode(0x5022, 1, 2, 0, 1658, 0),
+    BakedNode(0x2404, 1, 0, 1, 1658, 0),
+    BakedNode(0x5101, 1, 2, 0, 1658, 0),
+    BakedNode(0x5501, 1, 3, 0, 1658, 0),
+    BakedNode(0x5001, 1, 2, 1, 1658, 0),
+    BakedNode(0x5051, 1, 1, 2, 1658, 0),
+    BakedNode(0x5033, 1, 1, 0, 1658, 0),
+    BakedNode(0x401, 1, 2, 0, 1658, 0),
+    BakedNode(0x1401, 1, 2, 1, 1658, 0),
+    BakedNode(0x441, 1, 1, 0, 1658, 0),
+    BakedNode(0x2421, 1, 0, 1, 1658, 0),
+    BakedNode(0x433, 1, 1, 1, 1658, 0),
+    BakedNode(0x2441, 1, 0, 0, 1658, 0),
+    BakedNode(0x2433, 1, 1, 0, 1658, 0),
+    BakedNode(0x1131, 1, 1, 0, 1658, 0),
+    BakedNode(0x5121, 1, 2, 0, 1658, 0),
+    BakedNode(0x1331, 1, 0, 1, 1658, 0),
+    BakedNode(0x5035, 1, 1, 1, 1658, 0),
+    BakedNode(0x5521, 1, 1, 2, 1658, 0),
+    BakedNode(0x4401, 1, 0, 0, 1658, 0),
+    BakedNode(0x5402, 1, 2, 0, 1658, 0),
+    BakedNode(0x1421, 1, 0, 1, 1658, 0),
+    BakedNode(0x5332, 1, 1, 0, 1658, 0),
+    BakedNode(0x5532, 1, 2, 0, 1658, 0),
+    BakedNode(0x434, 1, 1, 0, 1658, 0),
+    BakedNode(0x4421, 1, 0, 1, 1658, 0),
+    BakedNode(0x1431, 1, 0, 1, 1658, 0),
+    BakedNode(0x4431, 1, 0, 2, 1658, 0),
+    BakedNode(0x2322, 1, 0, 0, 1658, 0),
+    BakedNode(0x2122, 1, 0, 1, 1658, 0),
+    BakedNode(0x1122, 1, 0, 2, 1658, 0),
+    BakedNode(0x3, 1, 1, 0, 1658, 0),
+    BakedNode(0x303, 1, 1, 1, 1658, 0),
+    BakedNode(0x522, 1, 2, 0, 1658, 0),
+    BakedNode(0x2502, 1, 1, 1, 1658, 0),
+    BakedNode(0x552, 1, 1, 2, 1658, 0),
+    BakedNode(0x2042, 1, 1, 0, 1658, 0),
+    BakedNode(0x4022, 1, 0, 1, 1658, 0),
+    BakedNode(0x4042, 1, 1, 1, 1658, 0),
+    BakedNode(0x4002, 1, 1, 0, 1658, 0),
+    BakedNode(0x2505, 1, 3, 0, 1658, 0),
+    BakedNode(0x42, 1, 0, 1, 1658, 0),
+    BakedNode(0x525, 1, 2, 1, 1658, 0),
+    BakedNode(0x2024, 1, 1, 0, 1658, 0),
+    BakedNode(0x2044, 1, 2, 0, 1658, 0),
+    BakedNode(0x4024, 1, 1, 1, 1658, 0),
+    BakedNode(0x24, 1, 1, 0, 1658, 0),
+    BakedNode(0x2004, 1, 0, 1, 1658, 0),
+    BakedNode(0x2052, 1, 0, 2, 1658, 0),
+    BakedNode(0x2025, 1, 1, 0, 1658, 0),
+    BakedNode(0x2055, 1, 2, 0, 1658, 0),
+    BakedNode(0x25, 1, 2, 0, 1658, 0),
+    BakedNode(0x2005, 1, 1, 1, 1658, 0),
+    BakedNode(0x5545, 2, 2, 0, 1658, 2),
+    BakedNode(0x4101, 1, 3, 0, 1660, 0),
+    BakedNode(0x41, 1, 1, 1, 1660, 0),
+    BakedNode(0x141, 1, 2, 1, 1660, 0),
+    BakedNode(0x1404, 1, 0, 2, 1660, 0),
+    BakedNode(0x1041, 1, 1, 2, 1660, 0),
+    BakedNode(0x334, 1, 0, 0, 1660, 0),
+    BakedNode(0x51, 1, 1, 0, 1660, 0),
+    BakedNode(0x151, 1, 2, 0, 1660, 0),
+    BakedNode(0x1333, 1, 0, 1, 1660, 0),
+    BakedNode(0x1051, 1, 1, 1, 1660, 0),
+    BakedNode(0x1442, 1, 0, 2, 1660, 0),
+    BakedNode(0x1422, 1, 1, 2, 1660, 0),
+    BakedNode(0x5303, 1, 1, 0, 1660, 0),
+    BakedNode(0x5122, 1, 2, 0, 1660, 0),
+    BakedNode(0x5042, 1, 3, 0, 1660, 0),
+    BakedNode(0x4332, 1, 1, 1, 1660, 0),
+    BakedNode(0x452, 1, 1, 2, 1660, 0),
+    BakedNode(0x5322, 1, 1, 0, 1660, 0),
+    BakedNode(0x5152, 1, 2, 0, 1660, 0),
+    BakedNode(0x5511, 2, 0, 0, 1660, 2),
+    BakedNode(0x4121, 1, 1, 0, 1662, 0),
+    BakedNode(0x34, 1, 2, 0, 1662, 0),
+    BakedNode(0x4034, 1, 3, 0, 1662, 0),
+    BakedNode(0x2141, 1, 0, 1, 1662, 0),
+    BakedNode(0x5554, 2, 1, 1, 1662, 2),
+    BakedNode(0x4033, 1, 2, 1, 1664, 0),
+    BakedNode(0x403, 1, 0, 2, 1664, 0),
+    BakedNode(0x4403, 1, 1, 2, 1664, 0),
+    BakedNode(0x443, 1, 0, 3, 1664, 0),
+    BakedNode(0x2334, 1, 1, 0, 1664, 0),
+    BakedNode(0x5105, 1, 2, 0, 1664, 0),
+    BakedNode(0x2151, 1, 0, 1, 1664, 0),
+    BakedNode(0x5024, 1, 1, 1, 1664, 0),
+    BakedNode(0x551, 1, 0, 2, 1664, 0),
+    BakedNode(0x335, 1, 1, 2, 1664, 0),
+    BakedNode(0x5003, 1, 2, 2, 1664, 0),
+    BakedNode(0x5323, 1, 1, 0, 1664, 0),
+    BakedNode(0x5053, 1, 2, 0, 1664, 0),
+    BakedNode(0x5305, 1, 1, 1, 1664, 0),
+    BakedNode(0x533, 1, 1, 0, 1664, 0),
+    BakedNode(0x2443, 1, 2, 0, 1664, 0),
+    BakedNode(0x2521, 1, 3, 0, 1664, 0),
+    BakedNode(0x35, 1, 0, 1, 1664, 0),
+    BakedNode(0x2423, 1, 1, 1, 1664, 0),
+    BakedNode(0x1521, 1, 2, 1, 1664, 0),
+    BakedNode(0x4423, 1, 0, 2, 1664, 0),
+    BakedNode(0x2405, 1, 1, 2, 1664, 0),
+    BakedNode(0x425, 1, 0, 3, 1664, 0),
+    BakedNode(0x2532, 1, 1, 0, 1664, 0),
+    BakedNode(0x5503, 1, 2, 0, 1664, 0),
+    BakedNode(0x2551, 1, 3, 0, 1664, 0),
+    BakedNode(0x2335, 1, 0, 1, 1664, 0),
+    BakedNode(0x5125, 1, 1, 2, 1664, 0),
+    BakedNode(0x2533, 1, 1, 0, 1664, 0),
+    BakedNode(0x535, 1, 2, 0, 1664, 0),
+    BakedNode(0x5133, 1, 2, 0, 1664, 0),
+    BakedNode(0x1531, 1, 1, 1, 1664, 0),
+    BakedNode(0x5135, 1, 1, 2, 1664, 0),
+    BakedNode(0x4131, 1, 1, 0, 1664, 0),
+    BakedNode(0x4331, 1, 1, 1, 1664, 0),
+    BakedNode(0x2451, 1, 1, 0, 1664, 0),
+    BakedNode(0x5351, 1, 2, 0, 1664, 0),
+    BakedNode(0x1433, 1, 0, 1, 1664, 0),
+    BakedNode(0x5041, 1, 1, 1, 1664, 0),
+    BakedNode(0x2435, 1, 0, 2, 1664, 0),
+    BakedNode(0x451, 1, 1, 0, 1664, 0),
+    BakedNode(0x5423, 1, 2, 0, 1664, 0),
+    BakedNode(0x1505, 1, 3, 0, 1664, 0),
+    BakedNode(0x1105, 1, 2, 1, 1664, 0),
+    BakedNode(0x1055, 1, 1, 2, 1664, 0),
+    BakedNode(0x1005, 1, 2, 2, 1664, 0),
+    BakedNode(0x155, 1, 0, 3, 1664, 0),
+    BakedNode(0x105, 1, 1, 3, 1664, 0),
+    BakedNode(0x2152, 1, 1, 0, 1664, 0),
+    BakedNode(0x2125, 1, 0, 1, 1664, 0),
+    BakedNode(0x2155, 1, 1, 1, 1664, 0),
+    BakedNode(0x353, 1, 2, 1, 1664, 0),
+    BakedNode(0x53, 1, 1, 2, 1664, 0),
+    BakedNode(0x503, 1, 0, 3, 1664, 0),
+    BakedNode(0x553, 1, 1, 3, 1664, 0),
+    BakedNode(0x2142, 1, 0, 0, 1664, 0),
+    BakedNode(0x43, 1, 1, 0, 1664, 0),
+    BakedNode(0x2523, 1, 2, 0, 1664, 0),
+    BakedNode(0x2045, 1, 0, 1, 1664, 0),
+    BakedNode(0x343, 1, 1, 1, 1664, 0),
+    BakedNode(0x2553, 1, 2, 1, 1664, 0),
+    BakedNode(0x2353, 1, 1, 2, 1664, 0),
+    BakedNode(0x1004, 1, 1, 0, 1664, 0),
+    BakedNode(0x1152, 1, 2, 0, 1664, 0),
+    BakedNode(0x5511, 2, 0, 1, 1664, 2),
+    BakedNode(0x1522, 1, 1, 1, 1666, 0),
+    BakedNode(0x1552, 1, 2, 1, 1666, 0),
+    BakedNode(0x5553, 2, 0, 2, 1666, 2),
+    BakedNode(0x355, 1, 1, 2, 1668, 0),
+    BakedNode(0x1525, 1, 0, 3, 1668, 0),
+    BakedNode(0x5155, 2, 0, 0, 1668, 2),
+    BakedNode(0x4303, 1, 1, 0, 1670, 0),
+    BakedNode(0x2124, 1, 2, 0, 1670, 0),
+    BakedNode(0x2355, 1, 3, 0, 1670, 0),
+    BakedNode(0x5553, 2, 0, 1, 1670, 2),
+    BakedNode(0x5545, 2, 1, 1, 1672, 2),
+    BakedNode(0x542, 1, 0, 2, 1674, 0),
+    BakedNode(0x2352, 1, 2, 2, 1674, 0),
+    BakedNode(0x2144, 1, 0, 0, 1674, 0),
+    BakedNode(0x4043, 1, 1, 0, 1674, 0),
+    BakedNode(0x2342, 1, 0, 1, 1674, 0),
+    BakedNode(0x4122, 1, 2, 0, 1674, 0),
+    BakedNode(0x4052, 1, 3, 0, 1674, 0),
+    BakedNode(0x304, 1, 0, 1, 1674, 0),
+    BakedNode(0x4104, 1, 1, 1, 1674, 0),
+    BakedNode(0x4502, 1, 2, 1, 1674, 0),
+    BakedNode(0x1124, 1, 0, 2, 1674, 0),
+    BakedNode(0x4025, 1, 1, 2, 1674, 0),
+    BakedNode(0x524, 1, 0, 3, 1674, 0),
+    BakedNode(0x2324, 1, 1, 0, 1674, 0),
+    BakedNode(0x344, 1, 2, 0, 1674, 0),
+    BakedNode(0x4323, 1, 0, 1, 1674, 0),
+    BakedNode(0x4142, 1, 1, 1, 1674, 0),
+    BakedNode(0x4304, 1, 0, 0, 1674, 0),
+    BakedNode(0x4322, 1, 1, 0, 1674, 0),
+    BakedNode(0x4124, 1, 0, 1, 1674, 0),
+    BakedNode(0x4134, 1, 1, 0, 1674, 0),
+    BakedNode(0x1334, 1, 0, 1, 1674, 0),
+    BakedNode(0x1134, 1, 1, 1, 1674, 0),
+    BakedNode(0x1533, 1, 0, 2, 1674, 0),
+    BakedNode(0x1535, 1, 0, 3, 1674, 0),
+    BakedNode(0x4133, 1, 1, 0, 1674, 0),
+    BakedNode(0x1135, 1, 2, 0, 1674, 0),
+    BakedNode(0x5104, 1, 3, 0, 1674, 0),
+    BakedNode(0x1335, 1, 1, 1, 1674, 0),
+    BakedNode(0x4501, 1, 1, 2, 1674, 0),
+    BakedNode(0x541, 1, 0, 3, 1674, 0),
+    BakedNode(0x4341, 1, 1, 0, 1674, 0),
+    BakedNode(0x4051, 1, 2, 0, 1674, 0),
+    BakedNode(0x5153, 1, 3, 0, 1674, 0),
+    BakedNode(0x1443, 1, 0, 1, 1674, 0),
+    BakedNode(0x1341, 1, 1, 1, 1674, 0),
+    BakedNode(0x1351, 1, 2, 1, 1674, 0),
+    BakedNode(0x1405, 1, 0, 2, 1674, 0),
+    BakedNode(0x5304, 1, 1, 0, 1674, 0),
+    BakedNode(0x4521, 1, 2, 0, 1674, 0),
+    BakedNode(0x4035, 1, 0, 1, 1674, 0),
+    BakedNode(0x5124, 1, 1, 1, 1674, 0),
+    BakedNode(0x5043, 1, 2, 0, 1674, 0),
+    BakedNode(0x5142, 1, 3, 0, 1674, 0),
+    BakedNode(0x453, 1, 0, 2, 1674, 0),
+    BakedNode(0x1452, 1, 1, 2, 1674, 0),
+    BakedNode(0x1425, 1, 0, 3, 1674, 0),
+    BakedNode(0x5324, 1, 2, 0, 1674, 0),
+    BakedNode(0x2453, 1, 1, 1, 1674, 0),
+    BakedNode(0x1143, 1, 1, 0, 1674, 0),
+    BakedNode(0x345, 1, 2, 0, 1674, 0),
+    BakedNode(0x4143, 1, 1, 1, 1674, 0),
+    BakedNode(0x145, 1, 0, 1, 1674, 0),
+    BakedNode(0x2345, 1, 0, 2, 1674, 0),
+    BakedNode(0x1343, 1, 1, 0, 1674, 0),
+    BakedNode(0x543, 1, 2, 0, 1674, 0),
+    BakedNode(0x1344, 1, 0, 1, 1674, 0),
+    BakedNode(0x1153, 1, 1, 1, 1674, 0),
+    BakedNode(0x4305, 1, 0, 2, 1674, 0),
+    BakedNode(0x1355, 1, 0, 3, 1674, 0),
+    BakedNode(0x2543, 1, 2, 0, 1674, 0),
+    BakedNode(0x1045, 1, 1, 1, 1674, 0),
+    BakedNode(0x4105, 1, 0, 2, 1674, 0),
+    BakedNode(0x4503, 1, 2, 0, 1674, 0),
+    BakedNode(0x4053, 1, 1, 1, 1674, 0),
+    BakedNode(0x1553, 1, 2, 1, 1674, 0),
+    BakedNode(0x354, 1, 0, 2, 1674, 0),
+    BakedNode(0x1353, 1, 1, 2, 1674, 0),
+    BakedNode(0x154, 1, 1, 0, 1674, 0),
+    BakedNode(0x2354, 1, 1, 1, 1674, 0),
+    BakedNode(0x4325, 1, 0, 2, 1674, 0),
+    BakedNode(0x2154, 1, 2, 0, 1674, 0),
+    BakedNode(0x4125, 1, 0, 2, 1674, 0),
+    BakedNode(0x1504, 1, 1, 1, 1674, 0),
+    BakedNode(0x4523, 1, 2, 1, 1674, 0),
+    BakedNode(0x1054, 1, 0, 2, 1674, 0),
+    BakedNode(0x4352, 1, 0, 3, 1674, 0),
+    BakedNode(0x1542, 1, 2, 0, 1674, 0),
+    BakedNode(0x4152, 1, 0, 2, 1674, 0),
+    BakedNode(0x1534, 1, 2, 0, 1674, 0),
+    BakedNode(0x4135, 1, 0, 2, 1674, 0),
+    BakedNode(0x2312, 1, 1, 0, 1674, 0),
+    BakedNode(0x2311, 1, 0, 1, 1674, 0),
+    BakedNode(0x2120, 1, 1, 0, 1674, 0),
+    BakedNode(0x2100, 1, 0, 1, 1674, 0),
+    BakedNode(0x120, 1, 1, 0, 1674, 0),
+    BakedNode(0x1020, 1, 0, 1, 1674, 0),
+    BakedNode(0x1120, 1, 1, 1, 1674, 0),
+    BakedNode(0x2231, 1, 0, 0, 1674, 0),
+    BakedNode(0x2140, 1, 0, 1, 1674, 0),
+    BakedNode(0x1232, 1, 1, 0, 1674, 0),
+    BakedNode(0x1231, 1, 0, 1, 1674, 0),
+    BakedNode(0x1420, 1, 1, 1, 1674, 0),
+    BakedNode(0x4120, 1, 0, 2, 1674, 0),
+    BakedNode(0x1300, 1, 0, 0, 1674, 0),
+    BakedNode(0x5120, 1, 1, 0, 1674, 0),
+    BakedNode(0x320, 1, 0, 0, 1674, 0),
+    BakedNode(0x5312, 1, 1, 0, 1674, 0),
+    BakedNode(0x130, 1, 1, 0, 1674, 0),
+    BakedNode(0x1030, 1, 0, 1, 1674, 0),
+    BakedNode(0x1130, 1, 1, 1, 1674, 0),
+    BakedNode(0x2300, 1, 0, 0, 1674, 0),
+    BakedNode(0x1340, 1, 1, 0, 1674, 0),
+    BakedNode(0x4130, 1, 0, 1, 1674, 0),
+    BakedNode(0x2030, 1, 0, 0, 1674, 0),
+    BakedNode(0x5231, 1, 1, 0, 1674, 0),
+    BakedNode(0x1430, 1, 0, 1, 1674, 0),
+    BakedNode(0x3121, 1, 0, 0, 1674, 0),
+    BakedNode(0x3421, 1, 0, 1, 1674, 0),
+    BakedNode(0x201, 1, 0, 0, 1674, 0),
+    BakedNode(0x241, 1, 0, 1, 1674, 0),
+    BakedNode(0x3124, 1, 1, 0, 1674, 0),
+    BakedNode(0x1243, 1, 0, 1, 1674, 0),
+    BakedNode(0x1201, 1, 0, 0, 1674, 0),
+    BakedNode(0x1223, 1, 1, 0, 1674, 0),
+    BakedNode(0x4201, 1, 0, 1, 1674, 0),
+    BakedNode(0x2113, 1, 0, 0, 1674, 0),
+    BakedNode(0x3142, 1, 1, 0, 1674, 0),
+    BakedNode(0x2413, 1, 0, 1, 1674, 0),
+    BakedNode(0x12, 1, 2, 0, 1674, 0),
+    BakedNode(0x2011, 1, 3, 0, 1674, 0),
+    BakedNode(0x3122, 1, 0, 1, 1674, 0),
+    BakedNode(0x412, 1, 1, 1, 1674, 0),
+    BakedNode(0x1204, 1, 0, 2, 1674, 0),
+    BakedNode(0x112, 1, 1, 2, 1674, 0),
+    BakedNode(0x1012, 1, 0, 0, 1674, 0),
+    BakedNode(0x2014, 1, 1, 0, 1674, 0),
+    BakedNode(0x4012, 1, 0, 1, 1674, 0),
+    BakedNode(0x232, 1, 0, 0, 1674, 0),
+    BakedNode(0x234, 1, 1, 0, 1674, 0),
+    BakedNode(0x311, 1, 0, 0, 1674, 0),
+    BakedNode(0x3132, 1, 1, 0, 1674, 0),
+    BakedNode(0x1233, 1, 1, 0, 1674, 0),
+    BakedNode(0x3321, 1, 0, 1, 1674, 0),
+    BakedNode(0x5012, 1, 1, 0, 1674, 0),
+    BakedNode(0x1235, 1, 0, 1, 1674, 0),
+    BakedNode(0x5201, 1, 2, 0, 1674, 0),
+    BakedNode(0x1520, 1, 1, 1, 1674, 0),
+    BakedNode(0x203, 1, 1, 0, 1674, 0),
+    BakedNode(0x223, 1, 0, 1, 1674, 0),
+    BakedNode(0x2513, 1, 1, 1, 1674, 0),
+    BakedNode(0x512, 1, 1, 2, 1674, 0),
+    BakedNode(0x13, 1, 1, 0, 1674, 0),
+    BakedNode(0x113, 1, 1, 1, 1674, 0),
+    BakedNode(0x2203, 1, 0, 0, 1674, 0),
+    BakedNode(0x251, 1, 2, 0, 1674, 0),
+    BakedNode(0x3123, 1, 0, 1, 1674, 0),
+    BakedNode(0x1253, 1, 1, 1, 1674, 0),
+    BakedNode(0x2015, 1, 0, 2, 1674, 0),
+    BakedNode(0x1013, 1, 0, 0, 1674, 0),
+    BakedNode(0x243, 1, 0, 1, 1674, 0),
+    BakedNode(0x3022, 1, 1, 1, 1674, 0),
+    BakedNode(0x3002, 1, 0, 2, 1674, 0),
+    BakedNode(0x3521, 1, 2, 2, 1674, 0),
+    BakedNode(0x1205, 1, 0, 3, 1674, 0),
+    BakedNode(0x3125, 1, 1, 3, 1674, 0),
+    BakedNode(0x3152, 1, 0, 4, 1674, 0),
+    BakedNode(0x4203, 1, 1, 0, 1674, 0),
+    BakedNode(0x3402, 1, 0, 1, 1674, 0),
+    BakedNode(0x3101, 1, 1, 0, 1674, 0),
+    BakedNode(0x3042, 1, 2, 0, 1674, 0),
+    BakedNode(0x3001, 1, 0, 1, 1674, 0),
+    BakedNode(0x3024, 1, 0, 2, 1674, 0),
+    BakedNode(0x3104, 1, 1, 0, 1674, 0),
+    BakedNode(0x3041, 1, 0, 1, 1674, 0),
+    BakedNode(0x5013, 1, 1, 0, 1674, 0),
+    BakedNode(0x315, 1, 0, 1, 1674, 0),
+    BakedNode(0x233, 1, 1, 0, 1674, 0),
+    BakedNode(0x3302, 1, 0, 1, 1674, 0),
+    BakedNode(0x3502, 1, 1, 0, 1674, 0),
+    BakedNode(0x3025, 1, 0, 1, 1674, 0),
+    BakedNode(0x323, 1, 1, 0, 1674, 0),
+    BakedNode(0x322, 1, 0, 1, 1674, 0),
+    BakedNode(0x21, 1, 1, 0, 1674, 0),
+    BakedNode(0x121, 1, 1, 1, 1674, 0),
+    BakedNode(0x331, 1, 1, 0, 1674, 0),
+    BakedNode(0x301, 1, 0, 1, 1674, 0),
+    BakedNode(0x1021, 1, 1, 0, 1674, 0),
+    BakedNode(0x2021, 1, 1, 1, 1674, 0),
+    BakedNode(0x1301, 1, 0, 0, 1674, 0),
+    BakedNode(0x421, 1, 0, 1, 1674, 0),
+    BakedNode(0x302, 1, 1, 0, 1674, 0),
+    BakedNode(0x2302, 1, 1, 1, 1674, 0),
+    BakedNode(0x122, 1, 3, 0, 1674, 0),
+    BakedNode(0x2101, 1, 1, 1, 1674, 0),
+    BakedNode(0x1022, 1, 2, 1, 1674, 0),
+    BakedNode(0x2001, 1, 0, 2, 1674, 0),
+    BakedNode(0x1304, 1, 0, 0, 1674, 0),
+    BakedNode(0x332, 1, 1, 0, 1674, 0),
+    BakedNode(0x2303, 1, 0, 1, 1674, 0),
+    BakedNode(0x31, 1, 1, 0, 1674, 0),
+    BakedNode(0x131, 1, 2, 0, 1674, 0),
+    BakedNode(0x1031, 1, 1, 1, 1674, 0),
+    BakedNode(0x2401, 1, 0, 0, 1674, 0),
+    BakedNode(0x4031, 1, 0, 1, 1674, 0),
+    BakedNode(0x2304, 1, 1, 0, 1674, 0),
+    BakedNode(0x342, 1, 0, 1, 1674, 0),
+    BakedNode(0x23, 1, 1, 0, 1674, 0),
+    BakedNode(0x2023, 1, 1, 1, 1674, 0),
+    BakedNode(0x124, 1, 2, 1, 1674, 0),
+    BakedNode(0x431, 1, 0, 2, 1674, 0),
+    BakedNode(0x1024, 1, 1, 2, 1674, 0),
+    BakedNode(0x2041, 1, 0, 3, 1674, 0),
+    BakedNode(0x1323, 1, 1, 0, 1674, 0),
+    BakedNode(0x1322, 1, 0, 1, 1674, 0),
+    BakedNode(0x2341, 1, 0, 0, 1674, 0),
+    BakedNode(0x5031, 1, 1, 0, 1674, 0),
+    BakedNode(0x2003, 1, 1, 0, 1674, 0),
+    BakedNode(0x2102, 1, 1, 1, 1674, 0),
+    BakedNode(0x102, 1, 0, 2, 1674, 0),
+    BakedNode(0x1102, 1, 0, 3, 1674, 0),
+    BakedNode(0x4102, 1, 0, 1, 1674, 0),
+    BakedNode(0x103, 1, 0, 2, 1674, 0),
+    BakedNode(0x1103, 1, 0, 3, 1674, 0),
+    BakedNode(0x1002, 1, 0, 0, 1674, 0),
+    BakedNode(0x2033, 1, 1, 0, 1674, 0),
+    BakedNode(0x1003, 1, 1, 0, 1674, 0),
+    BakedNode(0x2104, 1, 2, 0, 1674, 0),
+    BakedNode(0x133, 1, 0, 1, 1674, 0),
+    BakedNode(0x2403, 1, 1, 1, 1674, 0),
+    BakedNode(0x32, 1, 1, 0, 1674, 0),
+    BakedNode(0x2032, 1, 1, 1, 1674, 0),
+    BakedNode(0x1033, 1, 1, 0, 1674, 0),
+    BakedNode(0x1402, 1, 2, 0, 1674, 0),
+    BakedNode(0x142, 1, 1, 1, 1674, 0),
+    BakedNode(0x4032, 1, 2, 1, 1674, 0),
+    BakedNode(0x2043, 1, 0, 3, 1674, 0),
+    BakedNode(0x143, 1, 1, 0, 1674, 0),
+    BakedNode(0x1403, 1, 0, 1, 1674, 0),
+    BakedNode(0x1042, 1, 2, 0, 1674, 0),
+    BakedNode(0x432, 1, 1, 1, 1674, 0),
+    BakedNode(0x2034, 1, 0, 2, 1674, 0),
+    BakedNode(0x134, 1, 1, 0, 1674, 0),
+    BakedNode(0x1043, 1, 0, 1, 1674, 0),
+    BakedNode(0x1123, 1, 0, 0, 1674, 0),
+    BakedNode(0x1423, 1, 0, 1, 1674, 0),
+    BakedNode(0x1332, 1, 1, 0, 1674, 0),
+    BakedNode(0x1342, 1, 2, 0, 1674, 0),
+    BakedNode(0x2123, 1, 0, 1, 1674, 0),
+    BakedNode(0x4123, 1, 0, 2, 1674, 0),
+    BakedNode(0x2131, 1, 0, 0, 1674, 0),
+    BakedNode(0x2431, 1, 0, 1, 1674, 0),
+    BakedNode(0x523, 1, 2, 0, 1674, 0),
+    BakedNode(0x5032, 1, 1, 1, 1674, 0),
+    BakedNode(0x2305, 1, 0, 2, 1674, 0),
+    BakedNode(0x352, 1, 1, 0, 1674, 0),
+    BakedNode(0x531, 1, 2, 0, 1674, 0),
+    BakedNode(0x5103, 1, 1, 1, 1674, 0),
+    BakedNode(0x5102, 1, 2, 0, 1674, 0),
+    BakedNode(0x2501, 1, 1, 1, 1674, 0),
+    BakedNode(0x1132, 1, 0, 0, 1674, 0),
+    BakedNode(0x1432, 1, 0, 1, 1674, 0),
+    BakedNode(0x2132, 1, 1, 0, 1674, 0),
+    BakedNode(0x2133, 1, 0, 1, 1674, 0),
+    BakedNode(0x4132, 1, 1, 1, 1674, 0),
+    BakedNode(0x2134, 1, 0, 2, 1674, 0),
+    BakedNode(0x1502, 1, 1, 0, 1674, 0),
+    BakedNode(0x2053, 1, 2, 0, 1674, 0),
+    BakedNode(0x2105, 1, 0, 1, 1674, 0),
+    BakedNode(0x532, 1, 1, 1, 1674, 0),
+    BakedNode(0x1053, 1, 1, 0, 1674, 0),
+    BakedNode(0x135, 1, 0, 1, 1674, 0),
+    BakedNode(0x152, 1, 1, 0, 1674, 0),
+    BakedNode(0x2503, 1, 2, 0, 1674, 0),
+    BakedNode(0x2234, 1, 1, 0, 1674, 0),
+    BakedNode(0x3423, 1, 0, 1, 1674, 0),
+    BakedNode(0x3352, 1, 1, 0, 1674, 0),
+    BakedNode(0x3325, 1, 0, 1, 1674, 0),
+    BakedNode(0x224, 1, 0, 0, 1674, 0),
+    BakedNode(0x15, 1, 1, 0, 1674, 0),
+    BakedNode(0x242, 1, 1, 0, 1674, 0),
+    BakedNode(0x4202, 1, 0, 1, 1674, 0),
+    BakedNode(0x4041, 1, 1, 0, 1674, 0),
+    BakedNode(0x4001, 1, 0, 1, 1674, 0),
+    BakedNode(0x501, 1, 2, 0, 1674, 0),
+    BakedNode(0x1501, 1, 2, 1, 1674, 0),
+    BakedNode(0x1133, 1, 0, 0, 1674, 0),
+    BakedNode(0x1424, 1, 1, 0, 1674, 0),
+    BakedNode(0x104, 1, 0, 1, 1674, 0),
+    BakedNode(0x1104, 1, 0, 2, 1674, 0),
+    BakedNode(0x1125, 1, 0, 1, 1674, 0),
+    BakedNode(0x305, 1, 0, 2, 1674, 0),
+    BakedNode(0x144, 1, 1, 0, 1674, 0),
+    BakedNode(0x1044, 1, 0, 1, 1674, 0),
+    BakedNode(0x1142, 1, 0, 0, 1674, 0),
+    BakedNode(0x4003, 1, 1, 0, 1674, 0),
+    BakedNode(0x2504, 1, 1, 1, 1674, 0),
+    BakedNode(0x2054, 1, 0, 2, 1674, 0),
+];
@@ -0,0 +1,1329 @@
+use std::{fmt::Display, path::PathBuf, time::Duration};
+
+use crate::{
+    arbiter::Engine,
+    codemaker::Difficulty,
+    handicap::Handicap,
+    palette::{ColorChoice, Locale},
+    plugin::Contender,
+    preset::Preset,
+    progress,
+    strategy::Strategy,
+};
+
+pub const USAGE: &str = "\
+usage: mastermind_solver [--preset NAME] [COMMAND] [OPTIONS]
+
+commands:
+  interactive [--resume FILE] [--transcript FILE] [--handicap easy|medium|hard]
+                          let the solver guess a code you keep secret, continuing the
+                          game saved to FILE with `save FILE` in a previous session;
+                          with a handicap it plays worse guesses on purpose
+  tui                     the same on the whole terminal, with the board, the best
+                          guesses and the feedback entered by the arrow keys
+  autoplay [--code CODE | --random [--seed N]] [--transcript FILE] [--max-turns N]
+           [--explain]    let the solver guess a known or random code (default),
+                          failing if it takes more than N turns (default: 10), and
+                          show why it chose each guess with --explain
+  replay FILE             step through a transcript written with --transcript, which
+                          records the guesses of a game with the scores and timings
+  codemaker [--code CODE | --random [--seed N]] [--max-turns N]
+            [--difficulty easy|medium|hard]
+                          guess a code chosen by the program (at random by default),
+                          with hints which give away more every time; medium avoids
+                          codes the usual openings tell much about, and hard chooses
+                          no code but answers so that as many codes as possible remain
+  coach [--code CODE | --random [--seed N]] [--max-turns N]
+                          the same, but every guess is reviewed against the one of the
+                          solver, with the accuracy of the guesses at the end
+  hotseat [--max-turns N]  referee a game between two players, with hints from the solver
+  match [--rounds N] [--max-turns N] [--handicap easy|medium|hard]
+                          take turns with the solver at making and breaking codes
+  solve [--history FILE] [--book FILE] [--explain] [--fact FACT]...
+                          recommend the next guess for a game written down in FILE,
+                          one `guess | feedback` line per turn (default: stdin), looked
+                          up in a decision tree written by `tree` if a book is given;
+                          --explain shows how the candidates split up by the feedback
+                          to the guess, the information expected and the runner-up;
+                          --fact rules out codes by something known about the secret
+                          besides the feedback: `contains COLOR`, `no COLOR`,
+                          `N is COLOR` or `N is not COLOR` for the Nth field
+  simulate [--all | --sample N [--seed N]] [--compare STRATEGY,...] [--csv FILE]
+           [--plugin FILE]...
+                          play the solver against every possible code (the default)
+                          or N random ones and print statistics of the turns it needed,
+                          or compare the strategies entropy, knuth and mostparts side by
+                          side, with confidence intervals and significance tests for a
+                          sample; --csv writes the guesses and timings of every game;
+                          other names are solvers loaded as plugins from FILE or from
+                          ~/.config/mastermind_solver/plugins, see src/plugin.rs
+  arbiter ENGINE [ENGINE] [--games N] [--seed N] [--time MS] [--max-turns N]
+          [--results FILE]
+                          referee a match over N random codes (default: 10) between
+                          engines, which are programs started by the shell command
+                          ENGINE speaking the protocol in src/arbiter.rs, or strategies
+                          of the solver, the second engine if only one is given; a
+                          guess taking more than MS milliseconds (default: 5000) loses
+                          the game, and the games are added to FILE (default:
+                          ~/.local/share/mastermind_solver/results.csv)
+  ratings [--results FILE]
+                          rank the engines by Elo ratings over the matches in FILE
+                          (default: the results of arbiter), separately per preset
+  generate-dataset [--all | --sample N [--seed N]] [--out FILE]
+                          let the solver play every possible code (the default) or N
+                          random ones and write a record of every guess to FILE
+                          (default: stdout) to train policies on: the codes which
+                          remained, the guess and the guesses it took from there, as
+                          comma separated values with the columns in src/dataset.rs
+  tree [--out FILE] [--dot [--depth N] | --binary | --rust]
+                          write the complete decision tree of the solver to FILE
+                          (default: stdout), one line per guess indented by turn, or
+                          its first N turns as a Graphviz graph (default: 3), or in a
+                          compact binary format which `solve --book` reads as well, or
+                          as Rust source to compile into the solver, see src/baked.rs
+  puzzle [--candidates N] [--count N] [--seed N]
+                          search for N games (default: 1) after which N codes remain
+                          (default: 4) and only one of them tells the others apart, and
+                          write them like saved games with that code as the solution
+  serve [--port N]        answer HTTP requests to play games with the solver on port N
+                          of this machine (default: 8080), with a WebSocket per game
+                          streaming the search for a guess, see src/server.rs, rooms
+                          to play others in and a page at http://localhost:N/ to play
+                          in the browser
+  verify                  check the results of the solver for classic Mastermind
+                          against known ones, failing if it doesn't reach one
+  stats                   summarize the games logged by a build with the sqlite feature:
+                          the turns and longest winning streaks by preset and mode, and
+                          your games by month
+  analyze [--openings]    print facts about the configuration and the best opening,
+                          or rank every opening by the measure of each strategy
+  bench [--iterations N]  time the opening guess
+
+options:
+  --preset NAME           game configuration to play, see `--preset list`
+  --locale de|en          language of the color names
+  --output text|json|porcelain
+                          print results as text, as a JSON document or as one line per
+                          decision with colors as numbers, e.g. `3 2 1 0`
+  --porcelain             short for --output porcelain
+  --color auto|always|never
+                          show pegs in terminal colors (default: auto)
+  --progress bar|json     show the progress of long computations as a bar on a terminal
+                          (default), or write it to stderr as a JSON object per line
+                          with the steps done, the best guess so far and the seconds
+                          elapsed, even if stderr isn't a terminal
+  --config FILE           read defaults from FILE instead of
+                          ~/.config/mastermind_solver/config.toml
+  --threads N             number of threads the solver uses (default: one per core)
+  --strategy entropy|knuth|mostparts
+                          how the solver ranks guesses: by the information expected
+                          (default), the fewest codes left at worst or the most
+                          different feedbacks
+  --first-guess CODE      open every game with CODE instead of the solver's own guess
+  --script FILE           score guesses by the expression `score` in FILE instead of the
+                          strategy of the solver, e.g. `score = total - largest`, see
+                          src/script.rs for the variables and functions
+  --policy FILE           choose guesses by the network in FILE trained on the records of
+                          generate-dataset instead of by the strategy of the solver, see
+                          src/policy.rs for the format
+  --missing-colors COLOR,...
+                          colors your set has no pegs for, which the solver doesn't
+                          guess; the secret may still contain them
+  -v, --verbose           print diagnostics, repeat (-vv) to trace every scored guess
+  -q, --quiet             only print errors
+  -h, --help              print this help
+
+codes are given as comma separated color names or numbers, e.g. \"rot,blau,gelb,grün\"";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Output {
+    #[default]
+    Text,
+    Json,
+    /// One line per decision without any prose, for scripts.
+    Porcelain,
+}
+
+impl std::str::FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Output::Text),
+            "json" => Ok(Output::Json),
+            "porcelain" => Ok(Output::Porcelain),
+            _ => Err("expected \"text\", \"json\" or \"porcelain\"".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cli {
+    pub preset: Option<Preset>,
+    pub locale: Option<Locale>,
+    pub output: Output,
+    pub color: ColorChoice,
+    pub progress: progress::Format,
+    /// The number of `-v` flags, or -1 for `--quiet`.
+    pub verbosity: i8,
+    pub config: Option<PathBuf>,
+    pub threads: Option<usize>,
+    pub strategy: Option<Strategy>,
+    /// The guess to open with, parsed once the palette is set.
+    pub first_guess: Option<String>,
+    /// The script scoring the guesses, see `script`.
+    pub script: Option<PathBuf>,
+    /// The learned policy choosing the guesses, see `policy`.
+    pub policy: Option<PathBuf>,
+    /// Colors the codebreaker has no pegs for, parsed once the palette is set.
+    pub missing_colors: Option<Vec<String>>,
+    pub command: Command,
+}
+
+/// How the secret code of a game is chosen.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Secret {
+    /// A code given on the command line.
+    Code(String),
+    /// A random legal code, reproducible if a seed is given.
+    Random { seed: Option<u64> },
+}
+
+/// The formats of the `tree` command besides text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeFormat {
+    /// The first `depth` turns as a Graphviz graph.
+    Dot {
+        depth: usize,
+    },
+    Binary,
+    Rust,
+}
+
+/// Codes drawn at random to simulate games against, for configurations with too many codes
+/// to play them all.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    pub codes: usize,
+    /// Makes the sample reproducible.
+    pub seed: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Interactive {
+        /// A game saved in a previous session to continue.
+        resume: Option<PathBuf>,
+        transcript: Option<PathBuf>,
+        /// Makes the solver play worse.
+        handicap: Option<Handicap>,
+    },
+    Tui,
+    Autoplay {
+        secret: Option<Secret>,
+        transcript: Option<PathBuf>,
+        max_turns: usize,
+        /// Print why every guess was chosen.
+        explain: bool,
+    },
+    Replay {
+        transcript: PathBuf,
+    },
+    Codemaker {
+        secret: Option<Secret>,
+        max_turns: usize,
+        difficulty: Difficulty,
+    },
+    Coach {
+        secret: Option<Secret>,
+        max_turns: usize,
+    },
+    Hotseat {
+        max_turns: usize,
+    },
+    Match {
+        rounds: usize,
+        max_turns: usize,
+        handicap: Option<Handicap>,
+    },
+    Solve {
+        history: Option<PathBuf>,
+        /// A decision tree to look the guess up in before computing it.
+        book: Option<PathBuf>,
+        explain: bool,
+        /// Known facts about the secret, see `facts`, parsed once the palette is set.
+        facts: Vec<String>,
+    },
+    Simulate {
+        /// The strategies and plugins to compare, only the default strategy is played if there
+        /// are none.
+        compare: Option<Vec<Contender>>,
+        /// Plugins to look for the ones to compare in besides the plugin directory.
+        plugins: Vec<PathBuf>,
+        /// Where to write the games as comma separated values.
+        csv: Option<PathBuf>,
+        /// Play only these codes instead of every one.
+        sample: Option<Sample>,
+    },
+    Arbiter {
+        /// One or two engines, the solver plays the one given alone.
+        engines: Vec<Engine>,
+        games: usize,
+        /// Makes the codes reproducible.
+        seed: Option<u64>,
+        /// The time an engine may take for a guess.
+        time: Duration,
+        max_turns: usize,
+        /// Where to add the games instead of the results in the data directory.
+        results: Option<PathBuf>,
+    },
+    Ratings {
+        /// Where to read the games instead of the results in the data directory.
+        results: Option<PathBuf>,
+    },
+    GenerateDataset {
+        /// Play only these codes instead of every one.
+        sample: Option<Sample>,
+        /// Where to write the records instead of stdout.
+        out: Option<PathBuf>,
+    },
+    Tree {
+        out: Option<PathBuf>,
+        /// How to write the tree instead of as text.
+        format: Option<TreeFormat>,
+    },
+    Puzzle {
+        /// The number of codes which remain after the history of a puzzle.
+        candidates: usize,
+        count: usize,
+        /// Makes the puzzles reproducible.
+        seed: Option<u64>,
+    },
+    Serve {
+        port: u16,
+    },
+    Verify,
+    Stats,
+    Analyze {
+        /// Whether to rank every opening instead.
+        openings: bool,
+    },
+    Bench {
+        iterations: usize,
+    },
+    Help,
+    ListPresets,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliError(String);
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+struct Args<I: Iterator<Item = String>> {
+    args: I,
+    /// Value of a `--flag=value` style argument that has not been consumed yet.
+    inline_value: Option<String>,
+}
+
+impl<I: Iterator<Item = String>> Args<I> {
+    fn next(&mut self) -> Result<Option<String>, CliError> {
+        if let Some(value) = self.inline_value.take() {
+            return Err(CliError(format!("unexpected value {value:?}")));
+        }
+        Ok(self.args.next().map(|arg| match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                self.inline_value = Some(value.to_string());
+                flag.to_string()
+            }
+            _ => arg,
+        }))
+    }
+
+    fn value(&mut self, flag: &str) -> Result<String, CliError> {
+        self.inline_value
+            .take()
+            .or_else(|| self.args.next())
+            .ok_or_else(|| CliError(format!("missing value for {flag}")))
+    }
+
+    fn parsed<T: std::str::FromStr>(&mut self, flag: &str) -> Result<T, CliError>
+    where
+        T::Err: Display,
+    {
+        let value = self.value(flag)?;
+        value
+            .parse()
+            .map_err(|err| CliError(format!("invalid value {value:?} for {flag}: {err}")))
+    }
+}
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Cli, CliError> {
+    let mut args = Args {
+        args: args.into_iter(),
+        inline_value: None,
+    };
+    let mut preset = None;
+    let mut locale = None;
+    let mut output = Output::default();
+    let mut verbosity = 0;
+    let mut color = ColorChoice::default();
+    let mut progress = progress::Format::default();
+    let mut config = None;
+    let mut threads = None;
+    let mut strategy = None;
+    let mut first_guess = None;
+    let mut script = None;
+    let mut policy = None;
+    let mut missing_colors = None;
+    let mut help = false;
+    let mut list_presets = false;
+    let mut command_name = None;
+    // options which only some commands accept
+    let mut command_options = Vec::new();
+    let mut secret = None;
+    let mut seed = None;
+    // the seed of puzzle or arbiter
+    let mut command_seed = None;
+    let mut history = None;
+    let mut resume = None;
+    let mut transcript = None;
+    // the transcript to replay
+    let mut file = None;
+    let mut engines = Vec::new();
+    let mut results = None;
+    let mut games = 10;
+    let mut time = 5000;
+    let mut compare = None;
+    let mut plugins = Vec::new();
+    let mut handicap = None;
+    let mut difficulty = Difficulty::default();
+    let mut csv = None;
+    let mut sample = None;
+    let mut out = None;
+    let mut book = None;
+    let mut dot = false;
+    let mut tree_format = None;
+    let mut openings = false;
+    let mut explain = false;
+    let mut facts = Vec::new();
+    let mut depth = 3;
+    let mut iterations = 10;
+    let mut max_turns = 10;
+    let mut rounds = 4;
+    let mut candidates = 4;
+    let mut count = 1;
+    let mut port = 8080;
+
+    while let Some(arg) = args.next()? {
+        match arg.as_str() {
+            "-h" | "--help" => help = true,
+            "-q" | "--quiet" => verbosity = -1,
+            "--verbose" => verbosity += 1,
+            _ if arg.len() > 1 && arg.trim_start_matches('-') == "v".repeat(arg.len() - 1) => {
+                verbosity += arg.len() as i8 - 1
+            }
+            "--preset" => {
+                let name = args.value("--preset")?;
+                if name == "list" {
+                    list_presets = true;
+                } else {
+                    preset = Some(
+                        name.parse()
+                            .map_err(|err| CliError(format!("{err}, see `--preset list`")))?,
+                    );
+                }
+            }
+            "--locale" => locale = Some(args.parsed("--locale")?),
+            "--output" => output = args.parsed("--output")?,
+            "--porcelain" => output = Output::Porcelain,
+            "--color" => color = args.parsed("--color")?,
+            "--progress" => progress = args.parsed("--progress")?,
+            "--config" => config = Some(PathBuf::from(args.value("--config")?)),
+            "--threads" => match args.parsed("--threads")? {
+                0 => return Err(CliError("--threads must be positive".to_string())),
+                count => threads = Some(count),
+            },
+            "--strategy" => strategy = Some(args.parsed("--strategy")?),
+            "--first-guess" => first_guess = Some(args.value("--first-guess")?),
+            "--script" => script = Some(PathBuf::from(args.value("--script")?)),
+            "--policy" => policy = Some(PathBuf::from(args.value("--policy")?)),
+            "--missing-colors" => {
+                let colors = args.value("--missing-colors")?;
+                missing_colors = Some(colors.split(',').map(str::to_string).collect());
+            }
+            "--code" | "--random" if secret.is_some() => {
+                return Err(CliError(
+                    "only one of --code and --random can be given".to_string(),
+                ))
+            }
+            "--code" => {
+                secret = Some(Secret::Code(args.value("--code")?));
+                command_options.push("--code");
+            }
+            "--random" => {
+                secret = Some(Secret::Random { seed: None });
+                command_options.push("--random");
+            }
+            "--seed" => seed = Some(args.parsed("--seed")?),
+            "--history" => {
+                history = Some(PathBuf::from(args.value("--history")?));
+                command_options.push("--history");
+            }
+            "--resume" => {
+                resume = Some(PathBuf::from(args.value("--resume")?));
+                command_options.push("--resume");
+            }
+            "--transcript" => {
+                transcript = Some(PathBuf::from(args.value("--transcript")?));
+                command_options.push("--transcript");
+            }
+            "--all" => command_options.push("--all"),
+            "--sample" => {
+                sample = Some(Sample {
+                    codes: args.parsed("--sample")?,
+                    seed: None,
+                });
+                command_options.push("--sample");
+            }
+            "--difficulty" => {
+                difficulty = args.value("--difficulty")?.parse().map_err(CliError)?;
+                command_options.push("--difficulty");
+            }
+            "--handicap" => {
+                handicap = Some(args.value("--handicap")?.parse().map_err(CliError)?);
+                command_options.push("--handicap");
+            }
+            "--compare" => {
+                let contenders = args.value("--compare")?;
+                compare = Some(
+                    contenders
+                        .split(',')
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()
+                        .map_err(CliError)?,
+                );
+                command_options.push("--compare");
+            }
+            "--plugin" => {
+                plugins.push(PathBuf::from(args.value("--plugin")?));
+                command_options.push("--plugin");
+            }
+            "--openings" => {
+                openings = true;
+                command_options.push("--openings");
+            }
+            "--explain" => {
+                explain = true;
+                command_options.push("--explain");
+            }
+            "--fact" => {
+                facts.push(args.value("--fact")?);
+                command_options.push("--fact");
+            }
+            "--dot" => {
+                dot = true;
+                command_options.push("--dot");
+            }
+            "--binary" | "--rust" if tree_format.is_some() || dot => {
+                return Err(CliError(
+                    "only one of --dot, --binary and --rust can be given".to_string(),
+                ))
+            }
+            "--binary" => {
+                tree_format = Some(TreeFormat::Binary);
+                command_options.push("--binary");
+            }
+            "--rust" => {
+                tree_format = Some(TreeFormat::Rust);
+                command_options.push("--rust");
+            }
+            "--depth" => {
+                depth = args.parsed("--depth")?;
+                command_options.push("--depth");
+            }
+            "--book" => {
+                book = Some(PathBuf::from(args.value("--book")?));
+                command_options.push("--book");
+            }
+            "--out" => {
+                out = Some(PathBuf::from(args.value("--out")?));
+                command_options.push("--out");
+            }
+            "--csv" => {
+                csv = Some(PathBuf::from(args.value("--csv")?));
+                command_options.push("--csv");
+            }
+            "--iterations" => {
+                iterations = args.parsed("--iterations")?;
+                command_options.push("--iterations");
+            }
+            "--port" => {
+                port = args.parsed("--port")?;
+                command_options.push("--port");
+            }
+            "--rounds" => {
+                rounds = args.parsed("--rounds")?;
+                command_options.push("--rounds");
+            }
+            "--candidates" => match args.parsed("--candidates")? {
+                0..=3 => return Err(CliError("--candidates must be at least 4".to_string())),
+                value => {
+                    candidates = value;
+                    command_options.push("--candidates");
+                }
+            },
+            "--count" => {
+                count = args.parsed("--count")?;
+                command_options.push("--count");
+            }
+            "--games" => {
+                games = args.parsed("--games")?;
+                command_options.push("--games");
+            }
+            "--time" => {
+                time = args.parsed("--time")?;
+                command_options.push("--time");
+            }
+            "--results" => {
+                results = Some(PathBuf::from(args.value("--results")?));
+                command_options.push("--results");
+            }
+            "--max-turns" => {
+                max_turns = args.parsed("--max-turns")?;
+                command_options.push("--max-turns");
+            }
+            _ if arg.starts_with('-') => return Err(CliError(format!("unknown option {arg}"))),
+            _ if command_name.is_none() => command_name = Some(arg),
+            _ if command_name.as_deref() == Some("replay") && file.is_none() => {
+                file = Some(PathBuf::from(arg))
+            }
+            _ if command_name.as_deref() == Some("arbiter") && engines.len() < 2 => {
+                engines.push(arg.parse().map_err(CliError)?)
+            }
+            _ => return Err(CliError(format!("unexpected argument {arg:?}"))),
+        }
+    }
+
+    if command_options.contains(&"--depth") && !dot {
+        return Err(CliError("--depth can only be used with --dot".to_string()));
+    }
+    if dot && tree_format.is_some() {
+        return Err(CliError(
+            "only one of --dot, --binary and --rust can be given".to_string(),
+        ));
+    }
+    if dot {
+        tree_format = Some(TreeFormat::Dot { depth });
+    }
+
+    if command_options.contains(&"--all") && sample.is_some() {
+        return Err(CliError(
+            "only one of --all and --sample can be given".to_string(),
+        ));
+    }
+
+    if let Some(seed) = seed {
+        match (&mut secret, &mut sample) {
+            (Some(Secret::Random { seed: random_seed }), _)
+            | (
+                _,
+                Some(Sample {
+                    seed: random_seed, ..
+                }),
+            ) => *random_seed = Some(seed),
+            _ if matches!(command_name.as_deref(), Some("puzzle" | "arbiter")) => {
+                command_seed = Some(seed)
+            }
+            _ => {
+                return Err(CliError(
+                    "--seed can only be used with --random, --sample, puzzle or arbiter"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+
+    let command_name = command_name.unwrap_or_else(|| "autoplay".to_string());
+    let (command, accepted_options): (_, &[&str]) = match command_name.as_str() {
+        "interactive" => (
+            Command::Interactive {
+                resume,
+                transcript,
+                handicap,
+            },
+            &["--resume", "--transcript", "--handicap"],
+        ),
+        "tui" => (Command::Tui, &[]),
+        "autoplay" => (
+            Command::Autoplay {
+                secret,
+                transcript,
+                max_turns,
+                explain,
+            },
+            &[
+                "--code",
+                "--random",
+                "--transcript",
+                "--max-turns",
+                "--explain",
+            ],
+        ),
+        "replay" => match file {
+            Some(transcript) => (Command::Replay { transcript }, &[]),
+            None => return Err(CliError("replay needs a transcript".to_string())),
+        },
+        "codemaker" if difficulty != Difficulty::Easy && command_options.contains(&"--code") => {
+            return Err(CliError(format!(
+                "the {difficulty} codemaker chooses its own code, --code can't be given"
+            )))
+        }
+        "codemaker" => (
+            Command::Codemaker {
+                secret,
+                max_turns,
+                difficulty,
+            },
+            &["--code", "--random", "--max-turns", "--difficulty"],
+        ),
+        "coach" => (
+            Command::Coach { secret, max_turns },
+            &["--code", "--random", "--max-turns"],
+        ),
+        "hotseat" => (Command::Hotseat { max_turns }, &["--max-turns"]),
+        "match" => (
+            Command::Match {
+                rounds,
+                max_turns,
+                handicap,
+            },
+            &["--rounds", "--max-turns", "--handicap"],
+        ),
+        "solve" => (
+            Command::Solve {
+                history,
+                book,
+                explain,
+                facts,
+            },
+            &["--history", "--book", "--explain", "--fact"],
+        ),
+        "simulate" => (
+            Command::Simulate {
+                compare,
+                plugins,
+                csv,
+                sample,
+            },
+            &["--all", "--sample", "--compare", "--plugin", "--csv"],
+        ),
+        "arbiter" if engines.is_empty() => {
+            return Err(CliError("arbiter needs an engine".to_string()))
+        }
+        "arbiter" => (
+            Command::Arbiter {
+                engines,
+                games,
+                seed: command_seed,
+                time: Duration::from_millis(time),
+                max_turns,
+                results,
+            },
+            &["--games", "--time", "--max-turns", "--results"],
+        ),
+        "ratings" => (Command::Ratings { results }, &["--results"]),
+        "generate-dataset" => (
+            Command::GenerateDataset { sample, out },
+            &["--all", "--sample", "--out"],
+        ),
+        "tree" => (
+            Command::Tree {
+                out,
+                format: tree_format,
+            },
+            &["--out", "--dot", "--depth", "--binary", "--rust"],
+        ),
+        "puzzle" => (
+            Command::Puzzle {
+                candidates,
+                count,
+                seed: command_seed,
+            },
+            &["--candidates", "--count"],
+        ),
+        "serve" => (Command::Serve { port }, &["--port"]),
+        "verify" => (Command::Verify, &[]),
+        "stats" => (Command::Stats, &[]),
+        "analyze" => (Command::Analyze { openings }, &["--openings"]),
+        "bench" => (Command::Bench { iterations }, &["--iterations"]),
+        "help" => (Command::Help, &[]),
+        _ => return Err(CliError(format!("unknown command {command_name:?}"))),
+    };
+    if let Some(option) = command_options
+        .iter()
+        .find(|option| !accepted_options.contains(option))
+    {
+        return Err(CliError(format!(
+            "{option} is not supported by {command_name}"
+        )));
+    }
+    // the strategies are compared by their own measures
+    if (script.is_some() || policy.is_some())
+        && matches!(
+            command,
+            Command::Simulate {
+                compare: Some(_),
+                ..
+            }
+        )
+    {
+        return Err(CliError(
+            "--script and --policy can't be combined with --compare".to_string(),
+        ));
+    }
+    let command = if help {
+        Command::Help
+    } else if list_presets {
+        Command::ListPresets
+    } else {
+        command
+    };
+    Ok(Cli {
+        preset,
+        locale,
+        output,
+        color,
+        progress,
+        verbosity,
+        config,
+        threads,
+        strategy,
+        first_guess,
+        script,
+        policy,
+        missing_colors,
+        command,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Cli, CliError> {
+        parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn defaults_to_autoplay() {
+        assert_eq!(
+            parse_str(""),
+            Ok(Cli {
+                preset: None,
+                locale: None,
+                output: Output::Text,
+                color: ColorChoice::Auto,
+                progress: progress::Format::Bar,
+                verbosity: 0,
+                config: None,
+                threads: None,
+                strategy: None,
+                first_guess: None,
+                script: None,
+                policy: None,
+                missing_colors: None,
+                command: Command::Autoplay {
+                    secret: None,
+                    transcript: None,
+                    max_turns: 10,
+                    explain: false
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn parse_subcommand_with_options() {
+        assert_eq!(
+            parse_str("--preset classic autoplay --code rot,blau,gelb,grün"),
+            Ok(Cli {
+                preset: Some(Preset::Classic),
+                locale: None,
+                output: Output::Text,
+                color: ColorChoice::Auto,
+                progress: progress::Format::Bar,
+                verbosity: 0,
+                config: None,
+                threads: None,
+                strategy: None,
+                first_guess: None,
+                script: None,
+                policy: None,
+                missing_colors: None,
+                command: Command::Autoplay {
+                    secret: Some(Secret::Code("rot,blau,gelb,grün".to_string())),
+                    transcript: None,
+                    max_turns: 10,
+                    explain: false
+                }
+            })
+        );
+        assert_eq!(
+            parse_str(
+                "bench --iterations=3 --preset=mini --locale en --output json --config my.toml"
+            ),
+            Ok(Cli {
+                preset: Some(Preset::Mini),
+                locale: Some(Locale::English),
+                output: Output::Json,
+                color: ColorChoice::Auto,
+                progress: progress::Format::Bar,
+                verbosity: 0,
+                config: Some(PathBuf::from("my.toml")),
+                threads: None,
+                strategy: None,
+                first_guess: None,
+                script: None,
+                policy: None,
+                missing_colors: None,
+                command: Command::Bench { iterations: 3 }
+            })
+        );
+        assert_eq!(
+            parse_str("verify").map(|cli| cli.command),
+            Ok(Command::Verify)
+        );
+        assert_eq!(parse_str("tui").map(|cli| cli.command), Ok(Command::Tui));
+        assert_eq!(
+            parse_str("stats").map(|cli| cli.command),
+            Ok(Command::Stats)
+        );
+        assert_eq!(
+            parse_str("interactive --resume game.txt").map(|cli| cli.command),
+            Ok(Command::Interactive {
+                resume: Some(PathBuf::from("game.txt")),
+                transcript: None,
+                handicap: None
+            })
+        );
+        assert_eq!(
+            parse_str("autoplay --random --transcript game.txt --max-turns 6")
+                .map(|cli| cli.command),
+            Ok(Command::Autoplay {
+                secret: Some(Secret::Random { seed: None }),
+                transcript: Some(PathBuf::from("game.txt")),
+                max_turns: 6,
+                explain: false
+            })
+        );
+        assert_eq!(
+            parse_str("replay game.txt").map(|cli| cli.command),
+            Ok(Command::Replay {
+                transcript: PathBuf::from("game.txt")
+            })
+        );
+        assert!(parse_str("replay").is_err());
+        assert!(parse_str("replay game.txt other.txt").is_err());
+        assert!(parse_str("tui --resume game.txt").is_err());
+        assert_eq!(
+            parse_str("analyze --openings").map(|cli| cli.command),
+            Ok(Command::Analyze { openings: true })
+        );
+        assert!(parse_str("bench --openings").is_err());
+        assert_eq!(
+            parse_str("serve --port 3000").map(|cli| cli.command),
+            Ok(Command::Serve { port: 3000 })
+        );
+        assert!(parse_str("serve --port 70000").is_err());
+    }
+
+    #[test]
+    fn parse_solve() {
+        assert_eq!(
+            parse_str("solve --history game.txt").map(|cli| cli.command),
+            Ok(Command::Solve {
+                history: Some(PathBuf::from("game.txt")),
+                book: None,
+                explain: false,
+                facts: vec![]
+            })
+        );
+        assert_eq!(
+            parse_str("solve --book tree.txt --explain").map(|cli| cli.command),
+            Ok(Command::Solve {
+                history: None,
+                book: Some(PathBuf::from("tree.txt")),
+                explain: true,
+                facts: vec![]
+            })
+        );
+        assert_eq!(
+            parse(["solve", "--fact", "contains rot", "--fact=1 is not 2"].map(String::from))
+                .map(|cli| cli.command),
+            Ok(Command::Solve {
+                history: None,
+                book: None,
+                explain: false,
+                facts: vec!["contains rot".to_string(), "1 is not 2".to_string()]
+            })
+        );
+        assert!(parse_str("autoplay --fact 1").is_err());
+    }
+
+    #[test]
+    fn parse_simulate() {
+        assert_eq!(
+            parse_str("simulate --all").map(|cli| cli.command),
+            Ok(Command::Simulate {
+                compare: None,
+                plugins: vec![],
+                csv: None,
+                sample: None
+            })
+        );
+        assert_eq!(
+            parse_str("simulate --compare entropy,knuth,mostparts --csv games.csv")
+                .map(|cli| cli.command),
+            Ok(Command::Simulate {
+                compare: Some(Strategy::ALL.map(Contender::Strategy).to_vec()),
+                plugins: vec![],
+                csv: Some(PathBuf::from("games.csv")),
+                sample: None
+            })
+        );
+        assert_eq!(
+            parse_str("simulate --compare entropy,firstfit --plugin firstfit.so")
+                .map(|cli| cli.command),
+            Ok(Command::Simulate {
+                compare: Some(vec![
+                    Contender::Strategy(Strategy::Entropy),
+                    Contender::Plugin("firstfit".to_string())
+                ]),
+                plugins: vec![PathBuf::from("firstfit.so")],
+                csv: None,
+                sample: None
+            })
+        );
+        assert_eq!(
+            parse_str("simulate --sample 500 --seed 7").map(|cli| cli.command),
+            Ok(Command::Simulate {
+                compare: None,
+                plugins: vec![],
+                csv: None,
+                sample: Some(Sample {
+                    codes: 500,
+                    seed: Some(7)
+                })
+            })
+        );
+        assert!(parse_str("simulate --all --sample 500").is_err());
+        assert_eq!(
+            parse_str("generate-dataset --sample 100 --seed 3 --out games.csv")
+                .map(|cli| cli.command),
+            Ok(Command::GenerateDataset {
+                sample: Some(Sample {
+                    codes: 100,
+                    seed: Some(3)
+                }),
+                out: Some(PathBuf::from("games.csv"))
+            })
+        );
+        assert!(parse_str("generate-dataset --csv games.csv").is_err());
+        assert!(parse_str("simulate --seed 7").is_err());
+        assert!(parse_str("solve --all").is_err());
+        // names which aren't strategies are looked up among the plugins when playing
+        assert!(parse_str("simulate --compare entropy,").is_err());
+        assert!(parse_str("autoplay --plugin firstfit.so").is_err());
+        assert_eq!(
+            parse_str("tree --out tree.txt").map(|cli| cli.command),
+            Ok(Command::Tree {
+                out: Some(PathBuf::from("tree.txt")),
+                format: None
+            })
+        );
+        assert_eq!(
+            parse_str("tree --dot --depth 2").map(|cli| cli.command),
+            Ok(Command::Tree {
+                out: None,
+                format: Some(TreeFormat::Dot { depth: 2 })
+            })
+        );
+        assert_eq!(
+            parse_str("tree --binary --out tree.bin").map(|cli| cli.command),
+            Ok(Command::Tree {
+                out: Some(PathBuf::from("tree.bin")),
+                format: Some(TreeFormat::Binary)
+            })
+        );
+        assert_eq!(
+            parse_str("tree --rust").map(|cli| cli.command),
+            Ok(Command::Tree {
+                out: None,
+                format: Some(TreeFormat::Rust)
+            })
+        );
+        assert!(parse_str("tree --depth 2").is_err());
+        assert!(parse_str("tree --dot --binary").is_err());
+        assert!(parse_str("tree --rust --binary").is_err());
+    }
+
+    #[test]
+    fn parse_codemaker() {
+        assert_eq!(
+            parse_str("codemaker --max-turns 8").map(|cli| cli.command),
+            Ok(Command::Codemaker {
+                secret: None,
+                max_turns: 8,
+                difficulty: Difficulty::Easy
+            })
+        );
+        assert_eq!(
+            parse_str("codemaker --random").map(|cli| cli.command),
+            Ok(Command::Codemaker {
+                secret: Some(Secret::Random { seed: None }),
+                max_turns: 10,
+                difficulty: Difficulty::Easy
+            })
+        );
+        assert_eq!(
+            parse_str("codemaker --difficulty hard --random --seed 3").map(|cli| cli.command),
+            Ok(Command::Codemaker {
+                secret: Some(Secret::Random { seed: Some(3) }),
+                max_turns: 10,
+                difficulty: Difficulty::Hard
+            })
+        );
+        assert!(parse_str("codemaker --difficulty medium --code 0,1,2,3").is_err());
+        assert!(parse_str("codemaker --difficulty impossible").is_err());
+        assert!(parse_str("coach --difficulty hard").is_err());
+        assert_eq!(
+            parse_str("coach --code 0,1,2,3").map(|cli| cli.command),
+            Ok(Command::Coach {
+                secret: Some(Secret::Code("0,1,2,3".to_string())),
+                max_turns: 10
+            })
+        );
+        assert!(parse_str("coach --rounds 2").is_err());
+    }
+
+    #[test]
+    fn parse_arbiter() {
+        let args = [
+            "arbiter",
+            "./engine --fast",
+            "knuth",
+            "--games",
+            "4",
+            "--seed",
+            "2",
+        ];
+        assert_eq!(
+            parse(args.map(str::to_string)).map(|cli| cli.command),
+            Ok(Command::Arbiter {
+                engines: vec![
+                    Engine::Command("./engine --fast".to_string()),
+                    Engine::Solver(Strategy::Knuth)
+                ],
+                games: 4,
+                seed: Some(2),
+                time: Duration::from_secs(5),
+                max_turns: 10,
+                results: None
+            })
+        );
+        assert_eq!(
+            parse_str("arbiter ./engine --time 100 --results out.csv").map(|cli| cli.command),
+            Ok(Command::Arbiter {
+                engines: vec![Engine::Command("./engine".to_string())],
+                games: 10,
+                seed: None,
+                time: Duration::from_millis(100),
+                max_turns: 10,
+                results: Some(PathBuf::from("out.csv"))
+            })
+        );
+        assert_eq!(
+            parse_str("arbiter --games 3"),
+            Err(CliError("arbiter needs an engine".to_string()))
+        );
+        assert!(parse_str("arbiter a b c").is_err());
+        assert_eq!(
+            parse_str("ratings --results out.csv").map(|cli| cli.command),
+            Ok(Command::Ratings {
+                results: Some(PathBuf::from("out.csv"))
+            })
+        );
+        assert!(parse_str("simulate --time 100").is_err());
+    }
+
+    #[test]
+    fn parse_puzzle() {
+        assert_eq!(
+            parse_str("puzzle --candidates 5 --count 5 --seed 7").map(|cli| cli.command),
+            Ok(Command::Puzzle {
+                candidates: 5,
+                count: 5,
+                seed: Some(7)
+            })
+        );
+        assert_eq!(
+            parse_str("puzzle").map(|cli| cli.command),
+            Ok(Command::Puzzle {
+                candidates: 4,
+                count: 1,
+                seed: None
+            })
+        );
+        assert!(parse_str("puzzle --candidates 3").is_err());
+        assert!(parse_str("solve --count 2").is_err());
+    }
+
+    #[test]
+    fn parse_match() {
+        assert_eq!(
+            parse_str("match --rounds 2").map(|cli| cli.command),
+            Ok(Command::Match {
+                rounds: 2,
+                max_turns: 10,
+                handicap: None
+            })
+        );
+        assert_eq!(
+            parse_str("match --handicap easy").map(|cli| cli.command),
+            Ok(Command::Match {
+                rounds: 4,
+                max_turns: 10,
+                handicap: Some(Handicap::Easy)
+            })
+        );
+        assert!(parse_str("autoplay --rounds 2").is_err());
+        assert!(parse_str("match --handicap expert").is_err());
+        assert!(parse_str("autoplay --handicap easy").is_err());
+    }
+
+    #[test]
+    fn parse_seed() {
+        assert_eq!(
+            parse_str("autoplay --random --seed 42").map(|cli| cli.command),
+            Ok(Command::Autoplay {
+                secret: Some(Secret::Random { seed: Some(42) }),
+                transcript: None,
+                max_turns: 10,
+                explain: false
+            })
+        );
+        assert!(parse_str("autoplay --seed 42").is_err());
+        assert!(parse_str("autoplay --code rot --seed 42").is_err());
+        assert!(parse_str("autoplay --random --seed -1").is_err());
+    }
+
+    #[test]
+    fn parse_porcelain() {
+        assert_eq!(
+            parse_str("--porcelain simulate").map(|cli| cli.output),
+            Ok(Output::Porcelain)
+        );
+        assert_eq!(
+            parse_str("--output=porcelain").map(|cli| cli.output),
+            Ok(Output::Porcelain)
+        );
+    }
+
+    #[test]
+    fn parse_threads() {
+        assert_eq!(
+            parse_str("simulate --threads 2").map(|cli| cli.threads),
+            Ok(Some(2))
+        );
+        assert!(parse_str("--threads 0").is_err());
+        assert!(parse_str("--threads all").is_err());
+    }
+
+    #[test]
+    fn parse_strategy() {
+        assert_eq!(
+            parse_str("--strategy knuth simulate").map(|cli| cli.strategy),
+            Ok(Some(Strategy::Knuth))
+        );
+        assert_eq!(parse_str("simulate").map(|cli| cli.strategy), Ok(None));
+        assert!(parse_str("--strategy best").is_err());
+    }
+
+    #[test]
+    fn parse_first_guess() {
+        assert_eq!(
+            parse_str("--first-guess 0,0,1,1 simulate").map(|cli| cli.first_guess),
+            Ok(Some("0,0,1,1".to_string()))
+        );
+        assert!(parse_str("--first-guess").is_err());
+    }
+
+    #[test]
+    fn parse_missing_colors() {
+        assert_eq!(
+            parse_str("--missing-colors grau,8 solve").map(|cli| cli.missing_colors),
+            Ok(Some(vec!["grau".to_string(), "8".to_string()]))
+        );
+        assert_eq!(parse_str("").map(|cli| cli.missing_colors), Ok(None));
+        assert!(parse_str("--missing-colors").is_err());
+    }
+
+    #[test]
+    fn parse_verbosity() {
+        let verbosity = |args| parse_str(args).unwrap().verbosity;
+        assert_eq!(verbosity("-v"), 1);
+        assert_eq!(verbosity("-vv analyze"), 2);
+        assert_eq!(verbosity("-v --verbose"), 2);
+        assert_eq!(verbosity("--quiet"), -1);
+    }
+
+    #[test]
+    fn parse_script() {
+        assert_eq!(
+            parse_str("--script knuth.txt simulate").map(|cli| cli.script),
+            Ok(Some(PathBuf::from("knuth.txt")))
+        );
+        assert!(parse_str("--script").is_err());
+        assert!(parse_str("--script knuth.txt simulate --compare entropy,knuth").is_err());
+        assert_eq!(
+            parse_str("--policy net.txt simulate").map(|cli| cli.policy),
+            Ok(Some(PathBuf::from("net.txt")))
+        );
+        assert!(parse_str("--policy net.txt simulate --compare entropy,knuth").is_err());
+    }
+
+    #[test]
+    fn parse_progress() {
+        let progress = |args| parse_str(args).map(|cli| cli.progress);
+        assert_eq!(progress("simulate"), Ok(progress::Format::Bar));
+        assert_eq!(
+            progress("--progress json simulate"),
+            Ok(progress::Format::Json)
+        );
+        assert_eq!(progress("tree --progress=bar"), Ok(progress::Format::Bar));
+        assert!(progress("--progress dots").is_err());
+    }
+
+    #[test]
+    fn reject_invalid_arguments() {
+        assert!(parse_str("fly").is_err());
+        assert!(parse_str("--preset").is_err());
+        assert!(parse_str("--preset huge").is_err());
+        assert!(parse_str("bench --iterations many").is_err());
+        assert!(parse_str("--locale fr").is_err());
+        assert!(parse_str("--output yaml").is_err());
+        assert!(parse_str("--color blue").is_err());
+        assert!(parse_str("interactive --code rot").is_err());
+        assert!(parse_str("autoplay --history game.txt").is_err());
+        assert!(parse_str("interactive simulate").is_err());
+        assert!(parse_str("tui --max-turns 3").is_err());
+        assert!(parse_str("solve --max-turns 3").is_err());
+        assert!(parse_str("autoplay --random --code rot").is_err());
+        assert!(parse_str("simulate --random").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Reviews of the guesses of a user breaking a known code, the way chess engines review
+//! the moves of a game: the information each guess was expected to give and actually gave,
+//! next to the guess of the solver, and how accurate the guesses were over the game.
+
+use std::fmt::Display;
+
+use crate::{
+    commands::information_gained, palette::Pegs, Entry, Evaluation, Guess, SimpleGuesser, Solver,
+};
+
+pub struct Review<const FIELDS: usize> {
+    pub guess: Guess<FIELDS>,
+    /// The number of codes which fit the feedback before the guess.
+    pub candidates: usize,
+    /// The information the guess was expected to give in bit.
+    pub information: f64,
+    /// The information its feedback gave in bit.
+    pub gained: f64,
+    /// The guess of the solver.
+    pub best: Guess<FIELDS>,
+    pub best_information: f64,
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>
+    SimpleGuesser<FIELDS, COLORS, PARTITIONS>
+{
+    /// Reviews playing `guess` after `history`, which got the feedback `evaluation`.
+    pub fn review(
+        &mut self,
+        history: &[Entry<FIELDS>],
+        guess: Guess<FIELDS>,
+        evaluation: Evaluation<FIELDS>,
+    ) -> Review<FIELDS> {
+        let report = self.guess(history).expect("the code fits its own feedback");
+        let remaining = self
+            .candidates
+            .iter()
+            .filter(|candidate| self.rules.evaluate(**candidate, guess) == evaluation)
+            .count();
+        Review {
+            guess,
+            candidates: self.candidates.len(),
+            information: self.entropy(&self.partition(&self.candidates, guess)),
+            gained: information_gained(self.candidates.len(), remaining),
+            best: report.guess,
+            best_information: report.information,
+        }
+    }
+}
+
+impl<const FIELDS: usize> Review<FIELDS> {
+    /// How close the guess came to the one of the solver, from 0 to 1: the share of the
+    /// information the solver expected, or once the code is certain whether it was played.
+    pub fn accuracy(&self) -> f64 {
+        if self.guess == self.best {
+            1.
+        } else if self.best_information > 0. && self.candidates > 1 {
+            (self.information / self.best_information).min(1.)
+        } else {
+            0.
+        }
+    }
+}
+
+/// The mean accuracy of the guesses of a game, see `Review::accuracy`.
+pub fn accuracy<const FIELDS: usize>(reviews: &[Review<FIELDS>]) -> f64 {
+    reviews.iter().map(Review::accuracy).sum::<f64>() / reviews.len().max(1) as f64
+}
+
+impl<const FIELDS: usize> Display for Review<FIELDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.3} bit expected, {:.3} bit gained, accuracy {:.0}%",
+            self.information,
+            self.gained,
+            self.accuracy() * 100.
+        )?;
+        if self.guess == self.best {
+            write!(f, ", the guess of the solver")
+        } else {
+            write!(
+                f,
+                "\nthe solver would have played {} ({:.3} bit expected)",
+                Pegs(&self.best),
+                self.best_information
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, Preset};
+
+    #[test]
+    fn review_a_game() {
+        let rules = Preset::Classic.rules();
+        let code = Guess::new([3, 1, 1, 5]);
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let mut history = vec![];
+        let mut reviews = vec![];
+        // all pegs of one color are a poor opening, followed by the guesses of the solver
+        let mut guess = Guess::new([0, 0, 0, 0]);
+        loop {
+            let evaluation = rules.evaluate(code, guess);
+            reviews.push(guesser.review(&history, guess, evaluation));
+            if guess == code {
+                break;
+            }
+            history.push(Entry { guess, evaluation });
+            guess = guesser.guess(&history).unwrap().guess;
+        }
+        assert_eq!(reviews[0].candidates, 1296);
+        assert!(reviews[0].accuracy() < 0.6);
+        assert!(reviews[0].gained < reviews[0].information);
+        assert!(reviews[0]
+            .to_string()
+            .contains("the solver would have played"));
+        for review in &reviews[1..] {
+            assert_eq!(review.accuracy(), 1.);
+            assert!(review.to_string().ends_with(", the guess of the solver"));
+        }
+        let accuracy = accuracy(&reviews);
+        assert!(accuracy > reviews[0].accuracy() && accuracy < 1.);
+    }
+}
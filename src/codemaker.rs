@@ -0,0 +1,205 @@
+//! The program as the codemaker for a user breaking the code, at three difficulties: a code
+//! chosen at random, a code which the usual openings tell little about, or no code at all
+//! but a devil's advocate. That one answers every guess with the feedback which leaves the
+//! most codes possible, so a guess only wins once no other code is left, and reveals one of
+//! the codes left when the game ends.
+
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use crate::{random::Rng, Evaluation, Guess, Rules, SimpleGuesser, Solver, SolverOptions};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Difficulty {
+    /// A code chosen at random.
+    #[default]
+    Easy,
+    /// A code chosen at random among those the usual openings tell least about.
+    Medium,
+    /// No code, see `Codemaker::Adversary`.
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown difficulty {s:?}, expected easy, medium or hard"))
+    }
+}
+
+pub enum Codemaker<const FIELDS: usize> {
+    /// A code chosen before the game.
+    Fixed(Guess<FIELDS>),
+    /// The devil's advocate, with the codes which fit its feedback so far.
+    Adversary {
+        candidates: Vec<Guess<FIELDS>>,
+        rng: Rng,
+    },
+}
+
+impl<const FIELDS: usize> Codemaker<FIELDS> {
+    pub fn adversary<const COLORS: u32>(rules: Rules, rng: Rng) -> Self {
+        Codemaker::Adversary {
+            candidates: rules.codes::<FIELDS, COLORS>().collect(),
+            rng,
+        }
+    }
+
+    /// The feedback to `guess`. The devil's advocate gives the feedback which leaves the
+    /// most codes, and of equally many the feedback which doesn't win.
+    pub fn evaluate(&mut self, rules: Rules, guess: Guess<FIELDS>) -> Evaluation<FIELDS> {
+        let candidates = match self {
+            Codemaker::Fixed(code) => return rules.evaluate(*code, guess),
+            Codemaker::Adversary { candidates, .. } => candidates,
+        };
+        let mut counts = HashMap::new();
+        for code in candidates.iter() {
+            *counts.entry(rules.evaluate(*code, guess)).or_insert(0) += 1;
+        }
+        let (evaluation, _) = counts
+            .into_iter()
+            .max_by_key(|(evaluation, count)| {
+                (
+                    *count,
+                    evaluation.exact != FIELDS as u32,
+                    // makes the choice among equally good feedback independent of the order
+                    // the hash map iterates in
+                    std::cmp::Reverse(evaluation.to_u32()),
+                )
+            })
+            .expect("a code fits the feedback so far");
+        candidates.retain(|code| rules.evaluate(*code, guess) == evaluation);
+        evaluation
+    }
+
+    /// The secret, or one of the codes which fit the feedback of the devil's advocate.
+    pub fn code(&mut self) -> Guess<FIELDS> {
+        match self {
+            Codemaker::Fixed(code) => *code,
+            Codemaker::Adversary { candidates, rng } => candidates[rng.below(candidates.len())],
+        }
+    }
+}
+
+/// A code drawn from the quarter of the codes which leave the most other codes possible
+/// after the opening of the solver and the ones people tend to play: two colors, each on
+/// half of the fields, and distinct colors.
+pub fn biased_code<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    rng: &mut Rng,
+) -> Guess<FIELDS> {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let mut openings = vec![
+        guesser
+            .guess(&[])
+            .expect("every code fits no feedback")
+            .guess,
+        Guess::new(std::array::from_fn(|field| (2 * field >= FIELDS) as u32)),
+    ];
+    if FIELDS as u32 <= COLORS {
+        openings.push(Guess::new(std::array::from_fn(|field| field as u32)));
+    }
+    openings.retain(|opening| rules.is_legal_guess::<FIELDS, COLORS>(opening));
+
+    let codes: Vec<_> = rules.codes::<FIELDS, COLORS>().collect();
+    // how much is left to find out about each code after each opening, in bit
+    let mut unknown = vec![0.; codes.len()];
+    for opening in openings {
+        let evaluations: Vec<_> = codes
+            .iter()
+            .map(|code| rules.evaluate(*code, opening))
+            .collect();
+        let mut counts = HashMap::new();
+        for evaluation in &evaluations {
+            *counts.entry(*evaluation).or_insert(0) += 1;
+        }
+        for (unknown, evaluation) in unknown.iter_mut().zip(&evaluations) {
+            *unknown += (counts[evaluation] as f64).log2();
+        }
+    }
+    let mut order: Vec<_> = (0..codes.len()).collect();
+    order.sort_by(|a, b| unknown[*b].total_cmp(&unknown[*a]));
+    let quarter = codes.len().div_ceil(4);
+    codes[order[rng.below(quarter)]]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, Preset};
+
+    #[test]
+    fn devils_advocate() {
+        assert_eq!("HARD".parse(), Ok(Difficulty::Hard));
+        assert!("nightmare".parse::<Difficulty>().is_err());
+
+        let rules = Preset::Mini.rules();
+        let mut codemaker = Codemaker::<3>::adversary::<4>(rules, Rng::seeded(1));
+        let mut turns = 0;
+        // no guess wins while another code fits the feedback
+        for code in rules.codes::<3, 4>() {
+            turns += 1;
+            let evaluation = codemaker.evaluate(rules, code);
+            let Codemaker::Adversary { candidates, .. } = &codemaker else {
+                unreachable!()
+            };
+            assert!(candidates
+                .iter()
+                .all(|other| rules.evaluate(*other, code) == evaluation));
+            if evaluation.exact == 3 {
+                assert_eq!(candidates, &[code]);
+                break;
+            }
+            assert!(!candidates.is_empty());
+        }
+        assert!(turns > 1);
+        let code = codemaker.code();
+        assert_eq!(codemaker.evaluate(rules, code).exact, 3);
+    }
+
+    #[test]
+    fn avoid_the_usual_openings() {
+        let rules = Preset::Classic.rules();
+        let mut rng = Rng::seeded(2);
+        // after the feedback to the opening of the solver more codes remain than on average
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let opening = guesser.guess(&[]).unwrap().guess;
+        let codes: Vec<_> = rules.codes::<4, 6>().collect();
+        let left = |code| {
+            let evaluation = rules.evaluate(code, opening);
+            codes
+                .iter()
+                .filter(|other| rules.evaluate(**other, opening) == evaluation)
+                .count()
+        };
+        let average = codes.iter().map(|code| left(*code)).sum::<usize>() / codes.len();
+        for _ in 0..5 {
+            let code =
+                biased_code::<4, 6, { max_gauss(4) }>(rules, &SolverOptions::default(), &mut rng);
+            assert!(rules.is_legal_code::<4, 6>(&code));
+            assert!(left(code) > average, "{code}");
+        }
+    }
+}
@@ -0,0 +1,1156 @@
+use std::{
+    collections::HashSet,
+    io::Write,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    arena::Arena,
+    baked,
+    book::{Book, BookSolver},
+    cli::{Output, Sample, Secret, TreeFormat},
+    csv,
+    facts::Fact,
+    history, interactive,
+    json::Json,
+    max_gauss,
+    palette::Pegs,
+    plugin::{self, Contender},
+    preset::Preset,
+    progress::Progress,
+    random::Rng,
+    stats::{Calibration, LowerBound, PairedTest, Statistics},
+    store::{self, Breaker},
+    strategy::Strategy,
+    transcript,
+    tree::Node,
+    Entry, Evaluation, Guess, GuessIterator, GuessReport, InconsistentHistory, OpeningScores,
+    Rules, SimpleGuesser, Solver, SolverOptions,
+};
+
+const DEMO_CODE: [u32; 6] = [3, 2, 1, 0, 6, 5];
+
+/// Picks the secret code, checking that a code given by the user is allowed by the rules.
+pub fn secret_code<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    secret: Secret,
+) -> Result<Guess<FIELDS>, String> {
+    match secret {
+        Secret::Code(code) => {
+            let code = code.parse().map_err(|err| format!("invalid code: {err}"))?;
+            if !rules.is_legal_code::<FIELDS, COLORS>(&code) {
+                return Err(format!("[{code}] is not a legal code in this game"));
+            }
+            Ok(code)
+        }
+        Secret::Random { seed } => {
+            let mut rng = seed.map_or_else(Rng::from_entropy, Rng::seeded);
+            Ok(rules.random_code::<FIELDS, COLORS>(&mut rng))
+        }
+    }
+}
+
+/// The codes to simulate games against: every code, or as many different ones as the
+/// sample asks for, drawn at random.
+pub fn simulated_codes<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    sample: Option<&Sample>,
+) -> Vec<Guess<FIELDS>> {
+    let Some(sample) = sample else {
+        return rules.codes::<FIELDS, COLORS>().collect();
+    };
+    let mut rng = sample.seed.map_or_else(Rng::from_entropy, Rng::seeded);
+    let count = sample.codes.min(rules.code_count::<FIELDS, COLORS>());
+    let mut codes = HashSet::with_capacity(count);
+    while codes.len() < count {
+        codes.insert(rules.random_code::<FIELDS, COLORS>(&mut rng));
+    }
+    codes.into_iter().collect()
+}
+
+/// One move of a game played by the solver.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Turn<const FIELDS: usize> {
+    pub guess: Guess<FIELDS>,
+    pub evaluation: Evaluation<FIELDS>,
+    pub score: f64,
+    /// The number of codes which were still possible when the guess was made.
+    pub candidates: usize,
+    /// The information the guess was expected to give in bit.
+    pub information: f64,
+    /// The number of guesses the game was expected to take from this one on.
+    pub expected_guesses: f64,
+    /// The information the feedback actually gave in bit, by how much it reduced the
+    /// candidates.
+    pub gained: f64,
+    /// The number of guesses the solver scored against the candidates.
+    pub scored: usize,
+    pub elapsed: Duration,
+}
+
+/// The information in bit of narrowing `candidates` codes down to `remaining`.
+pub fn information_gained(candidates: usize, remaining: usize) -> f64 {
+    (candidates as f64 / remaining.max(1) as f64).log2()
+}
+
+/// Describes the work the solver did for one guess, with the time of all guesses so far.
+pub fn guess_stats(elapsed: Duration, scored: usize, candidates: usize, total: Duration) -> String {
+    format!(
+        "took {elapsed:.1?} to score {scored} guesses against {candidates} candidates, \
+        {total:.1?} in total"
+    )
+}
+
+impl<const FIELDS: usize> From<&Turn<FIELDS>> for Json {
+    fn from(turn: &Turn<FIELDS>) -> Self {
+        Json::object([
+            ("guess", turn.guess.into()),
+            ("feedback", turn.evaluation.into()),
+            ("score", turn.score.into()),
+            ("candidates", turn.candidates.into()),
+            ("information", turn.information.into()),
+            ("expected_guesses", turn.expected_guesses.into()),
+            ("gained", turn.gained.into()),
+            ("scored", turn.scored.into()),
+            ("elapsed", turn.elapsed.into()),
+        ])
+    }
+}
+
+/// Lets the solver guess `code` and returns the moves it needed, or the first `max_turns`
+/// if it needs more.
+pub fn play<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    guesser: &mut SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+    code: Guess<FIELDS>,
+    max_turns: usize,
+    mut on_turn: impl FnMut(&Turn<FIELDS>),
+) -> Vec<Turn<FIELDS>> {
+    let mut history = vec![];
+    let mut turns = vec![];
+    while turns.len() < max_turns {
+        let report = guesser
+            .guess(history.as_slice())
+            .expect("the code fits its own feedback");
+        let guess = report.guess;
+        let evaluation = guesser.rules.evaluate(code, guess);
+        let remaining = guesser
+            .candidates()
+            .iter()
+            .filter(|candidate| guesser.rules.evaluate(**candidate, guess) == evaluation)
+            .count();
+        let turn = Turn {
+            guess,
+            evaluation,
+            score: report.score,
+            candidates: report.candidates,
+            information: report.information,
+            expected_guesses: report.expected_guesses,
+            gained: information_gained(report.candidates, remaining),
+            scored: report.scored,
+            elapsed: report.elapsed,
+        };
+        history.push(Entry {
+            guess,
+            evaluation: turn.evaluation,
+        });
+        on_turn(&turn);
+        turns.push(turn);
+        if code == guess {
+            break;
+        }
+    }
+    turns
+}
+
+pub fn list_presets(output: Output) {
+    match output {
+        Output::Text => {
+            for preset in Preset::ALL {
+                println!("{:<10} {}", preset.name(), preset.description());
+            }
+        }
+        Output::Porcelain => {
+            for preset in Preset::ALL {
+                println!("{}", preset.name());
+            }
+        }
+        Output::Json => {
+            let presets = Preset::ALL
+                .iter()
+                .map(|preset| {
+                    Json::object([
+                        ("name", preset.name().into()),
+                        ("description", preset.description().into()),
+                        ("fields", preset.fields().into()),
+                        ("colors", preset.colors().into()),
+                        ("duplicates", preset.rules().duplicates.into()),
+                    ])
+                })
+                .collect::<Vec<_>>();
+            println!("{}", Json::from(presets));
+        }
+    }
+}
+
+pub fn autoplay<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    secret: Option<Secret>,
+    transcript: Option<PathBuf>,
+    max_turns: usize,
+    explain: bool,
+    output: Output,
+) -> Result<(), String> {
+    let code = match secret {
+        Some(secret) => secret_code::<FIELDS, COLORS>(rules, secret)?,
+        None => Guess::new(std::array::from_fn(|i| DEMO_CODE[i])),
+    };
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let start = Instant::now();
+    let mut total = Duration::ZERO;
+    // explained by a solver of its own, as `play` borrows the one which guesses
+    let mut explainer = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let mut history = vec![];
+    let mut explanations = vec![];
+    let turns = play(&mut guesser, code, max_turns, |turn| {
+        total += turn.elapsed;
+        if explain && output != Output::Porcelain {
+            explanations.push(explainer.explain(&history, turn.guess));
+            history.push(Entry {
+                guess: turn.guess,
+                evaluation: turn.evaluation,
+            });
+        }
+        if output == Output::Text {
+            println!(
+                "I'm guessing: [{}] ({} bit, about {:.1} guesses to go) {}",
+                Pegs(&turn.guess),
+                turn.score,
+                turn.expected_guesses,
+                turn.evaluation
+            );
+            println!(
+                "  {}",
+                guess_stats(turn.elapsed, turn.scored, turn.candidates, total)
+            );
+            if let Some(explanation) = explanations.last() {
+                println!("{explanation}");
+            }
+        }
+        if output == Output::Porcelain {
+            println!("{}", turn.guess.indices());
+        }
+    });
+    let solved = turns.last().is_some_and(|turn| turn.guess == code);
+    let game = store::Game {
+        turns: turns.len(),
+        solved,
+    };
+    store::record::<FIELDS, COLORS>(rules, "autoplay", Breaker::Solver, [game]);
+    if output == Output::Json {
+        let mut turns: Vec<_> = turns.iter().map(Json::from).collect();
+        for (turn, explanation) in turns.iter_mut().zip(&explanations) {
+            if let Json::Object(fields) = turn {
+                fields.push(("explanation".to_string(), explanation.into()));
+            }
+        }
+        let json = Json::object([
+            ("code", code.into()),
+            ("solved", solved.into()),
+            ("turns", turns.into()),
+            ("elapsed", start.elapsed().into()),
+        ]);
+        println!("{json}");
+    }
+    if let Some(path) = transcript {
+        let history: Vec<_> = turns
+            .iter()
+            .map(|turn| Entry {
+                guess: turn.guess,
+                evaluation: turn.evaluation,
+            })
+            .collect();
+        let comments: Vec<_> = turns.iter().map(transcript::annotate).collect();
+        transcript::write::<FIELDS, COLORS>(&path, rules, Some(code), &history, &comments)?;
+    }
+    if !solved {
+        return Err(format!(
+            "the solver didn't find [{}] within {max_turns} turns",
+            Pegs(&code)
+        ));
+    }
+    if output == Output::Text {
+        println!("Found the code in {} turns.", turns.len());
+    }
+    Ok(())
+}
+
+/// Recommends the next guess for the history in `path`. The book is only used without
+/// `facts`, as it is built for every code being possible.
+pub fn solve<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    path: Option<PathBuf>,
+    book: Option<PathBuf>,
+    explain: bool,
+    facts: &[String],
+    output: Output,
+) -> Result<(), String> {
+    let text = match &path {
+        Some(path) if path.as_os_str() != "-" => {
+            std::fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?
+        }
+        _ => std::io::read_to_string(std::io::stdin()).map_err(|err| err.to_string())?,
+    };
+    let history = history::parse::<FIELDS, COLORS>(rules, &text).map_err(|err| match &path {
+        Some(path) => format!("{}: {err}", path.display()),
+        None => err.to_string(),
+    })?;
+
+    let facts = facts
+        .iter()
+        .map(|text| Fact::parse::<FIELDS, COLORS>(text).map_err(|err| format!("{text:?}: {err}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    guesser.set_facts(facts.clone());
+    let book: Option<Box<dyn Book<FIELDS>>> = match &book {
+        _ if !facts.is_empty() => None,
+        Some(path) => Some(Box::new(Node::read::<COLORS>(rules, path)?)),
+        None => baked::book::<FIELDS, COLORS>(rules, options).map(|book| Box::new(book) as _),
+    };
+    let mut from_book = false;
+    let report = match book {
+        Some(book) => {
+            from_book = book.lookup(&history).is_some();
+            let mut solver = BookSolver::new(book, guesser);
+            let report = solver.guess(&history);
+            guesser = solver.fallback;
+            report
+        }
+        None => guesser.guess(&history),
+    };
+    let GuessReport {
+        guess,
+        score,
+        expected_guesses,
+        elapsed,
+        ..
+    } = report.map_err(|err| err.to_string())?;
+    let explanation =
+        (explain && output != Output::Porcelain).then(|| guesser.explain(&history, guess));
+    // the book doesn't know the candidates
+    guesser.update_candidates(&history);
+    let chances = (output != Output::Porcelain
+        && can_forecast::<FIELDS, COLORS>(rules, guesser.candidates().len()))
+    .then(|| {
+        let mut forecaster =
+            SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+        forecaster.set_facts(facts);
+        finish_chances(&mut forecaster, &history, FORECAST_TURNS)
+            .expect("the solver found a candidate")
+    });
+    match output {
+        Output::Text => {
+            interactive::print_candidates(guesser.candidates());
+            if from_book {
+                println!(
+                    "recommended guess: [{}] (from the book, about {expected_guesses:.1} \
+                     guesses to go)",
+                    Pegs(&guess)
+                );
+            } else {
+                println!(
+                    "recommended guess: [{}] ({score} bit, about {expected_guesses:.1} guesses \
+                     to go)",
+                    Pegs(&guess)
+                );
+            }
+            if let Some(chances) = &chances {
+                println!("chances to find it {}", describe_chances(chances));
+            }
+            if let Some(explanation) = explanation {
+                println!("{explanation}");
+            }
+        }
+        Output::Porcelain => println!("{}", guess.indices()),
+        Output::Json => {
+            let candidates = guesser.candidates().to_vec();
+            let mut fields = vec![
+                ("guess", guess.into()),
+                ("score", score.into()),
+                ("expected_guesses", expected_guesses.into()),
+                ("candidates", candidates.len().into()),
+                ("remaining", candidates.into()),
+                ("elapsed", elapsed.into()),
+            ];
+            if let Some(chances) = chances {
+                fields.push(("finish_chances", chances.into()));
+            }
+            if let Some(explanation) = &explanation {
+                fields.push(("explanation", explanation.into()));
+            }
+            println!("{}", Json::object(fields));
+        }
+    }
+    Ok(())
+}
+
+/// A game played by `play_all`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Game<const FIELDS: usize> {
+    pub code: Guess<FIELDS>,
+    /// The last guess is the code.
+    pub turns: Vec<Turn<FIELDS>>,
+}
+
+/// The games for `store::record`, the solver finds every code.
+fn solved<const FIELDS: usize>(games: &[Game<FIELDS>]) -> impl Iterator<Item = store::Game> + '_ {
+    games.iter().map(|game| store::Game {
+        turns: game.turns.len(),
+        solved: true,
+    })
+}
+
+/// Lets the solver play against all `codes` at once and returns the games, in the order of
+/// the codes. Every guess is made once for all codes which lead to it, so the game tree is
+/// searched instead of playing every game, and games share the times of their common guesses.
+pub fn play_all<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    guesser: &mut SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+    codes: impl IntoIterator<Item = Guess<FIELDS>>,
+) -> Vec<Game<FIELDS>> {
+    play_all_after(guesser, &[], codes)
+}
+
+/// Plays like `play_all` after the turns of `history`, which all `codes` have to fit. The
+/// games only have the turns after the history.
+fn play_all_after<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    guesser: &mut SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+    history: &[Entry<FIELDS>],
+    codes: impl IntoIterator<Item = Guess<FIELDS>>,
+) -> Vec<Game<FIELDS>> {
+    let mut arena = Arena::new();
+    let codes = arena.alloc(codes);
+    let mut games = Vec::with_capacity(codes.len());
+    let progress = Progress::new("simulating", codes.len());
+    search(
+        guesser,
+        &mut arena,
+        codes,
+        &mut history.to_vec(),
+        &mut vec![],
+        &mut games,
+        &progress,
+    );
+    games.sort_by_key(|game| game.code.packed());
+    games
+}
+
+/// Bounds the work of `finish_chances` to report them after every turn: the number of
+/// candidates times the number of guesses, which is about what the first guess of the
+/// solver costs for them. It takes a moment for classic Mastermind from the start.
+const FORECAST_WORK: usize = 2_000_000;
+
+/// The number of turns `finish_chances` are reported for.
+pub const FORECAST_TURNS: usize = 3;
+
+/// Whether `finish_chances` is quick enough for `candidates` to report it after a turn.
+pub fn can_forecast<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    candidates: usize,
+) -> bool {
+    candidates.saturating_mul(rules.guess_count::<FIELDS, COLORS>()) <= FORECAST_WORK
+}
+
+/// The chances that the solver finds the code within 1, 2, … `turns` more guesses after
+/// `history`, with every code which fits it equally likely. The solver plays against all
+/// of them, see `can_forecast` for when that is quick.
+pub fn finish_chances<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    guesser: &mut SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+    history: &[Entry<FIELDS>],
+    turns: usize,
+) -> Result<Vec<f64>, InconsistentHistory> {
+    guesser.update_candidates(history);
+    let codes = guesser.candidates().to_vec();
+    if codes.is_empty() {
+        return Err(InconsistentHistory);
+    }
+    let games = play_all_after(guesser, history, codes);
+    let mut finished = vec![0; turns];
+    for game in &games {
+        if let Some(count) = finished.get_mut(game.turns.len() - 1) {
+            *count += 1;
+        }
+    }
+    Ok(finished
+        .iter()
+        .scan(0, |total, count| {
+            *total += count;
+            Some(*total as f64 / games.len() as f64)
+        })
+        .collect())
+}
+
+/// The chances of `finish_chances` as text, e.g. `within 1, 2, 3 guesses: 4%, 58%, 100%`.
+pub fn describe_chances(chances: &[f64]) -> String {
+    let turns: Vec<_> = (1..=chances.len()).map(|turn| turn.to_string()).collect();
+    let chances: Vec<_> = chances
+        .iter()
+        .map(|chance| format!("{:.0}%", chance * 100.))
+        .collect();
+    format!(
+        "within {} guesses: {}",
+        turns.join(", "),
+        chances.join(", ")
+    )
+}
+
+fn search<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    guesser: &mut SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+    arena: &mut Arena<Guess<FIELDS>>,
+    codes: Range<usize>,
+    history: &mut Vec<Entry<FIELDS>>,
+    turns: &mut Vec<Turn<FIELDS>>,
+    games: &mut Vec<Game<FIELDS>>,
+    progress: &Progress,
+) {
+    let report = guesser
+        .guess(history)
+        .expect("the codes fit the feedback they gave");
+    let guess = report.guess;
+    // the codes played can be a sample of the candidates, what the last feedback gained is
+    // only known from the candidates left for this guess
+    let candidates = report.candidates;
+    if let Some(previous) = turns.last_mut() {
+        previous.gained = information_gained(previous.candidates, candidates);
+    }
+    let mark = arena.mark();
+    let parts: [_; PARTITIONS] = arena.split(guesser.rules, codes, guess);
+    for part in parts {
+        let Some(code) = arena.get(part.clone()).first().copied() else {
+            continue;
+        };
+        let evaluation = guesser.rules.evaluate(code, guess);
+        turns.push(Turn {
+            guess,
+            evaluation,
+            score: report.score,
+            candidates,
+            information: report.information,
+            expected_guesses: report.expected_guesses,
+            // set by the next guess unless this one wins
+            gained: information_gained(candidates, 1),
+            scored: report.scored,
+            elapsed: report.elapsed,
+        });
+        if code == guess {
+            games.push(Game {
+                code,
+                turns: turns.clone(),
+            });
+            progress.inc(1);
+        } else {
+            history.push(Entry { guess, evaluation });
+            search(guesser, arena, part, history, turns, games, progress);
+            history.pop();
+        }
+        turns.pop();
+    }
+    arena.release(mark);
+}
+
+/// Writes one line per game with its code, the number of turns, the guesses and the
+/// seconds it took to choose them, one column per turn. Codes are given as color numbers.
+pub fn write_csv<const FIELDS: usize>(
+    path: &Path,
+    games: &[(String, Vec<Game<FIELDS>>)],
+) -> Result<(), String> {
+    let error = |err: std::io::Error| format!("{}: {err}", path.display());
+    let file = std::fs::File::create(path).map_err(error)?;
+    let mut writer = csv::Writer::new(std::io::BufWriter::new(file));
+    let most_turns = games
+        .iter()
+        .flat_map(|(_, games)| games)
+        .map(|game| game.turns.len())
+        .max()
+        .unwrap_or(0);
+    let header = ["strategy", "code", "turns"]
+        .map(String::from)
+        .into_iter()
+        .chain((1..=most_turns).map(|turn| format!("guess_{turn}")))
+        .chain((1..=most_turns).map(|turn| format!("seconds_{turn}")));
+    writer.record(header).map_err(error)?;
+    for (strategy, games) in games {
+        for game in games {
+            let padding = most_turns - game.turns.len();
+            let record = [
+                strategy.clone(),
+                game.code.indices().to_string(),
+                game.turns.len().to_string(),
+            ]
+            .into_iter()
+            .chain(
+                game.turns
+                    .iter()
+                    .map(|turn| turn.guess.indices().to_string()),
+            )
+            .chain(std::iter::repeat_n(String::new(), padding))
+            .chain(
+                game.turns
+                    .iter()
+                    .map(|turn| turn.elapsed.as_secs_f64().to_string()),
+            )
+            .chain(std::iter::repeat_n(String::new(), padding));
+            writer.record(record).map_err(error)?;
+        }
+    }
+    writer.into_inner().flush().map_err(error)
+}
+
+pub fn simulate<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    csv: Option<PathBuf>,
+    sample: Option<Sample>,
+    output: Output,
+) -> Result<(), String> {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let codes = simulated_codes::<FIELDS, COLORS>(rules, sample.as_ref());
+    let start = Instant::now();
+    let games = play_all(&mut guesser, codes);
+    let statistics = Statistics::new(games.iter().map(|game| game.turns.len()), start.elapsed());
+    store::record::<FIELDS, COLORS>(rules, "simulate", Breaker::Solver, solved(&games));
+    let bound = lower_bound::<FIELDS, PARTITIONS>(rules.code_count::<FIELDS, COLORS>());
+    let mut calibration = Calibration::default();
+    for game in &games {
+        for (index, turn) in game.turns.iter().enumerate() {
+            calibration.add(index, turn.information, turn.gained);
+        }
+    }
+    match output {
+        Output::Text => {
+            for game in &games {
+                println!("[{}]: {} turns", game.code, game.turns.len());
+            }
+            statistics.print();
+            if sample.is_some() {
+                println!(
+                    "95% confidence interval: {:.3} ± {:.3} turns",
+                    statistics.average(),
+                    statistics.confidence()
+                );
+            }
+            bound.print(&statistics);
+            println!("information by turn in bit:");
+            calibration.print();
+        }
+        Output::Porcelain => {
+            for game in &games {
+                println!("{}\t{}", game.code.indices(), game.turns.len());
+            }
+        }
+        Output::Json => {
+            let games: Vec<_> = games
+                .iter()
+                .map(|game| {
+                    Json::object([
+                        ("code", game.code.into()),
+                        ("turns", game.turns.len().into()),
+                    ])
+                })
+                .collect();
+            let mut json = Json::from(&statistics);
+            if let Json::Object(fields) = &mut json {
+                if sample.is_some() {
+                    fields.push(("confidence".to_string(), statistics.confidence().into()));
+                }
+                fields.push(("lower_bound".to_string(), (&bound).into()));
+                fields.push(("calibration".to_string(), (&calibration).into()));
+                fields.push(("games".to_string(), games.into()));
+            }
+            println!("{json}");
+        }
+    }
+    match csv {
+        Some(path) => write_csv(&path, &[(guesser.strategy.to_string(), games)]),
+        None => Ok(()),
+    }
+}
+
+/// The lower bound for finding every one of `codes` codes. A guess can get every feedback
+/// but the one with all but one exact match, which is impossible.
+fn lower_bound<const FIELDS: usize, const PARTITIONS: usize>(codes: usize) -> LowerBound {
+    LowerBound::new(codes, PARTITIONS - usize::from(FIELDS > 0))
+}
+
+/// Plays every strategy and plugin against every code and prints their statistics side by
+/// side. For a sample of the codes the averages get confidence intervals, and every one is
+/// tested for whether it takes more or fewer turns than the first one. Plugins are looked
+/// for in `plugins` and the plugin directory, see `plugin::discover`.
+pub fn compare<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    contenders: &[Contender],
+    plugins: &[PathBuf],
+    csv: Option<PathBuf>,
+    sample: Option<Sample>,
+    output: Output,
+) -> Result<(), String> {
+    let codes = simulated_codes::<FIELDS, COLORS>(rules, sample.as_ref());
+    // the strategies share the evaluation table
+    let table = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::new(rules).table;
+    let plugins = if contenders
+        .iter()
+        .any(|contender| matches!(contender, Contender::Plugin(_)))
+    {
+        plugin::discover(plugins)?
+    } else {
+        vec![]
+    };
+    // before anything is played
+    for contender in contenders {
+        if let Contender::Plugin(name) = contender {
+            if !plugins.iter().any(|plugin| plugin.name == *name) {
+                return Err(unknown_contender(name, &plugins));
+            }
+        }
+    }
+    let results = contenders
+        .iter()
+        .map(|contender| {
+            let strategy = match contender {
+                Contender::Strategy(strategy) => *strategy,
+                Contender::Plugin(name) => {
+                    let plugin = plugins
+                        .iter()
+                        .find(|plugin| plugin.name == *name)
+                        .expect("the plugins are looked up before");
+                    let (games, elapsed) =
+                        plugin::play_all::<FIELDS, COLORS>(plugin, rules, &codes)?;
+                    return Ok((contender.name().to_string(), games, elapsed));
+                }
+            };
+            let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS> {
+                rules,
+                strategy,
+                table: table.clone(),
+                ..Default::default()
+            };
+            let start = Instant::now();
+            let games = play_all(&mut guesser, codes.iter().copied());
+            let elapsed = start.elapsed();
+            store::record::<FIELDS, COLORS>(rules, "compare", Breaker::Solver, solved(&games));
+            Ok((contender.name().to_string(), games, elapsed))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let statistics: Vec<_> = results
+        .iter()
+        .map(|(name, games, elapsed)| {
+            let turns = games.iter().map(|game| game.turns.len());
+            (name.as_str(), Statistics::new(turns, *elapsed))
+        })
+        .collect();
+    let bound = lower_bound::<FIELDS, PARTITIONS>(rules.code_count::<FIELDS, COLORS>());
+    // the games are in the order of the codes, so they are paired up by code
+    let turns = |games: &[Game<FIELDS>]| games.iter().map(|game| game.turns.len()).collect();
+    let tests: Vec<_> = match (sample.is_some(), results.first()) {
+        (true, Some((_, first, _))) => {
+            let first: Vec<_> = turns(first);
+            results[1..]
+                .iter()
+                .map(|(name, games, _)| {
+                    let games: Vec<_> = turns(games);
+                    (name.as_str(), PairedTest::new(&first, &games))
+                })
+                .collect()
+        }
+        _ => vec![],
+    };
+    match output {
+        Output::Text => {
+            let confidence = |statistics: &Statistics| match sample {
+                Some(_) => format!("±{:.3}", statistics.confidence()),
+                None => String::new(),
+            };
+            println!(
+                "{:<10} {:>7} {:>7} {:>4} {:>7} {:>7} {:>10}",
+                "strategy", "mean", "", "max", "median", "gap", "time/move"
+            );
+            for (strategy, statistics) in &statistics {
+                println!(
+                    "{:<10} {:>7.3} {:>7} {:>4} {:>7.1} {:>+7.3} {:>10}",
+                    strategy,
+                    statistics.average(),
+                    confidence(statistics),
+                    statistics.worst_case(),
+                    statistics.median(),
+                    statistics.average() - bound.average,
+                    format!("{:.1?}", statistics.time_per_move())
+                );
+            }
+            println!(
+                "{:<10} {:>7.3} {:>7} {:>4}",
+                "bound", bound.average, "", bound.worst_case
+            );
+            if let Some((first, _, _)) = results.first() {
+                for (strategy, test) in &tests {
+                    println!(
+                        "{strategy} against {first}: {:+.3} ± {:.3} turns, p = {:.4}{}",
+                        test.difference,
+                        test.confidence,
+                        test.p_value,
+                        if test.is_significant() {
+                            " (significant)"
+                        } else {
+                            ""
+                        }
+                    );
+                }
+            }
+        }
+        Output::Porcelain => {
+            for (strategy, statistics) in &statistics {
+                println!(
+                    "{strategy}\t{}\t{}\t{}\t{}",
+                    statistics.average(),
+                    statistics.worst_case(),
+                    statistics.median(),
+                    statistics.time_per_move().as_secs_f64()
+                );
+            }
+        }
+        Output::Json => {
+            let strategies = statistics
+                .iter()
+                .map(|(strategy, statistics)| {
+                    let mut json = Json::from(statistics);
+                    if let Json::Object(fields) = &mut json {
+                        fields.insert(0, ("strategy".to_string(), (*strategy).into()));
+                        if sample.is_some() {
+                            fields.push(("confidence".to_string(), statistics.confidence().into()));
+                        }
+                        if let Some((_, test)) = tests.iter().find(|(other, _)| other == strategy) {
+                            fields.push(("against_first".to_string(), test.into()));
+                        }
+                        fields.push(("lower_bound".to_string(), (&bound).into()));
+                    }
+                    json
+                })
+                .collect::<Vec<_>>();
+            println!("{}", Json::from(strategies));
+        }
+    }
+    match csv {
+        Some(path) => {
+            let games: Vec<_> = results
+                .into_iter()
+                .map(|(name, games, _)| (name, games))
+                .collect();
+            write_csv(&path, &games)
+        }
+        None => Ok(()),
+    }
+}
+
+/// The error for a name which is neither a strategy nor one of the `plugins`.
+fn unknown_contender(name: &str, plugins: &[plugin::Plugin]) -> String {
+    let mut names: Vec<_> = Strategy::ALL.iter().map(Strategy::name).collect();
+    names.extend(plugins.iter().map(|plugin| plugin.name.as_str()));
+    let directory = plugin::directory()
+        .map(|directory| format!(" in {}", directory.display()))
+        .unwrap_or_default();
+    format!(
+        "unknown strategy or plugin {name:?}, expected {} or a plugin{directory} or given \
+         with --plugin",
+        names.join(", ")
+    )
+}
+
+/// Writes the decision tree of the solver to `path`, or stdout if there is none, in the
+/// format chosen by `format` or `output`.
+pub fn tree<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    path: Option<PathBuf>,
+    format: Option<TreeFormat>,
+    output: Output,
+) -> Result<(), String> {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let start = Instant::now();
+    let tree = Node::build(&mut guesser, rules.codes::<FIELDS, COLORS>());
+    info!("expanded {} nodes in {:.1?}", tree.nodes(), start.elapsed());
+    let bytes = match (format, output) {
+        (Some(TreeFormat::Dot { depth }), _) => tree.dot(depth).into_bytes(),
+        (Some(TreeFormat::Binary), _) => tree.to_bytes::<COLORS>(rules),
+        (Some(TreeFormat::Rust), _) => baked::source::<FIELDS, COLORS>(&tree, rules).into_bytes(),
+        (None, Output::Text | Output::Porcelain) => tree.to_string().into_bytes(),
+        (None, Output::Json) => format!("{}\n", Json::from(&tree)).into_bytes(),
+    };
+    match path {
+        Some(path) => {
+            std::fs::write(&path, bytes).map_err(|err| format!("{}: {err}", path.display()))
+        }
+        None => std::io::stdout()
+            .write_all(&bytes)
+            .map_err(|err| err.to_string()),
+    }
+}
+
+/// Known results for classic Mastermind with 4 fields and 6 colors: no strategy can take
+/// fewer than 5625/1296 turns on average (Koyama and Lai, 1993), and Knuth's strategy never
+/// needs more than 5 turns (Knuth, 1977). The other bounds are the results of this solver,
+/// so that it can't get worse unnoticed.
+const OPTIMAL_CLASSIC_AVERAGE: f64 = 5625. / 1296.;
+/// The most average and worst case turns each strategy may take.
+const CLASSIC_BENCHMARKS: [(Strategy, f64, usize); 3] = [
+    (Strategy::Entropy, 4.65, 6),
+    (Strategy::Knuth, 4.48, 5),
+    (Strategy::MostParts, 4.42, 6),
+];
+
+/// A result of the solver compared with a known one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    pub name: String,
+    pub expected: String,
+    pub actual: String,
+    pub passed: bool,
+}
+
+impl From<&Check> for Json {
+    fn from(check: &Check) -> Self {
+        Json::object([
+            ("name", check.name.as_str().into()),
+            ("expected", check.expected.as_str().into()),
+            ("actual", check.actual.as_str().into()),
+            ("passed", check.passed.into()),
+        ])
+    }
+}
+
+/// Plays every strategy against every code of classic Mastermind and compares the results
+/// with `CLASSIC_BENCHMARKS`.
+pub fn classic_checks() -> Vec<Check> {
+    const FIELDS: usize = Preset::Classic.fields();
+    const COLORS: u32 = Preset::Classic.colors();
+    let rules = Preset::Classic.rules();
+    let table = SimpleGuesser::<FIELDS, COLORS, { max_gauss(FIELDS) }>::new(rules).table;
+    let mut checks = vec![];
+    for (strategy, average, worst_case) in CLASSIC_BENCHMARKS {
+        let mut guesser = SimpleGuesser::<FIELDS, COLORS, { max_gauss(FIELDS) }> {
+            rules,
+            strategy,
+            table: table.clone(),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let games = play_all(&mut guesser, rules.codes::<FIELDS, COLORS>());
+        let statistics =
+            Statistics::new(games.iter().map(|game| game.turns.len()), start.elapsed());
+        checks.push(Check {
+            name: format!("{strategy} average"),
+            expected: format!("{OPTIMAL_CLASSIC_AVERAGE:.3} to {average:.3} turns"),
+            actual: format!("{:.3} turns", statistics.average()),
+            passed: (OPTIMAL_CLASSIC_AVERAGE - 1e-9..=average).contains(&statistics.average()),
+        });
+        checks.push(Check {
+            name: format!("{strategy} worst case"),
+            expected: format!("at most {worst_case} turns"),
+            actual: format!("{} turns", statistics.worst_case()),
+            passed: statistics.worst_case() <= worst_case,
+        });
+    }
+    checks
+}
+
+/// Checks the solver against known results and fails if it doesn't reach one of them.
+pub fn verify(output: Output) -> Result<(), String> {
+    let checks = classic_checks();
+    match output {
+        Output::Text => {
+            for check in &checks {
+                println!(
+                    "{} {}: {} (expected {})",
+                    if check.passed { "ok  " } else { "FAIL" },
+                    check.name,
+                    check.actual,
+                    check.expected
+                );
+            }
+        }
+        Output::Porcelain => {
+            for check in &checks {
+                let status = if check.passed { "ok" } else { "fail" };
+                println!("{status}\t{}\t{}", check.name, check.actual);
+            }
+        }
+        Output::Json => {
+            let checks = checks.iter().map(Json::from).collect::<Vec<_>>();
+            println!("{}", Json::from(checks));
+        }
+    }
+    match checks.iter().filter(|check| !check.passed).count() {
+        0 => Ok(()),
+        failed => Err(format!(
+            "{failed} of {} checks failed against the known results",
+            checks.len()
+        )),
+    }
+}
+
+pub fn analyze<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    output: Output,
+) {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let codes = rules.codes::<FIELDS, COLORS>().count();
+    let guesses = GuessIterator::<FIELDS, COLORS>::default().count();
+    let GuessReport {
+        guess,
+        score,
+        elapsed,
+        ..
+    } = guesser.guess(&[]).expect("every code fits no feedback");
+    match output {
+        Output::Text => {
+            println!(
+                "fields: {FIELDS}, colors: {COLORS}, duplicates allowed: {}",
+                rules.duplicates
+            );
+            println!("possible codes: {codes}");
+            println!("possible guesses: {guesses}");
+            println!("feedback partitions: {PARTITIONS}");
+            println!("best opening: [{guess}] ({score} bit)");
+        }
+        Output::Porcelain => println!("{}", guess.indices()),
+        Output::Json => {
+            let json = Json::object([
+                ("fields", FIELDS.into()),
+                ("colors", COLORS.into()),
+                ("duplicates", rules.duplicates.into()),
+                ("codes", codes.into()),
+                ("guesses", guesses.into()),
+                ("partitions", PARTITIONS.into()),
+                (
+                    "opening",
+                    Json::object([
+                        ("guess", guess.into()),
+                        ("score", score.into()),
+                        ("elapsed", elapsed.into()),
+                    ]),
+                ),
+            ]);
+            println!("{json}");
+        }
+    }
+}
+
+/// Prints every canonical opening with its score by the measure of each strategy, ranked by
+/// the expected information, and the best opening by each measure.
+pub fn rank_openings<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    output: Output,
+) {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let openings = guesser.opening_scores();
+    match output {
+        Output::Text => {
+            println!("rank  information  worst case  expected size  partitions  opening");
+            for (rank, opening) in openings.iter().enumerate() {
+                println!(
+                    "{:>4}  {:>7.3} bit  {:>10}  {:>13.1}  {:>10}  [{}]",
+                    rank + 1,
+                    opening.information,
+                    opening.worst_case,
+                    opening.expected_size,
+                    opening.partitions,
+                    opening.guess
+                );
+            }
+            // the first of equally good openings, which has the most information of them
+            let best = |better: fn(&OpeningScores<FIELDS>, &OpeningScores<FIELDS>) -> bool| {
+                openings
+                    .iter()
+                    .reduce(|best, opening| if better(opening, best) { opening } else { best })
+                    .map(|opening| opening.guess)
+                    .expect("there is at least one opening")
+            };
+            println!();
+            println!(
+                "most information: [{}]",
+                best(|a, b| a.information > b.information)
+            );
+            println!(
+                "smallest worst case: [{}]",
+                best(|a, b| a.worst_case < b.worst_case)
+            );
+            println!(
+                "smallest expected size: [{}]",
+                best(|a, b| a.expected_size < b.expected_size)
+            );
+            println!(
+                "most partitions: [{}]",
+                best(|a, b| a.partitions > b.partitions)
+            );
+        }
+        Output::Porcelain => {
+            for opening in &openings {
+                println!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    opening.guess.indices(),
+                    opening.information,
+                    opening.worst_case,
+                    opening.expected_size,
+                    opening.partitions
+                );
+            }
+        }
+        Output::Json => {
+            let openings = openings
+                .iter()
+                .map(|opening| {
+                    Json::object([
+                        ("guess", opening.guess.into()),
+                        ("information", opening.information.into()),
+                        ("worst_case", (opening.worst_case as usize).into()),
+                        ("expected_size", opening.expected_size.into()),
+                        ("partitions", opening.partitions.into()),
+                    ])
+                })
+                .collect::<Vec<_>>();
+            println!("{}", Json::from(openings));
+        }
+    }
+}
+
+pub fn bench<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    iterations: usize,
+    output: Output,
+) {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let _ = std::hint::black_box(guesser.guess(&[]));
+    }
+    let elapsed = start.elapsed();
+    let per_guess = elapsed / iterations.max(1) as u32;
+    match output {
+        Output::Text => {
+            println!("{iterations} opening guesses in {elapsed:?} ({per_guess:?} per guess)")
+        }
+        Output::Porcelain => println!("{}", per_guess.as_secs_f64()),
+        Output::Json => {
+            let json = Json::object([
+                ("iterations", iterations.into()),
+                ("elapsed", elapsed.into()),
+                ("per_guess", per_guess.into()),
+            ]);
+            println!("{json}");
+        }
+    }
+}
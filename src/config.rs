@@ -0,0 +1,287 @@
+//! Defaults read from `~/.config/mastermind_solver/config.toml`.
+//!
+//! Only the subset of TOML needed for the settings is understood:
+//! `key = value` pairs with strings, integers, booleans and arrays, plus comments.
+
+use std::{fmt::Display, path::PathBuf};
+
+use crate::{palette::Locale, preset::Preset, strategy::Strategy};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub preset: Option<Preset>,
+    pub threads: Option<usize>,
+    /// How the solver ranks guesses, like `--strategy`.
+    pub strategy: Option<Strategy>,
+    pub locale: Option<Locale>,
+    pub palette: Option<Vec<String>>,
+    /// The colors the codebreaker has no pegs for, by name or number.
+    pub missing_colors: Option<Vec<String>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConfigError {
+    line: usize,
+    message: String,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "a string",
+            Value::Integer(_) => "an integer",
+            Value::Boolean(_) => "a boolean",
+            Value::Array(_) => "an array",
+        }
+    }
+}
+
+/// Location of the user's configuration file, if a home directory is known.
+pub fn default_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("mastermind_solver").join("config.toml"))
+}
+
+/// Loads the configuration from `path`, or from the default location if no path is given.
+/// A missing file at the default location yields the default configuration.
+pub fn load(path: Option<PathBuf>) -> Result<Config, String> {
+    let explicit = path.is_some();
+    let Some(path) = path.or_else(default_path) else {
+        return Ok(Config::default());
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => {
+            info!("reading defaults from {}", path.display());
+            parse(&contents).map_err(|err| format!("{}: {err}", path.display()))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound && !explicit => {
+            Ok(Config::default())
+        }
+        Err(err) => Err(format!("{}: {err}", path.display())),
+    }
+}
+
+pub fn parse(contents: &str) -> Result<Config, ConfigError> {
+    let mut config = Config::default();
+    let mut lines = contents.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let line_number = index + 1;
+        let error = |message: String| ConfigError {
+            line: line_number,
+            message,
+        };
+        let mut line = strip_comment(line).trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            return Err(error("tables are not supported".to_string()));
+        }
+        // arrays may span multiple lines
+        while line.contains('[') && !line.ends_with(']') {
+            let Some((_, next)) = lines.next() else {
+                return Err(error("unterminated array".to_string()));
+            };
+            line.push(' ');
+            line.push_str(strip_comment(next).trim());
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`".to_string()))?;
+        let key = key.trim();
+        let value = parse_value(value.trim()).map_err(error)?;
+        apply(&mut config, key, value).map_err(error)?;
+    }
+    Ok(config)
+}
+
+fn apply(config: &mut Config, key: &str, value: Value) -> Result<(), String> {
+    let expected = |expected: &str, value: &Value| {
+        format!("{key} must be {expected}, not {}", value.type_name())
+    };
+    match (key, value) {
+        ("preset", Value::String(name)) => {
+            config.preset = Some(name.parse().map_err(|err| format!("{err}"))?)
+        }
+        ("threads", Value::Integer(threads)) if threads > 0 => {
+            config.threads = Some(threads as usize)
+        }
+        ("threads", Value::Integer(_)) => return Err("threads must be positive".to_string()),
+        ("strategy", Value::String(strategy)) => config.strategy = Some(strategy.parse()?),
+        ("locale", Value::String(locale)) => {
+            config.locale = Some(locale.parse().map_err(|err| format!("{err}"))?)
+        }
+        ("palette" | "missing_colors", Value::Array(names)) => {
+            let names = names
+                .into_iter()
+                .map(|name| match name {
+                    Value::String(name) => Ok(name),
+                    other => Err(format!(
+                        "{key} entries must be strings, not {}",
+                        other.type_name()
+                    )),
+                })
+                .collect::<Result<_, _>>()?;
+            match key {
+                "palette" => config.palette = Some(names),
+                _ => config.missing_colors = Some(names),
+            }
+        }
+        ("preset" | "strategy" | "locale", value) => return Err(expected("a string", &value)),
+        ("threads", value) => return Err(expected("an integer", &value)),
+        ("palette" | "missing_colors", value) => {
+            return Err(expected("an array of strings", &value))
+        }
+        (key, _) => return Err(format!("unknown setting {key:?}")),
+    }
+    Ok(())
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '#') => return &line[..index],
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(value: &str) -> Result<Value, String> {
+    let (value, rest) = parse_partial_value(value)?;
+    if rest.trim().is_empty() {
+        Ok(value)
+    } else {
+        Err(format!("unexpected {:?} after value", rest.trim()))
+    }
+}
+
+/// Parses a value at the start of `input` and returns it with the remaining input.
+fn parse_partial_value(input: &str) -> Result<(Value, &str), String> {
+    let input = input.trim_start();
+    if let Some(rest) = input.strip_prefix('"') {
+        let mut string = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => return Ok((Value::String(string), &rest[index + 1..])),
+                '\\' => string.push(match chars.next() {
+                    Some((_, 'n')) => '\n',
+                    Some((_, 't')) => '\t',
+                    Some((_, c @ ('"' | '\\'))) => c,
+                    _ => return Err("invalid escape sequence".to_string()),
+                }),
+                c => string.push(c),
+            }
+        }
+        Err("unterminated string".to_string())
+    } else if let Some(rest) = input.strip_prefix('\'') {
+        let end = rest.find('\'').ok_or("unterminated string")?;
+        Ok((Value::String(rest[..end].to_string()), &rest[end + 1..]))
+    } else if let Some(mut rest) = input.strip_prefix('[') {
+        let mut values = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(rest) = rest.strip_prefix(']') {
+                return Ok((Value::Array(values), rest));
+            }
+            let (value, remaining) = parse_partial_value(rest)?;
+            values.push(value);
+            rest = remaining.trim_start();
+            if let Some(remaining) = rest.strip_prefix(',') {
+                rest = remaining;
+            } else if !rest.starts_with(']') {
+                return Err("expected `,` or `]` in array".to_string());
+            }
+        }
+    } else {
+        let end = input
+            .find(|c: char| c == ',' || c == ']' || c.is_whitespace())
+            .unwrap_or(input.len());
+        let (token, rest) = input.split_at(end);
+        let value = match token {
+            "true" => Value::Boolean(true),
+            "false" => Value::Boolean(false),
+            _ => Value::Integer(
+                token
+                    .replace('_', "")
+                    .parse()
+                    .map_err(|_| format!("invalid value {token:?}"))?,
+            ),
+        };
+        Ok((value, rest))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config = parse(
+            r#"
+            # my defaults
+            preset = "classic"
+            threads = 4 # leave some cores free
+            strategy = "knuth"
+            locale = 'en'
+            palette = [
+                "red", "green", "yellow",  # the usual
+                "blue", "orange", "pink#1",
+            ]
+            missing_colors = ["pink#1"]
+            "#,
+        );
+        assert_eq!(
+            config,
+            Ok(Config {
+                preset: Some(Preset::Classic),
+                threads: Some(4),
+                strategy: Some(Strategy::Knuth),
+                locale: Some(Locale::English),
+                palette: Some(
+                    ["red", "green", "yellow", "blue", "orange", "pink#1"]
+                        .map(String::from)
+                        .to_vec()
+                ),
+                missing_colors: Some(vec!["pink#1".to_string()]),
+            })
+        );
+    }
+
+    #[test]
+    fn reject_invalid_config() {
+        assert_eq!(parse("threads = \"many\"").unwrap_err().line, 1);
+        assert_eq!(parse("\npreset = \"huge\"").unwrap_err().line, 2);
+        assert!(parse("threads = 0").is_err());
+        assert!(parse("strategy = \"best\"").is_err());
+        assert!(parse("strategy = 1").is_err());
+        assert!(parse("color = 1").is_err());
+        assert!(parse("[solver]").is_err());
+        assert!(parse("palette = [\"rot\" \"blau\"]").is_err());
+        assert!(parse("palette = [\"rot\",").is_err());
+        assert!(parse("locale = \"en").is_err());
+        assert!(parse("missing_colors = \"grau\"").is_err());
+        assert!(parse("missing_colors = [8]").is_err());
+    }
+}
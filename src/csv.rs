@@ -0,0 +1,129 @@
+//! Writing comma separated values as described in RFC 4180, for raw data which is
+//! analyzed with other tools, and reading them back.
+
+use std::io::{self, Write};
+
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Writes one line, quoting the fields which need it.
+    pub fn record<S: AsRef<str>>(&mut self, fields: impl IntoIterator<Item = S>) -> io::Result<()> {
+        for (index, field) in fields.into_iter().enumerate() {
+            if index > 0 {
+                self.inner.write_all(b",")?;
+            }
+            let field = field.as_ref();
+            if field.contains([',', '"', '\r', '\n']) {
+                write!(self.inner, "\"{}\"", field.replace('"', "\"\""))?;
+            } else {
+                self.inner.write_all(field.as_bytes())?;
+            }
+        }
+        self.inner.write_all(b"\r\n")
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// The records of `text`, split into their fields and unquoted. Line breaks in quoted
+/// fields are kept.
+pub fn read(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    // whether anything of the record was read, so the empty last line is no record
+    let mut started = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() => {
+                started = true;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            line += (c == '\n') as usize;
+                            field.push(c);
+                        }
+                        None => return Err(format!("line {line}: a quote isn't closed")),
+                    }
+                }
+                if !matches!(chars.peek(), None | Some(',' | '\r' | '\n')) {
+                    return Err(format!("line {line}: text after a quoted field"));
+                }
+            }
+            ',' => {
+                started = true;
+                record.push(std::mem::take(&mut field));
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                started = false;
+            }
+            c => {
+                started = true;
+                field.push(c);
+            }
+        }
+    }
+    if started {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quote_fields() {
+        let mut writer = Writer::new(Vec::new());
+        writer.record(["code", "turns"]).unwrap();
+        writer.record(["rot,blau", "say \"hi\"", ""]).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "code,turns\r\n\"rot,blau\",\"say \"\"hi\"\"\",\r\n"
+        );
+    }
+
+    #[test]
+    fn read_records() {
+        let text = "code,turns\r\n\"rot,blau\",\"say \"\"hi\"\"\",\r\n\"two\nlines\",3";
+        assert_eq!(
+            read(text),
+            Ok(vec![
+                vec!["code".to_string(), "turns".to_string()],
+                vec![
+                    "rot,blau".to_string(),
+                    "say \"hi\"".to_string(),
+                    String::new()
+                ],
+                vec!["two\nlines".to_string(), "3".to_string()],
+            ])
+        );
+        assert_eq!(read("a\n\nb\n").unwrap().len(), 3);
+        assert_eq!(
+            read("a\n\"open"),
+            Err("line 2: a quote isn't closed".to_string())
+        );
+        assert!(read("\"quoted\" text").is_err());
+    }
+}
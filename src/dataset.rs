@@ -0,0 +1,174 @@
+//! Records of the solver playing itself, to train policies on: one comma separated line per
+//! guess with what was known about the code when it was made, the guess and how many
+//! guesses it took from there. The columns are
+//!
+//! ```text
+//! game               the number of the game, from 1
+//! turn               the number of the guess in its game, from 1
+//! candidates         the number of codes which were still possible
+//! share_F_C          the part of those codes with the color C (from 0) in the field F
+//!                    (from 1), one column for every field and color
+//! guess              the colors of the guess as numbers, e.g. `0 0 1 1`
+//! exact, white       the feedback to the guess
+//! turns_left         the guesses the solver needed from this one on, 1 for the last
+//! turns              the guesses of the whole game
+//! ```
+//!
+//! The shares of a field add up to 1, so `candidates` and the shares describe the codes
+//! which remain independently of the guesses which led to them, with the same number of
+//! columns on every turn. These columns are what a policy is given, see `policy`.
+
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    cli::Sample,
+    commands::{self, Game},
+    csv, Guess, Rules, SimpleGuesser, SolverOptions,
+};
+
+pub fn header<const FIELDS: usize, const COLORS: u32>() -> Vec<String> {
+    let shares = (1..=FIELDS)
+        .flat_map(|field| (0..COLORS).map(move |color| format!("share_{field}_{color}")));
+    ["game", "turn", "candidates"]
+        .map(String::from)
+        .into_iter()
+        .chain(shares)
+        .chain(["guess", "exact", "white", "turns_left", "turns"].map(String::from))
+        .collect()
+}
+
+/// The number of `candidates` followed by the part of them with each color in each field,
+/// by field and then color.
+pub fn features<const FIELDS: usize, const COLORS: u32>(candidates: &[Guess<FIELDS>]) -> Vec<f64> {
+    let mut counts = vec![0usize; FIELDS * COLORS as usize];
+    for candidate in candidates {
+        for (field, color) in candidate.colors().into_iter().enumerate() {
+            counts[field * COLORS as usize + color as usize] += 1;
+        }
+    }
+    let shares = counts
+        .into_iter()
+        .map(|count| count as f64 / candidates.len().max(1) as f64);
+    std::iter::once(candidates.len() as f64)
+        .chain(shares)
+        .collect()
+}
+
+/// The records of the turns of the game numbered `number`.
+pub fn records<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    number: usize,
+    game: &Game<FIELDS>,
+) -> Vec<Vec<String>> {
+    let mut candidates: Vec<_> = rules.codes::<FIELDS, COLORS>().collect();
+    let turns = game.turns.len();
+    game.turns
+        .iter()
+        .enumerate()
+        .map(|(index, turn)| {
+            let record = [number.to_string(), (index + 1).to_string()]
+                .into_iter()
+                .chain(
+                    features::<FIELDS, COLORS>(&candidates)
+                        .into_iter()
+                        .map(|feature| feature.to_string()),
+                )
+                .chain([
+                    turn.guess.indices(),
+                    turn.evaluation.exact.to_string(),
+                    turn.evaluation.correct_color.to_string(),
+                    (turns - index).to_string(),
+                    turns.to_string(),
+                ])
+                .collect();
+            candidates.retain(|&code| rules.evaluate(code, turn.guess) == turn.evaluation);
+            record
+        })
+        .collect()
+}
+
+/// Writes the header and the records of `games` to `writer`.
+pub fn write<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    games: &[Game<FIELDS>],
+    writer: impl Write,
+) -> io::Result<()> {
+    let mut writer = csv::Writer::new(writer);
+    writer.record(header::<FIELDS, COLORS>())?;
+    for (index, game) in games.iter().enumerate() {
+        for record in records::<FIELDS, COLORS>(rules, index + 1, game) {
+            writer.record(record)?;
+        }
+    }
+    writer.into_inner().flush()
+}
+
+/// Lets the solver play every code or a sample of them and writes the records of its
+/// guesses to `out`, or to stdout.
+pub fn run<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    sample: Option<Sample>,
+    out: Option<PathBuf>,
+) -> Result<(), String> {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let codes = commands::simulated_codes::<FIELDS, COLORS>(rules, sample.as_ref());
+    let games = commands::play_all(&mut guesser, codes);
+    match out {
+        Some(path) => {
+            let error = |err: io::Error| format!("{}: {err}", path.display());
+            let file = std::fs::File::create(&path).map_err(error)?;
+            write::<FIELDS, COLORS>(rules, &games, io::BufWriter::new(file)).map_err(error)
+        }
+        None => write::<FIELDS, COLORS>(rules, &games, io::stdout().lock())
+            .map_err(|err| format!("can't write the dataset: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::max_gauss;
+
+    #[test]
+    fn write_records() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let header = header::<2, 3>();
+        assert_eq!(
+            header.join(","),
+            "game,turn,candidates,share_1_0,share_1_1,share_1_2,share_2_0,share_2_1,share_2_2,\
+            guess,exact,white,turns_left,turns"
+        );
+        let codes = rules.codes::<2, 3>();
+        let games = commands::play_all(
+            &mut SimpleGuesser::<2, 3, { max_gauss(2) }>::new(rules),
+            codes,
+        );
+        let mut text = vec![];
+        write::<2, 3>(rules, &games, &mut text).unwrap();
+        let records = csv::read(&String::from_utf8(text).unwrap()).unwrap();
+        assert_eq!(records[0], header);
+        let turns: usize = games.iter().map(|game| game.turns.len()).sum();
+        assert_eq!(records.len(), 1 + turns);
+        // every game opens with all codes possible, colors spread evenly
+        let first = &records[1];
+        assert_eq!(first[..4], ["1", "1", "9", &(1. / 3.).to_string()]);
+        for record in &records[1..] {
+            assert_eq!(record.len(), header.len());
+            let shares: Vec<f64> = record[3..9].iter().map(|s| s.parse().unwrap()).collect();
+            assert!((shares[..3].iter().sum::<f64>() - 1.).abs() < 1e-9);
+            let [turns_left, turns] =
+                [&record[12], &record[13]].map(|n| n.parse::<usize>().unwrap());
+            assert!((1..=turns).contains(&turns_left));
+            // the last guess is the code
+            assert_eq!(record[10] == "2", turns_left == 1);
+            assert_ne!(record[2], "0");
+        }
+    }
+}
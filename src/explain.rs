@@ -0,0 +1,197 @@
+//! Why the solver chose a guess, for `--explain`: how the candidates split up by the
+//! feedback they would give to it, the information that is expected to give, and how the
+//! best other guess compares.
+
+use std::fmt::Display;
+
+use crate::{json::Json, palette::Pegs, Entry, Evaluation, Guess, SimpleGuesser};
+
+pub struct Explanation<const FIELDS: usize> {
+    pub guess: Guess<FIELDS>,
+    pub candidates: usize,
+    /// Every feedback the guess can get with the number of candidates which give it, the
+    /// most frequent first.
+    pub partitions: Vec<(Evaluation<FIELDS>, u32)>,
+    /// The expected information in bit, the entropy of the partitions.
+    pub information: f64,
+    /// The best other guess by the expected information.
+    pub runner_up: Option<RunnerUp<FIELDS>>,
+}
+
+pub struct RunnerUp<const FIELDS: usize> {
+    pub guess: Guess<FIELDS>,
+    pub information: f64,
+    /// The number of candidates in its largest partition.
+    pub worst_case: u32,
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>
+    SimpleGuesser<FIELDS, COLORS, PARTITIONS>
+{
+    /// Explains playing `guess` after `history`, which takes as long as ranking the guesses.
+    pub fn explain(
+        &mut self,
+        history: &[Entry<FIELDS>],
+        guess: Guess<FIELDS>,
+    ) -> Explanation<FIELDS> {
+        self.update_candidates(history);
+        let counts = self.partition(&self.candidates, guess);
+        let mut partitions: Vec<_> = (0..PARTITIONS as u32)
+            .filter_map(|index| Some((Evaluation::from_u32(index)?, counts[index as usize])))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        partitions.sort_by(|(a, a_count), (b, b_count)| {
+            b_count.cmp(a_count).then(a.to_u32().cmp(&b.to_u32()))
+        });
+        let runner_up = self
+            .suggestions(2)
+            .into_iter()
+            .find(|suggestion| suggestion.guess != guess)
+            .map(|suggestion| RunnerUp {
+                guess: suggestion.guess,
+                information: self.entropy(&self.partition(&self.candidates, suggestion.guess)),
+                worst_case: suggestion.worst_case,
+            });
+        Explanation {
+            guess,
+            candidates: self.candidates.len(),
+            partitions,
+            information: self.entropy(&counts),
+            runner_up,
+        }
+    }
+}
+
+impl<const FIELDS: usize> Explanation<FIELDS> {
+    fn worst_case(&self) -> u32 {
+        self.partitions.first().map_or(0, |(_, count)| *count)
+    }
+}
+
+fn codes(count: u32) -> String {
+    match count {
+        1 => "1 code".to_string(),
+        n => format!("{n} codes"),
+    }
+}
+
+impl<const FIELDS: usize> Display for Explanation<FIELDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.candidates == 1 && self.worst_case() == 1 {
+            return write!(f, "  [{}] is the only code left", Pegs(&self.guess));
+        }
+        writeln!(
+            f,
+            "  [{}] splits the {} candidates by their feedback:",
+            Pegs(&self.guess),
+            self.candidates
+        )?;
+        for (evaluation, count) in &self.partitions {
+            writeln!(f, "    {evaluation}  {count:>7}")?;
+        }
+        write!(
+            f,
+            "  {:.3} bit expected from {} different feedbacks, the worst leaves {}",
+            self.information,
+            self.partitions.len(),
+            codes(self.worst_case())
+        )?;
+        if let Some(runner_up) = &self.runner_up {
+            write!(
+                f,
+                "\n  runner-up [{}]: {:.3} bit ({:+.3}), the worst feedback leaves {}",
+                Pegs(&runner_up.guess),
+                runner_up.information,
+                runner_up.information - self.information,
+                codes(runner_up.worst_case)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<const FIELDS: usize> From<&Explanation<FIELDS>> for Json {
+    fn from(explanation: &Explanation<FIELDS>) -> Self {
+        let partitions = explanation
+            .partitions
+            .iter()
+            .map(|(evaluation, count)| {
+                Json::object([
+                    ("feedback", (*evaluation).into()),
+                    ("codes", (*count).into()),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let mut fields = vec![
+            ("guess", explanation.guess.into()),
+            ("candidates", explanation.candidates.into()),
+            ("information", explanation.information.into()),
+            ("partitions", partitions.into()),
+        ];
+        if let Some(runner_up) = &explanation.runner_up {
+            fields.push((
+                "runner_up",
+                Json::object([
+                    ("guess", runner_up.guess.into()),
+                    ("information", runner_up.information.into()),
+                    ("worst_case", runner_up.worst_case.into()),
+                ]),
+            ));
+        }
+        Json::object(fields)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, Rules, Solver};
+
+    #[test]
+    fn explain_the_opening() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let report = guesser.guess(&[]).unwrap();
+        let explanation = guesser.explain(&[], report.guess);
+        assert_eq!(explanation.candidates, 1296);
+        assert_eq!(explanation.partitions.len(), report.partitions);
+        assert_eq!(
+            explanation
+                .partitions
+                .iter()
+                .map(|(_, count)| *count as usize)
+                .sum::<usize>(),
+            1296
+        );
+        assert!(explanation
+            .partitions
+            .windows(2)
+            .all(|pair| pair[0].1 >= pair[1].1));
+        assert!((explanation.information - report.information).abs() < 1e-9);
+        let runner_up = explanation.runner_up.as_ref().unwrap();
+        assert_ne!(runner_up.guess, report.guess);
+        assert!(runner_up.information <= explanation.information + 1e-9);
+
+        let text = explanation.to_string();
+        assert!(text.contains("splits the 1296 candidates"));
+        assert!(text.contains("runner-up"));
+        let json = Json::from(&explanation).to_string();
+        assert!(json.contains("\"partitions\":[{\"feedback\":"));
+
+        // with one candidate left it can only win
+        let code = Guess::new([0, 1, 2, 3]);
+        let history = [Entry {
+            guess: code,
+            evaluation: rules.evaluate(code, code),
+        }];
+        let explanation = guesser.explain(&history, code);
+        assert_eq!(explanation.partitions, [(rules.evaluate(code, code), 1)]);
+        assert_eq!(
+            explanation.to_string(),
+            format!("  [{}] is the only code left", Pegs(&code))
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Facts about the secret code which are known besides the feedback, e.g. because a peg
+//! was glimpsed or a variant of the game gives them away, as further filters on the codes
+//! the solver considers. Fields are counted from 1:
+//!
+//! ```text
+//! contains rot
+//! no blau
+//! 1 is gelb
+//! 2 is not grün
+//! ```
+
+use std::fmt::Display;
+
+use crate::{palette::color_names, parse_color, Guess};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fact {
+    /// The code contains the color at least once.
+    Contains(u32),
+    /// The code doesn't contain the color.
+    Lacks(u32),
+    /// The field, counted from 0, has the color.
+    Is { field: usize, color: u32 },
+    /// The field, counted from 0, has another color.
+    IsNot { field: usize, color: u32 },
+}
+
+impl Fact {
+    /// Parses a fact about a code of this game, see the module documentation.
+    pub fn parse<const FIELDS: usize, const COLORS: u32>(text: &str) -> Result<Self, String> {
+        let words: Vec<_> = text.split_whitespace().collect();
+        let color = |name: &str| match parse_color(name) {
+            Ok(color) if color < COLORS => Ok(color),
+            Ok(_) => Err(format!("{name:?} isn't a color of this game")),
+            Err(err) => Err(err.to_string()),
+        };
+        let field = |number: &str| match number.parse::<usize>() {
+            Ok(field @ 1..) if field <= FIELDS => Ok(field - 1),
+            _ => Err(format!("the fields are numbered from 1 to {FIELDS}")),
+        };
+        match words[..] {
+            ["contains", name] => Ok(Fact::Contains(color(name)?)),
+            ["no", name] => Ok(Fact::Lacks(color(name)?)),
+            [number, "is", name] => Ok(Fact::Is {
+                field: field(number)?,
+                color: color(name)?,
+            }),
+            [number, "is", "not", name] => Ok(Fact::IsNot {
+                field: field(number)?,
+                color: color(name)?,
+            }),
+            _ => Err(
+                "expected `contains COLOR`, `no COLOR`, `N is COLOR` or `N is not COLOR`"
+                    .to_string(),
+            ),
+        }
+    }
+
+    pub fn holds<const FIELDS: usize>(&self, code: Guess<FIELDS>) -> bool {
+        match *self {
+            Fact::Contains(color) => code.colors().contains(&color),
+            Fact::Lacks(color) => !code.colors().contains(&color),
+            Fact::Is { field, color } => code.get(field) == color,
+            Fact::IsNot { field, color } => code.get(field) != color,
+        }
+    }
+}
+
+impl Display for Fact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = |color: u32| {
+            color_names()
+                .get(color as usize)
+                .cloned()
+                .unwrap_or_else(|| color.to_string())
+        };
+        match *self {
+            Fact::Contains(color) => write!(f, "contains {}", name(color)),
+            Fact::Lacks(color) => write!(f, "no {}", name(color)),
+            Fact::Is { field, color } => write!(f, "{} is {}", field + 1, name(color)),
+            Fact::IsNot { field, color } => write!(f, "{} is not {}", field + 1, name(color)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_facts() {
+        let facts = ["contains rot", "no 1", "4 is gelb", " 2  is not grün "]
+            .map(|text| Fact::parse::<4, 6>(text).unwrap());
+        assert_eq!(
+            facts,
+            [
+                Fact::Contains(0),
+                Fact::Lacks(1),
+                Fact::Is { field: 3, color: 2 },
+                Fact::IsNot { field: 1, color: 1 },
+            ]
+        );
+        for fact in facts {
+            assert_eq!(Fact::parse::<4, 6>(&fact.to_string()), Ok(fact));
+        }
+        let code = Guess::new([0, 3, 3, 2]);
+        assert!(facts.iter().all(|fact| fact.holds(code)));
+        assert!(!facts
+            .iter()
+            .all(|fact| fact.holds(Guess::new([0, 1, 3, 2]))));
+
+        for text in [
+            "",
+            "contains",
+            "contains lila",
+            "no 6",
+            "0 is rot",
+            "5 is rot",
+            "1 isnt 2",
+        ] {
+            assert!(Fact::parse::<4, 6>(text).is_err(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! A C API for playing with the solver from other languages, declared in
+//! `include/mastermind.h`. A game is an opaque handle which collects the guesses and their
+//! feedback, the board dimensions are those of a preset. Functions which can fail return
+//! `MASTERMIND_OK` or one of the error codes.
+
+use std::{
+    ffi::{c_char, c_int, c_void, CStr},
+    sync::Arc,
+};
+
+use crate::{max_gauss, Entry, Evaluation, Guess, Preset, Rules, ScoringFn, SimpleGuesser, Solver};
+
+pub const MASTERMIND_OK: c_int = 0;
+/// A pointer was null, or a color or the feedback was out of range.
+pub const MASTERMIND_INVALID_ARGUMENT: c_int = -1;
+/// No code is consistent with the feedback, which was left out.
+pub const MASTERMIND_INCONSISTENT: c_int = -2;
+
+/// The game behind the handle, for the board dimensions of its preset.
+trait Game {
+    fn fields(&self) -> usize;
+    fn next_guess(&mut self, colors: &mut [u32]);
+    fn push_feedback(&mut self, guess: &[u32], exact: u32, correct_color: u32) -> c_int;
+    fn candidates_count(&mut self) -> usize;
+    fn set_scoring(&mut self, scoring: Option<Scoring>);
+}
+
+/// A scoring function of the caller, see `ScoringFn`: it is given the number of codes which
+/// give each feedback, the number of feedbacks and the number of codes, and `user_data`.
+pub type MastermindScoringFn = unsafe extern "C" fn(*const u32, usize, usize, *mut c_void) -> f64;
+
+#[derive(Clone, Copy)]
+struct Scoring {
+    function: MastermindScoringFn,
+    user_data: *mut c_void,
+}
+
+// the caller of `mastermind_set_scoring` promises that the function can be called from
+// several threads at once
+unsafe impl Send for Scoring {}
+unsafe impl Sync for Scoring {}
+
+impl Scoring {
+    fn score<const PARTITIONS: usize>(&self, counts: &[u32; PARTITIONS], total: usize) -> f64 {
+        unsafe { (self.function)(counts.as_ptr(), PARTITIONS, total, self.user_data) }
+    }
+}
+
+pub struct MastermindGame(Box<dyn Game>);
+
+struct Handle<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    guesser: SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+    history: Vec<Entry<FIELDS>>,
+}
+
+fn handle<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+) -> Box<dyn Game> {
+    Box::new(Handle {
+        guesser: SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::new(rules),
+        history: vec![],
+    })
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> Game
+    for Handle<FIELDS, COLORS, PARTITIONS>
+{
+    fn fields(&self) -> usize {
+        FIELDS
+    }
+
+    fn next_guess(&mut self, colors: &mut [u32]) {
+        // `push_feedback` keeps the history consistent
+        let guess = self
+            .guesser
+            .guess(&self.history)
+            .expect("the history is consistent")
+            .guess;
+        colors.copy_from_slice(&guess.colors());
+    }
+
+    fn push_feedback(&mut self, guess: &[u32], exact: u32, correct_color: u32) -> c_int {
+        // checked before packing, as a color with too many bits would change the next field
+        if guess.iter().any(|color| *color >= COLORS) {
+            return MASTERMIND_INVALID_ARGUMENT;
+        }
+        let guess = Guess::new(std::array::from_fn(|field| guess[field]));
+        let rules = self.guesser.rules;
+        let Ok(evaluation) = Evaluation::new(exact, correct_color) else {
+            return MASTERMIND_INVALID_ARGUMENT;
+        };
+        if !rules.is_legal_guess::<FIELDS, COLORS>(&guess)
+            || rules.check_feedback(guess, evaluation).is_err()
+        {
+            return MASTERMIND_INVALID_ARGUMENT;
+        }
+        self.history.push(Entry { guess, evaluation });
+        if self.candidates_count() == 0 {
+            self.history.pop();
+            return MASTERMIND_INCONSISTENT;
+        }
+        MASTERMIND_OK
+    }
+
+    fn candidates_count(&mut self) -> usize {
+        self.guesser.update_candidates(&self.history);
+        self.guesser.candidates().len()
+    }
+
+    fn set_scoring(&mut self, scoring: Option<Scoring>) {
+        self.guesser.scoring = scoring.map(|scoring| {
+            Arc::new(move |counts: &[u32; PARTITIONS], total| scoring.score(counts, total))
+                as ScoringFn<PARTITIONS>
+        });
+    }
+}
+
+/// Starts a game of the preset with the name `preset`, see `--preset list`. Returns null if
+/// there is no such preset.
+///
+/// # Safety
+///
+/// `preset` has to be a null-terminated string. The game has to be freed with
+/// `mastermind_game_free`.
+#[no_mangle]
+pub unsafe extern "C" fn mastermind_game_new(preset: *const c_char) -> *mut MastermindGame {
+    if preset.is_null() {
+        return std::ptr::null_mut();
+    }
+    let Some(preset) = CStr::from_ptr(preset)
+        .to_str()
+        .ok()
+        .and_then(|name| name.parse::<Preset>().ok())
+    else {
+        return std::ptr::null_mut();
+    };
+    let game = with_preset!(preset, handle(preset.rules()));
+    Box::into_raw(Box::new(MastermindGame(game)))
+}
+
+/// Frees the game, null is ignored.
+///
+/// # Safety
+///
+/// `game` has to be null or returned by `mastermind_game_new`, and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn mastermind_game_free(game: *mut MastermindGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// The number of fields of a code, and so of colors the other functions read and write.
+///
+/// # Safety
+///
+/// `game` has to be returned by `mastermind_game_new`.
+#[no_mangle]
+pub unsafe extern "C" fn mastermind_fields(game: *const MastermindGame) -> usize {
+    game.as_ref().map_or(0, |game| game.0.fields())
+}
+
+/// Writes the colors of the best guess for the feedback so far to `colors`.
+///
+/// # Safety
+///
+/// `game` has to be returned by `mastermind_game_new` and `colors` has to have room for
+/// `mastermind_fields` colors.
+#[no_mangle]
+pub unsafe extern "C" fn mastermind_next_guess(
+    game: *mut MastermindGame,
+    colors: *mut u32,
+) -> c_int {
+    let Some(game) = game.as_mut() else {
+        return MASTERMIND_INVALID_ARGUMENT;
+    };
+    if colors.is_null() {
+        return MASTERMIND_INVALID_ARGUMENT;
+    }
+    let colors = std::slice::from_raw_parts_mut(colors, game.0.fields());
+    game.0.next_guess(colors);
+    MASTERMIND_OK
+}
+
+/// Adds the feedback to the guess with the given colors: the number of fields with the
+/// right color and the number of further right colors in the wrong field.
+///
+/// # Safety
+///
+/// `game` has to be returned by `mastermind_game_new` and `guess` has to hold
+/// `mastermind_fields` colors.
+#[no_mangle]
+pub unsafe extern "C" fn mastermind_push_feedback(
+    game: *mut MastermindGame,
+    guess: *const u32,
+    exact: u32,
+    correct_color: u32,
+) -> c_int {
+    let Some(game) = game.as_mut() else {
+        return MASTERMIND_INVALID_ARGUMENT;
+    };
+    if guess.is_null() {
+        return MASTERMIND_INVALID_ARGUMENT;
+    }
+    let guess = std::slice::from_raw_parts(guess, game.0.fields());
+    game.0.push_feedback(guess, exact, correct_color)
+}
+
+/// The number of codes which are consistent with the feedback so far.
+///
+/// # Safety
+///
+/// `game` has to be returned by `mastermind_game_new`.
+#[no_mangle]
+pub unsafe extern "C" fn mastermind_candidates_count(game: *mut MastermindGame) -> usize {
+    game.as_mut().map_or(0, |game| game.0.candidates_count())
+}
+
+/// Scores the guesses by `scoring` instead of the strategy of the solver, or by the
+/// strategy again if `scoring` is null. Higher scores are better, they may be negative and
+/// NaN is the worst.
+///
+/// # Safety
+///
+/// `game` has to be returned by `mastermind_game_new`. `scoring` is called with `user_data`
+/// from several threads at once until the game is freed or another function is set.
+#[no_mangle]
+pub unsafe extern "C" fn mastermind_set_scoring(
+    game: *mut MastermindGame,
+    scoring: Option<MastermindScoringFn>,
+    user_data: *mut c_void,
+) -> c_int {
+    let Some(game) = game.as_mut() else {
+        return MASTERMIND_INVALID_ARGUMENT;
+    };
+    game.0.set_scoring(scoring.map(|function| Scoring {
+        function,
+        user_data,
+    }));
+    MASTERMIND_OK
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn play_through_the_c_api() {
+        unsafe {
+            let game = mastermind_game_new(c"classic".as_ptr());
+            assert_eq!(mastermind_fields(game), 4);
+            assert_eq!(mastermind_candidates_count(game), 1296);
+            let rules = Preset::Classic.rules();
+            let code = Guess::<4>::new([3, 1, 1, 5]);
+            let mut guess = [0; 4];
+            for _ in 0..6 {
+                assert_eq!(
+                    mastermind_next_guess(game, guess.as_mut_ptr()),
+                    MASTERMIND_OK
+                );
+                let evaluation = rules.evaluate(code, Guess::new(guess));
+                let pushed = mastermind_push_feedback(
+                    game,
+                    guess.as_ptr(),
+                    evaluation.exact,
+                    evaluation.correct_color,
+                );
+                assert_eq!(pushed, MASTERMIND_OK);
+                if Guess::new(guess) == code {
+                    break;
+                }
+            }
+            assert_eq!(guess, code.colors());
+            assert_eq!(mastermind_candidates_count(game), 1);
+            // the code is known, no other feedback fits
+            let other = [0; 4];
+            assert_eq!(
+                mastermind_push_feedback(game, other.as_ptr(), 4, 0),
+                MASTERMIND_INCONSISTENT
+            );
+            assert_eq!(
+                mastermind_push_feedback(game, other.as_ptr(), 3, 2),
+                MASTERMIND_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                mastermind_push_feedback(game, [9; 4].as_ptr(), 0, 0),
+                MASTERMIND_INVALID_ARGUMENT
+            );
+            // 17 would carry into the next field, as [1, 1, 0, 0]
+            for colors in [[6, 0, 0, 0], [17, 0, 0, 0], [0, 0, 0, u32::MAX]] {
+                assert_eq!(
+                    mastermind_push_feedback(game, colors.as_ptr(), 0, 0),
+                    MASTERMIND_INVALID_ARGUMENT
+                );
+            }
+            assert_eq!(mastermind_candidates_count(game), 1);
+            mastermind_game_free(game);
+            assert!(mastermind_game_new(c"huge".as_ptr()).is_null());
+            assert_eq!(
+                mastermind_next_guess(std::ptr::null_mut(), guess.as_mut_ptr()),
+                MASTERMIND_INVALID_ARGUMENT
+            );
+            mastermind_game_free(std::ptr::null_mut());
+        }
+    }
+
+    /// The number of partitions, counting the calls in `user_data`.
+    unsafe extern "C" fn partitions(
+        counts: *const u32,
+        partitions: usize,
+        _total: usize,
+        user_data: *mut c_void,
+    ) -> f64 {
+        let calls = &*(user_data as *const std::sync::atomic::AtomicUsize);
+        calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let counts = std::slice::from_raw_parts(counts, partitions);
+        counts.iter().filter(|count| **count > 0).count() as f64
+    }
+
+    #[test]
+    fn score_by_a_c_function() {
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let user_data = &calls as *const _ as *mut c_void;
+        unsafe {
+            let game = mastermind_game_new(c"mini".as_ptr());
+            assert_eq!(
+                mastermind_set_scoring(game, Some(partitions), user_data),
+                MASTERMIND_OK
+            );
+            let mut guess = [0; 3];
+            assert_eq!(
+                mastermind_next_guess(game, guess.as_mut_ptr()),
+                MASTERMIND_OK
+            );
+            let scored = calls.load(std::sync::atomic::Ordering::Relaxed);
+            assert!(scored > 0);
+            // back to the strategy of the solver
+            assert_eq!(
+                mastermind_set_scoring(game, None, std::ptr::null_mut()),
+                MASTERMIND_OK
+            );
+            assert_eq!(
+                mastermind_next_guess(game, guess.as_mut_ptr()),
+                MASTERMIND_OK
+            );
+            assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), scored);
+            mastermind_game_free(game);
+            assert_eq!(
+                mastermind_set_scoring(std::ptr::null_mut(), None, user_data),
+                MASTERMIND_INVALID_ARGUMENT
+            );
+        }
+    }
+
+    /// Minus the largest partition, with the calls unused.
+    unsafe extern "C" fn largest(
+        counts: *const u32,
+        partitions: usize,
+        _total: usize,
+        _user_data: *mut c_void,
+    ) -> f64 {
+        let counts = std::slice::from_raw_parts(counts, partitions);
+        -(counts.iter().max().copied().unwrap_or(0) as f64)
+    }
+
+    #[test]
+    fn score_negative() {
+        let mut handle = Handle::<3, 4, { max_gauss(3) }> {
+            guesser: SimpleGuesser::new(Preset::Mini.rules()),
+            history: vec![],
+        };
+        handle.set_scoring(Some(Scoring {
+            function: largest,
+            user_data: std::ptr::null_mut(),
+        }));
+        let observed = Arc::new(std::sync::Mutex::new(vec![]));
+        let observer = observed.clone();
+        handle.guesser.observer = Some(Arc::new(move |guess, score| {
+            observer.lock().unwrap().push((guess, score));
+        }));
+        // the first guess is one of the openings, which aren't reported
+        let rules = Preset::Mini.rules();
+        let opening = Guess::new([0, 1, 2]);
+        let evaluation = rules.evaluate(Guess::new([1, 2, 3]), opening);
+        assert_eq!(
+            handle.push_feedback(
+                &opening.colors(),
+                evaluation.exact,
+                evaluation.correct_color
+            ),
+            MASTERMIND_OK
+        );
+        let mut guess = [0; 3];
+        handle.next_guess(&mut guess);
+        let observed = observed.lock().unwrap();
+        // every better guess is reported, ending with the score of the one played
+        assert!(observed.windows(2).all(|pair| pair[0].1 < pair[1].1));
+        let (_, score) = *observed.last().unwrap();
+        assert!(score < 0.);
+        let largest = handle
+            .guesser
+            .candidates()
+            .iter()
+            .map(|code| rules.evaluate(*code, Guess::new(guess)))
+            .fold([0; max_gauss(3)], |mut counts, evaluation| {
+                counts[evaluation.to_u32() as usize] += 1;
+                counts
+            })
+            .into_iter()
+            .max();
+        assert_eq!(Some(-score as u32), largest);
+    }
+
+    #[test]
+    fn header_declares_the_api() {
+        let header = include_str!("../include/mastermind.h");
+        let source = include_str!("ffi.rs");
+        for function in source
+            .split("pub unsafe extern \"C\" fn ")
+            .skip(1)
+            .filter_map(|rest| rest.split('(').next())
+        {
+            assert!(header.contains(&format!("{function}(")), "{function}");
+        }
+        for constant in [
+            "MASTERMIND_OK",
+            "MASTERMIND_INVALID_ARGUMENT",
+            "MASTERMIND_INCONSISTENT",
+        ] {
+            assert!(
+                header.contains(&format!("#define {constant} ")),
+                "{constant}"
+            );
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Throws arbitrary input at everything which reads text or files, and arbitrary histories
+//! at the solver: bytes made up of the characters the formats use, and valid input with
+//! bytes changed, inserted and cut off. Nothing may panic, and the partitions of the
+//! solver have to stay within `PARTITIONS`.
+
+use crate::{
+    cli, config, history, max_gauss, preset::Preset, random::Rng, save::SavedGame,
+    strategy::Strategy, tree, tree::Node, Entry, Evaluation, Guess, Rules, SimpleGuesser, Solver,
+};
+
+/// The number of inputs per target.
+const RUNS: usize = 3000;
+
+/// Characters of the text formats, so that random text gets past the first check.
+const ALPHABET: &[u8] = b"0123456789 ,|#:-=bw\n\t.rotblaugelbgr\xc3\xbcn[]\"";
+
+/// Valid input to change for every text format.
+const TEXTS: &[&str] = &[
+    "# guess | feedback\nrot blau gelb grün | 2 1\n0,0,3,3|0b2w # second turn\n",
+    "# preset: classic\n# palette: rot, grün, gelb, blau, orange, pink\n# secret: 3 1 1 5\n\
+     0 0 1 1 | 1 1  # 1296 candidates\n",
+    "0 0 1 1\n 0 0: 2 2 3 3\n  0 1: 4 4 4 5\n 1 1: 0 1 2 3\n",
+    "preset = \"classic\"\nlocale = \"de\"\ncolor = \"never\"\nstrategy = \"knuth\"\n",
+    "--preset classic --strategy entropy tree --binary --out tree.bin",
+];
+
+fn random_bytes(rng: &mut Rng) -> Vec<u8> {
+    (0..rng.below(64))
+        .map(|_| match rng.below(8) {
+            0 => rng.next_u64() as u8,
+            _ => ALPHABET[rng.below(ALPHABET.len())],
+        })
+        .collect()
+}
+
+/// Changes, inserts or removes a few bytes, or cuts the input off.
+fn mutate(rng: &mut Rng, mut bytes: Vec<u8>) -> Vec<u8> {
+    for _ in 0..1 + rng.below(4) {
+        let index = rng.below(bytes.len() + 1);
+        let byte = match rng.below(2) {
+            0 => rng.next_u64() as u8,
+            _ => ALPHABET[rng.below(ALPHABET.len())],
+        };
+        match rng.below(4) {
+            0 if index < bytes.len() => bytes[index] = byte,
+            1 => bytes.insert(index, byte),
+            2 if index < bytes.len() => {
+                bytes.remove(index);
+            }
+            _ => bytes.truncate(index),
+        }
+    }
+    bytes
+}
+
+/// Random text, half of the time made from one of `TEXTS`.
+fn inputs(seed: u64) -> impl Iterator<Item = String> {
+    let mut rng = Rng::seeded(seed);
+    (0..RUNS).map(move |_| {
+        let bytes = match rng.below(2) {
+            0 => random_bytes(&mut rng),
+            _ => {
+                let text = TEXTS[rng.below(TEXTS.len())];
+                mutate(&mut rng, text.as_bytes().to_vec())
+            }
+        };
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+#[test]
+fn parse_arbitrary_text() {
+    let rules = Rules {
+        duplicates: true,
+        missing_colors: 0,
+    };
+    for text in inputs(1) {
+        let _ = text.parse::<Guess<4>>();
+        let _ = text.parse::<Guess<8>>();
+        let _ = text.parse::<Evaluation<4>>();
+        let _ = text.parse::<Entry<4>>();
+        let _ = text.parse::<Preset>();
+        let _ = text.parse::<Strategy>();
+        let _ = history::parse::<4, 6>(rules, &text);
+        let _ = history::parse::<6, 10>(Rules::default(), &text);
+        let _ = Node::<4>::parse::<6>(rules, &text);
+        let _ = config::parse(&text);
+        let _ = cli::parse(text.split_whitespace().map(str::to_string));
+        if let Ok(saved) = SavedGame::parse(text) {
+            let _ = saved.comments();
+            let _ = saved.history::<4, 6>();
+        }
+    }
+}
+
+#[test]
+fn parse_arbitrary_binary_trees() {
+    let rules = Preset::Mini.rules();
+    let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::new(rules);
+    let tree = Node::build(&mut guesser, rules.codes::<3, 4>());
+    let bytes = tree.to_bytes::<4>(rules);
+    // the nodes start after the magic bytes, the five bytes of the game, the missing colors
+    // and two numbers
+    let header = 6 + 5 + 4 + 16;
+    let mut rng = Rng::seeded(2);
+    for _ in 0..RUNS {
+        let mut damaged = mutate(&mut rng, bytes.clone());
+        let _ = Node::<3>::from_bytes::<4>(rules, &damaged);
+        // with the checksum fixed, the nodes themselves are read
+        if damaged.len() >= header {
+            let sum = tree::checksum(&damaged[header..]);
+            damaged[header - 8..header].copy_from_slice(&sum.to_le_bytes());
+            let _ = Node::<3>::from_bytes::<4>(rules, &damaged);
+        }
+    }
+}
+
+fn play_arbitrary_histories<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    seed: u64,
+) {
+    let mut rng = Rng::seeded(seed);
+    for rules in [
+        Rules {
+            duplicates: false,
+            missing_colors: 0,
+        },
+        Rules {
+            duplicates: true,
+            missing_colors: 0,
+        },
+    ] {
+        let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::new(rules);
+        for _ in 0..RUNS / 30 {
+            let mut history = vec![];
+            for _ in 0..rng.below(5) {
+                let guess = rules.random_code::<FIELDS, COLORS>(&mut rng);
+                let exact = rng.below(FIELDS + 1) as u32;
+                let correct_color = rng.below(FIELDS + 1 - exact as usize) as u32;
+                let evaluation = Evaluation::new(exact, correct_color).unwrap();
+                if rules.check_feedback(guess, evaluation).is_ok() {
+                    history.push(Entry { guess, evaluation });
+                }
+            }
+            let report = guesser.guess(&history);
+            let candidates = guesser.candidates();
+            assert_eq!(report.is_ok(), !candidates.is_empty(), "{history:?}");
+            for code in candidates {
+                assert!(history
+                    .iter()
+                    .all(|entry| rules.evaluate(*code, entry.guess) == entry.evaluation));
+            }
+            if let Ok(report) = report {
+                assert!(report.partitions <= PARTITIONS, "{history:?}");
+                for code in candidates {
+                    let index = rules.evaluate(*code, report.guess).to_u32() as usize;
+                    assert!(index < PARTITIONS, "{history:?}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn solve_arbitrary_histories() {
+    play_arbitrary_histories::<3, 4, { max_gauss(3) }>(3);
+    play_arbitrary_histories::<4, 6, { max_gauss(4) }>(4);
+}
@@ -0,0 +1,186 @@
+//! Handicaps for the solver as the codebreaker, so that playing against it is fun for people
+//! who aren't experts. A handicapped solver ranks the guesses by only a random sample of the
+//! codes which are still possible, plays one of its favourites at random instead of the best,
+//! and every now and then greedily plays a possible code in the hope of winning right away.
+
+use std::{fmt::Display, str::FromStr};
+
+use rayon::prelude::*;
+
+use crate::{expected_guesses, random::Rng, Guess, GuessReport, SimpleGuesser};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Handicap {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Handicap {
+    pub const ALL: [Handicap; 3] = [Handicap::Easy, Handicap::Medium, Handicap::Hard];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Handicap::Easy => "easy",
+            Handicap::Medium => "medium",
+            Handicap::Hard => "hard",
+        }
+    }
+
+    /// The number of the best guesses one is picked from at random.
+    const fn favourites(&self) -> usize {
+        match self {
+            Handicap::Easy => 10,
+            Handicap::Medium => 4,
+            Handicap::Hard => 2,
+        }
+    }
+
+    /// The share of the possible codes the guesses are ranked by.
+    const fn sampled(&self) -> f64 {
+        match self {
+            Handicap::Easy => 0.25,
+            Handicap::Medium => 0.5,
+            Handicap::Hard => 1.,
+        }
+    }
+
+    /// The chance to play a random possible code instead.
+    const fn mistakes(&self) -> f64 {
+        match self {
+            Handicap::Easy => 0.25,
+            Handicap::Medium => 0.1,
+            Handicap::Hard => 0.,
+        }
+    }
+
+    /// The report of the guess to play instead of the one of `report`, which `guesser` has
+    /// to have chosen for the current history.
+    pub fn play<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+        &self,
+        guesser: &SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+        report: GuessReport<FIELDS>,
+        rng: &mut Rng,
+    ) -> GuessReport<FIELDS> {
+        let guess = self.pick(guesser, report.guess, rng);
+        if guess == report.guess {
+            return report;
+        }
+        let counts = guesser.partition(guesser.candidates(), guess);
+        let information = guesser.entropy(&counts);
+        // the feedback with `FIELDS` exact matches wins
+        let others = counts
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != FIELDS)
+            .map(|(_, count)| *count as usize);
+        GuessReport {
+            guess,
+            score: guesser.score(guess, &counts).1,
+            partitions: counts.iter().filter(|count| **count > 0).count(),
+            information,
+            expected_guesses: expected_guesses(report.candidates, others, information),
+            ..report
+        }
+    }
+
+    /// The guess to play instead of `best`, the one of the solver.
+    fn pick<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+        &self,
+        guesser: &SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+        best: Guess<FIELDS>,
+        rng: &mut Rng,
+    ) -> Guess<FIELDS> {
+        let candidates = guesser.candidates();
+        let playable: Vec<_> = candidates
+            .iter()
+            .filter(|code| guesser.rules.is_legal_guess::<FIELDS, COLORS>(code))
+            .collect();
+        if !playable.is_empty() && (rng.next_u64() as f64 / u64::MAX as f64) < self.mistakes() {
+            return *playable[rng.below(playable.len())];
+        }
+        let mut sample = candidates.to_vec();
+        let size = ((sample.len() as f64 * self.sampled()).ceil() as usize).max(1);
+        for index in 0..size {
+            let other = index + rng.below(sample.len() - index);
+            sample.swap(index, other);
+        }
+        sample.truncate(size);
+        let mut favourites = vec![];
+        guesser.for_each_guess_chunk(|guesses| {
+            favourites.par_extend(guesses.par_iter().map(|guess| {
+                let counts = guesser.partition(&sample, *guess);
+                (*guess, guesser.information(&counts))
+            }));
+            favourites.par_sort_by(|a, b| b.1.total_cmp(&a.1));
+            favourites.truncate(self.favourites());
+        });
+        // guesses which tell none of the sample apart aren't favourites, even if there are
+        // too few others
+        favourites.retain(|(_, score)| *score > 0.);
+        if favourites.is_empty() {
+            return best;
+        }
+        favourites[rng.below(favourites.len())].0
+    }
+}
+
+impl Display for Handicap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Handicap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Handicap::ALL
+            .into_iter()
+            .find(|handicap| handicap.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown handicap {s:?}, expected easy, medium or hard"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, Entry, Preset, Solver};
+
+    #[test]
+    fn handicapped_guesses() {
+        assert_eq!("Medium".parse(), Ok(Handicap::Medium));
+        assert!("expert".parse::<Handicap>().is_err());
+
+        let rules = Preset::Classic.rules();
+        let code = Guess::new([3, 1, 1, 5]);
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let mut rng = Rng::seeded(5);
+        for handicap in Handicap::ALL {
+            let mut history = vec![];
+            loop {
+                let best = guesser.guess(&history).unwrap();
+                let report = handicap.play(&guesser, best, &mut rng);
+                let guess = report.guess;
+                let counts = guesser.partition(guesser.candidates(), guess);
+                assert_eq!(report.information, guesser.entropy(&counts));
+                assert!(rules.is_legal_guess::<4, 6>(&guess));
+                if guesser.candidates() == [code] {
+                    assert_eq!(guess, code);
+                }
+                if handicap == Handicap::Hard {
+                    // one of the two best guesses is played
+                    assert!(guesser.information(&counts) >= guesser.suggestions(2)[1].score);
+                }
+                if guess == code {
+                    break;
+                }
+                history.push(Entry {
+                    guess,
+                    evaluation: rules.evaluate(code, guess),
+                });
+                assert!(history.len() < 12, "{handicap}");
+            }
+        }
+    }
+}
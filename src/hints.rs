@@ -0,0 +1,193 @@
+//! Hints for users breaking a code, deduced from the codes which still fit the feedback.
+//! They come in levels which give away more and more, starting with how many codes remain,
+//! so that asking once doesn't spoil the game.
+
+use crate::{palette::color_names, Entry, Guess, Rules};
+
+/// How much a hint gives away. Every `hint` goes one level further.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// The number of codes which fit the feedback.
+    Remaining,
+    /// The colors none of them contains.
+    Absent,
+    /// The fields which have the same color in all of them.
+    Fixed,
+    /// The guess the solver would play.
+    Guess,
+}
+
+impl Level {
+    /// The level of the next hint, which doesn't go beyond `deepest`.
+    pub fn next(previous: Option<Self>, deepest: Self) -> Self {
+        let level = match previous {
+            None => Level::Remaining,
+            Some(Level::Remaining) => Level::Absent,
+            Some(Level::Absent) => Level::Fixed,
+            Some(Level::Fixed | Level::Guess) => Level::Guess,
+        };
+        level.min(deepest)
+    }
+}
+
+/// What the feedback so far proves about the code.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Knowledge {
+    pub remaining: usize,
+    /// The colors which can't be in the code.
+    pub absent: Vec<u32>,
+    /// The fields whose color is certain, with that color.
+    pub fixed: Vec<(usize, u32)>,
+}
+
+impl Knowledge {
+    pub fn deduce<const FIELDS: usize, const COLORS: u32>(
+        rules: Rules,
+        history: &[Entry<FIELDS>],
+    ) -> Self {
+        let candidates: Vec<_> = rules
+            .codes::<FIELDS, COLORS>()
+            .filter(|code| {
+                history
+                    .iter()
+                    .all(|entry| rules.evaluate(*code, entry.guess) == entry.evaluation)
+            })
+            .collect();
+        Self::of::<FIELDS, COLORS>(&candidates)
+    }
+
+    fn of<const FIELDS: usize, const COLORS: u32>(candidates: &[Guess<FIELDS>]) -> Self {
+        let absent = (0..COLORS)
+            .filter(|color| candidates.iter().all(|code| !code.colors().contains(color)))
+            .collect();
+        let fixed = match candidates.first() {
+            Some(first) => (0..FIELDS)
+                .filter(|field| {
+                    candidates
+                        .iter()
+                        .all(|code| code.get(*field) == first.get(*field))
+                })
+                .map(|field| (field, first.get(field)))
+                .collect(),
+            None => vec![],
+        };
+        Self {
+            remaining: candidates.len(),
+            absent,
+            fixed,
+        }
+    }
+
+    /// The hint of a level up to `Level::Fixed`, the solver has to be asked for its guess.
+    pub fn hint(&self, level: Level) -> String {
+        let names = color_names();
+        match level {
+            Level::Remaining if self.remaining == 1 => {
+                "only 1 code fits all of the feedback".to_string()
+            }
+            Level::Remaining => format!("{} codes fit all of the feedback", self.remaining),
+            Level::Absent if self.absent.is_empty() => {
+                "every color may still be in the code".to_string()
+            }
+            Level::Absent => format!(
+                "the code contains none of {}",
+                self.absent
+                    .iter()
+                    .map(|color| names[*color as usize].as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Level::Fixed | Level::Guess if self.fixed.is_empty() => {
+                "no field is certain yet".to_string()
+            }
+            Level::Fixed | Level::Guess => format!(
+                "certain: {}",
+                self.fixed
+                    .iter()
+                    .map(|(field, color)| format!(
+                        "field {} is {}",
+                        field + 1,
+                        names[*color as usize]
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deduce_from_the_feedback() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let knowledge = Knowledge::deduce::<3, 4>(rules, &[]);
+        assert_eq!(knowledge.remaining, 64);
+        assert_eq!(
+            knowledge.hint(Level::Absent),
+            "every color may still be in the code"
+        );
+        assert_eq!(knowledge.hint(Level::Fixed), "no field is certain yet");
+
+        let code = Guess::new([0, 0, 1]);
+        let history: Vec<_> = [[2, 2, 3], [0, 0, 0], [0, 1, 1]]
+            .into_iter()
+            .map(|colors| {
+                let guess = Guess::new(colors);
+                Entry {
+                    guess,
+                    evaluation: rules.evaluate(code, guess),
+                }
+            })
+            .collect();
+        // only 0 0 1 and 0 1 0 are left
+        let knowledge = Knowledge::deduce::<3, 4>(rules, &history);
+        assert_eq!(
+            knowledge,
+            Knowledge {
+                remaining: 2,
+                absent: vec![2, 3],
+                fixed: vec![(0, 0)],
+            }
+        );
+        let names = color_names();
+        assert_eq!(
+            knowledge.hint(Level::Remaining),
+            "2 codes fit all of the feedback"
+        );
+        assert_eq!(
+            knowledge.hint(Level::Absent),
+            format!("the code contains none of {}, {}", names[2], names[3])
+        );
+        assert_eq!(
+            knowledge.hint(Level::Fixed),
+            format!("certain: field 1 is {}", names[0])
+        );
+    }
+
+    #[test]
+    fn levels_go_up_to_the_deepest() {
+        let mut level = None;
+        let mut levels = vec![];
+        for _ in 0..5 {
+            level = Some(Level::next(level, Level::Fixed));
+            levels.extend(level);
+        }
+        assert_eq!(
+            levels,
+            [
+                Level::Remaining,
+                Level::Absent,
+                Level::Fixed,
+                Level::Fixed,
+                Level::Fixed
+            ]
+        );
+        assert_eq!(Level::next(Some(Level::Fixed), Level::Guess), Level::Guess);
+    }
+}
@@ -0,0 +1,100 @@
+//! Reading games written down as text, one turn per line:
+//!
+//! ```text
+//! # guess             | feedback
+//! rot blau gelb grün  | 2 1
+//! rot rot blau blau   | 0b2w
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Entry, Rules};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct HistoryError {
+    line: usize,
+    message: String,
+}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl<const FIELDS: usize> FromStr for Entry<FIELDS> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (guess, evaluation) = s.split_once('|').ok_or("expected `guess | feedback`")?;
+        let guess = guess.parse().map_err(|err| format!("{err}"))?;
+        let evaluation = evaluation.parse().map_err(|err| format!("{err}"))?;
+        Ok(Entry { guess, evaluation })
+    }
+}
+
+/// Parses a history, checking every turn against the rules of the game.
+pub fn parse<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    text: &str,
+) -> Result<Vec<Entry<FIELDS>>, HistoryError> {
+    let mut history = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let error = |message: String| HistoryError {
+            line: index + 1,
+            message,
+        };
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let entry: Entry<FIELDS> = line.parse().map_err(error)?;
+        if !rules.is_legal_turn::<FIELDS, COLORS>(&entry.guess) {
+            return Err(error(format!(
+                "[{}] can't be played in this game",
+                entry.guess
+            )));
+        }
+        rules
+            .check_feedback(entry.guess, entry.evaluation)
+            .map_err(|err| error(err.to_string()))?;
+        history.push(entry);
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Evaluation, Guess};
+
+    #[test]
+    fn parse_history() {
+        let history = parse::<4, 6>(
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+            "# guess | feedback\n\nrot blau gelb grün | 2 1\n0,0,3,3|0b2w # second turn\n",
+        )
+        .unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].guess, Guess::new([0, 3, 2, 1]));
+        assert_eq!(
+            history[1].evaluation,
+            Evaluation {
+                correct_color: 2,
+                exact: 0
+            }
+        );
+    }
+
+    #[test]
+    fn reject_invalid_history() {
+        let error = |text| parse::<4, 6>(Rules::default(), text).unwrap_err().line;
+        assert_eq!(error("rot blau gelb grün 2 1"), 1);
+        assert_eq!(error("\nrot blau gelb | 2 1"), 2);
+        assert_eq!(error("rot blau gelb grau | 2 1"), 1);
+        assert_eq!(error("rot blau gelb grün | 3 2"), 1);
+    }
+}
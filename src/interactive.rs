@@ -0,0 +1,831 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cli::Secret,
+    coach::{self, Review},
+    codemaker::{biased_code, Codemaker, Difficulty},
+    commands::{
+        can_forecast, describe_chances, finish_chances, guess_stats, information_gained,
+        secret_code, Turn, FORECAST_TURNS,
+    },
+    facts::Fact,
+    handicap::Handicap,
+    hints::{Knowledge, Level},
+    palette::{color_names, Pegs},
+    random::Rng,
+    readline::{read_line, read_secret},
+    save::{self, SavedGame},
+    store::{self, Breaker},
+    transcript, Entry, Evaluation, Guess, ParseEvaluationError, Rules, SimpleGuesser, Solver,
+    SolverOptions,
+};
+
+const FEEDBACK_HELP: &str = "\
+Enter the feedback for my guess on one line, exact matches (black/red pegs) first:
+  `2 1`   two exact matches, one correct color in the wrong place
+  `2b1w`  the same using peg letters, `b` or `r` for exact and `w` for white
+  `●●○`   the same using peg glyphs
+Press enter without typing anything to be asked for each peg color separately.
+
+To play a different guess than mine, type `guess` followed by its colors,
+e.g. `guess rot blau gelb grün`, and then enter the feedback for that guess.
+`suggestions N` lists my N favourite guesses (5 if N is omitted).
+`fact contains rot` tells me something you know about the code besides the feedback,
+also `fact no rot`, `fact 1 is rot` and `fact 1 is not rot` for the first field.
+`save FILE` writes the game so far to FILE, `load FILE` continues a saved game.";
+
+/// Candidate codes are listed when at most this many remain.
+const LIST_CANDIDATES: usize = 10;
+
+const CODEMAKER_HELP: &str = "\
+Enter your guess as colors separated by commas or spaces, e.g. `rot blau gelb grün`.
+Colors can also be given by their number. The feedback shows `●` for every exact match,
+`○` for every correct color in the wrong place and `·` for the remaining fields.
+Type `give up` to see the code.";
+
+enum Input<const FIELDS: usize> {
+    Feedback(Evaluation<FIELDS>),
+    Guess(Guess<FIELDS>),
+    Suggestions(usize),
+    /// Something known about the code besides the feedback.
+    Fact(Fact),
+    Save(PathBuf),
+    Load(PathBuf),
+}
+
+/// Prints the number of candidates, and the candidates themselves if only a few remain.
+pub fn print_candidates<const FIELDS: usize>(candidates: &[Guess<FIELDS>]) {
+    match candidates.len() {
+        1 => println!("1 code remains consistent"),
+        n => println!("{n} codes remain consistent"),
+    }
+    if candidates.len() <= LIST_CANDIDATES {
+        for code in candidates {
+            println!("  {}", Pegs(code));
+        }
+    }
+}
+
+/// Prints the turns of a resumed game.
+fn print_turns<const FIELDS: usize>(history: &[Entry<FIELDS>]) {
+    if history.is_empty() {
+        return;
+    }
+    println!("continuing the game after these turns:");
+    for (turn, entry) in history.iter().enumerate() {
+        println!(
+            "{:>3}  {}  {}",
+            turn + 1,
+            entry.evaluation,
+            Pegs(&entry.guess)
+        );
+    }
+    println!();
+}
+
+fn read_count(message: &str) -> Option<u32> {
+    loop {
+        match read_line(message)?.parse() {
+            Ok(count) => return Some(count),
+            Err(_) => println!("please enter a number"),
+        }
+    }
+}
+
+/// Asks for the feedback on one line, falling back to asking for each peg color separately.
+/// Alternatively the user may replace the guess with one of their own.
+fn read_input<const FIELDS: usize, const COLORS: u32>(rules: Rules) -> Option<Input<FIELDS>> {
+    loop {
+        let line = read_line("input feedback (`help` for the format):")?;
+        if let Some(colors) = line.strip_prefix("guess ") {
+            match colors.parse() {
+                Ok(guess) if rules.is_legal_guess::<FIELDS, COLORS>(&guess) => {
+                    return Some(Input::Guess(guess))
+                }
+                Ok(guess) => println!("[{guess}] can't be played in this game"),
+                Err(err) => println!("{err}"),
+            }
+            continue;
+        }
+        if let Some(fact) = line.strip_prefix("fact ") {
+            match Fact::parse::<FIELDS, COLORS>(fact) {
+                Ok(fact) => return Some(Input::Fact(fact)),
+                Err(err) => println!("{err}"),
+            }
+            continue;
+        }
+        if let Some(path) = line.strip_prefix("save ") {
+            return Some(Input::Save(PathBuf::from(path.trim())));
+        }
+        if let Some(path) = line.strip_prefix("load ") {
+            return Some(Input::Load(PathBuf::from(path.trim())));
+        }
+        if let Some(count) = line.strip_prefix("suggestions") {
+            match count.trim() {
+                "" => return Some(Input::Suggestions(5)),
+                count => match count.parse() {
+                    Ok(count) => return Some(Input::Suggestions(count)),
+                    Err(_) => println!("{count:?} is not a number of suggestions"),
+                },
+            }
+            continue;
+        }
+        let evaluation = match line.as_str() {
+            "help" | "?" => {
+                println!("{FEEDBACK_HELP}");
+                continue;
+            }
+            "" => {
+                let correct_color = read_count("input correct colors (white):")?;
+                let exact = read_count("input exact_matches (red):")?;
+                Evaluation::new(exact, correct_color).map_err(ParseEvaluationError::Invalid)
+            }
+            _ => line.parse(),
+        };
+        match evaluation {
+            Ok(evaluation) => return Some(Input::Feedback(evaluation)),
+            Err(err @ ParseEvaluationError::Invalid(_)) => println!("{err}"),
+            Err(err) => println!("{err}, type `help` for the expected format"),
+        }
+    }
+}
+
+/// How a single game ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    Solved {
+        turns: usize,
+    },
+    /// The codebreaker gave up or ran out of turns.
+    Unsolved {
+        turns: usize,
+    },
+    /// No code fits the feedback given by the codemaker.
+    Contradiction,
+}
+
+impl Outcome {
+    /// Logs the game, see `store`, unless the feedback contradicted itself.
+    fn record<const FIELDS: usize, const COLORS: u32>(
+        &self,
+        rules: Rules,
+        mode: &str,
+        breaker: Breaker,
+    ) {
+        let game = match *self {
+            Outcome::Solved { turns } => store::Game {
+                turns,
+                solved: true,
+            },
+            Outcome::Unsolved { turns } => store::Game {
+                turns,
+                solved: false,
+            },
+            Outcome::Contradiction => return,
+        };
+        store::record::<FIELDS, COLORS>(rules, mode, breaker, [game]);
+    }
+
+    /// Points scored by the codemaker: one for every guess, and an extra one if the code
+    /// was not broken.
+    fn points(&self) -> usize {
+        match self {
+            Outcome::Solved { turns } => *turns,
+            Outcome::Unsolved { turns } => turns + 1,
+            Outcome::Contradiction => 0,
+        }
+    }
+}
+
+/// Lets the solver guess codes kept secret by the user, continuing the game `resumed` first.
+/// The transcript of every finished game is written to `transcript`, replacing the previous.
+/// With a handicap the solver plays worse, see `handicap`.
+pub fn run<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    resumed: Option<SavedGame>,
+    transcript: Option<PathBuf>,
+    handicap: Option<Handicap>,
+) -> Result<(), String> {
+    let mut history = match resumed {
+        Some(saved) => saved.history::<FIELDS, COLORS>()?,
+        None => vec![],
+    };
+    while let Some(outcome) = play_round::<FIELDS, COLORS, PARTITIONS>(
+        rules,
+        options,
+        std::mem::take(&mut history),
+        usize::MAX,
+        transcript.as_deref(),
+        handicap,
+    ) {
+        outcome.record::<FIELDS, COLORS>(rules, "interactive", Breaker::Solver);
+        match read_line("play again? [y/N]") {
+            Some(answer) if answer.to_lowercase().starts_with('y') => println!(),
+            _ => break,
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// Plays like `run` for scripts: prints every guess as numbers on its own line and reads
+/// the feedback for it from the next line of stdin, without any prompts.
+pub fn porcelain<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    resumed: Option<SavedGame>,
+    handicap: Option<Handicap>,
+) -> Result<(), String> {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let mut rng = Rng::from_entropy();
+    let mut history = match resumed {
+        Some(saved) => saved.history::<FIELDS, COLORS>()?,
+        None => vec![],
+    };
+    let mut lines = std::io::stdin().lines();
+    loop {
+        let mut report = guesser
+            .guess(history.as_slice())
+            .map_err(|err| err.to_string())?;
+        if let Some(handicap) = handicap {
+            report = handicap.play(&guesser, report, &mut rng);
+        }
+        let guess = report.guess;
+        println!("{}", guess.indices());
+        let Some(line) = lines.next() else {
+            return Ok(());
+        };
+        let line = line.map_err(|err| err.to_string())?;
+        let evaluation: Evaluation<FIELDS> = line
+            .trim()
+            .parse()
+            .map_err(|err| format!("invalid feedback {line:?}: {err}"))?;
+        rules
+            .check_feedback(guess, evaluation)
+            .map_err(|err| format!("invalid feedback {line:?}: {err}"))?;
+        if is_found(guesser.candidates(), guess, evaluation) {
+            return Ok(());
+        }
+        // a guess which can't be the code leaves no candidates, which the next guess reports
+        history.push(Entry { guess, evaluation });
+    }
+}
+
+/// Whether the feedback says that `guess` is the code, which it can only be if it is one of
+/// the `candidates`. Otherwise the feedback contradicts the turns before.
+fn is_found<const FIELDS: usize>(
+    candidates: &[Guess<FIELDS>],
+    guess: Guess<FIELDS>,
+    evaluation: Evaluation<FIELDS>,
+) -> bool {
+    evaluation.exact == FIELDS as u32 && candidates.contains(&guess)
+}
+
+/// Lets the solver guess a code kept secret by the user until it is found, no code fits the
+/// feedback anymore or `max_turns` guesses were made, continuing after the turns of
+/// `history`. Returns `None` if the input was closed, the transcript of the game is written
+/// to `transcript` otherwise.
+fn play_round<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    mut history: Vec<Entry<FIELDS>>,
+    max_turns: usize,
+    transcript: Option<&Path>,
+    handicap: Option<Handicap>,
+) -> Option<Outcome> {
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let mut rng = Rng::from_entropy();
+    // plays all candidates for the chances to find the code, which changes its candidates
+    let mut forecaster = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    let mut facts = vec![];
+    let start = Instant::now();
+    let mut codes = None;
+    let mut total = Duration::ZERO;
+    // the turns of a resumed game aren't annotated
+    let mut comments = vec![String::new(); history.len()];
+    let record = |history: &[Entry<FIELDS>], comments: &[String], secret| {
+        let Some(path) = transcript else {
+            return;
+        };
+        match transcript::write::<FIELDS, COLORS>(path, rules, secret, history, comments) {
+            Ok(()) => println!("Wrote the transcript to {}.", path.display()),
+            Err(err) => println!("{err}"),
+        }
+    };
+    print_turns(&history);
+    'turns: loop {
+        let report = match guesser.guess(history.as_slice()) {
+            Ok(report) => match handicap {
+                Some(handicap) => handicap.play(&guesser, report, &mut rng),
+                None => report,
+            },
+            Err(_) => {
+                println!("No code matches all of the feedback, some of it must have been wrong.");
+                let last = history.last().expect("every code fits no feedback");
+                println!(
+                    "The feedback for {} was {}.",
+                    Pegs(&last.guess),
+                    last.evaluation
+                );
+                match read_line("take it back and enter it again? [Y/n]") {
+                    Some(answer) if !answer.to_lowercase().starts_with('n') => {
+                        history.pop();
+                        comments.pop();
+                        continue 'turns;
+                    }
+                    _ => {
+                        record(&history, &comments, None);
+                        return Some(Outcome::Contradiction);
+                    }
+                }
+            }
+        };
+        let mut guess = report.guess;
+        total += report.elapsed;
+        let candidates = guesser.candidates().len();
+        let codes = *codes.get_or_insert(candidates);
+        print_candidates(guesser.candidates());
+        println!(
+            "({})",
+            guess_stats(report.elapsed, report.scored, candidates, total)
+        );
+        println!(
+            "\nI'm guessing: {} (about {:.1} guesses to go)",
+            Pegs(&guess),
+            report.expected_guesses
+        );
+        if can_forecast::<FIELDS, COLORS>(rules, candidates) {
+            // no game takes more turns than there are candidates
+            let left = max_turns.saturating_sub(history.len());
+            let turns = left.min(candidates).max(FORECAST_TURNS);
+            let chances =
+                finish_chances(&mut forecaster, &history, turns).expect("the solver found a guess");
+            let mut text = describe_chances(&chances[..FORECAST_TURNS]);
+            if left > FORECAST_TURNS && max_turns != usize::MAX {
+                text.push_str(&format!(
+                    ", {:.0}% within the {left} guesses left",
+                    chances[turns - 1] * 100.
+                ));
+            }
+            println!("(chances to find it {text})");
+        }
+
+        let evaluation = loop {
+            match read_input::<FIELDS, COLORS>(rules)? {
+                Input::Feedback(evaluation) => match rules.check_feedback(guess, evaluation) {
+                    Ok(()) => break evaluation,
+                    Err(err) => println!("{err}"),
+                },
+                Input::Guess(own_guess) => {
+                    guess = own_guess;
+                    println!(
+                        "playing {} instead, what is the feedback for it?",
+                        Pegs(&guess)
+                    );
+                }
+                Input::Suggestions(count) => {
+                    for (rank, suggestion) in guesser.suggestions(count).iter().enumerate() {
+                        println!(
+                            "{:>3}. {:<40} {:>7.3} bit, worst case {:>6} left{}",
+                            rank + 1,
+                            suggestion.guess.to_string(),
+                            suggestion.score,
+                            suggestion.worst_case,
+                            if suggestion.possible {
+                                ", possible secret"
+                            } else {
+                                ""
+                            }
+                        );
+                    }
+                }
+                Input::Fact(fact) => {
+                    facts.push(fact);
+                    guesser.set_facts(facts.clone());
+                    guesser.update_candidates(&history);
+                    if !guesser.candidates().is_empty() {
+                        forecaster.set_facts(facts.clone());
+                        println!("noted `{fact}`");
+                        continue 'turns;
+                    }
+                    facts.pop();
+                    guesser.set_facts(facts.clone());
+                    guesser.update_candidates(&history);
+                    println!("no code fits the feedback if `{fact}`, so it can't be true");
+                }
+                Input::Save(path) => match save::write::<FIELDS, COLORS>(&path, rules, &history) {
+                    Ok(()) => println!("saved the game to {}", path.display()),
+                    Err(err) => println!("{err}"),
+                },
+                Input::Load(path) => {
+                    match SavedGame::read(&path).and_then(|saved| saved.history::<FIELDS, COLORS>())
+                    {
+                        Ok(loaded) => {
+                            history = loaded;
+                            comments = vec![String::new(); history.len()];
+                            print_turns(&history);
+                            continue 'turns;
+                        }
+                        Err(err) => println!("{err}"),
+                    }
+                }
+            }
+        };
+        let remaining = guesser
+            .candidates()
+            .iter()
+            .filter(|candidate| rules.evaluate(**candidate, guess) == evaluation)
+            .count();
+        let gained = information_gained(candidates, remaining);
+        comments.push(if guess == report.guess {
+            transcript::annotate(&Turn {
+                guess,
+                evaluation,
+                score: report.score,
+                candidates,
+                information: report.information,
+                expected_guesses: report.expected_guesses,
+                gained,
+                scored: report.scored,
+                elapsed: report.elapsed,
+            })
+        } else {
+            transcript::annotate_own(candidates, gained)
+        });
+        history.push(Entry { guess, evaluation });
+        if is_found(guesser.candidates(), guess, evaluation) {
+            println!(
+                "Found the code {} in {} turns and {:.1?}, gaining {:.2} bit of information.",
+                Pegs(&guess),
+                history.len(),
+                start.elapsed(),
+                (codes as f64).log2()
+            );
+            record(&history, &comments, Some(guess));
+            return Some(Outcome::Solved {
+                turns: history.len(),
+            });
+        }
+        if evaluation.exact == FIELDS as u32 {
+            // no code is left, so the next guess offers to take the feedback back
+            continue 'turns;
+        }
+        if history.len() == max_turns {
+            println!("I'm out of turns.");
+            record(&history, &comments, None);
+            return Some(Outcome::Unsolved {
+                turns: history.len(),
+            });
+        }
+    }
+}
+
+/// Lets the user guess a code chosen by the program, which is harder to find the higher the
+/// difficulty, see `codemaker`.
+pub fn codemaker<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    secret: Option<Secret>,
+    max_turns: usize,
+    difficulty: Difficulty,
+) -> Result<(), String> {
+    let secret = secret.unwrap_or(Secret::Random { seed: None });
+    let mut rng = match secret {
+        Secret::Random { seed } => seed.map_or_else(Rng::from_entropy, Rng::seeded),
+        Secret::Code(_) => Rng::from_entropy(),
+    };
+    let codemaker = match difficulty {
+        Difficulty::Easy => Codemaker::Fixed(secret_code::<FIELDS, COLORS>(rules, secret)?),
+        Difficulty::Medium => Codemaker::Fixed(biased_code::<FIELDS, COLORS, PARTITIONS>(
+            rules, options, &mut rng,
+        )),
+        Difficulty::Hard => Codemaker::adversary::<COLORS>(rules, rng),
+    };
+    if difficulty == Difficulty::Hard {
+        println!(
+            "I'm thinking of {}, but I keep changing my mind.",
+            describe_code::<FIELDS, COLORS>(rules)
+        );
+    } else {
+        println!("I have chosen {}.", describe_code::<FIELDS, COLORS>(rules));
+    }
+    let hints = Some(Level::Fixed);
+    if let Some(outcome) =
+        break_code::<FIELDS, COLORS, PARTITIONS>(rules, options, codemaker, max_turns, hints, false)
+    {
+        outcome.record::<FIELDS, COLORS>(rules, "codemaker", Breaker::User);
+    }
+    Ok(())
+}
+
+/// Lets the user guess a code like `codemaker`, but without hints and with a review of
+/// every guess, see `coach`.
+pub fn coach<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    secret: Option<Secret>,
+    max_turns: usize,
+) -> Result<(), String> {
+    let code =
+        secret_code::<FIELDS, COLORS>(rules, secret.unwrap_or(Secret::Random { seed: None }))?;
+    println!("I have chosen {}.", describe_code::<FIELDS, COLORS>(rules));
+    if let Some(outcome) = break_code::<FIELDS, COLORS, PARTITIONS>(
+        rules,
+        options,
+        Codemaker::Fixed(code),
+        max_turns,
+        None,
+        true,
+    ) {
+        outcome.record::<FIELDS, COLORS>(rules, "coach", Breaker::User);
+    }
+    Ok(())
+}
+
+/// Referees a game between two users, one of them enters the code without it being shown.
+pub fn hotseat<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    max_turns: usize,
+) {
+    println!(
+        "Codemaker, choose {}. Your input will not be shown.",
+        describe_code::<FIELDS, COLORS>(rules)
+    );
+    let code = loop {
+        let Some(line) = read_secret("code:") else {
+            return;
+        };
+        let code = match secret_code::<FIELDS, COLORS>(rules, Secret::Code(line)) {
+            Ok(code) => code,
+            Err(err) => {
+                println!("{err}");
+                continue;
+            }
+        };
+        match read_secret("repeat the code:") {
+            Some(line) if line.parse() == Ok(code) => break code,
+            Some(_) => println!("the codes differ, please enter it again"),
+            None => return,
+        }
+    };
+    println!("\nCodebreaker, the code has been chosen. Type `hint` to ask the solver for help.");
+    let codemaker = Codemaker::Fixed(code);
+    break_code::<FIELDS, COLORS, PARTITIONS>(
+        rules,
+        options,
+        codemaker,
+        max_turns,
+        Some(Level::Guess),
+        false,
+    );
+}
+
+/// For example "a code of 4 pegs with distinct colors, the colors are …".
+fn describe_code<const FIELDS: usize, const COLORS: u32>(rules: Rules) -> String {
+    format!(
+        "a code of {FIELDS} pegs{}, the colors are {}",
+        if rules.duplicates {
+            ""
+        } else {
+            " with distinct colors"
+        },
+        color_names()[..COLORS as usize].join(", ")
+    )
+}
+
+/// Prints the accuracy of the reviewed guesses of a game.
+fn print_accuracy<const FIELDS: usize>(reviews: &[Review<FIELDS>]) {
+    if !reviews.is_empty() {
+        println!(
+            "accuracy: {:.0}% over {} guesses",
+            coach::accuracy(reviews) * 100.,
+            reviews.len()
+        );
+    }
+}
+
+/// Lets the user guess the code of `codemaker`, returns `None` if the input was closed.
+/// If `hints` is set the user may ask for hints up to that level, see `hints`.
+/// If `review` is set every guess is compared with the one of the solver, see `coach`.
+fn break_code<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    mut codemaker: Codemaker<FIELDS>,
+    max_turns: usize,
+    hints: Option<Level>,
+    review: bool,
+) -> Option<Outcome> {
+    println!("You have {max_turns} guesses, type `help` for instructions.");
+    let mut history = vec![];
+    let mut hint = None;
+    let mut reviewer =
+        review.then(|| SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options));
+    let mut reviews = vec![];
+
+    for turn in 1..=max_turns {
+        let guess = loop {
+            let Some(line) = read_line(&format!("guess {turn}/{max_turns}:")) else {
+                println!("\nthe code was {}", Pegs(&codemaker.code()));
+                return None;
+            };
+            match (line.as_str(), hints) {
+                ("help" | "?", _) => {
+                    println!("{CODEMAKER_HELP}");
+                    if hints.is_some() {
+                        println!(
+                            "Type `hint` for a hint, every further one gives away more: how \
+                             many codes remain, the missing colors, the certain fields{}.",
+                            if hints == Some(Level::Guess) {
+                                " and the guess of the solver"
+                            } else {
+                                ""
+                            }
+                        );
+                    }
+                }
+                ("hint", Some(deepest)) => {
+                    let level = Level::next(hint, deepest);
+                    hint = Some(level);
+                    if level == Level::Guess {
+                        let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(
+                            rules, options,
+                        );
+                        let report = guesser
+                            .guess(&history)
+                            .expect("the code fits its own feedback");
+                        print_candidates(guesser.candidates());
+                        println!(
+                            "the solver would play {} ({:.3} bit)",
+                            Pegs(&report.guess),
+                            report.score
+                        );
+                    } else {
+                        let knowledge = Knowledge::deduce::<FIELDS, COLORS>(rules, &history);
+                        println!("{}", knowledge.hint(level));
+                    }
+                }
+                ("give up" | "quit", _) => {
+                    println!("the code was {}", Pegs(&codemaker.code()));
+                    print_accuracy(&reviews);
+                    return Some(Outcome::Unsolved { turns: max_turns });
+                }
+                _ => match line.parse() {
+                    Ok(guess) if rules.is_legal_guess::<FIELDS, COLORS>(&guess) => break guess,
+                    Ok(guess) => println!("[{guess}] can't be played in this game"),
+                    Err(err) => println!("{err}, type `help` for instructions"),
+                },
+            }
+        };
+        let evaluation = codemaker.evaluate(rules, guess);
+        println!(
+            "{evaluation}  {} exact, {} in the wrong place",
+            evaluation.exact, evaluation.correct_color
+        );
+        if let Some(reviewer) = &mut reviewer {
+            let review = reviewer.review(&history, guess, evaluation);
+            println!("{review}");
+            reviews.push(review);
+        }
+        history.push(Entry { guess, evaluation });
+        if evaluation.exact == FIELDS as u32 {
+            println!("Congratulations, you cracked the code in {turn} turns!");
+            print_accuracy(&reviews);
+            return Some(Outcome::Solved { turns: turn });
+        }
+    }
+    println!("Out of turns, the code was {}", Pegs(&codemaker.code()));
+    print_accuracy(&reviews);
+    Some(Outcome::Unsolved { turns: max_turns })
+}
+
+/// Plays `rounds` games against the solver, alternating who makes the code, and scores
+/// them like the board game.
+pub fn play_match<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    rounds: usize,
+    max_turns: usize,
+    handicap: Option<Handicap>,
+) {
+    let mut rng = Rng::from_entropy();
+    // points of the user and the solver in every round
+    let mut scores = vec![];
+    for round in 1..=rounds {
+        let user_is_codemaker = round % 2 == 1;
+        println!(
+            "\nround {round}/{rounds}: {}",
+            if user_is_codemaker {
+                "think of a code and I'll guess it"
+            } else {
+                "guess my code"
+            }
+        );
+        let outcome = if user_is_codemaker {
+            play_round::<FIELDS, COLORS, PARTITIONS>(
+                rules,
+                options,
+                vec![],
+                max_turns,
+                None,
+                handicap,
+            )
+        } else {
+            let code = rules.random_code::<FIELDS, COLORS>(&mut rng);
+            println!("I have chosen {}.", describe_code::<FIELDS, COLORS>(rules));
+            let codemaker = Codemaker::Fixed(code);
+            break_code::<FIELDS, COLORS, PARTITIONS>(
+                rules, options, codemaker, max_turns, None, false,
+            )
+        };
+        let Some(outcome) = outcome else {
+            println!();
+            break;
+        };
+        let breaker = if user_is_codemaker {
+            Breaker::Solver
+        } else {
+            Breaker::User
+        };
+        outcome.record::<FIELDS, COLORS>(rules, "match", breaker);
+        let points = outcome.points();
+        scores.push(if user_is_codemaker {
+            (points, 0)
+        } else {
+            (0, points)
+        });
+        let (you, me) = total(&scores);
+        println!("score: you {you}, solver {me}");
+    }
+    print_scoreboard(&scores);
+}
+
+fn total(scores: &[(usize, usize)]) -> (usize, usize) {
+    scores
+        .iter()
+        .fold((0, 0), |(you, me), (round_you, round_me)| {
+            (you + round_you, me + round_me)
+        })
+}
+
+fn print_scoreboard(scores: &[(usize, usize)]) {
+    println!("\n{:>5} {:>6} {:>6}", "round", "you", "solver");
+    for (round, (you, me)) in scores.iter().enumerate() {
+        println!("{:>5} {you:>6} {me:>6}", round + 1);
+    }
+    let (you, me) = total(scores);
+    println!("{:>5} {you:>6} {me:>6}", "total");
+    match you.cmp(&me) {
+        std::cmp::Ordering::Greater => println!("You win the match!"),
+        std::cmp::Ordering::Less => println!("I win the match!"),
+        std::cmp::Ordering::Equal => println!("The match is a draw."),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn codemaker_points() {
+        assert_eq!(Outcome::Solved { turns: 4 }.points(), 4);
+        assert_eq!(Outcome::Unsolved { turns: 10 }.points(), 11);
+        assert_eq!(Outcome::Contradiction.points(), 0);
+        assert_eq!(total(&[(4, 0), (0, 6), (5, 0)]), (9, 6));
+    }
+
+    #[test]
+    fn found_only_candidates() {
+        let rules = crate::Preset::Mini.rules();
+        let mut guesser = SimpleGuesser::<3, 4, { crate::max_gauss(3) }>::new(rules);
+        let opening = Guess::new([0, 1, 2]);
+        let mut history = vec![Entry {
+            guess: opening,
+            evaluation: rules.evaluate(Guess::new([1, 2, 3]), opening),
+        }];
+        guesser.update_candidates(&history);
+        let won = Evaluation::new(3, 0).unwrap();
+        let code = guesser.candidates()[0];
+        assert!(is_found(guesser.candidates(), code, won));
+        assert!(!is_found(
+            guesser.candidates(),
+            code,
+            Evaluation::new(1, 2).unwrap()
+        ));
+        // a code with duplicates can't be the code of this game
+        let duplicates = Guess::new([2, 1, 1]);
+        assert!(!is_found(guesser.candidates(), duplicates, won));
+        // neither can a code which doesn't fit the feedback before
+        assert!(!is_found(guesser.candidates(), opening, won));
+        // which leaves no code to guess
+        history.push(Entry {
+            guess: duplicates,
+            evaluation: won,
+        });
+        assert!(guesser.guess(&history).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Just enough JSON to print results for `--output json`.
+
+use std::{fmt::Display, time::Duration};
+
+use crate::{palette::color_names, Evaluation, Guess};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<'a>(fields: impl IntoIterator<Item = (&'a str, Json)>) -> Self {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, string: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Number(value) if value.is_finite() => write!(f, "{value}"),
+            Json::Number(_) => write!(f, "null"),
+            Json::String(value) => write_string(f, value),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Number(value)
+    }
+}
+
+impl From<u32> for Json {
+    fn from(value: u32) -> Self {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+/// Durations are given in seconds.
+impl From<Duration> for Json {
+    fn from(value: Duration) -> Self {
+        Json::Number(value.as_secs_f64())
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Self {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Guesses are given as a list of color names.
+impl<const FIELDS: usize> From<Guess<FIELDS>> for Json {
+    fn from(guess: Guess<FIELDS>) -> Self {
+        let names = color_names();
+        Json::Array(
+            guess
+                .colors()
+                .iter()
+                .map(|color| Json::from(names[*color as usize].as_str()))
+                .collect(),
+        )
+    }
+}
+
+impl<const FIELDS: usize> From<Evaluation<FIELDS>> for Json {
+    fn from(evaluation: Evaluation<FIELDS>) -> Self {
+        Json::object([
+            ("exact", evaluation.exact.into()),
+            ("white", evaluation.correct_color.into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_json() {
+        let json = Json::object([
+            ("guess", Guess::new([0, 3]).into()),
+            ("score", 1.5.into()),
+            ("turns", 3usize.into()),
+            ("infinite", f64::INFINITY.into()),
+            ("name", "\"quoted\"\n".into()),
+            ("done", true.into()),
+            ("list", Vec::<u32>::new().into()),
+        ]);
+        assert_eq!(
+            json.to_string(),
+            r#"{"guess":["rot","blau"],"score":1.5,"turns":3,"infinite":null,"name":"\"quoted\"\n","done":true,"list":[]}"#
+        );
+    }
+}
@@ -0,0 +1,3067 @@
+#![cfg_attr(test, feature(test))]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+use once_cell::sync::OnceCell;
+use rayon::prelude::*;
+
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    io::IsTerminal,
+    str::FromStr,
+    sync::{
+        atomic::{self, AtomicU64},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+#[macro_use]
+mod log;
+#[macro_use]
+mod preset;
+mod arbiter;
+mod arena;
+mod baked;
+#[cfg(test)]
+mod bench;
+mod book;
+mod cache;
+mod classic_book;
+mod cli;
+mod coach;
+mod codemaker;
+mod commands;
+mod config;
+mod csv;
+mod dataset;
+mod explain;
+mod facts;
+mod ffi;
+#[cfg(test)]
+mod fuzz;
+mod handicap;
+mod hints;
+mod history;
+mod interactive;
+mod json;
+mod metrics;
+mod palette;
+mod plugin;
+mod policy;
+mod progress;
+#[cfg(test)]
+mod properties;
+mod puzzle;
+mod random;
+mod ratings;
+mod readline;
+mod rooms;
+mod save;
+mod script;
+mod server;
+#[cfg(feature = "simd")]
+mod simd;
+mod stats;
+mod store;
+mod strategy;
+#[cfg(not(feature = "simd"))]
+mod swar;
+mod table;
+mod transcript;
+mod tree;
+mod tui;
+mod websocket;
+use cache::OpeningCache;
+use cli::{Command, Output};
+use facts::Fact;
+use palette::{color_index, color_names};
+use policy::Policy;
+use preset::Preset;
+use progress::Progress;
+use random::Rng;
+use script::Script;
+pub use strategy::{entropy, minimax, most_parts, ScoringFn, Strategy};
+use table::EvaluationTable;
+
+pub const NUM_COLORS: u32 = 10;
+pub const NUM_FIELDS: u32 = 6;
+pub type ColorBitmask = u32;
+
+const BITS_PER_FIELD: usize = 4;
+/// Colors are packed into `BITS_PER_FIELD` bits, so there can't be more than this many.
+pub const MAX_COLORS: u32 = 1 << BITS_PER_FIELD;
+
+/// The number of candidates a guess is scored against before checking whether it can
+/// still beat the best guess so far.
+const PRUNING_BLOCK: usize = 1024;
+/// Guesses are only pruned if their bound is worse by more than this, so rounding errors
+/// can't prune a guess which is as good as the best one.
+const PRUNING_EPSILON: f64 = 1e-9;
+/// If there are more guesses than this, they are generated in chunks of `GUESS_CHUNK`
+/// whenever they are needed instead of being kept.
+const MAX_KEPT_GUESSES: usize = if cfg!(test) { 1000 } else { 1 << 24 };
+const GUESS_CHUNK: usize = if cfg!(test) { 300 } else { 1 << 16 };
+/// Knuth's and the most-parts strategy score whole numbers of codes or partitions, this
+/// is added for guesses which could win right away to prefer them among equally good ones.
+const POSSIBLE_BONUS: f64 = 0.5;
+/// Guesses show a progress bar if they evaluate at least this many codes.
+const PROGRESS_EVALUATIONS: usize = 1 << 28;
+/// `n log2 n` is looked up for counts below this and computed for larger ones, so the
+/// table stays small enough to be cached.
+const N_LOG2_N_ENTRIES: usize = 1 << 20;
+
+/// What a solver plays by besides the rules, see `SimpleGuesser::with_options`. `run` sets
+/// them up from the command line and hands them to the commands.
+#[derive(Clone, Default)]
+pub struct SolverOptions {
+    pub strategy: Strategy,
+    /// The colors of the guess to open with instead of the solver's own, see
+    /// `--first-guess`.
+    pub first_guess: Option<Vec<u32>>,
+    /// Scores the guesses instead of the strategy, see `--script`.
+    pub script: Option<Arc<Script>>,
+    /// Chooses the guesses instead of the strategy, see `--policy`.
+    pub policy: Option<Arc<Policy>>,
+}
+
+/// A code or guess, packed into one integer with `BITS_PER_FIELD` bits per field, the
+/// first field in the lowest bits. This keeps it cheap to copy, compare and hash.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Guess<const FIELDS: usize>(u64);
+
+impl<const FIELDS: usize> Guess<FIELDS> {
+    pub const fn new(colors: [u32; FIELDS]) -> Self {
+        const { assert!(FIELDS * BITS_PER_FIELD <= u64::BITS as usize) };
+        let mut packed = 0;
+        let mut field = FIELDS;
+        while field > 0 {
+            field -= 1;
+            packed = packed << BITS_PER_FIELD | colors[field] as u64;
+        }
+        Self(packed)
+    }
+
+    #[inline]
+    pub fn get(&self, field: usize) -> u32 {
+        (self.0 >> (field * BITS_PER_FIELD)) as u32 & (MAX_COLORS - 1)
+    }
+
+    #[inline]
+    pub fn set(&mut self, field: usize, color: u32) {
+        let shift = field * BITS_PER_FIELD;
+        self.0 = self.0 & !((MAX_COLORS as u64 - 1) << shift)
+            | ((color & (MAX_COLORS - 1)) as u64) << shift;
+    }
+
+    pub fn colors(&self) -> [u32; FIELDS] {
+        std::array::from_fn(|field| self.get(field))
+    }
+
+    /// The packed representation, which orders guesses like `GuessIterator` yields them.
+    pub fn packed(&self) -> u64 {
+        self.0
+    }
+
+    /// The guess of a packed representation, see `packed`.
+    pub const fn from_packed(packed: u64) -> Self {
+        Self(packed)
+    }
+
+    /// Whether this is the representative of the guesses which are the same up to renaming
+    /// the colors and reordering the fields: colors are numbered in order of appearance and
+    /// fields of one color are adjacent, longer runs first, e.g. `0 0 1 2`.
+    pub fn is_canonical(&self) -> bool {
+        // the length of the current and of the previous run of one color
+        let mut run = 0;
+        let mut previous_run = FIELDS;
+        for (field, color) in self.colors().into_iter().enumerate() {
+            match field.checked_sub(1).map(|previous| self.get(previous)) {
+                None if color == 0 => run = 1,
+                Some(previous) if color == previous => run += 1,
+                Some(previous) if color == previous + 1 && run <= previous_run => {
+                    previous_run = run;
+                    run = 1;
+                }
+                _ => return false,
+            }
+        }
+        run <= previous_run
+    }
+}
+
+impl<const FIELDS: usize> std::fmt::Debug for Guess<FIELDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Guess").field(&self.colors()).finish()
+    }
+}
+
+impl<const FIELDS: usize> Display for Guess<FIELDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = color_names();
+        let mut first = true;
+        for field in self.colors().iter() {
+            if first {
+                write!(f, "{}", names[*field as usize])?;
+            } else {
+                write!(f, ", {}", names[*field as usize])?;
+            }
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGuessError {
+    UnknownColor(String),
+    WrongLength { expected: usize, found: usize },
+}
+
+impl Display for ParseGuessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGuessError::UnknownColor(color) => write!(f, "unknown color {color:?}"),
+            ParseGuessError::WrongLength { expected, found } => {
+                write!(f, "expected {expected} colors but got {found}")
+            }
+        }
+    }
+}
+
+fn parse_color(color: &str) -> Result<u32, ParseGuessError> {
+    color
+        .parse()
+        .ok()
+        .filter(|index| (*index as usize) < color_names().len())
+        .or_else(|| color_index(color))
+        .filter(|index| *index < MAX_COLORS)
+        .ok_or_else(|| ParseGuessError::UnknownColor(color.to_string()))
+}
+
+/// Parses the guess to open every game with, which has to be legal under `rules`, into its
+/// colors.
+fn parse_first_guess<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    text: &str,
+) -> Result<Vec<u32>, String> {
+    let guess: Guess<FIELDS> = text.parse().map_err(|err| format!("{err}"))?;
+    if !rules.is_legal_guess::<FIELDS, COLORS>(&guess) {
+        return Err(format!("[{guess}] can't be played in this game"));
+    }
+    Ok(guess.colors().to_vec())
+}
+
+/// The bitmask of the colors named in `names`, which have to be colors of a game with
+/// `colors` colors.
+fn color_mask(names: &[String], colors: u32) -> Result<ColorBitmask, String> {
+    names
+        .iter()
+        .try_fold(0, |mask, name| match parse_color(name.trim()) {
+            Ok(color) if color < colors => Ok(mask | 1 << color),
+            Ok(_) => Err(format!("{name:?} isn't a color of this game")),
+            Err(err) => Err(err.to_string()),
+        })
+}
+
+/// Parses colors separated by commas or whitespace, given either by name or by index.
+impl<const FIELDS: usize> FromStr for Guess<FIELDS> {
+    type Err = ParseGuessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colors = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|color| !color.is_empty())
+            .map(parse_color)
+            .collect::<Result<Vec<_>, _>>()?;
+        let found = colors.len();
+        colors
+            .try_into()
+            .map(Guess::new)
+            .map_err(|_| ParseGuessError::WrongLength {
+                expected: FIELDS,
+                found,
+            })
+    }
+}
+
+impl<const FIELDS: usize> Guess<FIELDS> {
+    /// The colors as space separated numbers, e.g. `3 2 1 0`.
+    fn indices(&self) -> String {
+        self.colors()
+            .iter()
+            .map(|color| color.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn is_valid_code(&self) -> bool {
+        let mut colors: ColorBitmask = 0;
+        for color in self.colors() {
+            if colors & (1 << color) > 0 {
+                return false;
+            }
+            colors |= 1 << color;
+        }
+        true
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Evaluation<const FIELDS: usize> {
+    correct_color: u32,
+    exact: u32,
+}
+
+#[inline]
+pub const fn max_gauss(i: usize) -> usize {
+    (i + 2) * (i + 1) / 2
+}
+
+impl<const FIELDS: usize> Evaluation<FIELDS> {
+    const MAX_GAUSS: u32 = (FIELDS as u32 + 2) * (FIELDS + 1) as u32 / 2;
+    #[inline]
+    const fn lut_for_index(i: u32) -> u32 {
+        (i + 2) * (i + 1) / 2
+    }
+    /// The feedback, if there are enough pegs to hand out.
+    pub fn new(exact: u32, correct_color: u32) -> Result<Self, InvalidEvaluation> {
+        let evaluation = Self {
+            correct_color,
+            exact,
+        };
+        evaluation.validate().map(|()| evaluation)
+    }
+
+    /// Checks that there are enough pegs, there are only `FIELDS` to hand out.
+    pub fn validate(&self) -> Result<(), InvalidEvaluation> {
+        match self.exact.checked_add(self.correct_color) {
+            Some(pegs) if pegs <= FIELDS as u32 => Ok(()),
+            _ => Err(InvalidEvaluation::TooManyPegs { fields: FIELDS }),
+        }
+    }
+
+    /// Whether this feedback is possible at all, see `validate`.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+    #[inline]
+    pub fn to_u32(&self) -> u32 {
+        Self::MAX_GAUSS + self.exact - Self::lut_for_index(FIELDS as u32 - self.correct_color)
+    }
+
+    /// The feedback with the index `to_u32`, if the index is below `max_gauss(FIELDS)`.
+    pub fn from_u32(index: u32) -> Option<Self> {
+        (0..=FIELDS as u32).find_map(|correct_color| {
+            let first = Self::MAX_GAUSS - Self::lut_for_index(FIELDS as u32 - correct_color);
+            let exact = index.checked_sub(first)?;
+            (exact + correct_color <= FIELDS as u32).then_some(Self {
+                correct_color,
+                exact,
+            })
+        })
+    }
+}
+
+/// Shows the feedback as peg glyphs, `●` for exact matches, `○` for correct colors
+/// and `·` for the remaining fields.
+impl<const FIELDS: usize> Display for Evaluation<FIELDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for _ in 0..self.exact {
+            write!(f, "●")?;
+        }
+        for _ in 0..self.correct_color {
+            write!(f, "○")?;
+        }
+        for _ in (self.exact + self.correct_color) as usize..FIELDS {
+            write!(f, "·")?;
+        }
+        Ok(())
+    }
+}
+
+/// Feedback which no code can give.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidEvaluation {
+    /// More pegs than fields.
+    TooManyPegs { fields: usize },
+    /// A correct color in the only field which isn't an exact match, though the color has
+    /// to be in one of the other fields, which all match already.
+    MisplacedLastColor,
+}
+
+impl Display for InvalidEvaluation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidEvaluation::TooManyPegs { fields } => write!(
+                f,
+                "there are only {fields} pegs, exact and white matches can't add up to more"
+            ),
+            InvalidEvaluation::MisplacedLastColor => write!(
+                f,
+                "if all other fields match exactly, the last color can't be somewhere else"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseEvaluationError {
+    Unreadable(String),
+    Invalid(InvalidEvaluation),
+}
+
+impl Display for ParseEvaluationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseEvaluationError::Unreadable(feedback) => {
+                write!(f, "cannot read feedback {feedback:?}")
+            }
+            ParseEvaluationError::Invalid(invalid) => invalid.fmt(f),
+        }
+    }
+}
+
+/// Parses feedback written as `exact white` (`2 1`), with peg letters (`2b1w`, `2r 1w`)
+/// or as peg glyphs (`●●○`, `·` marks an empty field). Exact matches are black/red pegs,
+/// correct colors white pegs. There may not be more pegs than fields.
+impl<const FIELDS: usize> FromStr for Evaluation<FIELDS> {
+    type Err = ParseEvaluationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseEvaluationError::Unreadable(s.to_string());
+        let new = |exact, correct_color| {
+            Evaluation::new(exact, correct_color).map_err(ParseEvaluationError::Invalid)
+        };
+        let s = s.trim();
+        if !s.is_empty()
+            && s.chars()
+                .all(|c| matches!(c, '●' | '○' | '·') || c.is_whitespace())
+        {
+            return new(s.matches('●').count() as u32, s.matches('○').count() as u32);
+        }
+        let numbers: Vec<_> = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|number| !number.is_empty())
+            .collect();
+        if let [exact, correct_color] = numbers[..] {
+            if let (Ok(exact), Ok(correct_color)) = (exact.parse(), correct_color.parse()) {
+                return new(exact, correct_color);
+            }
+        }
+
+        let (mut exact, mut correct_color) = (None, None);
+        let mut count = String::new();
+        for c in s.chars().filter(|c| !c.is_whitespace()) {
+            let peg = match c.to_ascii_lowercase() {
+                digit if digit.is_ascii_digit() => {
+                    count.push(digit);
+                    continue;
+                }
+                'b' | 'r' => &mut exact,
+                'w' => &mut correct_color,
+                _ => return Err(error()),
+            };
+            if peg.is_some() {
+                return Err(error());
+            }
+            *peg = Some(count.parse().map_err(|_| error())?);
+            count.clear();
+        }
+        if !count.is_empty() || (exact.is_none() && correct_color.is_none()) {
+            return Err(error());
+        }
+        new(exact.unwrap_or(0), correct_color.unwrap_or(0))
+    }
+}
+
+/// The rules of a game variant which are not encoded in the board dimensions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Rules {
+    /// Whether the secret code may contain the same color more than once.
+    pub duplicates: bool,
+    /// The colors the codebreaker has no pegs for, e.g. of an incomplete set. Guesses
+    /// can't contain them, but the codemaker may still choose them, so a code with one
+    /// of them is named instead of played once it is the only one left.
+    pub missing_colors: ColorBitmask,
+}
+
+impl Rules {
+    #[inline]
+    pub fn evaluate<const FIELDS: usize>(
+        &self,
+        code: Guess<FIELDS>,
+        guess: Guess<FIELDS>,
+    ) -> Evaluation<FIELDS> {
+        if self.duplicates {
+            evaluate_with_duplicates(code, guess)
+        } else {
+            evaluate(code, guess)
+        }
+    }
+
+    /// Checks that a code can give the feedback to the guess. With all fields but one
+    /// matching exactly, the last color can only be somewhere else if the guess repeats a
+    /// color and each of its pegs counts for a code without duplicates, see `evaluate`.
+    pub fn check_feedback<const FIELDS: usize>(
+        &self,
+        guess: Guess<FIELDS>,
+        evaluation: Evaluation<FIELDS>,
+    ) -> Result<(), InvalidEvaluation> {
+        evaluation.validate()?;
+        if (evaluation.exact, evaluation.correct_color) == (FIELDS as u32 - 1, 1)
+            && (self.duplicates || guess.is_valid_code())
+        {
+            return Err(InvalidEvaluation::MisplacedLastColor);
+        }
+        Ok(())
+    }
+
+    /// Whether the codemaker is allowed to choose `code` in a game with `COLORS` colors.
+    pub fn is_legal_code<const FIELDS: usize, const COLORS: u32>(
+        &self,
+        code: &Guess<FIELDS>,
+    ) -> bool {
+        code.colors().iter().all(|color| *color < COLORS)
+            && (self.duplicates || code.is_valid_code())
+    }
+
+    /// Whether the codebreaker is allowed to play `guess` in a game with `COLORS` colors.
+    pub fn is_legal_guess<const FIELDS: usize, const COLORS: u32>(
+        &self,
+        guess: &Guess<FIELDS>,
+    ) -> bool {
+        let available = guess
+            .colors()
+            .iter()
+            .all(|color| self.missing_colors & (1 << color) == 0);
+        #[cfg(feature = "laura")]
+        return available && self.is_legal_code::<FIELDS, COLORS>(guess);
+        #[cfg(not(feature = "laura"))]
+        return available && guess.colors().iter().all(|color| *color < COLORS);
+    }
+
+    /// Whether `guess` can be a turn of a game: a legal guess, or a legal code named as
+    /// the last one left although it can't be played, see `missing_colors`.
+    pub fn is_legal_turn<const FIELDS: usize, const COLORS: u32>(
+        &self,
+        guess: &Guess<FIELDS>,
+    ) -> bool {
+        self.is_legal_guess::<FIELDS, COLORS>(guess) || self.is_legal_code::<FIELDS, COLORS>(guess)
+    }
+
+    /// Picks one of the legal codes at random, all equally likely. The colors are drawn
+    /// field by field, so this is quick even if there are too many codes to count.
+    pub fn random_code<const FIELDS: usize, const COLORS: u32>(
+        &self,
+        rng: &mut Rng,
+    ) -> Guess<FIELDS> {
+        let mut colors: Vec<u32> = (0..COLORS).collect();
+        let mut code = Guess::default();
+        for field in 0..FIELDS {
+            if self.duplicates {
+                code.set(field, colors[rng.below(colors.len())]);
+            } else {
+                code.set(field, colors.swap_remove(rng.below(colors.len())));
+            }
+        }
+        code
+    }
+
+    /// The code `codes` yields at `index`, which has to be below `code_count`.
+    pub fn code_at<const FIELDS: usize, const COLORS: u32>(&self, index: usize) -> Guess<FIELDS> {
+        if self.duplicates {
+            GuessIterator::<FIELDS, COLORS>::guess_at(index)
+        } else {
+            CodeIterator::<FIELDS, COLORS>::code_at(index)
+        }
+    }
+
+    /// Iterates over all codes the codemaker is allowed to choose.
+    pub fn codes<const FIELDS: usize, const COLORS: u32>(
+        &self,
+    ) -> Box<dyn Iterator<Item = Guess<FIELDS>>> {
+        if self.duplicates {
+            Box::new(GuessIterator::<FIELDS, COLORS>::default())
+        } else {
+            Box::new(CodeIterator::<FIELDS, COLORS>::default())
+        }
+    }
+
+    /// Iterates over all guesses the codebreaker is allowed to play.
+    pub fn guesses<const FIELDS: usize, const COLORS: u32>(
+        &self,
+    ) -> Box<dyn Iterator<Item = Guess<FIELDS>>> {
+        #[cfg(feature = "laura")]
+        let guesses = self.codes::<FIELDS, COLORS>();
+        #[cfg(not(feature = "laura"))]
+        let guesses = Box::new(GuessIterator::<FIELDS, COLORS>::default());
+        if self.missing_colors == 0 {
+            return guesses;
+        }
+        let rules = *self;
+        Box::new(guesses.filter(move |guess| rules.is_legal_guess::<FIELDS, COLORS>(guess)))
+    }
+
+    /// The number of items `codes` yields, saturating at `usize::MAX`.
+    pub fn code_count<const FIELDS: usize, const COLORS: u32>(&self) -> usize {
+        Self::count::<FIELDS>(self.duplicates, COLORS as usize)
+    }
+
+    /// The number of items `guesses` yields, saturating at `usize::MAX`.
+    pub fn guess_count<const FIELDS: usize, const COLORS: u32>(&self) -> usize {
+        let colors = (0..COLORS)
+            .filter(|color| self.missing_colors & (1 << color) == 0)
+            .count();
+        Self::count::<FIELDS>(cfg!(not(feature = "laura")) || self.duplicates, colors)
+    }
+
+    /// The number of codes of `colors` colors, saturating at `usize::MAX`.
+    fn count<const FIELDS: usize>(duplicates: bool, colors: usize) -> usize {
+        if duplicates {
+            (0..FIELDS).fold(1usize, |count, _| count.saturating_mul(colors))
+        } else {
+            (0..FIELDS).fold(1usize, |count, field| {
+                count.saturating_mul(colors.saturating_sub(field))
+            })
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Entry<const FIELDS: usize> {
+    guess: Guess<FIELDS>,
+    evaluation: Evaluation<FIELDS>,
+}
+
+/// Implements the iterator traits for an iterator over the positions `front..back` of a
+/// sequence, with the item at a position given by `$at`. Knowing the positions lets the
+/// iterators tell their length and jump ahead, e.g. to split them into chunks.
+macro_rules! indexed_iterator {
+    ($iterator:ident, $at:ident) => {
+        impl<const FIELDS: usize, const COLORS: u32> Iterator for $iterator<FIELDS, COLORS> {
+            type Item = Guess<FIELDS>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                (self.front < self.back).then(|| {
+                    self.front += 1;
+                    Self::$at(self.front - 1)
+                })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.back - self.front;
+                (len, Some(len))
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.front = self.front.saturating_add(n).min(self.back);
+                self.next()
+            }
+        }
+
+        impl<const FIELDS: usize, const COLORS: u32> DoubleEndedIterator
+            for $iterator<FIELDS, COLORS>
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                (self.front < self.back).then(|| {
+                    self.back -= 1;
+                    Self::$at(self.back)
+                })
+            }
+
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.back = self.back.saturating_sub(n).max(self.front);
+                self.next_back()
+            }
+        }
+
+        impl<const FIELDS: usize, const COLORS: u32> ExactSizeIterator
+            for $iterator<FIELDS, COLORS>
+        {
+        }
+
+        impl<const FIELDS: usize, const COLORS: u32> std::iter::FusedIterator
+            for $iterator<FIELDS, COLORS>
+        {
+        }
+    };
+}
+
+/// Iterates over all guesses in the order of `Guess::packed`, the first field changing
+/// the fastest.
+#[derive(Clone, Debug)]
+pub struct GuessIterator<const FIELDS: usize, const COLORS: u32> {
+    front: usize,
+    back: usize,
+}
+
+impl<const FIELDS: usize, const COLORS: u32> Default for GuessIterator<FIELDS, COLORS> {
+    fn default() -> Self {
+        const {
+            assert!(
+                (COLORS as usize).checked_pow(FIELDS as u32).is_some(),
+                "the positions of the guesses have to fit into a usize"
+            )
+        };
+        Self {
+            front: 0,
+            back: Rules {
+                duplicates: true,
+                missing_colors: 0,
+            }
+            .code_count::<FIELDS, COLORS>(),
+        }
+    }
+}
+
+impl<const FIELDS: usize, const COLORS: u32> GuessIterator<FIELDS, COLORS> {
+    /// The guess at `index` in the order of the iterator: the colors are the digits of
+    /// `index` in base `COLORS`, the first field the least significant one.
+    pub fn guess_at(mut index: usize) -> Guess<FIELDS> {
+        let mut guess = Guess::default();
+        for field in 0..FIELDS {
+            guess.set(field, (index % COLORS as usize) as u32);
+            index /= COLORS as usize;
+        }
+        guess
+    }
+}
+
+indexed_iterator!(GuessIterator, guess_at);
+
+/// Iterates over all codes without duplicate colors, in the order of `GuessIterator`.
+#[derive(Clone, Debug)]
+pub struct CodeIterator<const FIELDS: usize, const COLORS: u32> {
+    front: usize,
+    back: usize,
+}
+
+impl<const FIELDS: usize, const COLORS: u32> Default for CodeIterator<FIELDS, COLORS> {
+    fn default() -> Self {
+        Self {
+            front: 0,
+            back: Rules {
+                duplicates: false,
+                missing_colors: 0,
+            }
+            .code_count::<FIELDS, COLORS>(),
+        }
+    }
+}
+
+impl<const FIELDS: usize, const COLORS: u32> CodeIterator<FIELDS, COLORS> {
+    /// The code at `index` in the order of the iterator, `index` has to be below the
+    /// number of codes. The last field is the most significant one: for each of its
+    /// colors there is a block of codes, one for every arrangement of the other fields
+    /// with the colors left.
+    pub fn code_at(mut index: usize) -> Guess<FIELDS> {
+        let mut code = Guess::default();
+        let mut used: ColorBitmask = 0;
+        for field in (0..FIELDS).rev() {
+            let left = COLORS as usize - (FIELDS - field);
+            let block: usize = (0..field).map(|lower| left - lower).product();
+            let color = (0..COLORS)
+                .filter(|color| used & 1 << color == 0)
+                .nth(index / block)
+                .expect("the index is below the number of codes");
+            index %= block;
+            used |= 1 << color;
+            code.set(field, color);
+        }
+        code
+    }
+}
+
+indexed_iterator!(CodeIterator, code_at);
+
+pub trait Solver<const FIELDS: usize> {
+    fn guess(
+        &mut self,
+        history: &[Entry<FIELDS>],
+    ) -> Result<GuessReport<FIELDS>, InconsistentHistory>;
+}
+
+/// No code fits all of the feedback of the history, so some of it must be wrong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InconsistentHistory;
+
+impl Display for InconsistentHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no code matches all of the feedback")
+    }
+}
+
+/// A guess chosen by a solver, with what it took to choose it.
+#[derive(Clone, Copy, Debug)]
+pub struct GuessReport<const FIELDS: usize> {
+    pub guess: Guess<FIELDS>,
+    /// The score by the solver's strategy, the expected information in bit by default.
+    pub score: f64,
+    /// The number of different feedbacks the remaining candidates could give to the guess.
+    pub partitions: usize,
+    /// The number of codes which were still possible.
+    pub candidates: usize,
+    /// The information the guess is expected to give in bit, whatever the strategy.
+    pub information: f64,
+    /// The number of guesses the game is expected to take from here, see `expected_guesses`.
+    pub expected_guesses: f64,
+    /// The number of guesses which were scored against the candidates.
+    pub scored: usize,
+    pub elapsed: Duration,
+}
+
+//#[inline(never)]
+pub fn evaluate<const FIELDS: usize>(
+    code: Guess<FIELDS>,
+    guess: Guess<FIELDS>,
+) -> Evaluation<FIELDS> {
+    let mut exact_matches = 0;
+    let mut inexact_matches = 0;
+    let mut colors: ColorBitmask = 0;
+
+    for i in 0..FIELDS {
+        colors |= 1 << code.get(i)
+    }
+
+    for i in 0..FIELDS {
+        exact_matches += (code.get(i) == guess.get(i)) as u32;
+        inexact_matches += (colors & (1 << guess.get(i)) > 0) as u32;
+    }
+    debug_assert!(inexact_matches <= FIELDS as u32);
+    Evaluation {
+        correct_color: inexact_matches - exact_matches,
+        exact: exact_matches,
+    }
+}
+
+/// Evaluates a guess against a code which may contain duplicate colors.
+/// Every code peg can be matched by at most one guess peg.
+pub fn evaluate_with_duplicates<const FIELDS: usize>(
+    code: Guess<FIELDS>,
+    guess: Guess<FIELDS>,
+) -> Evaluation<FIELDS> {
+    let mut exact_matches = 0;
+    let mut code_colors = [0u8; ColorBitmask::BITS as usize];
+    let mut guess_colors = [0u8; ColorBitmask::BITS as usize];
+
+    for i in 0..FIELDS {
+        exact_matches += (code.get(i) == guess.get(i)) as u32;
+        code_colors[code.get(i) as usize] += 1;
+        guess_colors[guess.get(i) as usize] += 1;
+    }
+    let color_matches: u32 = code_colors
+        .iter()
+        .zip(guess_colors.iter())
+        .map(|(code, guess)| *code.min(guess) as u32)
+        .sum();
+    Evaluation {
+        correct_color: color_matches - exact_matches,
+        exact: exact_matches,
+    }
+}
+
+#[cfg(test)]
+struct DummyGuesser<const FIELDS: usize>;
+
+#[cfg(test)]
+impl<const FIELDS: usize> Solver<FIELDS> for DummyGuesser<FIELDS> {
+    fn guess(
+        &mut self,
+        _history: &[Entry<FIELDS>],
+    ) -> Result<GuessReport<FIELDS>, InconsistentHistory> {
+        Ok(GuessReport {
+            guess: Guess::default(),
+            score: 0.,
+            partitions: 1,
+            candidates: 1,
+            information: 0.,
+            expected_guesses: 1.,
+            scored: 0,
+            elapsed: Duration::ZERO,
+        })
+    }
+}
+
+/// A guess ranked by the solver.
+pub struct Suggestion<const FIELDS: usize> {
+    guess: Guess<FIELDS>,
+    /// The expected information in bit.
+    score: f64,
+    /// The number of candidates left in the largest partition.
+    worst_case: u32,
+    /// Whether the guess is one of the candidates and could win right away.
+    possible: bool,
+}
+
+/// An opening scored by the measure of every strategy, to compare them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpeningScores<const FIELDS: usize> {
+    pub guess: Guess<FIELDS>,
+    /// The expected information in bit.
+    pub information: f64,
+    /// The number of codes left in the largest partition.
+    pub worst_case: u32,
+    /// The number of codes expected to be left.
+    pub expected_size: f64,
+    /// The number of different feedbacks the codes could give.
+    pub partitions: usize,
+}
+
+/// Buffers which are reused from turn to turn and game to game instead of being allocated
+/// for every guess.
+#[derive(Default)]
+struct Scratch<const FIELDS: usize> {
+    /// Receives the candidates which are still consistent with the history.
+    candidates: Vec<Guess<FIELDS>>,
+    /// Receives the table indices of those candidates.
+    indices: Vec<u32>,
+    /// The candidates packed for `swar::evaluate_batch`, `PRUNING_BLOCK` per block.
+    #[cfg(not(feature = "simd"))]
+    packed: Vec<swar::PackedCodes<FIELDS>>,
+    /// The canonical guesses scored for the opening.
+    openings: Vec<Guess<FIELDS>>,
+    /// `n log2 n` for every number of candidates up to the most there were, so that scoring
+    /// a guess doesn't need a logarithm per partition.
+    n_log2_n: Vec<f64>,
+}
+
+#[derive(Default)]
+struct SimpleGuesser<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    rules: Rules,
+    strategy: Strategy,
+    /// The codes which were consistent with the history at the last call to `guess`.
+    candidates: Vec<Guess<FIELDS>>,
+    /// The indices of the candidates in the table, if there is one.
+    indices: Vec<u32>,
+    /// Known facts about the code which the candidates have to satisfy besides the history.
+    facts: Vec<Fact>,
+    /// The guess played instead of the best one before any feedback.
+    first_guess: Option<Guess<FIELDS>>,
+    /// The history the candidates were filtered by, `None` before the first guess.
+    filtered_by: Option<Vec<Entry<FIELDS>>>,
+    /// Precomputed feedback, only available for small games.
+    table: Option<Arc<EvaluationTable<FIELDS>>>,
+    /// All guesses allowed by the rules, generated on first use if there is no table.
+    guesses: OnceCell<Vec<Guess<FIELDS>>>,
+    /// The first two guesses kept on disk, loaded when they are first needed.
+    opening_cache: Option<Option<OpeningCache<FIELDS>>>,
+    /// Told about every guess which scores better than the ones before while the best
+    /// guess is searched for.
+    observer: Option<Observer<FIELDS>>,
+    /// Scores the guesses instead of the strategy if set, see `ScoringFn`.
+    scoring: Option<ScoringFn<PARTITIONS>>,
+    /// Whether the first of equally good guesses is played, as a script may ask for.
+    first_of_ties: bool,
+    /// Chooses the guesses instead of the strategy if set, see `policy`.
+    policy: Option<Arc<Policy>>,
+    scratch: Scratch<FIELDS>,
+}
+
+/// Receives a guess and its score, see `SimpleGuesser::observer`.
+pub type Observer<const FIELDS: usize> = Arc<dyn Fn(Guess<FIELDS>, f64) + Send + Sync>;
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> Solver<FIELDS>
+    for SimpleGuesser<FIELDS, COLORS, PARTITIONS>
+{
+    fn guess(
+        &mut self,
+        history: &[Entry<FIELDS>],
+    ) -> Result<GuessReport<FIELDS>, InconsistentHistory> {
+        let () = Self::PARAMETERS;
+        let _span = log::span(log::Level::Debug, "guess");
+        let start = Instant::now();
+        self.update_candidates(history);
+        if self.candidates.is_empty() {
+            return Err(InconsistentHistory);
+        }
+        self.extend_n_log2_n();
+        let first_guess = self.first_guess.filter(|_| history.is_empty());
+        // only the first two guesses are cached, and only for every code being possible
+        // before them
+        let opening = first_guess.is_none()
+            && self.policy.is_none()
+            && history.len() <= 1
+            && self.facts.is_empty();
+        let cached = if opening {
+            self.opening_cache().and_then(|cache| cache.get(history))
+        } else {
+            None
+        };
+        // renaming colors only keeps the score of a guess if all of them can be played
+        let symmetric = self.facts.is_empty() && self.rules.missing_colors == 0;
+        let ((mut guess, mut score), scored) = match (first_guess, cached) {
+            (Some(first), _) => (
+                self.score(first, &self.partition(&self.candidates, first)),
+                1,
+            ),
+            (None, Some(cached)) => {
+                debug!("using the cached guess");
+                (cached, 0)
+            }
+            (None, None) if self.policy.is_some() => self.policy_guess(),
+            (None, None) if history.is_empty() && symmetric => self.opening(),
+            (None, None) => self.best_guess(),
+        };
+        let mut counts = self.partition(&self.candidates, guess);
+        // if no guess which can be played tells the codes left apart, as they only differ
+        // in missing colors, they are named one by one
+        if counts[FIELDS] == 0 && counts.iter().filter(|count| **count > 0).count() == 1 {
+            guess = self.candidates[0];
+            counts = self.partition(&self.candidates, guess);
+            score = self.score(guess, &counts).1;
+        }
+        if cached.is_none() && opening {
+            if let Some(cache) = self.opening_cache() {
+                cache.insert(history, guess, score);
+            }
+        }
+        debug!("best guess: {guess} ({score:?} bit)");
+        let information = self.entropy(&counts);
+        // the feedback with `FIELDS` exact matches wins
+        let others = counts
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != FIELDS)
+            .map(|(_, count)| *count as usize);
+        Ok(GuessReport {
+            guess,
+            score,
+            partitions: counts.iter().filter(|count| **count > 0).count(),
+            candidates: self.candidates.len(),
+            information,
+            expected_guesses: expected_guesses(self.candidates.len(), others, information),
+            scored,
+            elapsed: start.elapsed(),
+        })
+    }
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>
+    SimpleGuesser<FIELDS, COLORS, PARTITIONS>
+{
+    /// Fails to compile for parameters which don't fit together: partitions are counted in
+    /// arrays indexed by `Evaluation::to_u32` and colors in bitmasks.
+    const PARAMETERS: () = {
+        assert!(
+            PARTITIONS == max_gauss(FIELDS),
+            "PARTITIONS must be max_gauss(FIELDS), the number of different feedbacks"
+        );
+        assert!(
+            COLORS <= MAX_COLORS,
+            "colors are packed into BITS_PER_FIELD bits"
+        );
+        assert!(
+            COLORS <= ColorBitmask::BITS,
+            "colors have to fit into a ColorBitmask"
+        );
+    };
+
+    /// The guess the policy predicts to take the fewest guesses, with its score by the
+    /// strategy, or the best guess if it can't tell the candidates apart.
+    fn policy_guess(&mut self) -> ((Guess<FIELDS>, f64), usize) {
+        let policy = self.policy.clone().expect("only called with a policy");
+        let predict = policy
+            .predictor::<FIELDS, COLORS>(&dataset::features::<FIELDS, COLORS>(&self.candidates));
+        let mut best: Option<(Guess<FIELDS>, f64)> = None;
+        let mut scored = 0;
+        self.for_each_guess_chunk(|guesses| {
+            scored += guesses.len();
+            for &guess in guesses {
+                let turns = predict(guess);
+                if best.is_none_or(|(_, best)| turns < best) {
+                    best = Some((guess, turns));
+                }
+            }
+        });
+        let (guess, turns) = best.expect("there is at least one guess");
+        let counts = self.partition(&self.candidates, guess);
+        if counts[FIELDS] == 0 && counts.iter().filter(|count| **count > 0).count() == 1 {
+            debug!("the policy's guess {guess} tells nothing, falling back");
+            let (best, fallback) = self.best_guess();
+            return (best, scored + fallback);
+        }
+        debug!("the policy predicts {turns:.2} guesses from {guess} on");
+        (self.score(guess, &counts), scored)
+    }
+
+    /// The best guess for the current candidates and the number of guesses scored.
+    fn best_guess(&mut self) -> ((Guess<FIELDS>, f64), usize) {
+        match self.table.clone() {
+            Some(table) => {
+                let indices = &self.indices;
+                debug!(
+                    "looking up {} guesses against {} candidates",
+                    table.guesses().len(),
+                    indices.len()
+                );
+                let best = AtomicU64::new(0);
+                let progress = self.progress(table.guesses().len());
+                let scored = (0..table.guesses().len())
+                    .into_par_iter()
+                    .filter_map(|index| {
+                        progress.inc(1);
+                        let row = table.row(index);
+                        let counts = self.partition_pruned(
+                            indices.chunks(PRUNING_BLOCK),
+                            |block| Self::partition_indexed(row, block),
+                            indices.len(),
+                            &best,
+                        )?;
+                        Some(self.score_best(table.guesses()[index], &counts, &best, &progress))
+                    });
+                (self.best(scored), table.guesses().len())
+            }
+            None => {
+                // packing the candidates pays off as every guess is scored against them
+                #[cfg(not(feature = "simd"))]
+                if !self.rules.duplicates {
+                    self.pack_candidates();
+                }
+                let codes = &self.candidates;
+                let guess_count = self.rules.guess_count::<FIELDS, COLORS>();
+                debug!(
+                    "scoring {guess_count} guesses against {} candidates",
+                    codes.len()
+                );
+                #[cfg(not(feature = "simd"))]
+                let packed = (!self.rules.duplicates).then_some(&self.scratch.packed);
+                let best = AtomicU64::new(0);
+                let progress = self.progress(guess_count);
+                let score = |guess: &Guess<FIELDS>| {
+                    progress.inc(1);
+                    #[cfg(not(feature = "simd"))]
+                    if let Some(packed) = &packed {
+                        let counts = self.partition_pruned(
+                            packed.iter(),
+                            |block| swar::evaluate_batch(*guess, block),
+                            codes.len(),
+                            &best,
+                        )?;
+                        return Some(self.score_best(*guess, &counts, &best, &progress));
+                    }
+                    let counts = self.partition_pruned(
+                        codes.chunks(PRUNING_BLOCK),
+                        |block| self.partition(block, *guess),
+                        codes.len(),
+                        &best,
+                    )?;
+                    Some(self.score_best(*guess, &counts, &best, &progress))
+                };
+                let mut best_guess = None;
+                self.for_each_guess_chunk(|guesses| {
+                    // every guess of a chunk can be pruned by the best one of the chunks before
+                    let chunk = guesses.par_iter().filter_map(score);
+                    best_guess = Some(self.best(best_guess.into_par_iter().chain(chunk)));
+                });
+                (
+                    best_guess.expect("there is at least one guess"),
+                    guess_count,
+                )
+            }
+        }
+    }
+
+    /// A solver with the default strategy and nothing else to play by.
+    fn new(rules: Rules) -> Self {
+        Self::with_options(rules, &SolverOptions::default())
+    }
+
+    /// A solver which plays by the `options`, as the commands set it up.
+    fn with_options(rules: Rules, options: &SolverOptions) -> Self {
+        let () = Self::PARAMETERS;
+        Self {
+            rules,
+            strategy: options.strategy,
+            candidates: Vec::new(),
+            indices: Vec::new(),
+            facts: Vec::new(),
+            first_guess: options
+                .first_guess
+                .as_ref()
+                .and_then(|colors| Some(Guess::new(colors.as_slice().try_into().ok()?))),
+            filtered_by: None,
+            table: EvaluationTable::shared::<COLORS>(rules),
+            guesses: OnceCell::new(),
+            opening_cache: None,
+            observer: None,
+            scoring: options
+                .script
+                .as_ref()
+                .map(|script| script.scoring::<FIELDS, PARTITIONS>()),
+            first_of_ties: options
+                .script
+                .as_ref()
+                .is_some_and(|script| script.first_of_ties),
+            policy: options.policy.clone(),
+            scratch: Scratch::default(),
+        }
+    }
+
+    /// Calls `f` with all guesses allowed by the rules in the order of `Rules::guesses`.
+    /// They are generated once and kept, unless there are too many of them to keep in
+    /// memory, then they are generated in chunks every time.
+    fn for_each_guess_chunk(&self, mut f: impl FnMut(&[Guess<FIELDS>])) {
+        if let Some(table) = &self.table {
+            return f(table.guesses());
+        }
+        if self.rules.guess_count::<FIELDS, COLORS>() <= MAX_KEPT_GUESSES {
+            return f(self
+                .guesses
+                .get_or_init(|| self.rules.guesses::<FIELDS, COLORS>().collect()));
+        }
+        let mut guesses = self.rules.guesses::<FIELDS, COLORS>();
+        let mut chunk = Vec::with_capacity(GUESS_CHUNK);
+        loop {
+            chunk.clear();
+            chunk.extend(guesses.by_ref().take(GUESS_CHUNK));
+            if chunk.is_empty() {
+                return;
+            }
+            f(&chunk);
+        }
+    }
+
+    /// A progress bar for scoring `guesses` guesses, hidden if that is quick.
+    fn progress(&self, guesses: usize) -> Progress {
+        if guesses.saturating_mul(self.candidates.len()) >= PROGRESS_EVALUATIONS {
+            Progress::new("scoring guesses", guesses)
+        } else {
+            Progress::hidden()
+        }
+    }
+
+    fn candidates(&self) -> &[Guess<FIELDS>] {
+        &self.candidates
+    }
+
+    /// The cache of the first two guesses, if caching is turned on. Guesses scored by a
+    /// scoring function of the user aren't cached.
+    fn opening_cache(&mut self) -> Option<&mut OpeningCache<FIELDS>> {
+        if self.scoring.is_some() {
+            return None;
+        }
+        let (rules, strategy) = (self.rules, self.strategy);
+        self.opening_cache
+            .get_or_insert_with(|| {
+                cache::directory()
+                    .map(|directory| OpeningCache::open::<COLORS>(directory, rules, strategy))
+            })
+            .as_mut()
+    }
+
+    /// Makes sure `n_log2_n` can look up every count up to the number of candidates.
+    fn extend_n_log2_n(&mut self) {
+        let table = &mut self.scratch.n_log2_n;
+        let entries = (self.candidates.len() + 1).min(N_LOG2_N_ENTRIES);
+        if table.len() < entries {
+            table.extend((table.len()..entries).map(compute_n_log2_n));
+        }
+    }
+
+    fn n_log2_n(&self, n: usize) -> f64 {
+        self.scratch
+            .n_log2_n
+            .get(n)
+            .copied()
+            .unwrap_or_else(|| compute_n_log2_n(n))
+    }
+
+    /// The best first guess. Before the first guess every code is a candidate, so guesses
+    /// which are the same up to renaming colors and reordering fields are equally good and
+    /// only one of each has to be scored.
+    fn opening(&mut self) -> ((Guess<FIELDS>, f64), usize) {
+        let mut openings = std::mem::take(&mut self.scratch.openings);
+        openings.clear();
+        self.for_each_guess_chunk(|guesses| {
+            openings.extend(guesses.iter().copied().filter(Guess::is_canonical))
+        });
+        debug!("scoring {} canonical openings", openings.len());
+        let guess = self.best(
+            openings
+                .par_iter()
+                .map(|guess| self.score(*guess, &self.partition(&self.candidates, *guess))),
+        );
+        let scored = openings.len();
+        self.scratch.openings = openings;
+        (guess, scored)
+    }
+
+    /// Packs the candidates into `scratch.packed`, reusing the blocks of the last turn.
+    #[cfg(not(feature = "simd"))]
+    fn pack_candidates(&mut self) {
+        let blocks = self.candidates.chunks(PRUNING_BLOCK);
+        self.scratch
+            .packed
+            .resize_with(blocks.len(), || swar::PackedCodes::new(&[]));
+        for (packed, block) in self.scratch.packed.iter_mut().zip(blocks) {
+            packed.repack(block);
+        }
+    }
+
+    /// Counts how many of the `codes` would give each possible feedback to `guess`.
+    fn partition(&self, codes: &[Guess<FIELDS>], guess: Guess<FIELDS>) -> [u32; PARTITIONS] {
+        #[cfg(feature = "simd")]
+        if !self.rules.duplicates {
+            return simd::partition(codes, guess);
+        }
+        let mut counts = [0; PARTITIONS];
+        for code in codes.iter() {
+            let result = self.rules.evaluate(*code, guess);
+            let index = result.to_u32() as usize;
+            counts[index] += 1;
+        }
+        counts
+    }
+
+    /// Counts the feedback in `row` of the table for the codes with the given indices.
+    fn partition_indexed(row: &[u8], indices: &[u32]) -> [u32; PARTITIONS] {
+        let mut counts = [0; PARTITIONS];
+        for index in indices {
+            counts[row[*index as usize] as usize] += 1;
+        }
+        counts
+    }
+
+    /// Adds up the partitions of the `blocks` of candidates, but gives up once the guess
+    /// can no longer be better than the `best` score so far.
+    fn partition_pruned<B>(
+        &self,
+        blocks: impl Iterator<Item = B>,
+        partition: impl Fn(B) -> [u32; PARTITIONS],
+        total: usize,
+        best: &AtomicU64,
+    ) -> Option<[u32; PARTITIONS]> {
+        let mut counts = [0; PARTITIONS];
+        let mut seen = 0;
+        for block in blocks {
+            for (count, block_count) in counts.iter_mut().zip(partition(block)) {
+                *count += block_count;
+                seen += block_count as usize;
+            }
+            // a single candidate gets the bonus for winning, which the bound doesn't know of
+            let best = from_ordered_bits(best.load(atomic::Ordering::Relaxed));
+            if seen < total
+                && total > 1
+                && self.score_bound(&counts, total) + PRUNING_EPSILON < best
+            {
+                return None;
+            }
+        }
+        Some(counts)
+    }
+
+    /// An upper bound for the score of a guess whose partition of the first candidates is
+    /// `counts`, out of `total` candidates.
+    fn score_bound(&self, counts: &[u32; PARTITIONS], total: usize) -> f64 {
+        if self.scoring.is_some() {
+            return f64::INFINITY;
+        }
+        match self.strategy {
+            Strategy::Entropy => self.information_bound(counts, total),
+            // the largest partition can only grow
+            Strategy::Knuth => {
+                (total - counts.iter().max().copied().unwrap_or(0) as usize) as f64 + POSSIBLE_BONUS
+            }
+            // every remaining candidate could open a partition of its own
+            Strategy::MostParts => {
+                let remaining = total - counts.iter().sum::<u32>() as usize;
+                let partitions = counts.iter().filter(|count| **count > 0).count();
+                (partitions + remaining.min(PARTITIONS - partitions)) as f64 + POSSIBLE_BONUS
+            }
+        }
+    }
+
+    /// An upper bound for the information of a guess whose partition of the first candidates
+    /// is `counts`, out of `total` candidates. As `x log x` is superadditive, the information
+    /// is largest if the remaining candidates are spread evenly over all partitions.
+    fn information_bound(&self, counts: &[u32; PARTITIONS], total: usize) -> f64 {
+        let remaining = total - counts.iter().sum::<u32>() as usize;
+        // `remaining log2 (remaining / PARTITIONS)`
+        let spread = self.n_log2_n(remaining) - remaining as f64 * (PARTITIONS as f64).log2();
+        let seen: f64 = counts
+            .iter()
+            .map(|count| self.n_log2_n(*count as usize))
+            .sum();
+        (self.n_log2_n(total) - seen - spread) / total as f64
+    }
+
+    /// The best of the scored guesses, see `better` for which one if several are equally
+    /// good. Every thread only keeps its best guess so far, which are then compared with
+    /// each other.
+    fn best(
+        &self,
+        scored: impl ParallelIterator<Item = (Guess<FIELDS>, f64)>,
+    ) -> (Guess<FIELDS>, f64) {
+        let better = |a, b| self.better(a, b);
+        scored
+            .fold(
+                || None,
+                |best, guess| Some(best.map_or(guess, |best| better(best, guess))),
+            )
+            .reduce(
+                || None,
+                |a, b| match (a, b) {
+                    (Some(a), Some(b)) => Some(better(a, b)),
+                    (a, b) => a.or(b),
+                },
+            )
+            .expect("there is at least one guess")
+    }
+
+    /// The better of two scored guesses, `b` if they are equally good. Knuth's strategy
+    /// takes the first of equally good guesses instead, as Knuth did, unless a scoring
+    /// function replaces it, and so do scripts which ask for it.
+    fn better(&self, a: (Guess<FIELDS>, f64), b: (Guess<FIELDS>, f64)) -> (Guess<FIELDS>, f64) {
+        match a.1.partial_cmp(&b.1).unwrap_or(Ordering::Greater) {
+            Ordering::Greater => a,
+            Ordering::Equal
+                if self.first_of_ties
+                    || (self.strategy == Strategy::Knuth && self.scoring.is_none()) =>
+            {
+                a
+            }
+            _ => b,
+        }
+    }
+
+    /// Scores the guess and raises the `best` score so far if it is better.
+    fn score_best(
+        &self,
+        guess: Guess<FIELDS>,
+        counts: &[u32; PARTITIONS],
+        best: &AtomicU64,
+        progress: &Progress,
+    ) -> (Guess<FIELDS>, f64) {
+        let scored = self.score(guess, counts);
+        let bits = ordered_bits(scored.1);
+        if bits > best.load(atomic::Ordering::Relaxed)
+            && best.fetch_max(bits, atomic::Ordering::Relaxed) < bits
+        {
+            if let Some(observer) = &self.observer {
+                observer(guess, scored.1);
+            }
+            progress.set_best(guess, scored.1);
+        }
+        scored
+    }
+
+    /// Scores the guess by its partition of the candidates according to the strategy or the
+    /// scoring function, higher is better.
+    fn score(&self, guess: Guess<FIELDS>, counts: &[u32; PARTITIONS]) -> (Guess<FIELDS>, f64) {
+        let total = counts.iter().sum::<u32>() as usize;
+        let score = match (&self.scoring, self.strategy) {
+            (Some(scoring), _) => scoring(counts, total),
+            // the same as `strategy::entropy` with the logarithms looked up
+            (None, Strategy::Entropy) => self.information(counts),
+            (None, Strategy::Knuth) => minimax::<FIELDS, PARTITIONS>(counts, total),
+            (None, Strategy::MostParts) => most_parts::<FIELDS, PARTITIONS>(counts, total),
+        };
+        // scoring functions may fail to give a number, which makes the guess the worst one
+        let score = if score.is_nan() {
+            f64::NEG_INFINITY
+        } else {
+            score
+        };
+        trace!("guess: {guess} | {score:?}, {} winning", counts[FIELDS]);
+        (guess, score)
+    }
+
+    /// The expected information of a guess given its partition of the candidates.
+    /// A guess which is certain to win is preferred over every other guess.
+    fn information(&self, counts: &[u32; PARTITIONS]) -> f64 {
+        let sum: u32 = counts.iter().sum();
+        let mut information = self.entropy(counts);
+        if counts[FIELDS] == 1 && sum == 1 {
+            information += PARTITIONS as f64 - 1.;
+        }
+        information
+    }
+
+    /// The entropy of the partition of the candidates in bit, which is the information the
+    /// guess is expected to give.
+    fn entropy(&self, counts: &[u32; PARTITIONS]) -> f64 {
+        let sum: u32 = counts.iter().sum();
+        if sum == 0 {
+            return 0.;
+        }
+        // the sum of `-p log2 p` over the partitions with `p = count / sum`, written so that
+        // the logarithms only depend on the counts
+        let partitions: f64 = counts
+            .iter()
+            .map(|count| self.n_log2_n(*count as usize))
+            .sum();
+        (self.n_log2_n(sum as usize) - partitions) / sum as f64
+    }
+
+    /// The `n` best guesses for the candidates of the last call to `guess`, best first.
+    fn suggestions(&self, n: usize) -> Vec<Suggestion<FIELDS>> {
+        let mut scores = Vec::new();
+        self.for_each_guess_chunk(|guesses| {
+            scores.par_extend(guesses.par_iter().map(|guess| {
+                let counts = self.partition(&self.candidates, *guess);
+                (*guess, self.information(&counts), counts)
+            }));
+            // the sort is stable, so equally good guesses stay in the order of the chunks
+            scores.par_sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Less));
+            scores.truncate(n);
+        });
+        scores
+            .into_iter()
+            .map(|(guess, score, counts)| Suggestion {
+                guess,
+                score,
+                worst_case: counts.into_iter().max().unwrap_or(0),
+                possible: self.candidates.contains(&guess),
+            })
+            .collect()
+    }
+
+    /// Every canonical opening, see `opening`, scored by the measure of every strategy and
+    /// ranked by the expected information. With colors missing every guess is an opening of
+    /// its own.
+    pub fn opening_scores(&mut self) -> Vec<OpeningScores<FIELDS>> {
+        self.update_candidates(&[]);
+        let mut openings = vec![];
+        let complete = self.rules.missing_colors == 0;
+        self.for_each_guess_chunk(|guesses| {
+            openings.extend(
+                guesses
+                    .iter()
+                    .copied()
+                    .filter(|guess| !complete || guess.is_canonical()),
+            )
+        });
+        self.extend_n_log2_n();
+        let total = self.candidates.len() as f64;
+        let mut scores: Vec<_> = openings
+            .par_iter()
+            .map(|guess| {
+                let counts = self.partition(&self.candidates, *guess);
+                OpeningScores {
+                    guess: *guess,
+                    information: self.entropy(&counts),
+                    worst_case: counts.iter().max().copied().unwrap_or(0),
+                    expected_size: counts
+                        .iter()
+                        .map(|count| (*count as f64).powi(2))
+                        .sum::<f64>()
+                        / total,
+                    partitions: counts.iter().filter(|count| **count > 0).count(),
+                }
+            })
+            .collect();
+        scores.sort_by(|a, b| b.information.total_cmp(&a.information));
+        scores
+    }
+
+    /// Restricts the candidates to the codes for which all `facts` hold from the next guess
+    /// on.
+    fn set_facts(&mut self, facts: Vec<Fact>) {
+        self.facts = facts;
+        self.filtered_by = None;
+    }
+
+    /// Brings the candidates up to date with `history`. If it continues the history they
+    /// were filtered by before, only the new entries have to be checked.
+    fn update_candidates(&mut self, history: &[Entry<FIELDS>]) {
+        let new_entries = match &self.filtered_by {
+            Some(filtered_by) if history.starts_with(filtered_by) => &history[filtered_by.len()..],
+            _ => {
+                let facts = &self.facts;
+                let holds = |code: &Guess<FIELDS>| facts.iter().all(|fact| fact.holds(*code));
+                match &self.table {
+                    Some(table) => {
+                        self.indices.clear();
+                        self.indices.extend(
+                            (0..table.codes().len() as u32)
+                                .filter(|index| holds(&table.codes()[*index as usize])),
+                        );
+                    }
+                    None => {
+                        self.candidates.clear();
+                        self.candidates
+                            .extend(self.rules.codes::<FIELDS, COLORS>().filter(holds));
+                    }
+                }
+                history
+            }
+        };
+        let rules = self.rules;
+        match &self.table {
+            Some(table) => {
+                let consistent = &mut self.scratch.indices;
+                Self::consistent_indices(rules, table, &self.indices, new_entries, consistent);
+                std::mem::swap(&mut self.indices, consistent);
+                self.candidates.clear();
+                self.candidates.extend(
+                    self.indices
+                        .iter()
+                        .map(|index| table.codes()[*index as usize]),
+                );
+            }
+            None => {
+                // extending by a filtered parallel iterator keeps the order of the codes
+                let consistent = &mut self.scratch.candidates;
+                consistent.clear();
+                consistent.par_extend(
+                    self.candidates
+                        .par_iter()
+                        .copied()
+                        .filter(|code| Self::code_is_valid(rules, new_entries, *code)),
+                );
+                std::mem::swap(&mut self.candidates, consistent);
+            }
+        }
+        let filtered_by = self.filtered_by.get_or_insert_with(Vec::new);
+        filtered_by.clear();
+        filtered_by.extend_from_slice(history);
+    }
+
+    fn code_is_valid(
+        rules: Rules,
+        history: &[Entry<FIELDS>],
+        current_guess: Guess<FIELDS>,
+    ) -> bool {
+        for entry in history {
+            debug_assert!(
+                entry.evaluation.correct_color + entry.evaluation.exact <= FIELDS as u32,
+                "The provided evaluation was not valid"
+            );
+            if !(rules.evaluate(current_guess, entry.guess) == entry.evaluation) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Replaces `consistent` with the `indices` of codes in `table` which are consistent
+    /// with the history.
+    fn consistent_indices(
+        rules: Rules,
+        table: &EvaluationTable<FIELDS>,
+        indices: &[u32],
+        history: &[Entry<FIELDS>],
+        consistent: &mut Vec<u32>,
+    ) {
+        let rows: Vec<_> = history
+            .iter()
+            .map(|entry| {
+                let feedback = entry.evaluation.to_u32() as u8;
+                (
+                    entry,
+                    table
+                        .guess_index(&entry.guess)
+                        .map(|index| table.row(index)),
+                    feedback,
+                )
+            })
+            .collect();
+        consistent.clear();
+        consistent.par_extend(indices.par_iter().copied().filter(|index| {
+            let index = *index as usize;
+            rows.iter().all(|(entry, row, feedback)| match row {
+                Some(row) => row[index] == *feedback,
+                None => rules.evaluate(table.codes()[index], entry.guess) == entry.evaluation,
+            })
+        }));
+    }
+}
+
+/// Estimates the number of guesses a game takes from a guess on, the guess included, by how
+/// it splits the `candidates`: `partitions` are the numbers of codes which give each feedback
+/// but the winning one. Finding one of several codes takes at least two more guesses, and
+/// every further guess is assumed to give as much `information` as this one, but at least
+/// a bit. Over the positions of classic Mastermind this is off by 0.01 guesses on average
+/// and by 0.4 at most.
+pub fn expected_guesses(
+    candidates: usize,
+    partitions: impl IntoIterator<Item = usize>,
+    information: f64,
+) -> f64 {
+    let information = information.max(1.);
+    let guesses: f64 = partitions
+        .into_iter()
+        .map(|codes| match codes {
+            0 | 1 => codes as f64,
+            _ => codes as f64 * (1. + ((codes as f64).log2() / information).max(1.)),
+        })
+        .sum();
+    1. + guesses / candidates.max(1) as f64
+}
+
+/// `n log2 n`, which is zero for `n = 0` like its limit.
+fn compute_n_log2_n(n: usize) -> f64 {
+    if n == 0 {
+        0.
+    } else {
+        n as f64 * (n as f64).log2()
+    }
+}
+
+/// The bits of `score` as a number which is ordered like the scores, negative ones
+/// included, so that the best score so far can be kept in an `AtomicU64`. Zero is below
+/// every score but NaN and is read back as NaN.
+fn ordered_bits(score: f64) -> u64 {
+    let bits = score.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | 1 << 63
+    }
+}
+
+/// The score whose bits are ordered as `bits`, see `ordered_bits`.
+fn from_ordered_bits(bits: u64) -> f64 {
+    f64::from_bits(if bits >> 63 == 1 {
+        bits & !(1 << 63)
+    } else {
+        !bits
+    })
+}
+
+/// Runs `f` on a thread pool of its own with the given number of threads, or on the global
+/// pool if `threads` is `None`. The solver only uses the pool it is called on, so this bounds
+/// its CPU usage.
+pub fn with_threads<R: Send>(
+    threads: Option<usize>,
+    f: impl FnOnce() -> R + Send,
+) -> Result<R, String> {
+    match threads {
+        Some(threads) => {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_err(|err| format!("can't start {threads} threads: {err}"))?;
+            Ok(pool.install(f))
+        }
+        None => Ok(f()),
+    }
+}
+
+/// Runs the command line program with the arguments of the process.
+pub fn run() {
+    let cli = match cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    log::set_max_level(log::Level::from_verbosity(cli.verbosity));
+    progress::set_format(cli.progress);
+    // progress in JSON is asked for by programs, which want it even if stderr isn't a terminal
+    progress::set_enabled(
+        cli.progress == progress::Format::Json || progress::should_enable(cli.verbosity < 0),
+    );
+    let config = match config::load(cli.config) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: invalid configuration: {err}");
+            std::process::exit(2);
+        }
+    };
+    // a resumed or replayed game is played with the preset and the palette it was saved with
+    let resumed = match &cli.command {
+        Command::Interactive {
+            resume: Some(path), ..
+        }
+        | Command::Replay { transcript: path } => match save::SavedGame::read(path) {
+            Ok(saved) => Some(saved),
+            Err(err) => {
+                eprintln!("error: {err}");
+                std::process::exit(2);
+            }
+        },
+        _ => None,
+    };
+    let preset = resumed
+        .as_ref()
+        .map(|saved| saved.preset)
+        .or(cli.preset)
+        .or(config.preset)
+        .unwrap_or_default();
+    let rules = preset.rules();
+    let names = match (&resumed, cli.locale, config.palette) {
+        (Some(saved), _, _) => saved.palette.clone(),
+        (None, None, Some(palette)) => palette,
+        (None, locale, _) => locale
+            .or(config.locale)
+            .unwrap_or_default()
+            .names()
+            .map(String::from)
+            .to_vec(),
+    };
+    if names.len() < preset.colors() as usize {
+        eprintln!(
+            "error: the palette has {} colors but the {} preset needs {}",
+            names.len(),
+            preset.name(),
+            preset.colors()
+        );
+        std::process::exit(2);
+    }
+    palette::set_palette(names).expect("palette is only set once");
+    let rules = match cli.missing_colors.or(config.missing_colors) {
+        Some(names) => {
+            let missing_colors = color_mask(&names, preset.colors()).unwrap_or_else(|err| {
+                eprintln!("error: invalid missing colors: {err}");
+                std::process::exit(2);
+            });
+            // guesses without duplicates need a color for every field
+            let needed = if cfg!(feature = "laura") && !rules.duplicates {
+                preset.fields()
+            } else {
+                1
+            };
+            if ((preset.colors() - missing_colors.count_ones()) as usize) < needed {
+                eprintln!("error: no guess can be played without the missing colors");
+                std::process::exit(2);
+            }
+            Rules {
+                missing_colors,
+                ..rules
+            }
+        }
+        None => rules,
+    };
+    let mut options = SolverOptions {
+        strategy: cli.strategy.or(config.strategy).unwrap_or_default(),
+        ..SolverOptions::default()
+    };
+    if let Some(text) = &cli.first_guess {
+        match with_preset!(preset, parse_first_guess(rules, text)) {
+            Ok(colors) => options.first_guess = Some(colors),
+            Err(err) => {
+                eprintln!("error: invalid first guess: {err}");
+                std::process::exit(2);
+            }
+        }
+    }
+    if let Some(path) = &cli.policy {
+        let inputs = Policy::inputs_for(preset.fields(), preset.colors());
+        match Policy::read(path) {
+            Ok(policy) if policy.inputs() == inputs => options.policy = Some(Arc::new(policy)),
+            Ok(policy) => {
+                eprintln!(
+                    "error: the policy takes {} inputs but the {} preset has {inputs}",
+                    policy.inputs(),
+                    preset.name()
+                );
+                std::process::exit(2);
+            }
+            Err(err) => {
+                eprintln!("error: invalid policy: {err}");
+                std::process::exit(2);
+            }
+        }
+    }
+    if let Some(path) = &cli.script {
+        match script::Script::read(path) {
+            Ok(script) => options.script = Some(Arc::new(script)),
+            Err(err) => {
+                eprintln!("error: invalid script: {err}");
+                std::process::exit(2);
+            }
+        }
+    }
+    palette::set_colored_output(cli.color.enabled());
+    cache::set_directory(cache::default_directory());
+    store::set_path(store::default_path());
+    let threads = cli.threads.or(config.threads);
+    let output = cli.output;
+    let result = with_threads(threads, move || match cli.command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+        Command::ListPresets => {
+            commands::list_presets(output);
+            Ok(())
+        }
+        Command::Interactive { handicap, .. } if output == Output::Porcelain => {
+            with_preset!(
+                preset,
+                interactive::porcelain(rules, &options, resumed, handicap)
+            )
+        }
+        Command::Interactive { .. }
+        | Command::Replay { .. }
+        | Command::Tui
+        | Command::Codemaker { .. }
+        | Command::Coach { .. }
+        | Command::Hotseat { .. }
+        | Command::Match { .. }
+            if output != Output::Text =>
+        {
+            Err("interactive modes only support text output".to_string())
+        }
+        Command::Interactive {
+            transcript,
+            handicap,
+            ..
+        } => {
+            with_preset!(
+                preset,
+                interactive::run(rules, &options, resumed, transcript, handicap)
+            )
+        }
+        Command::Replay { .. } => {
+            let transcript = resumed.expect("transcripts are read before the palette is set");
+            let step = std::io::stdin().is_terminal();
+            with_preset!(
+                preset,
+                transcript::replay(rules, &options, transcript, step)
+            )
+        }
+        Command::Tui => with_preset!(preset, tui::run(rules, &options)),
+        Command::Codemaker {
+            secret,
+            max_turns,
+            difficulty,
+        } => {
+            with_preset!(
+                preset,
+                interactive::codemaker(rules, &options, secret, max_turns, difficulty)
+            )
+        }
+        Command::Coach { secret, max_turns } => {
+            with_preset!(
+                preset,
+                interactive::coach(rules, &options, secret, max_turns)
+            )
+        }
+        Command::Hotseat { max_turns } => {
+            with_preset!(preset, interactive::hotseat(rules, &options, max_turns));
+            Ok(())
+        }
+        Command::Match {
+            rounds,
+            max_turns,
+            handicap,
+        } => {
+            with_preset!(
+                preset,
+                interactive::play_match(rules, &options, rounds, max_turns, handicap)
+            );
+            Ok(())
+        }
+        Command::Autoplay {
+            secret,
+            transcript,
+            max_turns,
+            explain,
+        } => {
+            with_preset!(
+                preset,
+                commands::autoplay(rules, &options, secret, transcript, max_turns, explain, output)
+            )
+        }
+        Command::Solve {
+            history,
+            book,
+            explain,
+            facts,
+        } => {
+            with_preset!(
+                preset,
+                commands::solve(rules, &options, history, book, explain, &facts, output)
+            )
+        }
+        Command::Arbiter {
+            engines,
+            games,
+            seed,
+            time,
+            max_turns,
+            results,
+        } => with_preset!(
+            preset,
+            arbiter::run(rules, &options, &engines, games, seed, time, max_turns, results, output)
+        ),
+        Command::Ratings { results } => ratings::run(results, output),
+        Command::Simulate {
+            compare: None,
+            csv,
+            sample,
+            ..
+        } => {
+            with_preset!(
+                preset,
+                commands::simulate(rules, &options, csv, sample, output)
+            )
+        }
+        Command::Simulate {
+            compare: Some(contenders),
+            plugins,
+            csv,
+            sample,
+        } => {
+            with_preset!(
+                preset,
+                commands::compare(rules, &contenders, &plugins, csv, sample, output)
+            )
+        }
+        Command::GenerateDataset { .. } if output != Output::Text => {
+            Err("the dataset is only written as comma separated values".to_string())
+        }
+        Command::GenerateDataset { sample, out } => {
+            with_preset!(preset, dataset::run(rules, &options, sample, out))
+        }
+        Command::Tree { out, format } => {
+            with_preset!(preset, commands::tree(rules, &options, out, format, output))
+        }
+        Command::Puzzle { .. } if output != Output::Text => {
+            Err("puzzles are only written as text".to_string())
+        }
+        Command::Puzzle {
+            candidates,
+            count,
+            seed,
+        } => with_preset!(preset, puzzle::run(rules, candidates, count, seed)),
+        Command::Verify => commands::verify(output),
+        Command::Stats => store::stats(store::path(), output),
+        Command::Serve { port } => with_preset!(preset, server::serve(rules, &options, port)),
+        Command::Analyze { openings: false } => {
+            with_preset!(preset, commands::analyze(rules, &options, output));
+            Ok(())
+        }
+        Command::Analyze { openings: true } => {
+            with_preset!(preset, commands::rank_openings(rules, &options, output));
+            Ok(())
+        }
+        Command::Bench { iterations } => {
+            with_preset!(preset, commands::bench(rules, &options, iterations, output));
+            Ok(())
+        }
+    })
+    .and_then(|result| result);
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dummy_guesser() {
+        let report = DummyGuesser.guess(&[]).unwrap();
+        assert_eq!(report.guess.colors(), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn evaluate_guess() {
+        let code = Guess::new([1, 2, 3, 4]);
+        let guess = Guess::new([1, 3, 3, 5]);
+        let result = evaluate(code, guess);
+        assert_eq!(
+            result,
+            Evaluation {
+                correct_color: 1,
+                exact: 2
+            }
+        );
+    }
+
+    #[test]
+    fn evaluate_guess_six_element_guess() {
+        let code = Guess::new([1, 2, 3, 4, 6, 7]);
+        let guess = Guess::new([1, 3, 6, 6, 6, 5]);
+        let result = evaluate(code, guess);
+        assert_eq!(
+            result,
+            Evaluation {
+                correct_color: 3,
+                exact: 2
+            }
+        );
+    }
+
+    #[test]
+    fn evaluate_guess_with_duplicates() {
+        let code = Guess::new([1, 2, 3, 4]);
+        let guess = Guess::new([1, 3, 3, 5]);
+        let result = evaluate_with_duplicates(code, guess);
+        assert_eq!(
+            result,
+            Evaluation {
+                correct_color: 0,
+                exact: 2
+            }
+        );
+        let code = Guess::new([1, 1, 2, 2]);
+        let guess = Guess::new([2, 1, 1, 3]);
+        let result = evaluate_with_duplicates(code, guess);
+        assert_eq!(
+            result,
+            Evaluation {
+                correct_color: 2,
+                exact: 1
+            }
+        );
+    }
+
+    #[test]
+    fn random_code_is_legal() {
+        let rules = Rules {
+            duplicates: false,
+            missing_colors: 0,
+        };
+        let mut rng = Rng::seeded(7);
+        for _ in 0..10 {
+            assert!(rules.is_legal_code::<4, 6>(&rules.random_code::<4, 6>(&mut rng)));
+        }
+        assert_eq!(
+            rules.random_code::<4, 6>(&mut Rng::seeded(42)),
+            rules.random_code::<4, 6>(&mut Rng::seeded(42))
+        );
+    }
+
+    #[test]
+    fn pack_guess() {
+        let mut guess = Guess::new([3, 2, 1, 0, 6, 5]);
+        assert_eq!(guess.colors(), [3, 2, 1, 0, 6, 5]);
+        assert_eq!(guess.get(4), 6);
+        guess.set(4, 15);
+        assert_eq!(guess.colors(), [3, 2, 1, 0, 15, 5]);
+        assert!(Guess::new([1, 0]).packed() < Guess::new([0, 1]).packed());
+        assert_eq!(format!("{guess:?}"), "Guess([3, 2, 1, 0, 15, 5])");
+        // a million codes of the 6×10 game take 8 MB instead of 24 MB as `[u32; 6]`
+        assert_eq!(std::mem::size_of::<Guess<6>>(), 8);
+    }
+
+    #[test]
+    fn canonical_guesses() {
+        let canonical: Vec<_> = GuessIterator::<4, 6>::default()
+            .filter(Guess::is_canonical)
+            .map(|guess| guess.indices())
+            .collect();
+        assert_eq!(
+            canonical,
+            ["0 0 0 0", "0 0 0 1", "0 0 1 1", "0 0 1 2", "0 1 2 3"]
+        );
+        assert_eq!(
+            GuessIterator::<4, 3>::default()
+                .filter(Guess::is_canonical)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn opening_is_as_good_as_any_guess() {
+        for rules in [
+            Rules {
+                duplicates: false,
+                missing_colors: 0,
+            },
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+        ] {
+            let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+            let report = guesser.guess(&[]).unwrap();
+            let best = guesser.suggestions(1)[0].score;
+            assert!((report.score - best).abs() < 1e-9);
+            assert!(report.scored < rules.guess_count::<4, 6>());
+            let partitions: std::collections::HashSet<_> = rules
+                .codes::<4, 6>()
+                .map(|code| rules.evaluate(code, report.guess).to_u32())
+                .collect();
+            assert_eq!(report.partitions, partitions.len());
+        }
+    }
+
+    #[test]
+    fn score_bound_holds_for_every_prefix() {
+        let codes: Vec<_> = CodeIterator::<4, 6>::default().collect();
+        for strategy in Strategy::ALL {
+            let guesser = SimpleGuesser::<4, 6, { max_gauss(4) }> {
+                strategy,
+                ..Default::default()
+            };
+            for guess in GuessIterator::<4, 6>::default().step_by(13) {
+                let mut counts = [0; max_gauss(4)];
+                let mut bounds = vec![];
+                for code in &codes {
+                    bounds.push(guesser.score_bound(&counts, codes.len()));
+                    counts[evaluate(*code, guess).to_u32() as usize] += 1;
+                }
+                let (_, score) = guesser.score(guess, &counts);
+                assert!(bounds.iter().all(|bound| *bound + 1e-12 >= score));
+            }
+        }
+    }
+
+    #[test]
+    fn score_by_strategy() {
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::default();
+        let guess = Guess::new([0, 0, 1, 1]);
+        // 2 codes give one feedback, 5 another one and 1 code is the guess
+        let mut counts = [0; max_gauss(4)];
+        counts[0] = 2;
+        counts[1] = 5;
+        counts[4] = 1;
+        guesser.strategy = Strategy::Knuth;
+        assert_eq!(guesser.score(guess, &counts).1, 3. + POSSIBLE_BONUS);
+        guesser.strategy = Strategy::MostParts;
+        assert_eq!(guesser.score(guess, &counts).1, 3. + POSSIBLE_BONUS);
+        counts[4] = 0;
+        assert_eq!(guesser.score(guess, &counts).1, 2.);
+    }
+
+    #[test]
+    fn order_scores_by_bits() {
+        let scores = [
+            f64::NEG_INFINITY,
+            -3.5,
+            -1e-300,
+            0.,
+            1e-300,
+            2.,
+            f64::INFINITY,
+        ];
+        for pair in scores.windows(2) {
+            assert!(ordered_bits(pair[0]) < ordered_bits(pair[1]), "{pair:?}");
+        }
+        for score in scores {
+            assert!(ordered_bits(score) > 0);
+            assert_eq!(from_ordered_bits(ordered_bits(score)), score);
+        }
+        assert!(from_ordered_bits(0).is_nan());
+    }
+
+    #[test]
+    fn custom_scoring() {
+        let rules = Preset::Classic.rules();
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        // the fewest candidates left on average, which no strategy of the solver plays for
+        guesser.scoring = Some(Arc::new(|counts: &[u32; max_gauss(4)], total| {
+            let squares: f64 = counts.iter().map(|count| (*count as f64).powi(2)).sum();
+            total as f64 - squares / total as f64
+        }));
+        for code in rules.codes::<4, 6>().step_by(97) {
+            let mut history = vec![];
+            loop {
+                let report = guesser.guess(&history).unwrap();
+                if report.guess == code {
+                    break;
+                }
+                history.push(Entry {
+                    guess: report.guess,
+                    evaluation: rules.evaluate(code, report.guess),
+                });
+                assert!(history.len() < 8, "{code}");
+            }
+        }
+        // the built-in scoring functions play the guesses of the strategies
+        let mut entropy = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        entropy.scoring = Some(Strategy::Entropy.scoring::<4, { max_gauss(4) }>());
+        let mut default = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let code = Guess::new([3, 1, 1, 5]);
+        let mut history = vec![];
+        loop {
+            let report = entropy.guess(&history).unwrap();
+            assert_eq!(report.guess, default.guess(&history).unwrap().guess);
+            if !history.is_empty() && report.candidates > 1 {
+                // no guess is pruned
+                assert_eq!(report.scored, rules.guess_count::<4, 6>());
+            }
+            if report.guess == code {
+                break;
+            }
+            history.push(Entry {
+                guess: report.guess,
+                evaluation: rules.evaluate(code, report.guess),
+            });
+        }
+    }
+
+    #[test]
+    fn information_matches_entropy() {
+        let guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::default();
+        let mut counts = [0; max_gauss(4)];
+        counts[..5].copy_from_slice(&[3, 0, 12, 1, 7]);
+        let sum = 23.;
+        let entropy: f64 = counts
+            .iter()
+            .filter(|count| **count > 0)
+            .map(|count| -(*count as f64 / sum) * (*count as f64 / sum).log2())
+            .sum();
+        assert!((guesser.information(&counts) - entropy).abs() < 1e-12);
+        // counts beyond the table are computed
+        assert_eq!(
+            guesser.n_log2_n(N_LOG2_N_ENTRIES + 1),
+            compute_n_log2_n(N_LOG2_N_ENTRIES + 1)
+        );
+        assert_eq!(guesser.information(&[0; max_gauss(4)]), 0.);
+        // the table is extended to the number of candidates before scoring
+        let mut guesser = guesser;
+        guesser.guess(&[]).unwrap();
+        assert_eq!(
+            guesser.scratch.n_log2_n.len(),
+            guesser.rules.code_count::<4, 6>() + 1
+        );
+    }
+
+    #[test]
+    fn stream_guesses_in_chunks() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let guesser = SimpleGuesser::<4, 6, { max_gauss(4) }> {
+            rules,
+            ..Default::default()
+        };
+        let mut chunks = 0;
+        let mut guesses = vec![];
+        guesser.for_each_guess_chunk(|chunk| {
+            chunks += 1;
+            guesses.extend_from_slice(chunk);
+        });
+        assert!(chunks > 1);
+        assert_eq!(guesses, rules.guesses::<4, 6>().collect::<Vec<_>>());
+        assert!(guesser.guesses.get().is_none());
+    }
+
+    #[test]
+    fn play_all_matches_playing_every_game() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::new(rules);
+        let games = commands::play_all(&mut guesser, rules.codes::<3, 4>());
+        let codes: Vec<_> = rules.codes::<3, 4>().collect();
+        assert_eq!(games.len(), codes.len());
+        for (game, expected) in games.into_iter().zip(codes) {
+            assert_eq!(game.code, expected);
+            // everything but the times
+            let audit = |turn: &commands::Turn<3>| {
+                (turn.guess, turn.candidates, turn.information, turn.gained)
+            };
+            let guesses: Vec<_> = game.turns.iter().map(audit).collect();
+            let played: Vec<_> = commands::play(&mut guesser, game.code, usize::MAX, |_| {})
+                .iter()
+                .map(audit)
+                .collect();
+            assert_eq!(guesses, played);
+        }
+    }
+
+    #[test]
+    fn estimate_expected_guesses() {
+        // the guess is the last candidate, or one of two
+        assert_eq!(expected_guesses(1, [], 0.), 1.);
+        assert_eq!(expected_guesses(2, [1], 1.), 1.5);
+        // two codes left take two more guesses
+        assert_eq!(expected_guesses(4, [2, 2], 1.), 3.);
+
+        // the solver needs 4.64 guesses on average for classic Mastermind
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let report = guesser.guess(&[]).unwrap();
+        assert!((4.3..5.).contains(&report.expected_guesses));
+        let games = commands::play_all(&mut guesser, rules.codes::<4, 6>());
+        let average =
+            games.iter().map(|game| game.turns.len()).sum::<usize>() as f64 / games.len() as f64;
+        assert!((report.expected_guesses - average).abs() < 0.3);
+    }
+
+    #[test]
+    fn play_up_to_max_turns() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let code = Guess::new([3, 1, 1, 5]);
+        let turns = commands::play(&mut guesser, code, 10, |_| {});
+        assert_eq!(turns.last().unwrap().guess, code);
+        let limited = commands::play(&mut guesser, code, turns.len() - 1, |_| {});
+        assert_eq!(limited.len(), turns.len() - 1);
+        assert!(limited.iter().all(|turn| turn.guess != code));
+        assert!(commands::play(&mut guesser, code, 0, |_| {}).is_empty());
+    }
+
+    #[test]
+    fn open_with_the_first_guess() {
+        let rules = Preset::Classic.rules();
+        let parse = |rules, text| parse_first_guess::<4, 6, { max_gauss(4) }>(rules, text);
+        assert_eq!(parse(rules, "rot,blau,blau,5"), Ok(vec![0, 3, 3, 5]));
+        assert!(parse(rules, "rot,blau,blau").is_err());
+        assert!(parse(rules, "rot,blau,blau,6").is_err());
+        let incomplete = Rules {
+            missing_colors: 1 << 3,
+            ..rules
+        };
+        assert!(parse(incomplete, "rot,blau,blau,5").is_err());
+
+        let first = Guess::new([0, 3, 3, 5]);
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        guesser.first_guess = Some(first);
+        for code in [Guess::new([3, 1, 1, 5]), first] {
+            let turns = commands::play(&mut guesser, code, usize::MAX, |_| {});
+            assert_eq!(turns[0].guess, first);
+            assert_eq!(turns.last().unwrap().guess, code);
+        }
+        // the solver takes over from the second turn
+        let history = [Entry {
+            guess: first,
+            evaluation: rules.evaluate(Guess::new([3, 1, 1, 5]), first),
+        }];
+        let mut solver = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        assert_eq!(
+            guesser.guess(&history).unwrap().guess,
+            solver.guess(&history).unwrap().guess
+        );
+    }
+
+    #[test]
+    fn play_without_some_colors() {
+        for duplicates in [true, false] {
+            let rules = Rules {
+                duplicates,
+                missing_colors: 0b1010,
+            };
+            let guesses: Vec<_> = rules.guesses::<3, 5>().collect();
+            assert_eq!(guesses.len(), rules.guess_count::<3, 5>());
+            assert_eq!(
+                guesses.len(),
+                if cfg!(feature = "laura") && !duplicates {
+                    6
+                } else {
+                    27
+                }
+            );
+            assert!(guesses
+                .iter()
+                .all(|guess| !guess.colors().contains(&1) && !guess.colors().contains(&3)));
+            let code = Guess::new([3, 2, 1]);
+            assert!(!rules.is_legal_guess::<3, 5>(&code));
+            assert!(rules.is_legal_turn::<3, 5>(&code));
+
+            // the codes which only differ in missing colors are named one by one
+            let mut guesser = SimpleGuesser::<3, 5, { max_gauss(3) }>::new(rules);
+            let games = commands::play_all(&mut guesser, rules.codes::<3, 5>());
+            assert_eq!(games.len(), rules.code_count::<3, 5>());
+            for game in games {
+                let (last, turns) = game.turns.split_last().unwrap();
+                assert_eq!(last.guess, game.code);
+                assert!(turns
+                    .iter()
+                    .all(|turn| rules.is_legal_guess::<3, 5>(&turn.guess)
+                        || rules.is_legal_code::<3, 5>(&turn.guess)));
+            }
+        }
+    }
+
+    #[test]
+    fn narrow_down_by_facts() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let facts = vec![
+            Fact::Contains(0),
+            Fact::Is { field: 0, color: 2 },
+            Fact::IsNot { field: 1, color: 3 },
+        ];
+        let code = Guess::new([2, 1, 0, 3]);
+        // with and without an evaluation table
+        fn play<const COLORS: u32>(rules: Rules, facts: &[Fact], code: Guess<4>) {
+            let mut guesser = SimpleGuesser::<4, COLORS, { max_gauss(4) }>::new(rules);
+            guesser.set_facts(facts.to_vec());
+            let mut history = vec![];
+            loop {
+                let report = guesser.guess(&history).unwrap();
+                let expected: Vec<_> = rules
+                    .codes::<4, COLORS>()
+                    .filter(|code| facts.iter().all(|fact| fact.holds(*code)))
+                    .filter(|code| {
+                        history
+                            .iter()
+                            .all(|entry| rules.evaluate(*code, entry.guess) == entry.evaluation)
+                    })
+                    .collect();
+                assert_eq!(guesser.candidates(), expected);
+                if report.guess == code {
+                    break;
+                }
+                history.push(Entry {
+                    guess: report.guess,
+                    evaluation: rules.evaluate(code, report.guess),
+                });
+            }
+            guesser.set_facts(vec![Fact::Lacks(0)]);
+            assert!(guesser.guess(&history).is_err());
+        }
+        play::<6>(rules, &facts, code);
+        play::<10>(rules, &facts, code);
+    }
+
+    #[test]
+    fn chances_to_finish() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::new(rules);
+        let games = commands::play_all(&mut guesser, rules.codes::<3, 4>());
+        let within = |games: &[&commands::Game<3>], turns| {
+            games
+                .iter()
+                .filter(|game| game.turns.len() <= turns)
+                .count() as f64
+                / games.len() as f64
+        };
+        let all: Vec<_> = games.iter().collect();
+        let chances = commands::finish_chances(&mut guesser, &[], 6).unwrap();
+        assert_eq!(chances.len(), 6);
+        for (turn, chance) in chances.iter().enumerate() {
+            assert_eq!(*chance, within(&all, turn + 1));
+        }
+        assert_eq!(chances[0], 1. / 64.);
+        assert_eq!(chances.last(), Some(&1.));
+
+        // after the first turn of a game, the chances are those of the games it continues
+        let first = &games[40].turns[0];
+        let history = [Entry {
+            guess: first.guess,
+            evaluation: first.evaluation,
+        }];
+        let continued: Vec<_> = games
+            .iter()
+            .filter(|game| game.turns[0].evaluation == first.evaluation)
+            .collect();
+        let chances = commands::finish_chances(&mut guesser, &history, 3).unwrap();
+        for (turn, chance) in chances.iter().enumerate() {
+            assert_eq!(*chance, within(&continued, turn + 2));
+        }
+
+        let impossible = [0, 1].map(|exact| Entry {
+            guess: Guess::new([0, 0, 0]),
+            evaluation: Evaluation {
+                correct_color: 0,
+                exact,
+            },
+        });
+        assert_eq!(
+            commands::finish_chances(&mut guesser, &impossible, 3),
+            Err(InconsistentHistory)
+        );
+        assert_eq!(
+            commands::describe_chances(&[0.25, 0.5, 1.]),
+            "within 1, 2, 3 guesses: 25%, 50%, 100%"
+        );
+    }
+
+    #[test]
+    fn play_by_a_policy() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        // every guess is predicted to take as long, so the first one the rules allow wins
+        let inputs = Policy::inputs_for(3, 4);
+        let zeros = vec!["0"; inputs + 1].join(" ");
+        let policy = Policy::parse(&format!(
+            "mastermind-policy 1\ndense {inputs} 1 linear\n{zeros}\n"
+        ))
+        .unwrap();
+        let options = SolverOptions {
+            policy: Some(Arc::new(policy)),
+            ..SolverOptions::default()
+        };
+        let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::with_options(rules, &options);
+        let games = commands::play_all(&mut guesser, rules.codes::<3, 4>());
+        assert_eq!(games.len(), 64);
+        let first = Guess::new([0, 0, 0]);
+        assert!(games.iter().all(|game| game.turns[0].guess == first));
+        // once it tells nothing more, the solver's own guesses take over
+        assert!(games
+            .iter()
+            .all(|game| game.turns.last().unwrap().guess == game.code));
+        assert!(games
+            .iter()
+            .flat_map(|game| &game.turns[1..])
+            .any(|turn| turn.guess != first));
+    }
+
+    #[test]
+    fn play_a_sample_of_the_codes() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let sample = cli::Sample {
+            codes: 10,
+            seed: Some(1),
+        };
+        let codes = commands::simulated_codes::<3, 4>(rules, Some(&sample));
+        assert_eq!(
+            codes.iter().collect::<std::collections::HashSet<_>>().len(),
+            10
+        );
+        let everything = cli::Sample {
+            codes: 100,
+            seed: None,
+        };
+        assert_eq!(
+            commands::simulated_codes::<3, 4>(rules, Some(&everything)).len(),
+            64
+        );
+        // the games are the same as if every code was played, but the times
+        let audit = |game: &commands::Game<3>| {
+            let turns: Vec<_> = game
+                .turns
+                .iter()
+                .map(|turn| (turn.guess, turn.candidates, turn.gained))
+                .collect();
+            (game.code, turns)
+        };
+        let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::new(rules);
+        let all: Vec<_> = commands::play_all(&mut guesser, rules.codes::<3, 4>())
+            .iter()
+            .map(audit)
+            .collect();
+        for game in commands::play_all(&mut guesser, codes) {
+            assert!(all.contains(&audit(&game)));
+        }
+    }
+
+    #[test]
+    fn expected_information_is_gained_on_average() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let mut calibration = stats::Calibration::default();
+        for game in commands::play_all(&mut guesser, rules.codes::<4, 6>()) {
+            for (index, turn) in game.turns.iter().enumerate() {
+                calibration.add(index, turn.information, turn.gained);
+            }
+        }
+        for (_, expected, gained) in calibration.averages() {
+            assert!((expected - gained).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn score_openings_by_every_measure() {
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(Rules {
+            duplicates: true,
+            missing_colors: 0,
+        });
+        let openings = guesser.opening_scores();
+        let guesses: Vec<_> = openings.iter().map(|opening| opening.guess).collect();
+        assert_eq!(
+            guesses,
+            [
+                [0, 1, 2, 3],
+                [0, 0, 1, 2],
+                [0, 0, 1, 1],
+                [0, 0, 0, 1],
+                [0, 0, 0, 0]
+            ]
+            .map(Guess::new)
+        );
+        // Knuth's opening leaves at most 256 codes
+        assert_eq!(openings[2].worst_case, 256);
+        assert!(openings.iter().all(|opening| opening.worst_case >= 256));
+        assert!((openings[1].expected_size - 185.27).abs() < 0.01);
+        assert_eq!(openings[4].partitions, 5);
+    }
+
+    #[test]
+    fn reach_known_results() {
+        for check in commands::classic_checks() {
+            assert!(check.passed, "{check:?}");
+        }
+    }
+
+    #[test]
+    fn run_on_own_thread_pool() {
+        let threads = with_threads(Some(3), rayon::current_num_threads);
+        assert_eq!(threads, Ok(3));
+        let guess = with_threads(Some(1), || {
+            SimpleGuesser::<4, 6, { max_gauss(4) }>::new(Rules::default())
+                .guess(&[])
+                .unwrap()
+                .guess
+        });
+        assert!(guess.is_ok());
+    }
+
+    #[test]
+    fn filter_candidates_incrementally() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let code = Guess::new([0, 1, 1, 2]);
+        let with_table = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let without_table = SimpleGuesser::<4, 6, { max_gauss(4) }> {
+            rules,
+            ..Default::default()
+        };
+        for mut guesser in [with_table, without_table] {
+            let mut history = vec![];
+            for guess in [Guess::new([0, 0, 1, 1]), Guess::new([2, 3, 4, 5])] {
+                guesser.guess(&history).unwrap();
+                let evaluation = rules.evaluate(code, guess);
+                history.push(Entry { guess, evaluation });
+            }
+            guesser.guess(&history).unwrap();
+            let expected: Vec<_> = rules
+                .codes::<4, 6>()
+                .filter(|candidate| {
+                    history
+                        .iter()
+                        .all(|entry| rules.evaluate(*candidate, entry.guess) == entry.evaluation)
+                })
+                .collect();
+            assert_eq!(guesser.candidates(), expected);
+            // a history which doesn't continue the last one starts over
+            guesser.guess(&history[1..]).unwrap();
+            assert!(guesser.candidates().len() > expected.len());
+        }
+    }
+
+    #[test]
+    fn inconsistent_history() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let guess = Guess::new([0, 0, 1, 1]);
+        let mut history = vec![Entry {
+            guess,
+            evaluation: "0 0".parse().unwrap(),
+        }];
+        assert!(guesser.guess(&history).is_ok());
+        history.push(Entry {
+            guess,
+            evaluation: "1 0".parse().unwrap(),
+        });
+        assert_eq!(
+            guesser.guess(&history).map(|report| report.guess),
+            Err(InconsistentHistory)
+        );
+        assert!(guesser.candidates().is_empty());
+    }
+
+    #[test]
+    fn guess_indices() {
+        assert_eq!(Guess::new([3, 2, 1, 0, 6, 5]).indices(), "3 2 1 0 6 5");
+    }
+
+    #[test]
+    fn parse_guess() {
+        assert_eq!("rot, blau,gelb 7".parse(), Ok(Guess::new([0, 3, 2, 7])));
+        assert_eq!("Weiß grün".parse(), Ok(Guess::new([6, 1])));
+        assert_eq!(
+            "rot,lila".parse::<Guess<2>>(),
+            Err(ParseGuessError::UnknownColor("lila".to_string()))
+        );
+        assert_eq!(
+            "rot".parse::<Guess<2>>(),
+            Err(ParseGuessError::WrongLength {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn parse_evaluation() {
+        let expected = Evaluation::<4> {
+            correct_color: 1,
+            exact: 2,
+        };
+        for input in [
+            "2 1",
+            "2,1",
+            "2b1w",
+            "1w 2B",
+            "2r1w",
+            "●●○",
+            "● ○ ●",
+            "●●○·",
+        ] {
+            assert_eq!(input.parse(), Ok(expected), "{input}");
+        }
+        assert_eq!(
+            "3b".parse(),
+            Ok(Evaluation::<4> {
+                correct_color: 0,
+                exact: 3,
+            })
+        );
+        for input in ["", "2", "2 1 0", "b", "2b2b", "2b1", "zwei", "●x"] {
+            assert!(input.parse::<Evaluation<4>>().is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn display_evaluation() {
+        let evaluation: Evaluation<4> = "2 1".parse().unwrap();
+        assert_eq!(evaluation.to_string(), "●●○·");
+        assert_eq!(evaluation.to_string().parse(), Ok(evaluation));
+        let evaluation: Evaluation<3> = "0 0".parse().unwrap();
+        assert_eq!(evaluation.to_string(), "···");
+        assert_eq!(evaluation.to_string().parse(), Ok(evaluation));
+    }
+
+    #[test]
+    fn validate_evaluation() {
+        let evaluation: Evaluation<4> = "2 2".parse().unwrap();
+        assert!(evaluation.is_valid());
+        let too_many = Err(InvalidEvaluation::TooManyPegs { fields: 4 });
+        assert_eq!(Evaluation::<4>::new(3, 2), too_many);
+        assert_eq!(Evaluation::<4>::new(u32::MAX, 1), too_many);
+        assert_eq!(
+            "3 2".parse::<Evaluation<4>>(),
+            Err(ParseEvaluationError::Invalid(
+                InvalidEvaluation::TooManyPegs { fields: 4 }
+            ))
+        );
+        assert!("●●●○○".parse::<Evaluation<4>>().is_err());
+        assert!("5b".parse::<Evaluation<4>>().is_err());
+
+        let misplaced = "3 1".parse().unwrap();
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let guess = Guess::new([0, 0, 1, 2]);
+        assert_eq!(
+            rules.check_feedback(guess, misplaced),
+            Err(InvalidEvaluation::MisplacedLastColor)
+        );
+        assert_eq!(rules.check_feedback(guess, "2 2".parse().unwrap()), Ok(()));
+        // a repeated color is counted once for every peg without duplicates
+        let rules = Rules {
+            duplicates: false,
+            missing_colors: 0,
+        };
+        assert_eq!(rules.evaluate(Guess::new([0, 3, 1, 2]), guess), misplaced);
+        assert_eq!(rules.check_feedback(guess, misplaced), Ok(()));
+        assert!(rules
+            .check_feedback(Guess::new([0, 3, 1, 2]), misplaced)
+            .is_err());
+    }
+
+    #[test]
+    fn generate_guess_iterator() {
+        let mut iter = GuessIterator::<3, 4>::default();
+        assert_eq!(iter.next(), Some(Guess::new([0, 0, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([1, 0, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([2, 0, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([3, 0, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([0, 1, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([1, 1, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([2, 1, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([3, 1, 0])));
+        let mut iter = iter.skip(55);
+        assert_eq!(iter.next(), Some(Guess::new([3, 3, 3])));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_color_bitmask() {
+        assert!(NUM_COLORS as usize <= std::mem::size_of::<ColorBitmask>() * 8);
+    }
+
+    #[test]
+    fn test_color_fields() {
+        const { assert!(NUM_COLORS >= NUM_FIELDS) };
+    }
+
+    #[test]
+    fn generate_code_iterator() {
+        let mut iter = CodeIterator::<3, 4>::default();
+        assert_eq!(iter.next(), Some(Guess::new([2, 1, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([3, 1, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([1, 2, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([3, 2, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([1, 3, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([2, 3, 0])));
+        assert_eq!(iter.next(), Some(Guess::new([2, 0, 1])));
+    }
+
+    #[test]
+    fn jump_in_code_iterators() {
+        for rules in [
+            Rules {
+                duplicates: false,
+                missing_colors: 0,
+            },
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+        ] {
+            let codes: Vec<_> = rules.codes::<4, 6>().collect();
+            let previous: Vec<_> = GuessIterator::<4, 6>::default()
+                .filter(|code| rules.duplicates || code.is_valid_code())
+                .collect();
+            assert_eq!(codes, previous);
+            assert_eq!(codes.len(), rules.code_count::<4, 6>());
+            for (index, code) in codes.iter().enumerate() {
+                assert_eq!(rules.code_at::<4, 6>(index), *code);
+            }
+        }
+        assert!(CodeIterator::<4, 6>::default()
+            .zip(CodeIterator::<4, 6>::default().skip(1))
+            .all(|(code, next)| code.packed() < next.packed()));
+
+        let mut iter = CodeIterator::<3, 4>::default();
+        assert_eq!(iter.len(), 24);
+        assert_eq!(iter.nth(5), Some(Guess::new([2, 3, 0])));
+        assert_eq!(iter.next_back(), Some(Guess::new([1, 2, 3])));
+        assert_eq!(iter.nth_back(1), Some(Guess::new([2, 1, 3])));
+        assert_eq!(iter.len(), 15);
+        let mut reversed: Vec<_> = iter.clone().rev().collect();
+        reversed.reverse();
+        assert_eq!(iter.clone().collect::<Vec<_>>(), reversed);
+        assert_eq!(iter.nth(100), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter = GuessIterator::<3, 4>::default();
+        assert_eq!(iter.size_hint(), (64, Some(64)));
+        assert_eq!(iter.next_back(), Some(Guess::new([3, 3, 3])));
+        assert_eq!(iter.nth(4), Some(Guess::new([0, 1, 0])));
+        assert_eq!(iter.len(), 58);
+    }
+
+    /// Checks the iterators against the packed guesses with colors below `COLORS`, in order.
+    fn check_enumeration<const FIELDS: usize, const COLORS: u32>() {
+        let guesses: Vec<_> = (0..1u64 << (FIELDS * BITS_PER_FIELD))
+            .map(Guess::<FIELDS>::from_packed)
+            .filter(|guess| guess.colors().iter().all(|color| *color < COLORS))
+            .collect();
+        let codes: Vec<_> = guesses
+            .iter()
+            .copied()
+            .filter(Guess::is_valid_code)
+            .collect();
+        let all = GuessIterator::<FIELDS, COLORS>::default();
+        assert_eq!(all.len(), guesses.len(), "{FIELDS} fields, {COLORS} colors");
+        assert_eq!(all.collect::<Vec<_>>(), guesses);
+        let all = CodeIterator::<FIELDS, COLORS>::default();
+        assert_eq!(all.len(), codes.len(), "{FIELDS} fields, {COLORS} colors");
+        assert_eq!(all.rev().collect::<Vec<_>>(), {
+            let mut codes = codes.clone();
+            codes.reverse();
+            codes
+        });
+        for rules in [
+            Rules {
+                duplicates: false,
+                missing_colors: 0,
+            },
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+        ] {
+            assert_eq!(
+                rules.codes::<FIELDS, COLORS>().count(),
+                rules.code_count::<FIELDS, COLORS>()
+            );
+        }
+    }
+
+    #[test]
+    fn enumerate_small_games() {
+        check_enumeration::<1, 1>();
+        check_enumeration::<1, 2>();
+        check_enumeration::<1, 16>();
+        check_enumeration::<2, 1>();
+        check_enumeration::<2, 2>();
+        check_enumeration::<2, 3>();
+        check_enumeration::<2, 16>();
+        check_enumeration::<3, 1>();
+        check_enumeration::<3, 2>();
+        check_enumeration::<3, 3>();
+        check_enumeration::<3, 5>();
+        check_enumeration::<4, 3>();
+        check_enumeration::<4, 4>();
+        check_enumeration::<5, 2>();
+        check_enumeration::<5, 6>();
+    }
+
+    #[test]
+    fn evaluation_to_u32_one_zero() {
+        let evaluation: Evaluation<3> = Evaluation {
+            correct_color: 1,
+            exact: 0,
+        };
+        let result = evaluation.to_u32();
+        assert_eq!(result, 4);
+    }
+    #[test]
+    fn evaluation_to_u32_zero_zero() {
+        let evaluation: Evaluation<3> = Evaluation {
+            correct_color: 0,
+            exact: 0,
+        };
+        let result = evaluation.to_u32();
+        assert_eq!(result, 0);
+    }
+    #[test]
+    fn evaluation_to_u32_zero_one() {
+        let evaluation: Evaluation<3> = Evaluation {
+            correct_color: 0,
+            exact: 1,
+        };
+        let result = evaluation.to_u32();
+        assert_eq!(result, 1);
+    }
+    #[test]
+    fn evaluation_to_u32_one_two() {
+        let evaluation: Evaluation<3> = Evaluation {
+            correct_color: 1,
+            exact: 2,
+        };
+        let result = evaluation.to_u32();
+        assert_eq!(result, 6);
+    }
+    #[test]
+    fn evaluation_to_u32_two_one() {
+        let evaluation: Evaluation<3> = Evaluation {
+            correct_color: 2,
+            exact: 1,
+        };
+        let result = evaluation.to_u32();
+        assert_eq!(result, 8);
+    }
+    #[test]
+    fn evaluation_to_u32_three_zero() {
+        let evaluation: Evaluation<3> = Evaluation {
+            correct_color: 3,
+            exact: 0,
+        };
+        let result = evaluation.to_u32();
+        assert_eq!(result, 9);
+    }
+    #[test]
+    fn evaluation_to_u32_four_fields_one_three() {
+        let evaluation: Evaluation<4> = Evaluation {
+            correct_color: 1,
+            exact: 3,
+        };
+        let result = evaluation.to_u32();
+        assert_eq!(result, 8);
+    }
+}
@@ -0,0 +1,111 @@
+//! Diagnostics on stderr, filtered by the verbosity given on the command line.
+
+use std::{
+    fmt::Arguments,
+    sync::atomic::{AtomicU8, Ordering},
+    time::Instant,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Level {
+    Error = 1,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// The level for the number of `-v` flags, or `Error` for `--quiet`.
+    pub fn from_verbosity(verbosity: i8) -> Self {
+        match verbosity {
+            i8::MIN..=-1 => Level::Error,
+            0 => Level::Info,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+pub fn set_max_level(level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+pub fn write(level: Level, args: Arguments) {
+    eprintln!("{:>5} {args}", level.label());
+}
+
+/// Logs how long the enclosing scope took once it is left.
+pub struct Span {
+    level: Level,
+    name: &'static str,
+    start: Instant,
+}
+
+pub fn span(level: Level, name: &'static str) -> Span {
+    Span {
+        level,
+        name,
+        start: Instant::now(),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if enabled(self.level) {
+            write(
+                self.level,
+                format_args!("{} took {:?}", self.name, self.start.elapsed()),
+            );
+        }
+    }
+}
+
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, format_args!($($arg)+));
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)+) => { log!($crate::log::Level::Info, $($arg)+) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => { log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => { log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn verbosity_levels() {
+        assert_eq!(Level::from_verbosity(-1), Level::Error);
+        assert_eq!(Level::from_verbosity(0), Level::Info);
+        assert_eq!(Level::from_verbosity(1), Level::Debug);
+        assert_eq!(Level::from_verbosity(3), Level::Trace);
+        assert!(Level::Error < Level::Trace);
+    }
+}
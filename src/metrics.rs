@@ -0,0 +1,165 @@
+//! What the server did, in the text format Prometheus scrapes from `GET /metrics`:
+//!
+//! ```text
+//! mastermind_sessions_active               games kept, a gauge
+//! mastermind_sessions_started_total        games started
+//! mastermind_sessions_expired_total        games ended for being idle
+//! mastermind_guesses_total                 guesses computed
+//! mastermind_guess_duration_seconds        a summary of the time a guess took, with the
+//!                                          median and 95th percentile of the latest guesses
+//! mastermind_guess_candidates              a histogram of the codes possible at a guess
+//! ```
+
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Write},
+    time::Duration,
+};
+
+/// The quantiles of the guess durations are taken over this many latest guesses.
+const LATENCY_WINDOW: usize = 1000;
+/// The upper bounds of the buckets of the candidate histogram.
+const CANDIDATE_BUCKETS: [u64; 7] = [1, 10, 100, 1000, 10_000, 100_000, 1_000_000];
+
+#[derive(Default)]
+pub struct Metrics {
+    pub started: u64,
+    pub expired: u64,
+    guesses: u64,
+    /// The durations of the latest guesses in seconds, the oldest first.
+    latencies: VecDeque<f64>,
+    latency_sum: f64,
+    /// The guesses with at most as many candidates as the bucket bound and more than the
+    /// previous one, the last for more than all bounds.
+    candidates: [u64; CANDIDATE_BUCKETS.len() + 1],
+    candidate_sum: u64,
+}
+
+impl Metrics {
+    pub fn record_guess(&mut self, elapsed: Duration, candidates: usize) {
+        self.guesses += 1;
+        if self.latencies.len() == LATENCY_WINDOW {
+            self.latencies.pop_front();
+        }
+        self.latencies.push_back(elapsed.as_secs_f64());
+        self.latency_sum += elapsed.as_secs_f64();
+        let bucket = CANDIDATE_BUCKETS.partition_point(|bound| *bound < candidates as u64);
+        self.candidates[bucket] += 1;
+        self.candidate_sum += candidates as u64;
+    }
+
+    /// The quantile `q` of the latest guess durations by nearest rank, NaN before the first.
+    fn latency(&self, q: f64) -> f64 {
+        let mut latencies: Vec<_> = self.latencies.iter().copied().collect();
+        latencies.sort_by(f64::total_cmp);
+        let rank = (q * latencies.len() as f64).ceil() as usize;
+        latencies
+            .get(rank.saturating_sub(1))
+            .copied()
+            .unwrap_or(f64::NAN)
+    }
+
+    /// The metrics in the Prometheus text format, with `sessions` games kept.
+    pub fn render(&self, sessions: usize) -> String {
+        let mut text = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, String)]| {
+            let _ = writeln!(text, "# HELP {name} {help}\n# TYPE {name} {kind}");
+            for (suffix, value) in samples {
+                let _ = writeln!(text, "{name}{suffix} {value}");
+            }
+        };
+        let sample = |suffix: &str, value: &dyn Display| (suffix.to_string(), value.to_string());
+        metric(
+            "mastermind_sessions_active",
+            "gauge",
+            "Games the server keeps.",
+            &[sample("", &sessions)],
+        );
+        metric(
+            "mastermind_sessions_started_total",
+            "counter",
+            "Games started.",
+            &[sample("", &self.started)],
+        );
+        metric(
+            "mastermind_sessions_expired_total",
+            "counter",
+            "Games ended because nobody asked about them.",
+            &[sample("", &self.expired)],
+        );
+        metric(
+            "mastermind_guesses_total",
+            "counter",
+            "Guesses computed.",
+            &[sample("", &self.guesses)],
+        );
+        metric(
+            "mastermind_guess_duration_seconds",
+            "summary",
+            &format!("Time to compute a guess, quantiles of the latest {LATENCY_WINDOW}."),
+            &[
+                sample("{quantile=\"0.5\"}", &self.latency(0.5)),
+                sample("{quantile=\"0.95\"}", &self.latency(0.95)),
+                sample("_sum", &self.latency_sum),
+                sample("_count", &self.guesses),
+            ],
+        );
+        let mut buckets = vec![];
+        let mut cumulative = 0;
+        for (bound, count) in CANDIDATE_BUCKETS.iter().zip(&self.candidates) {
+            cumulative += count;
+            buckets.push((format!("_bucket{{le=\"{bound}\"}}"), cumulative.to_string()));
+        }
+        buckets.push(("_bucket{le=\"+Inf\"}".to_string(), self.guesses.to_string()));
+        buckets.push(("_sum".to_string(), self.candidate_sum.to_string()));
+        buckets.push(("_count".to_string(), self.guesses.to_string()));
+        metric(
+            "mastermind_guess_candidates",
+            "histogram",
+            "Codes possible when a guess was computed.",
+            &buckets,
+        );
+        text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_metrics() {
+        let mut metrics = Metrics::default();
+        let text = metrics.render(0);
+        assert!(text.contains("\nmastermind_guess_duration_seconds{quantile=\"0.95\"} NaN\n"));
+        metrics.started = 2;
+        for millis in 1..=100 {
+            metrics.record_guess(Duration::from_millis(millis), millis as usize * 20);
+        }
+        let text = metrics.render(2);
+        assert!(text.starts_with(
+            "# HELP mastermind_sessions_active Games the server keeps.\n\
+            # TYPE mastermind_sessions_active gauge\n\
+            mastermind_sessions_active 2\n"
+        ));
+        assert!(text.contains("\nmastermind_sessions_started_total 2\n"));
+        assert!(text.contains("\nmastermind_guesses_total 100\n"));
+        assert!(text.contains("\nmastermind_guess_duration_seconds{quantile=\"0.5\"} 0.05\n"));
+        assert!(text.contains("\nmastermind_guess_duration_seconds{quantile=\"0.95\"} 0.095\n"));
+        assert!(text.contains("\nmastermind_guess_duration_seconds_count 100\n"));
+        // 20 to 2000 candidates
+        assert!(text.contains("\nmastermind_guess_candidates_bucket{le=\"10\"} 0\n"));
+        assert!(text.contains("\nmastermind_guess_candidates_bucket{le=\"100\"} 5\n"));
+        assert!(text.contains("\nmastermind_guess_candidates_bucket{le=\"1000\"} 50\n"));
+        assert!(text.contains("\nmastermind_guess_candidates_bucket{le=\"10000\"} 100\n"));
+        assert!(text.contains("\nmastermind_guess_candidates_bucket{le=\"+Inf\"} 100\n"));
+        assert!(text.contains("\nmastermind_guess_candidates_sum 101000\n"));
+        // only the latest guesses count for the quantiles
+        for _ in 0..LATENCY_WINDOW {
+            metrics.record_guess(Duration::from_secs(1), 1);
+        }
+        assert!(metrics
+            .render(2)
+            .contains("\nmastermind_guess_duration_seconds{quantile=\"0.5\"} 1\n"));
+    }
+}
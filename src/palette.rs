@@ -0,0 +1,171 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use once_cell::sync::OnceCell;
+
+use crate::Guess;
+
+pub const NAMES: [&str; 10] = [
+    "rot", "grün", "gelb", "blau", "orange", "pink", "weiß", "grau", "schwarz", "braun",
+];
+
+const ENGLISH_NAMES: [&str; 10] = [
+    "red", "green", "yellow", "blue", "orange", "pink", "white", "grey", "black", "brown",
+];
+
+/// 256-color terminal codes matching the built-in color names.
+const TERMINAL_COLORS: [u8; 10] = [196, 34, 226, 27, 208, 213, 255, 244, 16, 130];
+
+/// Language of the built-in color names.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Locale {
+    #[default]
+    German,
+    English,
+}
+
+impl Locale {
+    pub fn names(&self) -> [&'static str; 10] {
+        match self {
+            Locale::German => NAMES,
+            Locale::English => ENGLISH_NAMES,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownLocale(String);
+
+impl Display for UnknownLocale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown locale {:?}, expected \"de\" or \"en\"", self.0)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = UnknownLocale;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "de" | "german" | "deutsch" => Ok(Locale::German),
+            "en" | "english" => Ok(Locale::English),
+            _ => Err(UnknownLocale(s.to_string())),
+        }
+    }
+}
+
+static PALETTE: OnceCell<Vec<String>> = OnceCell::new();
+
+/// Sets the color names used for displaying and parsing codes.
+/// Only the first call has an effect, later calls return the rejected palette.
+pub fn set_palette(names: Vec<String>) -> Result<(), Vec<String>> {
+    PALETTE.set(names)
+}
+
+/// The color names, indexed by color.
+pub fn color_names() -> &'static [String] {
+    PALETTE.get_or_init(|| NAMES.iter().map(|name| name.to_string()).collect())
+}
+
+pub fn color_index(name: &str) -> Option<u32> {
+    let name = name.to_lowercase();
+    color_names()
+        .iter()
+        .position(|color| color.to_lowercase() == name)
+        .map(|index| index as u32)
+}
+
+/// When to render pegs in terminal colors.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err("expected \"auto\", \"always\" or \"never\"".to_string()),
+        }
+    }
+}
+
+impl ColorChoice {
+    /// Colors are used automatically if stdout is a terminal and `NO_COLOR` is not set.
+    pub fn enabled(&self) -> bool {
+        use std::io::IsTerminal;
+        match self {
+            ColorChoice::Auto => {
+                std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+static COLORED_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_colored_output(enabled: bool) {
+    COLORED_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+/// Displays a guess as colored pegs with their names if colored output is enabled,
+/// and as a plain list of names otherwise.
+pub struct Pegs<'a, const FIELDS: usize>(pub &'a Guess<FIELDS>);
+
+impl<const FIELDS: usize> Display for Pegs<'_, FIELDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !COLORED_OUTPUT.load(Ordering::Relaxed) {
+            return write!(f, "{}", self.0);
+        }
+        let names = color_names();
+        for (index, color) in self.0.colors().iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            match TERMINAL_COLORS.get(*color as usize) {
+                Some(code) => write!(f, "\x1b[38;5;{code}m●\x1b[0m ")?,
+                None => write!(f, "● ")?,
+            }
+            write!(f, "{}", names[*color as usize])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_locale() {
+        assert_eq!("de".parse(), Ok(Locale::German));
+        assert_eq!("English".parse(), Ok(Locale::English));
+        assert!("fr".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn parse_color_choice() {
+        assert_eq!("never".parse(), Ok(ColorChoice::Never));
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+    }
+
+    #[test]
+    fn look_up_default_names() {
+        assert_eq!(color_index("Blau"), Some(3));
+        assert_eq!(color_index("lila"), None);
+    }
+}
@@ -0,0 +1,438 @@
+//! Solvers compiled separately and loaded at runtime, so that other strategies can be
+//! compared with the ones of the solver without changing it. A plugin is a shared library
+//! with the functions declared in `include/mastermind_plugin.h`. `simulate --compare` plays
+//! a plugin by the name it gives, looking for it among the ones given with `--plugin FILE`
+//! and the libraries in `~/.config/mastermind_solver/plugins`.
+
+use std::{
+    ffi::{c_char, c_int, c_void, CStr, CString},
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    commands::{Game, Turn},
+    config,
+    progress::Progress,
+    strategy::Strategy,
+    Guess, Rules,
+};
+
+/// The version of the functions a plugin has to implement, see `mastermind_plugin_abi`.
+pub const ABI_VERSION: u32 = 1;
+/// Games of a plugin which takes more turns than this are given up on.
+const MAX_TURNS: usize = 50;
+
+type AbiFn = unsafe extern "C" fn() -> u32;
+type NameFn = unsafe extern "C" fn() -> *const c_char;
+type NewFn = unsafe extern "C" fn(usize, u32, c_int) -> *mut c_void;
+type FreeFn = unsafe extern "C" fn(*mut c_void);
+type GuessFn = unsafe extern "C" fn(*mut c_void, *const u32, *const u32, usize, *mut u32) -> c_int;
+
+/// A strategy of the solver or a plugin, as `simulate --compare` plays them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Contender {
+    Strategy(Strategy),
+    Plugin(String),
+}
+
+impl Contender {
+    pub fn name(&self) -> &str {
+        match self {
+            Contender::Strategy(strategy) => strategy.name(),
+            Contender::Plugin(name) => name,
+        }
+    }
+}
+
+impl FromStr for Contender {
+    type Err = String;
+
+    /// Names which aren't strategies are taken for plugins, which are only looked for when
+    /// they are played.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("expected the name of a strategy or a plugin".to_string());
+        }
+        Ok(s.parse()
+            .map_or_else(|_| Contender::Plugin(s.to_string()), Contender::Strategy))
+    }
+}
+
+pub struct Plugin {
+    pub name: String,
+    /// The handle `dlopen` returned, null for plugins linked into the program.
+    library: *mut c_void,
+    new: NewFn,
+    free: FreeFn,
+    guess: GuessFn,
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        if !self.library.is_null() {
+            // SAFETY: the handle came from `dlopen`, and every solver borrows the plugin, so
+            // none of its functions is called after this.
+            unsafe { libc::dlclose(self.library) };
+        }
+    }
+}
+
+/// The last error of `dlopen` or `dlsym`.
+fn dl_error() -> String {
+    // SAFETY: `dlerror` takes no arguments and keeps the error of each thread apart.
+    let error = unsafe { libc::dlerror() };
+    if error.is_null() {
+        "unknown error".to_string()
+    } else {
+        // SAFETY: the message is null-terminated and lives until the next call of `dlerror`.
+        unsafe { CStr::from_ptr(error) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// The function `name` of the library, which has to have the type `T`, a function pointer.
+/// The library has to be a handle `dlopen` returned.
+unsafe fn symbol<T: Copy>(library: *mut c_void, name: &CStr) -> Result<T, String> {
+    let pointer = libc::dlsym(library, name.as_ptr());
+    if pointer.is_null() {
+        return Err(format!("no function {}", name.to_string_lossy()));
+    }
+    Ok(std::mem::transmute_copy(&pointer))
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let path_error = |message| format!("{}: {message}", path.display());
+        let c_path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|_| path_error("invalid path".to_string()))?;
+        // SAFETY: the path is null-terminated.
+        let library = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+        if library.is_null() {
+            return Err(dl_error());
+        }
+        // SAFETY: the library was just opened, and `bind` checks the version of the interface
+        // before it looks up the other functions.
+        match unsafe { Self::bind(library) } {
+            Ok(plugin) => Ok(plugin),
+            Err(err) => {
+                // SAFETY: binding failed, so nothing of the library is used anymore.
+                unsafe { libc::dlclose(library) };
+                Err(path_error(err))
+            }
+        }
+    }
+
+    /// The plugin in `library`, a handle `dlopen` returned, which is closed when the plugin is
+    /// dropped. The functions are only looked up if it implements `ABI_VERSION` of the
+    /// interface, so they have the types of this version.
+    unsafe fn bind(library: *mut c_void) -> Result<Self, String> {
+        let abi: AbiFn = symbol(library, c"mastermind_plugin_abi")?;
+        if abi() != ABI_VERSION {
+            return Err(format!(
+                "the plugin is built for version {} of the interface, not {ABI_VERSION}",
+                abi()
+            ));
+        }
+        let name: NameFn = symbol(library, c"mastermind_plugin_name")?;
+        let name = name();
+        if name.is_null() {
+            return Err("the plugin has no name".to_string());
+        }
+        let name = CStr::from_ptr(name)
+            .to_str()
+            .map_err(|_| "the name of the plugin isn't UTF-8".to_string())?;
+        if name.is_empty() || name.contains(',') || name.parse::<Strategy>().is_ok() {
+            return Err(format!("the plugin can't be named {name:?}"));
+        }
+        Ok(Plugin {
+            name: name.to_string(),
+            library,
+            new: symbol(library, c"mastermind_plugin_new")?,
+            free: symbol(library, c"mastermind_plugin_free")?,
+            guess: symbol(library, c"mastermind_plugin_guess")?,
+        })
+    }
+
+    /// A solver of the plugin for the game, if it plays it.
+    pub fn solver<const FIELDS: usize, const COLORS: u32>(
+        &self,
+        rules: Rules,
+    ) -> Result<PluginSolver<'_, FIELDS, COLORS>, String> {
+        // SAFETY: the version of the interface was checked when the plugin was bound, and the
+        // library stays open as long as `self`.
+        let solver = unsafe { (self.new)(FIELDS, COLORS, rules.duplicates as c_int) };
+        if solver.is_null() {
+            return Err(format!(
+                "{} doesn't play {FIELDS} fields with {COLORS} colors{}",
+                self.name,
+                if rules.duplicates {
+                    ""
+                } else {
+                    " without duplicates"
+                }
+            ));
+        }
+        Ok(PluginSolver {
+            plugin: self,
+            solver,
+            rules,
+        })
+    }
+}
+
+/// The plugins given by `paths` and the ones in the plugin directory, see the module
+/// documentation.
+pub fn discover(paths: &[PathBuf]) -> Result<Vec<Plugin>, String> {
+    let mut paths = paths.to_vec();
+    if let Some(directory) = directory() {
+        if let Ok(entries) = std::fs::read_dir(&directory) {
+            let mut libraries: Vec<_> = entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|extension| extension == std::env::consts::DLL_EXTENSION)
+                })
+                .collect();
+            libraries.sort();
+            paths.extend(libraries);
+        }
+    }
+    let mut plugins: Vec<Plugin> = vec![];
+    for path in paths {
+        let plugin = Plugin::load(&path)?;
+        if plugins.iter().any(|other| other.name == plugin.name) {
+            return Err(format!(
+                "{}: another plugin is named {:?} already",
+                path.display(),
+                plugin.name
+            ));
+        }
+        plugins.push(plugin);
+    }
+    Ok(plugins)
+}
+
+/// Where plugins are looked for, next to the configuration file.
+pub fn directory() -> Option<PathBuf> {
+    Some(config::default_path()?.parent()?.join("plugins"))
+}
+
+pub struct PluginSolver<'a, const FIELDS: usize, const COLORS: u32> {
+    plugin: &'a Plugin,
+    solver: *mut c_void,
+    rules: Rules,
+}
+
+impl<const FIELDS: usize, const COLORS: u32> Drop for PluginSolver<'_, FIELDS, COLORS> {
+    fn drop(&mut self) {
+        // SAFETY: the solver came from `new` of the plugin and is freed once, and the library
+        // is still open since the solver borrows the plugin.
+        unsafe { (self.plugin.free)(self.solver) };
+    }
+}
+
+impl<const FIELDS: usize, const COLORS: u32> PluginSolver<'_, FIELDS, COLORS> {
+    /// The guess of the plugin after the turns of `history`, which has to be a legal one.
+    pub fn guess(&mut self, history: &[Turn<FIELDS>]) -> Result<Guess<FIELDS>, String> {
+        let guesses: Vec<u32> = history
+            .iter()
+            .flat_map(|turn| turn.guess.colors())
+            .collect();
+        let feedback: Vec<u32> = history
+            .iter()
+            .flat_map(|turn| [turn.evaluation.exact, turn.evaluation.correct_color])
+            .collect();
+        let mut colors = [0; FIELDS];
+        // SAFETY: the solver is valid until drop and the `&'a Plugin` keeps the library open,
+        // `guesses` holds `history.len() * FIELDS` colors, `feedback` holds
+        // `history.len() * 2` pegs and `colors` has room for the `FIELDS` colors of the
+        // guess, as `include/mastermind_plugin.h` declares.
+        let status = unsafe {
+            (self.plugin.guess)(
+                self.solver,
+                guesses.as_ptr(),
+                feedback.as_ptr(),
+                history.len(),
+                colors.as_mut_ptr(),
+            )
+        };
+        if status != 0 {
+            return Err(format!("{} failed with {status}", self.plugin.name));
+        }
+        if colors.iter().any(|color| *color >= COLORS) {
+            return Err(format!(
+                "{} guessed the colors {colors:?}",
+                self.plugin.name
+            ));
+        }
+        let guess = Guess::new(colors);
+        if !self.rules.is_legal_guess::<FIELDS, COLORS>(&guess) {
+            return Err(format!(
+                "{} guessed [{guess}], which isn't legal",
+                self.plugin.name
+            ));
+        }
+        Ok(guess)
+    }
+
+    /// Plays against `code`. The turns only have the guesses, the feedback and the time
+    /// the plugin took, as it tells nothing else.
+    pub fn play(&mut self, code: Guess<FIELDS>) -> Result<Game<FIELDS>, String> {
+        let mut turns = vec![];
+        while turns.len() < MAX_TURNS {
+            let start = Instant::now();
+            let guess = self.guess(&turns)?;
+            turns.push(Turn {
+                guess,
+                evaluation: self.rules.evaluate(code, guess),
+                score: 0.,
+                candidates: 0,
+                information: 0.,
+                expected_guesses: 0.,
+                gained: 0.,
+                scored: 0,
+                elapsed: start.elapsed(),
+            });
+            if guess == code {
+                return Ok(Game { code, turns });
+            }
+        }
+        Err(format!(
+            "{} didn't find [{code}] in {MAX_TURNS} turns",
+            self.plugin.name
+        ))
+    }
+}
+
+/// Plays the plugin against every one of `codes`, with the games in the order of
+/// `commands::play_all`, and the time it took.
+pub fn play_all<const FIELDS: usize, const COLORS: u32>(
+    plugin: &Plugin,
+    rules: Rules,
+    codes: &[Guess<FIELDS>],
+) -> Result<(Vec<Game<FIELDS>>, Duration), String> {
+    let mut solver = plugin.solver::<FIELDS, COLORS>(rules)?;
+    let progress = Progress::new("simulating", codes.len());
+    let start = Instant::now();
+    let mut games = codes
+        .iter()
+        .map(|code| {
+            progress.inc(1);
+            solver.play(*code)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let elapsed = start.elapsed();
+    games.sort_by_key(|game| game.code.packed());
+    Ok((games, elapsed))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Evaluation, Preset};
+
+    /// A plugin which guesses the first code which fits the feedback so far, for the mini
+    /// preset.
+    mod first_fit {
+        use super::*;
+
+        pub unsafe extern "C" fn new(fields: usize, colors: u32, duplicates: c_int) -> *mut c_void {
+            if (fields, colors, duplicates) != (3, 4, 0) {
+                return std::ptr::null_mut();
+            }
+            Box::into_raw(Box::new(Preset::Mini.rules())) as *mut c_void
+        }
+
+        pub unsafe extern "C" fn free(solver: *mut c_void) {
+            drop(Box::from_raw(solver as *mut Rules));
+        }
+
+        pub unsafe extern "C" fn guess(
+            solver: *mut c_void,
+            guesses: *const u32,
+            feedback: *const u32,
+            turns: usize,
+            guess: *mut u32,
+        ) -> c_int {
+            let rules = *(solver as *const Rules);
+            let guesses = std::slice::from_raw_parts(guesses, turns * 3);
+            let feedback = std::slice::from_raw_parts(feedback, turns * 2);
+            let code = rules.codes::<3, 4>().find(|code| {
+                (0..turns).all(|turn| {
+                    let guess = Guess::new(std::array::from_fn(|field| guesses[turn * 3 + field]));
+                    let evaluation = Evaluation::new(feedback[turn * 2], feedback[turn * 2 + 1]);
+                    evaluation == Ok(rules.evaluate(*code, guess))
+                })
+            });
+            match code {
+                Some(code) => {
+                    std::slice::from_raw_parts_mut(guess, 3).copy_from_slice(&code.colors());
+                    0
+                }
+                None => -1,
+            }
+        }
+    }
+
+    fn first_fit() -> Plugin {
+        Plugin {
+            name: "firstfit".to_string(),
+            library: std::ptr::null_mut(),
+            new: first_fit::new,
+            free: first_fit::free,
+            guess: first_fit::guess,
+        }
+    }
+
+    #[test]
+    fn play_a_plugin() {
+        let rules = Preset::Mini.rules();
+        let plugin = first_fit();
+        let codes: Vec<_> = rules.codes::<3, 4>().collect();
+        let (games, _) = play_all::<3, 4>(&plugin, rules, &codes).unwrap();
+        assert_eq!(games.len(), codes.len());
+        for game in &games {
+            assert_eq!(game.turns.last().unwrap().guess, game.code);
+            assert!(game
+                .turns
+                .iter()
+                .all(|turn| rules.evaluate(game.code, turn.guess) == turn.evaluation));
+        }
+        // the first code fits no feedback and is guessed right away
+        let first = games.iter().find(|game| game.code == codes[0]).unwrap();
+        assert_eq!(first.turns.len(), 1);
+        assert_eq!(
+            plugin.solver::<4, 6>(Preset::Classic.rules()).err(),
+            Some("firstfit doesn't play 4 fields with 6 colors".to_string())
+        );
+    }
+
+    #[test]
+    fn load_plugins() {
+        assert_eq!("knuth".parse(), Ok(Contender::Strategy(Strategy::Knuth)));
+        assert_eq!(
+            "firstfit".parse(),
+            Ok(Contender::Plugin("firstfit".to_string()))
+        );
+        assert!("".parse::<Contender>().is_err());
+
+        assert!(Plugin::load(Path::new("/nonexistent/plugin.so")).is_err());
+        // a library which isn't a plugin
+        let libc = Plugin::load(Path::new("libc.so.6")).err().unwrap();
+        assert_eq!(libc, "libc.so.6: no function mastermind_plugin_abi");
+
+        let header = include_str!("../include/mastermind_plugin.h");
+        assert!(header.contains(&format!("#define MASTERMIND_PLUGIN_ABI {ABI_VERSION}\n")));
+        for function in include_str!("plugin.rs")
+            .split("symbol(library, c\"")
+            .skip(1)
+            .filter_map(|rest| rest.split('"').next())
+        {
+            assert!(header.contains(&format!("{function}(")), "{function}");
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Learned policies: with `--policy FILE` the solver plays the guess a neural network
+//! predicts to take the fewest guesses from there, as trained on the records written by
+//! `generate-dataset`. The network is a stack of dense layers kept as text, one line per
+//! output of a layer with its weights and then its bias:
+//!
+//! ```text
+//! mastermind-policy 1
+//! # 49 inputs for classic Mastermind, 8 hidden units
+//! dense 49 8 relu
+//! 0.12 -0.4 … 0.03
+//! …
+//! dense 8 1 linear
+//! 0.7 0.1 … 1.9
+//! ```
+//!
+//! The activations are `linear`, `relu`, `tanh` and `sigmoid`, and the last layer has a
+//! single output, the predicted `turns_left`. The inputs are the columns `candidates` and
+//! `share_F_C` of the dataset followed by the guess, 1 for every `F` and `C` where the guess
+//! has the color `C` in the field `F` and 0 otherwise, in the same order as the shares.
+//! Scaling the inputs for training has to be folded into the first layer.
+//!
+//! Only guesses the rules allow are scored, and if the best of them can't tell the
+//! remaining codes apart, the solver's own guess is played instead.
+
+use std::path::Path;
+
+use crate::Guess;
+
+/// The version of the format in the first line of a policy.
+const VERSION: &str = "mastermind-policy 1";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Activation {
+    Linear,
+    Relu,
+    Tanh,
+    Sigmoid,
+}
+
+impl Activation {
+    fn apply(self, x: f64) -> f64 {
+        match self {
+            Activation::Linear => x,
+            Activation::Relu => x.max(0.),
+            Activation::Tanh => x.tanh(),
+            Activation::Sigmoid => 1. / (1. + (-x).exp()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Layer {
+    inputs: usize,
+    /// The weights of every output in a row of `inputs`.
+    weights: Vec<f64>,
+    biases: Vec<f64>,
+    activation: Activation,
+}
+
+impl Layer {
+    fn apply(&self, values: &[f64]) -> Vec<f64> {
+        self.weights
+            .chunks(self.inputs)
+            .zip(&self.biases)
+            .map(|(row, bias)| {
+                let sum = row.iter().zip(values).map(|(w, x)| w * x).sum::<f64>();
+                self.activation.apply(sum + bias)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Policy {
+    layers: Vec<Layer>,
+}
+
+impl Policy {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text =
+            std::fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        Self::parse(&text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+        match lines.next() {
+            Some((_, VERSION)) => {}
+            Some((line, _)) => return Err(format!("line {line}: expected {VERSION:?}")),
+            None => return Err("the policy is empty".to_string()),
+        }
+        let mut layers: Vec<Layer> = vec![];
+        while let Some((line, text)) = lines.next() {
+            let error = |err: &str| format!("line {line}: {err}");
+            let [dense, inputs, outputs, activation] =
+                text.split_whitespace().collect::<Vec<_>>()[..]
+            else {
+                return Err(error("expected `dense INPUTS OUTPUTS ACTIVATION`"));
+            };
+            if dense != "dense" {
+                return Err(error("expected `dense INPUTS OUTPUTS ACTIVATION`"));
+            }
+            let size = |size: &str| match size.parse() {
+                Ok(0) | Err(_) => Err(error(&format!("invalid size {size:?}"))),
+                Ok(size) => Ok(size),
+            };
+            let (inputs, outputs): (usize, usize) = (size(inputs)?, size(outputs)?);
+            if let Some(last) = layers.last() {
+                if last.biases.len() != inputs {
+                    return Err(error(&format!(
+                        "the layer before has {} outputs, not {inputs}",
+                        last.biases.len()
+                    )));
+                }
+            }
+            let activation = match activation {
+                "linear" => Activation::Linear,
+                "relu" => Activation::Relu,
+                "tanh" => Activation::Tanh,
+                "sigmoid" => Activation::Sigmoid,
+                _ => return Err(error(&format!("unknown activation {activation:?}"))),
+            };
+            let mut weights = Vec::with_capacity(inputs * outputs);
+            let mut biases = Vec::with_capacity(outputs);
+            for _ in 0..outputs {
+                let Some((line, row)) = lines.next() else {
+                    return Err(format!("the layer in line {line} misses weights"));
+                };
+                let row = row
+                    .split_whitespace()
+                    .map(|number| match number.parse::<f64>() {
+                        Ok(number) if number.is_finite() => Ok(number),
+                        _ => Err(format!("line {line}: invalid number {number:?}")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if row.len() != inputs + 1 {
+                    return Err(format!(
+                        "line {line}: expected {inputs} weights and a bias, found {} numbers",
+                        row.len()
+                    ));
+                }
+                weights.extend(&row[..inputs]);
+                biases.push(row[inputs]);
+            }
+            layers.push(Layer {
+                inputs,
+                weights,
+                biases,
+                activation,
+            });
+        }
+        match layers.last() {
+            None => Err("the policy has no layers".to_string()),
+            Some(last) if last.biases.len() != 1 => {
+                Err("the last layer has to have a single output".to_string())
+            }
+            Some(_) => Ok(Self { layers }),
+        }
+    }
+
+    /// The number of inputs the network takes.
+    pub fn inputs(&self) -> usize {
+        self.layers[0].inputs
+    }
+
+    /// The inputs a policy takes for a game with this many fields and colors.
+    pub const fn inputs_for(fields: usize, colors: u32) -> usize {
+        1 + 2 * fields * colors as usize
+    }
+
+    /// Predicts the guesses it takes from a guess on, with `features` the inputs describing
+    /// the candidates. They are only weighed once, as the guesses add a single weight per
+    /// field to the first layer.
+    pub fn predictor<const FIELDS: usize, const COLORS: u32>(
+        &self,
+        features: &[f64],
+    ) -> impl Fn(Guess<FIELDS>) -> f64 + '_ {
+        let first = &self.layers[0];
+        let partial: Vec<f64> = first
+            .weights
+            .chunks(first.inputs)
+            .zip(&first.biases)
+            .map(|(row, bias)| bias + row.iter().zip(features).map(|(w, x)| w * x).sum::<f64>())
+            .collect();
+        let offset = features.len();
+        move |guess| {
+            let values = first
+                .weights
+                .chunks(first.inputs)
+                .zip(&partial)
+                .map(|(row, partial)| {
+                    let guessed = (0..FIELDS)
+                        .map(|field| {
+                            row[offset + field * COLORS as usize + guess.get(field) as usize]
+                        })
+                        .sum::<f64>();
+                    first.activation.apply(partial + guessed)
+                })
+                .collect::<Vec<_>>();
+            self.layers[1..]
+                .iter()
+                .fold(values, |values, layer| layer.apply(&values))[0]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The output of the network for `inputs`, all of them weighed by the first layer.
+    fn evaluate(policy: &Policy, inputs: &[f64]) -> f64 {
+        policy
+            .layers
+            .iter()
+            .fold(inputs.to_vec(), |values, layer| layer.apply(&values))[0]
+    }
+
+    #[test]
+    fn parse_and_evaluate() {
+        let policy = Policy::parse(
+            "# a tiny network\n\
+            mastermind-policy 1\n\
+            dense 3 2 relu\n\
+            1 0 -1 0.5\n\
+            0 2 0 -1\n\
+            \n\
+            dense 2 1 linear\n\
+            1 1 0\n",
+        )
+        .unwrap();
+        assert_eq!(policy.inputs(), 3);
+        assert_eq!(evaluate(&policy, &[1., 1., 0.]), 1.5 + 1.);
+        // the relu cuts off the first unit
+        assert_eq!(evaluate(&policy, &[0., 0., 1.]), 0.);
+
+        assert!(Policy::parse("").is_err());
+        assert_eq!(
+            Policy::parse("mastermind-policy 2\n"),
+            Err("line 1: expected \"mastermind-policy 1\"".to_string())
+        );
+        assert_eq!(
+            Policy::parse("mastermind-policy 1\ndense 2 1 relu\n1 2\n"),
+            Err("line 3: expected 2 weights and a bias, found 2 numbers".to_string())
+        );
+        assert_eq!(
+            Policy::parse("mastermind-policy 1\ndense 2 2 relu\n1 2 3\n1 2 3\ndense 3 1 linear\n"),
+            Err("line 5: the layer before has 2 outputs, not 3".to_string())
+        );
+        assert!(Policy::parse("mastermind-policy 1\ndense 2 2 relu\n1 2 3\n1 2 3\n").is_err());
+        assert!(Policy::parse("mastermind-policy 1\ndense 1 1 softmax\n1 2\n").is_err());
+        assert!(Policy::parse("mastermind-policy 1\ndense 1 1 relu\n1 NaN\n").is_err());
+    }
+
+    #[test]
+    fn predict_like_evaluate() {
+        // 1 candidate feature and 2 fields of 2 colors for the shares and the guess
+        let inputs = Policy::inputs_for(2, 2);
+        let rows = |outputs: usize, seed: usize| {
+            (0..outputs)
+                .map(|output| {
+                    (0..=inputs)
+                        .map(|input| ((output * 7 + input * 3 + seed) % 11) as f64 / 5. - 1.)
+                        .map(|weight| weight.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let policy = Policy::parse(&format!(
+            "mastermind-policy 1\ndense {inputs} 3 tanh\n{}\ndense 3 1 sigmoid\n0.5 -1 2 0.1\n",
+            rows(3, 1)
+        ))
+        .unwrap();
+        let features = [4., 0.5, 0.5, 0.25, 0.75];
+        let predict = policy.predictor::<2, 2>(&features);
+        let guess = Guess::<2>::new([1, 0]);
+        let mut inputs = features.to_vec();
+        inputs.extend([0., 1., 1., 0.]);
+        assert!((predict(guess) - evaluate(&policy, &inputs)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,178 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{palette::NAMES, Rules, MAX_COLORS, NUM_COLORS, NUM_FIELDS};
+
+/// Commonly played game configurations, selectable by name.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Preset {
+    Mini,
+    Classic,
+    Super,
+    #[default]
+    Default,
+    BullsAndCows,
+}
+
+// Every preset can be played: its colors can be packed into a guess and have built-in
+// names, and there are enough of them for codes without duplicates.
+const _: () = {
+    let mut index = 0;
+    while index < Preset::ALL.len() {
+        let preset = Preset::ALL[index];
+        assert!(preset.colors() <= MAX_COLORS);
+        assert!(preset.colors() as usize <= NAMES.len());
+        assert!(preset.rules().duplicates || preset.colors() as usize >= preset.fields());
+        index += 1;
+    }
+};
+
+impl Preset {
+    pub const ALL: [Preset; 5] = [
+        Preset::Mini,
+        Preset::Classic,
+        Preset::Super,
+        Preset::Default,
+        Preset::BullsAndCows,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Preset::Mini => "mini",
+            Preset::Classic => "classic",
+            Preset::Super => "super",
+            Preset::Default => "default",
+            Preset::BullsAndCows => "bulls",
+        }
+    }
+
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Preset::Mini => "3 fields, 4 colors, no duplicates",
+            Preset::Classic => "classic Mastermind: 4 fields, 6 colors, duplicates allowed",
+            Preset::Super => "Super Mastermind: 5 fields, 8 colors, duplicates allowed",
+            Preset::Default => "6 fields, 10 colors, no duplicates",
+            Preset::BullsAndCows => "Bulls & Cows: 4 digits, 10 symbols, no duplicates",
+        }
+    }
+
+    pub const fn fields(&self) -> usize {
+        match self {
+            Preset::Mini => 3,
+            Preset::Classic => 4,
+            Preset::Super => 5,
+            Preset::Default => NUM_FIELDS as usize,
+            Preset::BullsAndCows => 4,
+        }
+    }
+
+    pub const fn colors(&self) -> u32 {
+        match self {
+            Preset::Mini => 4,
+            Preset::Classic => 6,
+            Preset::Super => 8,
+            Preset::Default => NUM_COLORS,
+            Preset::BullsAndCows => 10,
+        }
+    }
+
+    /// The preset with the given board dimensions and rules.
+    pub fn find(fields: usize, colors: u32, rules: Rules) -> Option<Preset> {
+        // the preset of a game is the same with colors missing from the set
+        let rules = Rules {
+            missing_colors: 0,
+            ..rules
+        };
+        Preset::ALL.into_iter().find(|preset| {
+            (preset.fields(), preset.colors(), preset.rules()) == (fields, colors, rules)
+        })
+    }
+
+    pub const fn rules(&self) -> Rules {
+        match self {
+            Preset::Classic | Preset::Super => Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+            Preset::Mini | Preset::Default | Preset::BullsAndCows => Rules {
+                duplicates: false,
+                missing_colors: 0,
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownPreset(String);
+
+impl Display for UnknownPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown preset: {:?}", self.0)
+    }
+}
+
+impl FromStr for Preset {
+    type Err = UnknownPreset;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownPreset(s.to_string()))
+    }
+}
+
+/// Calls a function generic over `<FIELDS, COLORS, PARTITIONS>` with the
+/// board dimensions of the given preset.
+macro_rules! with_preset {
+    ($preset:expr, $($func:ident)::+($($arg:expr),* $(,)?)) => {
+        match $preset {
+            Preset::Mini => $($func)::+::<
+                { Preset::Mini.fields() },
+                { Preset::Mini.colors() },
+                { max_gauss(Preset::Mini.fields()) },
+            >($($arg),*),
+            Preset::Classic => $($func)::+::<
+                { Preset::Classic.fields() },
+                { Preset::Classic.colors() },
+                { max_gauss(Preset::Classic.fields()) },
+            >($($arg),*),
+            Preset::Super => $($func)::+::<
+                { Preset::Super.fields() },
+                { Preset::Super.colors() },
+                { max_gauss(Preset::Super.fields()) },
+            >($($arg),*),
+            Preset::Default => $($func)::+::<
+                { Preset::Default.fields() },
+                { Preset::Default.colors() },
+                { max_gauss(Preset::Default.fields()) },
+            >($($arg),*),
+            Preset::BullsAndCows => $($func)::+::<
+                { Preset::BullsAndCows.fields() },
+                { Preset::BullsAndCows.colors() },
+                { max_gauss(Preset::BullsAndCows.fields()) },
+            >($($arg),*),
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_preset_names() {
+        for preset in Preset::ALL {
+            assert_eq!(preset.name().parse(), Ok(preset));
+        }
+        assert_eq!("Classic".parse(), Ok(Preset::Classic));
+        assert!("huge".parse::<Preset>().is_err());
+    }
+
+    #[test]
+    fn presets_fit_color_names() {
+        for preset in Preset::ALL {
+            assert!(preset.colors() as usize <= crate::palette::NAMES.len());
+            assert!(preset.rules().duplicates || preset.colors() as usize >= preset.fields());
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! A progress bar on stderr for computations which take long, so that they don't look like
+//! they hang. Bars are only drawn once a computation has run for a while, and only if
+//! stderr is a terminal and diagnostics aren't turned off.
+//!
+//! With `--progress json` the progress is written as one JSON object per line instead, for
+//! wrappers which monitor or time-box the solver, whether stderr is a terminal or not:
+//!
+//! ```text
+//! {"event":"start","task":"scoring guesses","done":0,"total":1296,"elapsed":0}
+//! {"event":"progress","task":"scoring guesses","done":612,"total":1296,"elapsed":1.0,"best":["rot","rot","grün","gelb"],"score":1.47}
+//! {"event":"done","task":"scoring guesses","done":1296,"total":1296,"elapsed":1.9,"best":["rot","rot","grün","gelb"],"score":1.53}
+//! ```
+
+use std::{
+    io::Write,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::json::Json;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static JSON: AtomicBool = AtomicBool::new(false);
+/// Whether a bar is shown, bars of computations within it are hidden so they don't draw
+/// over each other.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Computations which are done before this are never shown.
+const DELAY: Duration = Duration::from_secs(1);
+/// The time between redraws.
+const INTERVAL: Duration = Duration::from_millis(100);
+const WIDTH: usize = 30;
+/// The time between JSON progress events, which are read by programs rather than watched.
+const JSON_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Bar,
+    /// JSON lines, see the module documentation.
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bar" => Ok(Format::Bar),
+            "json" => Ok(Format::Json),
+            _ => Err("expected \"bar\" or \"json\"".to_string()),
+        }
+    }
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn set_format(format: Format) {
+    JSON.store(format == Format::Json, Ordering::Relaxed);
+}
+
+/// Whether bars should be drawn: stderr is a terminal and `quiet` wasn't asked for.
+pub fn should_enable(quiet: bool) -> bool {
+    use std::io::IsTerminal;
+    !quiet && std::io::stderr().is_terminal()
+}
+
+pub struct Progress {
+    label: &'static str,
+    total: usize,
+    done: AtomicUsize,
+    start: Instant,
+    /// When the bar is drawn next, in nanoseconds since `start`.
+    next_draw: AtomicU64,
+    /// Whether the bar was drawn and has to be cleared.
+    drawn: Mutex<bool>,
+    enabled: bool,
+    /// Whether JSON events are written instead of a bar.
+    json: bool,
+    /// The best result so far with its score, for JSON events.
+    best: Mutex<Option<(Json, f64)>>,
+}
+
+impl Progress {
+    /// A bar for `total` steps, which is hidden if bars are turned off or another bar is
+    /// shown.
+    pub fn new(label: &'static str, total: usize) -> Self {
+        let enabled = ENABLED.load(Ordering::Relaxed)
+            && ACTIVE
+                .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok();
+        let progress = Self::with_enabled(label, total, enabled);
+        if progress.enabled && progress.json {
+            eprintln!("{}", progress.event("start", 0, Duration::ZERO));
+        }
+        progress
+    }
+
+    /// A bar which is never drawn, for computations which are known to be short.
+    pub fn hidden() -> Self {
+        Self::with_enabled("", 0, false)
+    }
+
+    fn with_enabled(label: &'static str, total: usize, enabled: bool) -> Self {
+        let json = JSON.load(Ordering::Relaxed);
+        Self {
+            label,
+            total,
+            done: AtomicUsize::new(0),
+            start: Instant::now(),
+            next_draw: AtomicU64::new(if json { JSON_INTERVAL } else { DELAY }.as_nanos() as u64),
+            drawn: Mutex::new(false),
+            enabled,
+            json,
+            best: Mutex::new(None),
+        }
+    }
+
+    /// Records `best` as the best result so far if its score is higher than the one before.
+    pub fn set_best(&self, best: impl Into<Json>, score: f64) {
+        if !self.enabled || !self.json {
+            return;
+        }
+        let mut current = self.best.lock().unwrap_or_else(|err| err.into_inner());
+        if current.as_ref().is_none_or(|(_, current)| score > *current) {
+            *current = Some((best.into(), score));
+        }
+    }
+
+    /// Records `steps` more steps as done and redraws the bar if it is time to.
+    pub fn inc(&self, steps: usize) {
+        if !self.enabled {
+            return;
+        }
+        let done = self.done.fetch_add(steps, Ordering::Relaxed) + steps;
+        let now = self.start.elapsed().as_nanos() as u64;
+        let next_draw = self.next_draw.load(Ordering::Relaxed);
+        let interval = if self.json { JSON_INTERVAL } else { INTERVAL };
+        // only one of the threads which find it is time draws
+        if now < next_draw
+            || self
+                .next_draw
+                .compare_exchange(
+                    next_draw,
+                    now + interval.as_nanos() as u64,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_err()
+        {
+            return;
+        }
+        let done = done.min(self.total);
+        if self.json {
+            let event = self.event("progress", done, self.start.elapsed());
+            // the lock keeps the lines of several threads apart
+            let _drawn = self.drawn.lock().unwrap_or_else(|err| err.into_inner());
+            eprintln!("{event}");
+            return;
+        }
+        let line = self.line(done, self.start.elapsed());
+        let mut drawn = self.drawn.lock().unwrap_or_else(|err| err.into_inner());
+        eprint!("\r{line}");
+        let _ = std::io::stderr().flush();
+        *drawn = true;
+    }
+
+    /// The bar after `done` steps in `elapsed`, with the time left estimated from the speed
+    /// so far.
+    fn line(&self, done: usize, elapsed: Duration) -> String {
+        let fraction = done as f64 / self.total.max(1) as f64;
+        let filled = (fraction * WIDTH as f64) as usize;
+        let eta = if done > 0 {
+            format!(
+                "{:.0?}",
+                elapsed.mul_f64((self.total - done) as f64 / done as f64)
+            )
+        } else {
+            "?".to_string()
+        };
+        format!(
+            "{} [{}{}] {:>3.0}% ETA {eta:<8}",
+            self.label,
+            "#".repeat(filled),
+            "-".repeat(WIDTH - filled),
+            fraction * 100.
+        )
+    }
+
+    /// The JSON event `kind` after `done` steps in `elapsed`, with the best result so far if
+    /// there is one.
+    fn event(&self, kind: &str, done: usize, elapsed: Duration) -> Json {
+        let mut fields = vec![
+            ("event", Json::from(kind)),
+            ("task", Json::from(self.label)),
+            ("done", Json::from(done)),
+            ("total", Json::from(self.total)),
+            ("elapsed", Json::from(elapsed)),
+        ];
+        let best = self.best.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some((best, score)) = best.as_ref() {
+            fields.push(("best", best.clone()));
+            fields.push(("score", Json::from(*score)));
+        }
+        Json::object(fields)
+    }
+}
+
+impl Drop for Progress {
+    /// Clears the bar so that it doesn't mix with the output, or writes the last event.
+    fn drop(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        if self.json {
+            let done = self.done.load(Ordering::Relaxed).min(self.total);
+            eprintln!("{}", self.event("done", done, self.start.elapsed()));
+            ACTIVE.store(false, Ordering::Relaxed);
+            return;
+        }
+        let drawn = self.drawn.get_mut().unwrap_or_else(|err| err.into_inner());
+        if *drawn {
+            eprint!("\r\x1b[2K");
+            let _ = std::io::stderr().flush();
+        }
+        ACTIVE.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn draw_bar() {
+        let progress = Progress::new("simulating", 200);
+        assert_eq!(
+            progress.line(50, Duration::from_secs(3)),
+            "simulating [#######-----------------------]  25% ETA 9s      "
+        );
+        assert_eq!(
+            progress.line(0, Duration::ZERO),
+            "simulating [------------------------------]   0% ETA ?       "
+        );
+        // nothing is drawn in tests
+        progress.inc(10);
+        assert!(!*progress.drawn.lock().unwrap());
+    }
+
+    #[test]
+    fn json_events() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("dots".parse::<Format>().is_err());
+
+        let mut progress = Progress::with_enabled("scoring guesses", 1296, true);
+        progress.json = true;
+        assert_eq!(
+            progress
+                .event("progress", 300, Duration::from_millis(1500))
+                .to_string(),
+            r#"{"event":"progress","task":"scoring guesses","done":300,"total":1296,"elapsed":1.5}"#
+        );
+        progress.set_best("a", 1.5);
+        // a worse result found later by another thread doesn't replace the best
+        progress.set_best("b", 1.25);
+        progress.set_best("c", 2.);
+        progress.set_best("d", 0.5);
+        assert_eq!(
+            progress
+                .event("done", 1296, Duration::from_secs(2))
+                .to_string(),
+            r#"{"event":"done","task":"scoring guesses","done":1296,"total":1296,"elapsed":2,"best":"c","score":2}"#
+        );
+        // the done event isn't written by a progress which wasn't started by `new`
+        progress.enabled = false;
+    }
+}
@@ -0,0 +1,111 @@
+//! Invariants of the feedback which have to hold for every code and guess, checked for
+//! random codes and guesses of several board dimensions.
+
+use crate::{evaluate, evaluate_with_duplicates, max_gauss, random::Rng, Evaluation, Guess, Rules};
+
+/// The number of random codes and guesses per board dimensions.
+const CASES: usize = 2000;
+
+fn random_guess<const FIELDS: usize, const COLORS: u32>(rng: &mut Rng) -> Guess<FIELDS> {
+    let mut guess = Guess::default();
+    for field in 0..FIELDS {
+        guess.set(field, rng.below(COLORS as usize) as u32);
+    }
+    guess
+}
+
+fn check<const FIELDS: usize, const COLORS: u32>(seed: u64) {
+    let mut rng = Rng::seeded(seed);
+    for rules in [
+        Rules {
+            duplicates: false,
+            missing_colors: 0,
+        },
+        Rules {
+            duplicates: true,
+            missing_colors: 0,
+        },
+    ] {
+        for _ in 0..CASES {
+            let code = rules.random_code::<FIELDS, COLORS>(&mut rng);
+            let guess = random_guess::<FIELDS, COLORS>(&mut rng);
+            let evaluation = rules.evaluate(code, guess);
+            let case = format!("{code:?} and {guess:?} with {rules:?}");
+
+            assert!(evaluation.is_valid(), "{evaluation:?} for {case}");
+            assert_eq!(
+                rules.check_feedback(guess, evaluation),
+                Ok(()),
+                "{evaluation:?} for {case}"
+            );
+            assert_eq!(
+                rules.evaluate(code, code),
+                Evaluation {
+                    correct_color: 0,
+                    exact: FIELDS as u32,
+                },
+                "{case}"
+            );
+            assert_eq!(
+                evaluation.exact == FIELDS as u32,
+                code == guess,
+                "{evaluation:?} for {case}"
+            );
+            // with duplicates it doesn't matter which one is the code
+            assert_eq!(
+                evaluate_with_duplicates(code, guess),
+                evaluate_with_duplicates(guess, code),
+                "{case}"
+            );
+            if code.is_valid_code() && guess.is_valid_code() {
+                // neither has duplicates, so both ways of counting agree
+                assert_eq!(evaluate(code, guess), evaluate(guess, code), "{case}");
+                assert_eq!(
+                    evaluate(code, guess),
+                    evaluate_with_duplicates(code, guess),
+                    "{case}"
+                );
+            }
+
+            let index = evaluation.to_u32();
+            assert!((index as usize) < max_gauss(FIELDS), "{evaluation:?}");
+            assert_eq!(Evaluation::from_u32(index), Some(evaluation));
+        }
+    }
+}
+
+/// `to_u32` numbers every valid feedback differently and `from_u32` reverses it.
+fn check_indices<const FIELDS: usize>() {
+    let mut indices = vec![];
+    for exact in 0..=FIELDS as u32 {
+        for correct_color in 0..=FIELDS as u32 - exact {
+            let evaluation = Evaluation::<FIELDS>::new(exact, correct_color).unwrap();
+            assert_eq!(Evaluation::from_u32(evaluation.to_u32()), Some(evaluation));
+            indices.push(evaluation.to_u32());
+        }
+    }
+    indices.sort_unstable();
+    assert_eq!(indices, (0..max_gauss(FIELDS) as u32).collect::<Vec<_>>());
+    assert_eq!(
+        Evaluation::<FIELDS>::from_u32(max_gauss(FIELDS) as u32),
+        None
+    );
+}
+
+#[test]
+fn evaluation_invariants() {
+    check::<3, 4>(1);
+    check::<4, 6>(2);
+    check::<5, 8>(3);
+    check::<6, 10>(4);
+    check::<8, 15>(5);
+}
+
+#[test]
+fn evaluation_indices_round_trip() {
+    check_indices::<1>();
+    check_indices::<3>();
+    check_indices::<4>();
+    check_indices::<6>();
+    check_indices::<8>();
+}
@@ -0,0 +1,218 @@
+//! Puzzles from the middle of a game: a history after which a few codes remain and only one
+//! of them is safe to play, as it gives each of the others different feedback. Playing it
+//! finds the code now or with the next guess for sure, which no other guess does, so it is
+//! the best guess, to be found by the reader. The histories are searched backwards from the
+//! feedback instead of by playing against a secret: for random guesses the feedback leaving
+//! the fewest codes which are still enough is chosen first, and other feedback is tried when
+//! that runs into a dead end. A puzzle is written like a saved game, so that
+//! `solve --history` reads it, with the solution at the end:
+//!
+//! ```text
+//! # preset: classic
+//! # palette: rot, grün, gelb, blau, orange, pink
+//! # puzzle: 4 codes remain, find the only safe guess
+//! 3 4 5 2 | 2 1
+//! 3 0 1 4 | 2 0
+//! # solution: 3 3 5 4
+//! ```
+
+use std::collections::HashSet;
+
+use crate::{random::Rng, save, Entry, Evaluation, Guess, Rules};
+
+/// The number of random guesses tried after each history.
+const GUESSES_PER_TURN: usize = 4;
+/// Puzzles are at most this many turns long.
+const MAX_TURNS: usize = 6;
+/// The number of histories looked at before a search gives up.
+const MAX_HISTORIES: usize = 2000;
+/// The number of searches before giving up on finding a puzzle at all.
+const MAX_SEARCHES: usize = 20;
+
+pub struct Puzzle<const FIELDS: usize> {
+    pub history: Vec<Entry<FIELDS>>,
+    /// The codes which fit the feedback.
+    pub candidates: Vec<Guess<FIELDS>>,
+    /// The only one of the candidates which tells all of them apart.
+    pub solution: Guess<FIELDS>,
+}
+
+struct Search<'a, const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    rules: Rules,
+    rng: &'a mut Rng,
+    /// The number of codes the puzzle leaves.
+    candidates: usize,
+    /// The number of histories left to look at.
+    budget: usize,
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>
+    Search<'_, FIELDS, COLORS, PARTITIONS>
+{
+    fn random_guess(&mut self) -> Guess<FIELDS> {
+        loop {
+            let guess = self.rules.random_code::<FIELDS, COLORS>(self.rng);
+            if self.rules.is_legal_guess::<FIELDS, COLORS>(&guess) {
+                return guess;
+            }
+        }
+    }
+
+    /// Looks for a puzzle among the histories continuing `history`, after which `codes` remain.
+    fn search(
+        &mut self,
+        history: &mut Vec<Entry<FIELDS>>,
+        codes: &[Guess<FIELDS>],
+    ) -> Option<Puzzle<FIELDS>> {
+        self.budget = self.budget.checked_sub(1)?;
+        if codes.len() == self.candidates {
+            return self.check(history, codes);
+        }
+        if history.len() == MAX_TURNS {
+            return None;
+        }
+        for _ in 0..GUESSES_PER_TURN {
+            let guess = self.random_guess();
+            let mut partitions = vec![vec![]; PARTITIONS];
+            for code in codes {
+                let index = self.rules.evaluate(*code, guess).to_u32() as usize;
+                partitions[index].push(*code);
+            }
+            // feedback which narrows the codes down, but not too far
+            let mut feedback: Vec<_> = (0..PARTITIONS)
+                .filter(|index| (self.candidates..codes.len()).contains(&partitions[*index].len()))
+                .collect();
+            feedback.sort_by_key(|index| partitions[*index].len());
+            for index in feedback {
+                let evaluation =
+                    Evaluation::from_u32(index as u32).expect("codes only give valid feedback");
+                history.push(Entry { guess, evaluation });
+                let puzzle = self.search(history, &partitions[index]);
+                history.pop();
+                if puzzle.is_some() || self.budget == 0 {
+                    return puzzle;
+                }
+            }
+        }
+        None
+    }
+
+    /// The puzzle of the history after which `codes` remain, if exactly one of them is safe.
+    fn check(&self, history: &[Entry<FIELDS>], codes: &[Guess<FIELDS>]) -> Option<Puzzle<FIELDS>> {
+        let mut safe = codes.iter().filter(|guess| {
+            let feedback: HashSet<_> = codes
+                .iter()
+                .map(|code| self.rules.evaluate(*code, **guess))
+                .collect();
+            feedback.len() == codes.len()
+        });
+        let solution = *safe.next()?;
+        safe.next().is_none().then(|| Puzzle {
+            history: history.to_vec(),
+            candidates: codes.to_vec(),
+            solution,
+        })
+    }
+}
+
+/// Searches for a puzzle which leaves `candidates` codes, at least 4: feedback doesn't change
+/// when the guess and the secret are swapped, so of 3 codes never exactly one is safe.
+/// Returns `None` if none was found, which is likely if hardly any history leaves that many
+/// codes.
+pub fn generate<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    candidates: usize,
+    rng: &mut Rng,
+) -> Option<Puzzle<FIELDS>> {
+    let codes: Vec<_> = rules.codes::<FIELDS, COLORS>().collect();
+    let mut search = Search::<FIELDS, COLORS, PARTITIONS> {
+        rules,
+        rng,
+        candidates: candidates.max(4),
+        budget: 0,
+    };
+    (0..MAX_SEARCHES).find_map(|_| {
+        search.budget = MAX_HISTORIES;
+        search.search(&mut vec![], &codes)
+    })
+}
+
+/// The puzzle as a saved game, see the module documentation.
+pub fn format<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    puzzle: &Puzzle<FIELDS>,
+) -> String {
+    let mut text = save::header::<FIELDS, COLORS>(rules);
+    text.push_str(&format!(
+        "# puzzle: {} codes remain, find the only safe guess\n",
+        puzzle.candidates.len()
+    ));
+    for entry in &puzzle.history {
+        text.push_str(&save::turn(entry));
+        text.push('\n');
+    }
+    text.push_str(&format!("# solution: {}\n", puzzle.solution.indices()));
+    text
+}
+
+/// Prints `count` puzzles which leave `candidates` codes, reproducible if a seed is given.
+pub fn run<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    candidates: usize,
+    count: usize,
+    seed: Option<u64>,
+) -> Result<(), String> {
+    let mut rng = seed.map_or_else(Rng::from_entropy, Rng::seeded);
+    for index in 0..count {
+        let puzzle = generate::<FIELDS, COLORS, PARTITIONS>(rules, candidates, &mut rng)
+            .ok_or_else(|| format!("found no puzzle which leaves {candidates} codes"))?;
+        if index > 0 {
+            println!();
+        }
+        print!("{}", format::<FIELDS, COLORS>(rules, &puzzle));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{history, max_gauss, save::SavedGame, Preset};
+
+    #[test]
+    fn generate_puzzles() {
+        let rules = Preset::Classic.rules();
+        let mut rng = Rng::seeded(3);
+        for candidates in [4, 6] {
+            let puzzle = generate::<4, 6, { max_gauss(4) }>(rules, candidates, &mut rng).unwrap();
+            assert_eq!(puzzle.candidates.len(), candidates);
+            assert!(puzzle.candidates.contains(&puzzle.solution));
+            for code in &puzzle.candidates {
+                assert!(puzzle
+                    .history
+                    .iter()
+                    .all(|entry| rules.evaluate(*code, entry.guess) == entry.evaluation));
+                // the feedback to the solution tells the code
+                let feedback = rules.evaluate(*code, puzzle.solution);
+                assert_eq!(
+                    puzzle
+                        .candidates
+                        .iter()
+                        .filter(|other| rules.evaluate(**other, puzzle.solution) == feedback)
+                        .count(),
+                    1
+                );
+            }
+
+            let text = format::<4, 6>(rules, &puzzle);
+            assert!(text.contains(&format!("# puzzle: {candidates} codes remain")));
+            let saved = SavedGame::parse(text.clone()).unwrap();
+            assert_eq!(saved.history::<4, 6>().as_ref(), Ok(&puzzle.history));
+            assert_eq!(history::parse::<4, 6>(rules, &text), Ok(puzzle.history));
+            assert_eq!(
+                saved.value("solution"),
+                Some(puzzle.solution.indices().to_string().as_str())
+            );
+        }
+    }
+}
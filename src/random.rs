@@ -0,0 +1,55 @@
+//! A small seedable random number generator (SplitMix64) for choosing secret codes.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn seeded(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeds the generator from the per-process randomness of the standard library.
+    /// The seed is logged so that the game can be reproduced with `--seed`.
+    pub fn from_entropy() -> Self {
+        let seed = RandomState::new().build_hasher().finish();
+        debug!("random seed: {seed}");
+        Self::seeded(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, `bound` must not be zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seeded_sequence_is_reproducible() {
+        let mut a = Rng::seeded(42);
+        let mut b = Rng::seeded(42);
+        for _ in 0..100 {
+            let value = a.below(7);
+            assert_eq!(value, b.below(7));
+            assert!(value < 7);
+        }
+        assert_ne!(Rng::seeded(1).next_u64(), Rng::seeded(2).next_u64());
+    }
+}
@@ -0,0 +1,245 @@
+//! Elo ratings of the engines which played matches refereed by `arbiter`, from the games
+//! recorded in its results. Every engine starts at `INITIAL` and the games are rated in
+//! the order they were played, each moving the ratings of both engines by up to `K`
+//! points. Engines are rated separately for every preset, since an engine may be strong
+//! at one game and weak at another.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{
+    arbiter::{self, COLUMNS},
+    cli::Output,
+    csv,
+    json::Json,
+};
+
+/// The rating of an engine without games.
+const INITIAL: f64 = 1500.;
+/// How far a single game moves the ratings.
+const K: f64 = 24.;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rating {
+    pub engine: String,
+    pub rating: f64,
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+}
+
+impl Rating {
+    fn new(engine: &str) -> Self {
+        Self {
+            engine: engine.to_string(),
+            rating: INITIAL,
+            won: 0,
+            drawn: 0,
+            lost: 0,
+        }
+    }
+
+    pub fn games(&self) -> usize {
+        self.won + self.drawn + self.lost
+    }
+
+    fn add(&mut self, points: f64, expected: f64) {
+        self.rating += K * (points - expected);
+        match points {
+            1. => self.won += 1,
+            0. => self.lost += 1,
+            _ => self.drawn += 1,
+        }
+    }
+}
+
+/// The points the first of two engines with these ratings is expected to score.
+fn expected(first: f64, second: f64) -> f64 {
+    1. / (1. + 10f64.powf((second - first) / 400.))
+}
+
+/// The ratings by preset, best first, from the results as `arbiter` records them.
+pub fn rate(results: &str) -> Result<BTreeMap<String, Vec<Rating>>, String> {
+    let records = csv::read(results)?;
+    let Some((header, games)) = records.split_first() else {
+        return Ok(BTreeMap::new());
+    };
+    if *header != COLUMNS {
+        return Err(format!(
+            "expected the columns {}, found {}",
+            COLUMNS.join(","),
+            header.join(",")
+        ));
+    }
+    let mut presets: BTreeMap<String, BTreeMap<String, Rating>> = BTreeMap::new();
+    for (index, game) in games.iter().enumerate() {
+        let line = index + 2;
+        let [_, preset, first, second, first_turns, second_turns] = &game[..] else {
+            return Err(format!(
+                "line {line}: expected {} fields, found {}",
+                COLUMNS.len(),
+                game.len()
+            ));
+        };
+        let turns = |turns: &str| match turns {
+            "" => Ok(None),
+            turns => turns
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("line {line}: invalid turns {turns:?}")),
+        };
+        let points = arbiter::points([turns(first_turns)?, turns(second_turns)?]);
+        let ratings = presets.entry(preset.clone()).or_default();
+        let rating = |ratings: &mut BTreeMap<String, Rating>, engine: &str| {
+            ratings
+                .entry(engine.to_string())
+                .or_insert_with(|| Rating::new(engine))
+                .rating
+        };
+        let (first_rating, second_rating) = (rating(ratings, first), rating(ratings, second));
+        let expected = expected(first_rating, second_rating);
+        ratings
+            .get_mut(first)
+            .expect("rated above")
+            .add(points, expected);
+        ratings
+            .get_mut(second)
+            .expect("rated above")
+            .add(1. - points, 1. - expected);
+    }
+    Ok(presets
+        .into_iter()
+        .map(|(preset, ratings)| {
+            let mut ratings: Vec<_> = ratings.into_values().collect();
+            ratings.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+            (preset, ratings)
+        })
+        .collect())
+}
+
+/// Prints the leaderboards of the results at `path`, by default the ones `arbiter` adds
+/// to.
+pub fn run(path: Option<PathBuf>, output: Output) -> Result<(), String> {
+    let path = path
+        .or_else(arbiter::default_results)
+        .ok_or("no results are known without a home directory")?;
+    let results = match std::fs::read_to_string(&path) {
+        Ok(results) => results,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(format!("can't read {}: {err}", path.display())),
+    };
+    let presets = rate(&results).map_err(|err| format!("{}: {err}", path.display()))?;
+    match output {
+        Output::Text if presets.is_empty() => {
+            println!("no matches in {} yet, see `arbiter`", path.display())
+        }
+        Output::Text => {
+            for (index, (preset, ratings)) in presets.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                let width = ratings
+                    .iter()
+                    .map(|rating| rating.engine.chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(6);
+                println!("{preset}");
+                println!(
+                    "{:>4}  {:<width$}  {:>6}  {:>5}  {:>5}  {:>5}  {:>5}",
+                    "rank", "engine", "rating", "games", "won", "drawn", "lost"
+                );
+                for (rank, rating) in ratings.iter().enumerate() {
+                    println!(
+                        "{:>4}  {:<width$}  {:>6.0}  {:>5}  {:>5}  {:>5}  {:>5}",
+                        rank + 1,
+                        rating.engine,
+                        rating.rating,
+                        rating.games(),
+                        rating.won,
+                        rating.drawn,
+                        rating.lost
+                    );
+                }
+            }
+        }
+        Output::Porcelain => {
+            for (preset, ratings) in &presets {
+                for rating in ratings {
+                    println!(
+                        "{preset}\t{}\t{:.0}\t{}\t{}\t{}",
+                        rating.engine, rating.rating, rating.won, rating.drawn, rating.lost
+                    );
+                }
+            }
+        }
+        Output::Json => {
+            let presets: Vec<_> = presets
+                .iter()
+                .map(|(preset, ratings)| {
+                    let ratings: Vec<_> = ratings
+                        .iter()
+                        .map(|rating| {
+                            Json::object([
+                                ("engine", rating.engine.as_str().into()),
+                                ("rating", rating.rating.into()),
+                                ("games", rating.games().into()),
+                                ("won", rating.won.into()),
+                                ("drawn", rating.drawn.into()),
+                                ("lost", rating.lost.into()),
+                            ])
+                        })
+                        .collect();
+                    Json::object([
+                        ("preset", preset.as_str().into()),
+                        ("ratings", ratings.into()),
+                    ])
+                })
+                .collect();
+            println!("{}", Json::from(presets));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rate_engines() {
+        assert_eq!(expected(1500., 1500.), 0.5);
+        assert!((expected(1900., 1500.) - 10. / 11.).abs() < 1e-12);
+        assert_eq!(rate(""), Ok(BTreeMap::new()));
+
+        let results = "played_at,preset,first,second,first_turns,second_turns\r\n\
+            1,mini,walker,solver-entropy,9,3\r\n\
+            1,mini,walker,solver-entropy,,3\r\n\
+            2,mini,solver-knuth,solver-entropy,3,3\r\n\
+            3,classic,walker,solver-entropy,2,5\r\n";
+        let presets = rate(results).unwrap();
+        assert_eq!(presets.keys().collect::<Vec<_>>(), ["classic", "mini"]);
+        let mini = &presets["mini"];
+        let engines: Vec<_> = mini.iter().map(|rating| rating.engine.as_str()).collect();
+        assert_eq!(engines, ["solver-entropy", "solver-knuth", "walker"]);
+        // the first game moves both by half of K, the second by a little less
+        let walker = &mini[2];
+        assert_eq!((walker.won, walker.drawn, walker.lost), (0, 0, 2));
+        let second = K * (1. - expected(INITIAL + K / 2., INITIAL - K / 2.));
+        assert!((walker.rating - (INITIAL - K / 2. - second)).abs() < 1e-9);
+        // a draw against a stronger engine gains
+        assert!(mini[1].rating > INITIAL);
+        assert_eq!(mini[1].drawn, 1);
+        // points are kept apart by preset
+        assert_eq!(presets["classic"][0].engine, "walker");
+        assert_eq!(presets["classic"][0].rating, INITIAL + K / 2.);
+
+        assert!(rate("a,b\r\n").is_err());
+        assert_eq!(
+            rate("played_at,preset,first,second,first_turns,second_turns\r\n1,mini,a,b,x,3\r\n"),
+            Err("line 2: invalid turns \"x\"".to_string())
+        );
+        assert!(
+            rate("played_at,preset,first,second,first_turns,second_turns\r\n1,mini\r\n").is_err()
+        );
+    }
+}
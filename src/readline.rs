@@ -0,0 +1,328 @@
+//! Line editing for interactive sessions: cursor movement, a history of previous
+//! inputs and tab completion of color names. Falls back to plain line reading if
+//! stdin is not a terminal.
+
+use std::{
+    io::{IsTerminal, Read, Write},
+    sync::Mutex,
+};
+
+use crate::palette::color_names;
+
+static HISTORY: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Changes the mode of the terminal and restores it when dropped.
+pub struct TerminalMode {
+    original: libc::termios,
+}
+
+impl TerminalMode {
+    /// Turns off the given local flags, e.g. `ECHO`, returns `None` if stdin is not a terminal.
+    pub fn without(flags: libc::tcflag_t) -> Option<Self> {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        // SAFETY: `termios` is a plain C struct which `tcgetattr` fills in.
+        unsafe {
+            let mut original = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return None;
+            }
+            let mut raw = original;
+            raw.c_lflag &= !flags;
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &raw) != 0 {
+                return None;
+            }
+            Some(Self { original })
+        }
+    }
+}
+
+impl Drop for TerminalMode {
+    fn drop(&mut self) {
+        // SAFETY: restores the settings read in `without`.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &self.original);
+        }
+    }
+}
+
+pub enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    Tab,
+    /// Ctrl-D
+    EndOfInput,
+    /// Ctrl-U
+    ClearToStart,
+    /// Ctrl-K
+    ClearToEnd,
+    Unknown,
+}
+
+fn read_byte(input: &mut impl Read) -> Option<u8> {
+    let mut byte = [0];
+    match input.read(&mut byte) {
+        Ok(1) => Some(byte[0]),
+        _ => None,
+    }
+}
+
+pub fn read_key(input: &mut impl Read) -> Option<Key> {
+    let key = match read_byte(input)? {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        127 | 8 => Key::Backspace,
+        1 => Key::Home,
+        4 => Key::EndOfInput,
+        5 => Key::End,
+        11 => Key::ClearToEnd,
+        21 => Key::ClearToStart,
+        0x1b => match (read_byte(input)?, read_byte(input)?) {
+            (b'[', b'A') => Key::Up,
+            (b'[', b'B') => Key::Down,
+            (b'[', b'C') => Key::Right,
+            (b'[', b'D') => Key::Left,
+            (b'[' | b'O', b'H') => Key::Home,
+            (b'[' | b'O', b'F') => Key::End,
+            (b'[', b'3') if read_byte(input)? == b'~' => Key::Delete,
+            _ => Key::Unknown,
+        },
+        byte if byte < 0x20 => Key::Unknown,
+        byte => {
+            // the number of continuation bytes is given by the leading ones of the first byte
+            let mut bytes = vec![byte];
+            for _ in 1..byte.leading_ones().max(1) {
+                bytes.push(read_byte(input)?);
+            }
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) => Key::Char(c),
+                None => Key::Unknown,
+            }
+        }
+    };
+    Some(key)
+}
+
+/// The result of completing the word in front of the cursor.
+#[derive(Debug, PartialEq, Eq)]
+enum Completion {
+    /// Text to insert at the cursor.
+    Insert(String),
+    /// The word is ambiguous and could be completed to any of these.
+    Ambiguous(Vec<String>),
+    None,
+}
+
+fn complete(word: &str, candidates: &[String]) -> Completion {
+    let word = word.to_lowercase();
+    let matches: Vec<&String> = candidates
+        .iter()
+        .filter(|candidate| candidate.to_lowercase().starts_with(&word))
+        .collect();
+    match matches.as_slice() {
+        [] => Completion::None,
+        [only] => Completion::Insert(format!(
+            "{} ",
+            only.chars().skip(word.chars().count()).collect::<String>()
+        )),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.to_lowercase(), |common, candidate| {
+                common
+                    .chars()
+                    .zip(candidate.to_lowercase().chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect()
+            });
+            if common.chars().count() > word.chars().count() {
+                Completion::Insert(common.chars().skip(word.chars().count()).collect())
+            } else {
+                Completion::Ambiguous(matches.into_iter().cloned().collect())
+            }
+        }
+    }
+}
+
+fn redraw(prompt: &str, line: &[char], cursor: usize) {
+    let text: String = line.iter().collect();
+    print!("\r{prompt}{text}\x1b[K");
+    if cursor < line.len() {
+        print!("\x1b[{}D", line.len() - cursor);
+    }
+    std::io::stdout().flush().unwrap();
+}
+
+fn edit_line(prompt: &str) -> Option<String> {
+    let mut stdin = std::io::stdin().lock();
+    let history = HISTORY.lock().unwrap().clone();
+    // index into the history while browsing it, `history.len()` is the line being edited
+    let mut position = history.len();
+    let mut edited = Vec::new();
+    let mut line: Vec<char> = Vec::new();
+    let mut cursor = 0;
+    redraw(prompt, &line, cursor);
+    loop {
+        match read_key(&mut stdin)? {
+            Key::Char(c) => {
+                line.insert(cursor, c);
+                cursor += 1;
+            }
+            Key::Enter => break,
+            Key::Backspace if cursor > 0 => {
+                cursor -= 1;
+                line.remove(cursor);
+            }
+            Key::Delete if cursor < line.len() => {
+                line.remove(cursor);
+            }
+            Key::EndOfInput if line.is_empty() => {
+                println!();
+                return None;
+            }
+            Key::EndOfInput if cursor < line.len() => {
+                line.remove(cursor);
+            }
+            Key::Left => cursor = cursor.saturating_sub(1),
+            Key::Right => cursor = (cursor + 1).min(line.len()),
+            Key::Home => cursor = 0,
+            Key::End => cursor = line.len(),
+            Key::ClearToStart => {
+                line.drain(..cursor);
+                cursor = 0;
+            }
+            Key::ClearToEnd => line.truncate(cursor),
+            Key::Up if position > 0 => {
+                if position == history.len() {
+                    edited = line.clone();
+                }
+                position -= 1;
+                line = history[position].chars().collect();
+                cursor = line.len();
+            }
+            Key::Down if position < history.len() => {
+                position += 1;
+                line = history
+                    .get(position)
+                    .map_or_else(|| edited.clone(), |entry| entry.chars().collect());
+                cursor = line.len();
+            }
+            Key::Tab => {
+                let start = line[..cursor]
+                    .iter()
+                    .rposition(|c| c.is_whitespace() || *c == ',')
+                    .map_or(0, |index| index + 1);
+                let word: String = line[start..cursor].iter().collect();
+                match complete(&word, color_names()) {
+                    Completion::Insert(text) => {
+                        for c in text.chars() {
+                            line.insert(cursor, c);
+                            cursor += 1;
+                        }
+                    }
+                    Completion::Ambiguous(matches) => println!("\n{}", matches.join("  ")),
+                    Completion::None => {}
+                }
+            }
+            _ => {}
+        }
+        redraw(prompt, &line, cursor);
+    }
+    println!();
+    Some(line.into_iter().collect())
+}
+
+fn read_plain_line(prompt: &str) -> Option<String> {
+    print!("{prompt}");
+    std::io::stdout().flush().unwrap();
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line),
+    }
+}
+
+/// Reads a line without showing it on the terminal and without adding it to the history.
+pub fn read_secret(prompt: &str) -> Option<String> {
+    let no_echo = TerminalMode::without(libc::ECHO);
+    let line = read_plain_line(prompt);
+    if no_echo.is_some() {
+        println!();
+    }
+    Some(line?.trim().to_string())
+}
+
+/// Reads a line from stdin, returns `None` once the input is closed.
+/// Non-empty lines are added to the history.
+pub fn read_line(prompt: &str) -> Option<String> {
+    let raw_mode = TerminalMode::without(libc::ICANON | libc::ECHO);
+    let line = match raw_mode {
+        Some(_) => edit_line(prompt)?,
+        None => read_plain_line(prompt)?,
+    };
+    drop(raw_mode);
+    let line = line.trim().to_string();
+    let mut history = HISTORY.lock().unwrap();
+    if !line.is_empty() && history.last() != Some(&line) {
+        history.push(line.clone());
+    }
+    Some(line)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn complete_color_names() {
+        let names = ["rot", "grün", "gelb", "grau"].map(String::from);
+        assert_eq!(complete("r", &names), Completion::Insert("ot ".to_string()));
+        assert_eq!(
+            complete("GE", &names),
+            Completion::Insert("lb ".to_string())
+        );
+        assert_eq!(
+            complete("g", &names),
+            Completion::Ambiguous(vec![
+                "grün".to_string(),
+                "gelb".to_string(),
+                "grau".to_string()
+            ])
+        );
+        assert_eq!(
+            complete("gr", &names),
+            Completion::Ambiguous(vec!["grün".to_string(), "grau".to_string()])
+        );
+        assert_eq!(complete("blau", &names), Completion::None);
+    }
+
+    #[test]
+    fn read_keys() {
+        let mut input: &[u8] = b"a\x1b[D\x1b[3~\xc3\xbc\t\x7f";
+        let keys: Vec<_> = std::iter::from_fn(|| read_key(&mut input)).collect();
+        assert!(matches!(
+            keys.as_slice(),
+            [
+                Key::Char('a'),
+                Key::Left,
+                Key::Delete,
+                Key::Char('ü'),
+                Key::Tab,
+                Key::Backspace
+            ]
+        ));
+    }
+}
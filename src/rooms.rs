@@ -0,0 +1,379 @@
+//! Rooms where people play each other on the server, one WebSocket per member at
+//! `/rooms/NAME/events`. A room is opened by its first member and closed with its last.
+//! Members join as spectators and send these messages:
+//!
+//! ```text
+//! maker            take the seat of the codemaker
+//! breaker          take the seat of the codebreaker
+//! solver           let the solver break the codes for as long as the room is open
+//! spectator        give up the seat
+//! code COLORS      the codemaker chooses the code, e.g. `code 0 1 1 2`
+//! guess COLORS     the codebreaker guesses
+//! ```
+//!
+//! Everyone in the room gets the events of the game, a JSON object with the name of the
+//! event in `event`: `joined`, `left` and `seat` with the `member` and their `role`,
+//! `ready` once the code is chosen, `turn` with the `guess` and its `feedback`, and
+//! `solved` with the `code` and the number of `turns`, after which the codemaker chooses
+//! the next code. Joining, a member gets a `room` event with their id in `you`, the
+//! `members` and the `turns` so far. Messages which can't be followed are answered with
+//! an `error` event to the sender only.
+
+use std::{io, sync::Arc};
+
+use crate::{json::Json, server::Limit, Entry, Guess, Rules, SimpleGuesser, Solver, SolverOptions};
+
+/// Sends an event with the fields of the JSON object to a member.
+pub type Sender = Arc<dyn Fn(&str, Json) -> io::Result<()> + Send + Sync>;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Maker,
+    Breaker,
+    Spectator,
+}
+
+impl Role {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Role::Maker => "maker",
+            Role::Breaker => "breaker",
+            Role::Spectator => "spectator",
+        }
+    }
+}
+
+struct Member {
+    id: u64,
+    role: Role,
+    send: Sender,
+}
+
+pub struct Room<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    rules: Rules,
+    /// How the solver is set up when it takes a seat.
+    options: SolverOptions,
+    members: Vec<Member>,
+    /// Breaks the code in place of a member.
+    solver: Option<SimpleGuesser<FIELDS, COLORS, PARTITIONS>>,
+    code: Option<Guess<FIELDS>>,
+    history: Vec<Entry<FIELDS>>,
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>
+    Room<FIELDS, COLORS, PARTITIONS>
+{
+    pub fn new(rules: Rules, options: SolverOptions) -> Self {
+        Self {
+            rules,
+            options,
+            members: vec![],
+            solver: None,
+            code: None,
+            history: vec![],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Sends the event to everyone in the room. Members who went away are noticed by the
+    /// threads reading from them.
+    fn broadcast(&self, event: &str, body: Json) {
+        for member in &self.members {
+            let _ = (member.send)(event, body.clone());
+        }
+    }
+
+    fn seat(id: u64, role: Role) -> Json {
+        Json::object([
+            ("member", (id as usize).into()),
+            ("role", role.name().into()),
+        ])
+    }
+
+    fn turn(entry: &Entry<FIELDS>) -> Json {
+        Json::object([
+            ("guess", entry.guess.into()),
+            ("feedback", entry.evaluation.into()),
+        ])
+    }
+
+    pub fn join(&mut self, id: u64, send: Sender) {
+        self.broadcast("joined", Self::seat(id, Role::Spectator));
+        self.members.push(Member {
+            id,
+            role: Role::Spectator,
+            send: Arc::clone(&send),
+        });
+        let mut members: Vec<_> = self
+            .members
+            .iter()
+            .map(|member| Self::seat(member.id, member.role))
+            .collect();
+        if self.solver.is_some() {
+            members.push(Json::object([
+                ("member", "solver".into()),
+                ("role", Role::Breaker.name().into()),
+            ]));
+        }
+        let turns: Vec<_> = self.history.iter().map(Self::turn).collect();
+        let _ = send(
+            "room",
+            Json::object([
+                ("you", (id as usize).into()),
+                ("members", members.into()),
+                ("turns", turns.into()),
+                ("ready", self.code.is_some().into()),
+            ]),
+        );
+    }
+
+    pub fn leave(&mut self, id: u64) {
+        if let Some(index) = self.members.iter().position(|member| member.id == id) {
+            let member = self.members.remove(index);
+            self.broadcast("left", Self::seat(id, member.role));
+        }
+    }
+
+    /// Follows the message of the member `id`, computing the guesses of the solver within
+    /// the limit of the server.
+    pub fn message(&mut self, id: u64, text: &str, guesses: &Limit) {
+        if let Err(err) = self.follow(id, text, guesses) {
+            if let Some(member) = self.members.iter().find(|member| member.id == id) {
+                let _ = (member.send)("error", Json::object([("error", err.into())]));
+            }
+        }
+    }
+
+    fn follow(&mut self, id: u64, text: &str, guesses: &Limit) -> Result<(), String> {
+        let role = self
+            .members
+            .iter()
+            .find(|member| member.id == id)
+            .map(|member| member.role)
+            .ok_or("you aren't in the room")?;
+        let (command, argument) = text.trim().split_once(' ').unwrap_or((text.trim(), ""));
+        match command {
+            "maker" | "breaker" | "spectator" => {
+                let wanted = match command {
+                    "maker" => Role::Maker,
+                    "breaker" => Role::Breaker,
+                    _ => Role::Spectator,
+                };
+                if wanted != Role::Spectator && self.taken(wanted) {
+                    return Err(format!("the seat of the {command} is taken"));
+                }
+                self.take(id, wanted);
+                Ok(())
+            }
+            "solver" => {
+                if self.taken(Role::Breaker) {
+                    return Err("the seat of the breaker is taken".to_string());
+                }
+                if role == Role::Maker {
+                    return Err("the codemaker can't break the code".to_string());
+                }
+                self.solver = Some(SimpleGuesser::with_options(self.rules, &self.options));
+                self.broadcast(
+                    "seat",
+                    Json::object([
+                        ("member", "solver".into()),
+                        ("role", Role::Breaker.name().into()),
+                    ]),
+                );
+                self.play_solver(guesses);
+                Ok(())
+            }
+            "code" => {
+                if role != Role::Maker {
+                    return Err("only the codemaker chooses the code".to_string());
+                }
+                if self.code.is_some() {
+                    return Err("the code is chosen already".to_string());
+                }
+                let code = self.parse(argument)?;
+                if !self.rules.is_legal_code::<FIELDS, COLORS>(&code) {
+                    return Err(format!("[{code}] can't be the code in this game"));
+                }
+                self.code = Some(code);
+                self.broadcast("ready", Json::object([]));
+                self.play_solver(guesses);
+                Ok(())
+            }
+            "guess" => {
+                if role != Role::Breaker {
+                    return Err("only the codebreaker guesses".to_string());
+                }
+                if self.code.is_none() {
+                    return Err("the code isn't chosen yet".to_string());
+                }
+                let guess = self.parse(argument)?;
+                if !self.rules.is_legal_turn::<FIELDS, COLORS>(&guess) {
+                    return Err(format!("[{guess}] can't be played in this game"));
+                }
+                self.play(guess);
+                Ok(())
+            }
+            _ => Err(format!("unknown message {command:?}")),
+        }
+    }
+
+    fn parse(&self, colors: &str) -> Result<Guess<FIELDS>, String> {
+        colors.parse().map_err(|err| format!("{err}"))
+    }
+
+    fn taken(&self, role: Role) -> bool {
+        (role == Role::Breaker && self.solver.is_some())
+            || self.members.iter().any(|member| member.role == role)
+    }
+
+    fn take(&mut self, id: u64, role: Role) {
+        if let Some(member) = self.members.iter_mut().find(|member| member.id == id) {
+            member.role = role;
+        }
+        self.broadcast("seat", Self::seat(id, role));
+    }
+
+    /// Plays `guess` against the code and starts the next game once it's solved.
+    fn play(&mut self, guess: Guess<FIELDS>) {
+        let Some(code) = self.code else {
+            return;
+        };
+        let evaluation = self.rules.evaluate(code, guess);
+        let entry = Entry { guess, evaluation };
+        self.history.push(entry);
+        let mut turn = Self::turn(&entry);
+        if let Json::Object(fields) = &mut turn {
+            fields.insert(0, ("turn".to_string(), self.history.len().into()));
+        }
+        self.broadcast("turn", turn);
+        if guess == code {
+            self.broadcast(
+                "solved",
+                Json::object([("code", code.into()), ("turns", self.history.len().into())]),
+            );
+            self.code = None;
+            self.history.clear();
+        }
+    }
+
+    /// Lets the solver break the code if it has the seat and the code is chosen.
+    fn play_solver(&mut self, guesses: &Limit) {
+        while self.code.is_some() {
+            let Some(solver) = &mut self.solver else {
+                return;
+            };
+            let report = {
+                let _entered = guesses.enter();
+                solver.guess(&self.history)
+            };
+            match report {
+                Ok(report) => self.play(report.guess),
+                Err(err) => {
+                    // only if the rules of a guess and a code differ, which they don't here
+                    self.broadcast("error", Json::object([("error", err.to_string().into())]));
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{max_gauss, Preset};
+
+    type Events = Arc<Mutex<Vec<String>>>;
+
+    fn member() -> (Events, Sender) {
+        let events = Events::default();
+        let sent = Arc::clone(&events);
+        let send: Sender = Arc::new(move |event: &str, body: Json| {
+            let mut fields = vec![("event".to_string(), Json::String(event.to_string()))];
+            if let Json::Object(body) = body {
+                fields.extend(body);
+            }
+            sent.lock().unwrap().push(Json::Object(fields).to_string());
+            Ok(())
+        });
+        (events, send)
+    }
+
+    fn last(events: &Events) -> String {
+        events.lock().unwrap().last().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn play_in_a_room() {
+        let limit = Limit::new(1);
+        let mut room =
+            Room::<4, 6, { max_gauss(4) }>::new(Preset::Classic.rules(), SolverOptions::default());
+        let (maker, send) = member();
+        room.join(1, send);
+        assert_eq!(
+            last(&maker),
+            "{\"event\":\"room\",\"you\":1,\"members\":[{\"member\":1,\"role\":\"spectator\"}],\
+            \"turns\":[],\"ready\":false}"
+        );
+        room.message(1, "maker", &limit);
+        let (breaker, send) = member();
+        room.join(2, send);
+        let (spectator, send) = member();
+        room.join(3, send);
+        assert_eq!(
+            last(&maker),
+            "{\"event\":\"joined\",\"member\":3,\"role\":\"spectator\"}"
+        );
+        room.message(2, "guess 0 0 1 1", &limit);
+        assert_eq!(
+            last(&breaker),
+            "{\"event\":\"error\",\"error\":\"only the codebreaker guesses\"}"
+        );
+        room.message(2, "breaker", &limit);
+        room.message(3, "maker", &limit);
+        assert!(last(&spectator).contains("taken"));
+        room.message(2, "guess 0 0 1 1", &limit);
+        assert!(last(&breaker).contains("the code isn't chosen yet"));
+        room.message(1, "code 0 1 6 2", &limit);
+        assert!(last(&maker).contains("can't be the code"));
+        room.message(1, "code 0 1 1 2", &limit);
+        assert_eq!(last(&spectator), "{\"event\":\"ready\"}");
+        room.message(2, "guess 0 0 1 1", &limit);
+        assert_eq!(
+            last(&spectator),
+            "{\"event\":\"turn\",\"turn\":1,\"guess\":[\"rot\",\"rot\",\"grün\",\"grün\"],\
+            \"feedback\":{\"exact\":2,\"white\":1}}"
+        );
+        room.message(2, "guess rot grün grün gelb", &limit);
+        assert_eq!(
+            last(&spectator),
+            "{\"event\":\"solved\",\"code\":[\"rot\",\"grün\",\"grün\",\"gelb\"],\"turns\":2}"
+        );
+        // the next game starts with the next code
+        room.message(2, "guess 0 0 1 1", &limit);
+        assert!(last(&breaker).contains("the code isn't chosen yet"));
+
+        // the solver takes the seat of the breaker
+        room.leave(2);
+        assert_eq!(
+            last(&maker),
+            "{\"event\":\"left\",\"member\":2,\"role\":\"breaker\"}"
+        );
+        room.message(3, "solver", &limit);
+        room.message(1, "code 5 4 3 3", &limit);
+        assert!(last(&spectator).starts_with("{\"event\":\"solved\""));
+        let events = spectator.lock().unwrap();
+        let turns = events
+            .iter()
+            .skip_while(|event| !event.contains("\"solver\""))
+            .filter(|event| event.starts_with("{\"event\":\"turn\""))
+            .count();
+        assert!((1..=5).contains(&turns));
+        assert!(!room.is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! Saving games to continue them later, e.g. a game on a physical board which was
+//! interrupted. A saved game is a history as read by `history::parse`, with colors as
+//! numbers and the preset and palette of the game in comments in front, so it can also be
+//! passed to `solve --history`:
+//!
+//! ```text
+//! # preset: classic
+//! # palette: rot, grün, gelb, blau, orange, pink
+//! 0 3 2 1 | 2 1
+//! ```
+
+use std::path::Path;
+
+use crate::{history, palette::color_names, preset::Preset, Entry, Rules};
+
+pub struct SavedGame {
+    pub preset: Preset,
+    pub palette: Vec<String>,
+    /// The turns, which can only be parsed for the board dimensions of the preset.
+    text: String,
+}
+
+impl SavedGame {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("can't read {}: {err}", path.display()))?;
+        Self::parse(text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    pub fn parse(text: String) -> Result<Self, String> {
+        let preset = value(&text, "preset")
+            .ok_or("the preset of the game is missing")?
+            .parse()
+            .map_err(|err| format!("{err}"))?;
+        let palette = match value(&text, "palette") {
+            Some(names) => names
+                .split(',')
+                .map(|name| name.trim().to_string())
+                .collect(),
+            None => return Err("the palette of the game is missing".to_string()),
+        };
+        Ok(Self {
+            preset,
+            palette,
+            text,
+        })
+    }
+
+    /// The value of a `# key: value` comment.
+    pub fn value(&self, key: &str) -> Option<&str> {
+        value(&self.text, key)
+    }
+
+    /// The comment after every turn, empty if there is none.
+    pub fn comments(&self) -> Vec<&str> {
+        self.text
+            .lines()
+            .filter_map(|line| {
+                let (turn, comment) = line.split_once('#').unwrap_or((line, ""));
+                (!turn.trim().is_empty()).then_some(comment.trim())
+            })
+            .collect()
+    }
+
+    pub fn history<const FIELDS: usize, const COLORS: u32>(
+        &self,
+    ) -> Result<Vec<Entry<FIELDS>>, String> {
+        if (self.preset.fields(), self.preset.colors()) != (FIELDS, COLORS) {
+            return Err(format!(
+                "the game was saved for the {} preset",
+                self.preset.name()
+            ));
+        }
+        history::parse::<FIELDS, COLORS>(self.preset.rules(), &self.text)
+            .map_err(|err| err.to_string())
+    }
+}
+
+fn value<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+    text.lines()
+        .find_map(|line| {
+            line.strip_prefix("# ")?
+                .strip_prefix(key)?
+                .strip_prefix(':')
+        })
+        .map(str::trim)
+}
+
+/// Writes the game with the current palette to `path`.
+pub fn write<const FIELDS: usize, const COLORS: u32>(
+    path: &Path,
+    rules: Rules,
+    history: &[Entry<FIELDS>],
+) -> Result<(), String> {
+    std::fs::write(path, format::<FIELDS, COLORS>(rules, history))
+        .map_err(|err| format!("can't write {}: {err}", path.display()))
+}
+
+fn format<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    history: &[Entry<FIELDS>],
+) -> String {
+    let mut text = header::<FIELDS, COLORS>(rules);
+    for entry in history {
+        text.push_str(&turn(entry));
+        text.push('\n');
+    }
+    text
+}
+
+/// The comments with the preset and the current palette which a saved game starts with.
+pub fn header<const FIELDS: usize, const COLORS: u32>(rules: Rules) -> String {
+    let preset = Preset::find(FIELDS, COLORS, rules)
+        .expect("games are played with the dimensions of a preset");
+    format!(
+        "# preset: {}\n# palette: {}\n",
+        preset.name(),
+        color_names().join(", ")
+    )
+}
+
+/// The line of a turn, without a line break.
+pub fn turn<const FIELDS: usize>(entry: &Entry<FIELDS>) -> String {
+    format!(
+        "{} | {} {}",
+        entry.guess.indices(),
+        entry.evaluation.exact,
+        entry.evaluation.correct_color
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Evaluation, Guess};
+
+    #[test]
+    fn save_and_resume() {
+        let history = vec![
+            Entry {
+                guess: Guess::new([0, 0, 1, 1]),
+                evaluation: Evaluation {
+                    correct_color: 1,
+                    exact: 1,
+                },
+            },
+            Entry {
+                guess: Guess::new([2, 1, 3, 4]),
+                evaluation: Evaluation {
+                    correct_color: 0,
+                    exact: 2,
+                },
+            },
+        ];
+        let text = format::<4, 6>(Preset::Classic.rules(), &history);
+        assert!(text.starts_with("# preset: classic\n# palette: "));
+        assert!(text.ends_with("\n0 0 1 1 | 1 1\n2 1 3 4 | 2 0\n"));
+
+        let saved = SavedGame::parse(text).unwrap();
+        assert_eq!(saved.preset, Preset::Classic);
+        assert_eq!(saved.palette, color_names());
+        assert_eq!(saved.history::<4, 6>(), Ok(history));
+        assert_eq!(saved.value("preset"), Some("classic"));
+        assert_eq!(saved.comments(), ["", ""]);
+        assert!(saved.history::<4, 10>().is_err());
+
+        assert!(SavedGame::parse("0 0 1 1 | 1 1".to_string()).is_err());
+        assert!(SavedGame::parse("# preset: huge\n# palette: a, b".to_string()).is_err());
+    }
+}
@@ -0,0 +1,537 @@
+//! Scoring functions written as scripts, so that strategies can be tried out from the
+//! command line without writing Rust: with `--script FILE` the solver scores every guess by
+//! an expression over its partition of the candidates instead of by its strategy. A script
+//! defines one name per line, `score` is the score of a guess and `ties` says which of
+//! equally good guesses is played:
+//!
+//! ```text
+//! # the fewest codes left on average, preferring guesses which may win
+//! expected = sum(count ^ 2) / total
+//! score = total - expected + possible / 2
+//! ties = first
+//! ```
+//!
+//! Expressions are made of numbers, `+ - * / ^`, parentheses, the names defined before and
+//! these variables and functions:
+//!
+//! - `total`: the number of candidates
+//! - `parts`: the number of feedbacks given by at least one candidate
+//! - `largest`: the number of candidates which give the most common feedback
+//! - `possible`: 1 if the guess is one of the candidates, otherwise 0
+//! - `entropy`: the information the guess is expected to give in bit
+//! - `sum(e)`: the sum of `e` over the feedbacks given by at least one candidate, with
+//!   `count` the number of candidates which give it
+//! - `log2(e)`, `sqrt(e)`, `min(a, b)` and `max(a, b)`
+//!
+//! Higher scores are better, they may be negative and a score which isn't a number, as of
+//! `0 / 0`, is the worst, as for every `ScoringFn`. With
+//! `ties = first` the first of equally good guesses in the order of `Rules::guesses` is
+//! played, as Knuth did, with `ties = last` (the default) the last one.
+
+use std::{collections::HashMap, fmt::Display, iter::Peekable, path::Path, str::Chars, sync::Arc};
+
+use crate::{compute_n_log2_n, ScoringFn};
+
+#[derive(Debug, PartialEq)]
+pub struct Script {
+    score: Expr,
+    /// Whether the first of equally good guesses is played instead of the last one.
+    pub first_of_ties: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScriptError {
+    /// The line the error is in, `None` for errors about the whole script.
+    line: Option<usize>,
+    message: String,
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Variable {
+    Total,
+    Parts,
+    Largest,
+    Possible,
+    Entropy,
+    /// The number of candidates giving the feedback `sum` is at.
+    Count,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Function {
+    Log2,
+    Sqrt,
+    Min,
+    Max,
+    Sum,
+}
+
+impl Function {
+    fn arguments(&self) -> usize {
+        match self {
+            Function::Log2 | Function::Sqrt | Function::Sum => 1,
+            Function::Min | Function::Max => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Number(f64),
+    Variable(Variable),
+    Negate(Box<Expr>),
+    Binary(Operator, Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+/// What the variables stand for when scoring a guess.
+struct Partition<'a> {
+    counts: &'a [u32],
+    total: f64,
+    possible: bool,
+}
+
+impl Expr {
+    fn evaluate(&self, partition: &Partition, count: f64) -> f64 {
+        match self {
+            Expr::Number(number) => *number,
+            Expr::Variable(variable) => match variable {
+                Variable::Total => partition.total,
+                Variable::Parts => {
+                    partition.counts.iter().filter(|count| **count > 0).count() as f64
+                }
+                Variable::Largest => partition.counts.iter().max().copied().unwrap_or(0) as f64,
+                Variable::Possible => partition.possible as u32 as f64,
+                Variable::Entropy if partition.total > 0. => {
+                    let partitions: f64 = partition
+                        .counts
+                        .iter()
+                        .map(|count| compute_n_log2_n(*count as usize))
+                        .sum();
+                    (compute_n_log2_n(partition.total as usize) - partitions) / partition.total
+                }
+                Variable::Entropy => 0.,
+                Variable::Count => count,
+            },
+            Expr::Negate(expr) => -expr.evaluate(partition, count),
+            Expr::Binary(operator, a, b) => {
+                let (a, b) = (a.evaluate(partition, count), b.evaluate(partition, count));
+                match operator {
+                    Operator::Add => a + b,
+                    Operator::Subtract => a - b,
+                    Operator::Multiply => a * b,
+                    Operator::Divide => a / b,
+                    Operator::Power => a.powf(b),
+                }
+            }
+            Expr::Call(Function::Sum, arguments) => partition
+                .counts
+                .iter()
+                .filter(|count| **count > 0)
+                .map(|count| arguments[0].evaluate(partition, *count as f64))
+                .sum(),
+            Expr::Call(function, arguments) => {
+                let argument = |index: usize| arguments[index].evaluate(partition, count);
+                match function {
+                    Function::Log2 => argument(0).log2(),
+                    Function::Sqrt => argument(0).sqrt(),
+                    Function::Min => argument(0).min(argument(1)),
+                    Function::Max => argument(0).max(argument(1)),
+                    Function::Sum => unreachable!("sums are evaluated above"),
+                }
+            }
+        }
+    }
+}
+
+/// Parses the expression of one line, replacing the names defined before by their
+/// expressions.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    definitions: &'a HashMap<String, Expr>,
+    /// How many `sum`s the parser is in, `count` is only defined in one.
+    sums: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    /// Consumes `c` if it is next.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&c).is_some()
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(match self.chars.peek() {
+                Some(found) => format!("expected `{c}`, found `{found}`"),
+                None => format!("expected `{c}`"),
+            })
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+        loop {
+            let operator = if self.eat('+') {
+                Operator::Add
+            } else if self.eat('-') {
+                Operator::Subtract
+            } else {
+                return Ok(expr);
+            };
+            expr = Expr::Binary(operator, Box::new(expr), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        loop {
+            let operator = if self.eat('*') {
+                Operator::Multiply
+            } else if self.eat('/') {
+                Operator::Divide
+            } else {
+                return Ok(expr);
+            };
+            expr = Expr::Binary(operator, Box::new(expr), Box::new(self.unary()?));
+        }
+    }
+
+    /// A negation binds weaker than a power, `-x ^ 2` is `-(x ^ 2)`.
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat('-') {
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        let base = self.atom()?;
+        if self.eat('^') {
+            return Ok(Expr::Binary(
+                Operator::Power,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        if self.eat('(') {
+            let expr = self.expression()?;
+            self.expect(')')?;
+            return Ok(expr);
+        }
+        match self.chars.peek() {
+            Some(c) if c.is_ascii_digit() || *c == '.' => {
+                let mut number = String::new();
+                while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                    number.push(c);
+                }
+                number
+                    .parse()
+                    .map(Expr::Number)
+                    .map_err(|_| format!("invalid number `{number}`"))
+            }
+            Some(c) if c.is_alphabetic() || *c == '_' => {
+                let name = self.name();
+                self.reference(&name)
+            }
+            Some(c) => Err(format!("unexpected `{c}`")),
+            None => Err("unexpected end of line".to_string()),
+        }
+    }
+
+    fn name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+            name.push(c);
+        }
+        name
+    }
+
+    fn reference(&mut self, name: &str) -> Result<Expr, String> {
+        if let Some(function) = function(name) {
+            self.expect('(')?;
+            self.sums += (function == Function::Sum) as usize;
+            let mut arguments = vec![self.expression()?];
+            while self.eat(',') {
+                arguments.push(self.expression()?);
+            }
+            self.sums -= (function == Function::Sum) as usize;
+            self.expect(')')?;
+            if arguments.len() != function.arguments() {
+                return Err(format!(
+                    "`{name}` takes {} arguments, not {}",
+                    function.arguments(),
+                    arguments.len()
+                ));
+            }
+            return Ok(Expr::Call(function, arguments));
+        }
+        match variable(name) {
+            Some(Variable::Count) if self.sums == 0 => {
+                Err("`count` is only defined in `sum`".to_string())
+            }
+            Some(variable) => Ok(Expr::Variable(variable)),
+            None => self
+                .definitions
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("`{name}` isn't defined")),
+        }
+    }
+}
+
+fn variable(name: &str) -> Option<Variable> {
+    Some(match name {
+        "total" => Variable::Total,
+        "parts" => Variable::Parts,
+        "largest" => Variable::Largest,
+        "possible" => Variable::Possible,
+        "entropy" => Variable::Entropy,
+        "count" => Variable::Count,
+        _ => return None,
+    })
+}
+
+fn function(name: &str) -> Option<Function> {
+    Some(match name {
+        "log2" => Function::Log2,
+        "sqrt" => Function::Sqrt,
+        "min" => Function::Min,
+        "max" => Function::Max,
+        "sum" => Function::Sum,
+        _ => return None,
+    })
+}
+
+impl Script {
+    pub fn parse(text: &str) -> Result<Self, ScriptError> {
+        let mut definitions = HashMap::new();
+        let mut first_of_ties = false;
+        for (index, line) in text.lines().enumerate() {
+            let error = |message| ScriptError {
+                line: Some(index + 1),
+                message,
+            };
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Some((name, value)) = line.split_once('=') else {
+                return Err(error("expected `name = expression`".to_string()));
+            };
+            let (name, value) = (name.trim(), value.trim());
+            if name == "ties" {
+                first_of_ties = match value {
+                    "first" => true,
+                    "last" => false,
+                    _ => {
+                        return Err(error(
+                            "expected `ties = first` or `ties = last`".to_string(),
+                        ))
+                    }
+                };
+                continue;
+            }
+            if name.is_empty()
+                || name.starts_with(|c: char| c.is_ascii_digit())
+                || !name.chars().all(|c| c.is_alphanumeric() || c == '_')
+            {
+                return Err(error(format!("invalid name `{name}`")));
+            }
+            if variable(name).is_some() || function(name).is_some() {
+                return Err(error(format!("`{name}` is built in")));
+            }
+            if definitions.contains_key(name) {
+                return Err(error(format!("`{name}` is defined twice")));
+            }
+            let mut parser = Parser {
+                chars: value.chars().peekable(),
+                definitions: &definitions,
+                sums: 0,
+            };
+            let expr = parser.expression().map_err(error)?;
+            parser.skip_whitespace();
+            if let Some(c) = parser.chars.peek() {
+                return Err(error(format!("unexpected `{c}`")));
+            }
+            definitions.insert(name.to_string(), expr);
+        }
+        let score = definitions.remove("score").ok_or_else(|| ScriptError {
+            line: None,
+            message: "the script doesn't define `score`".to_string(),
+        })?;
+        Ok(Script {
+            score,
+            first_of_ties,
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text =
+            std::fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        Self::parse(&text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    /// The score of a guess with the partition `counts` of `total` candidates.
+    pub fn score<const FIELDS: usize>(&self, counts: &[u32], total: usize) -> f64 {
+        let partition = Partition {
+            counts,
+            total: total as f64,
+            possible: counts[FIELDS] > 0,
+        };
+        self.score.evaluate(&partition, 0.)
+    }
+
+    pub fn scoring<const FIELDS: usize, const PARTITIONS: usize>(
+        self: &Arc<Self>,
+    ) -> ScoringFn<PARTITIONS> {
+        let script = Arc::clone(self);
+        Arc::new(move |counts: &[u32; PARTITIONS], total| script.score::<FIELDS>(counts, total))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        max_gauss, minimax, strategy, Entry, Guess, Preset, SimpleGuesser, Solver, SolverOptions,
+    };
+
+    #[test]
+    fn score_by_a_script() {
+        let script = Script::parse(
+            "# the fewest codes left on average\n\
+             expected = sum(count ^ 2) / total  # codes left\n\
+             \n\
+             score = total - expected + possible / 2\n\
+             ties = first\n",
+        )
+        .unwrap();
+        assert!(script.first_of_ties);
+        let mut counts = [0; max_gauss(4)];
+        counts[..5].copy_from_slice(&[2, 5, 0, 0, 1]);
+        assert_eq!(script.score::<4>(&counts, 8), 8. - 30. / 8. + 0.5);
+
+        // the measures of the strategies written as scripts
+        let entropy = Script::parse("score = sum(count * log2(total / count)) / total").unwrap();
+        let builtin = Script::parse("score = entropy").unwrap();
+        let knuth = Script::parse("score = total - largest + 0.5 * min(possible, 1)").unwrap();
+        let parts = Script::parse("score = parts + -(-2) ^ -1 * possible").unwrap();
+        assert!(!knuth.first_of_ties);
+        for counts in [[3, 0, 12, 1, 7], [0, 0, 0, 0, 1], [4, 4, 4, 4, 0]] {
+            let mut partition = [0; max_gauss(4)];
+            partition[..5].copy_from_slice(&counts);
+            let total = counts.iter().sum::<u32>() as usize;
+            // the winning bonus aside
+            let expected = strategy::entropy::<4, { max_gauss(4) }>(&partition, total);
+            if total > 1 {
+                assert!((entropy.score::<4>(&partition, total) - expected).abs() < 1e-12);
+                assert_eq!(builtin.score::<4>(&partition, total), expected);
+            }
+            assert_eq!(
+                knuth.score::<4>(&partition, total),
+                minimax::<4, { max_gauss(4) }>(&partition, total)
+            );
+            assert_eq!(
+                parts.score::<4>(&partition, total),
+                strategy::most_parts::<4, { max_gauss(4) }>(&partition, total)
+            );
+        }
+    }
+
+    #[test]
+    fn score_negative() {
+        let rules = Preset::Mini.rules();
+        let guesser = |script: &str| {
+            let options = SolverOptions {
+                script: Some(Arc::new(Script::parse(script).unwrap())),
+                ..Default::default()
+            };
+            SimpleGuesser::<3, 4, { max_gauss(3) }>::with_options(rules, &options)
+        };
+        // past the opening, which isn't reported
+        let opening = Guess::new([0, 1, 2]);
+        let history = [Entry {
+            guess: opening,
+            evaluation: rules.evaluate(Guess::new([1, 2, 3]), opening),
+        }];
+        let mut negative = guesser("score = -largest");
+        let observed = Arc::new(std::sync::Mutex::new(vec![]));
+        let observer = observed.clone();
+        negative.observer = Some(Arc::new(move |_, score| {
+            observer.lock().unwrap().push(score);
+        }));
+        let report = Solver::guess(&mut negative, &history).unwrap();
+        let largest = negative
+            .candidates()
+            .iter()
+            .map(|code| rules.evaluate(*code, report.guess).to_u32())
+            .fold([0; max_gauss(3)], |mut counts, index| {
+                counts[index as usize] += 1;
+                counts
+            })
+            .into_iter()
+            .max()
+            .unwrap();
+        assert_eq!(report.score, -(largest as f64));
+        assert_eq!(observed.lock().unwrap().last(), Some(&report.score));
+        // the same guess as for the positive scores
+        let positive = Solver::guess(&mut guesser("score = total - largest"), &history);
+        assert_eq!(positive.unwrap().guess, report.guess);
+
+        // no number at all is the worst score, but still a guess is played
+        let report = Solver::guess(&mut guesser("score = 0 / 0"), &history).unwrap();
+        assert_eq!(report.score, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn reject_invalid_scripts() {
+        let error = |text| Script::parse(text).unwrap_err().to_string();
+        assert_eq!(error("ties = first"), "the script doesn't define `score`");
+        assert_eq!(error("score = total +"), "line 1: unexpected end of line");
+        assert_eq!(
+            error("\nscore = count"),
+            "line 2: `count` is only defined in `sum`"
+        );
+        assert_eq!(error("score = spread"), "line 1: `spread` isn't defined");
+        assert_eq!(
+            error("score = log2(1, 2)"),
+            "line 1: `log2` takes 1 arguments, not 2"
+        );
+        assert_eq!(error("score = (total"), "line 1: expected `)`");
+        assert_eq!(error("score = total total"), "line 1: unexpected `t`");
+        assert_eq!(error("score = 1.2.3"), "line 1: invalid number `1.2.3`");
+        assert_eq!(error("total = 1"), "line 1: `total` is built in");
+        assert_eq!(error("a = 1\na = 2"), "line 2: `a` is defined twice");
+        assert_eq!(error("2a = 1"), "line 1: invalid name `2a`");
+        assert_eq!(error("score"), "line 1: expected `name = expression`");
+        assert_eq!(
+            error("ties = random"),
+            "line 1: expected `ties = first` or `ties = last`"
+        );
+    }
+}
@@ -0,0 +1,998 @@
+//! A small HTTP server which lets other programs play with the solver, answering in JSON:
+//!
+//! ```text
+//! GET    /                      a page to play in the browser, from the web directory
+//! POST   /games                 starts a game, answers with its id and the color names
+//! GET    /games/ID              the turns so far and the number of candidates
+//! GET    /games/ID/guess        the solver's next guess
+//! POST   /games/ID/feedback     adds turns, one `guess | feedback` line each
+//! GET    /games/ID/candidates   the codes which are still possible
+//! DELETE /games/ID              ends the game
+//! GET    /games/ID/events       a WebSocket for the game, see below
+//! GET    /rooms/NAME/events     a WebSocket to play others in a room, see src/rooms.rs
+//! GET    /metrics               metrics for Prometheus in its text format, see src/metrics.rs
+//! ```
+//!
+//! Every connection is answered on its own thread, so a long guess only holds up requests
+//! for the same game. At most `MAX_CONNECTIONS` are answered at once, the others wait. Each game keeps its own turns and solver, and games which nobody
+//! asked about for `IDLE` are forgotten, unless a request or a WebSocket is using them.
+//! Clients which stall for `TIMEOUT` in the middle of a request or an answer are hung up on.
+//! At most `MAX_SESSIONS` games are kept, and at most as many guesses are computed at once
+//! as the machine has cores, the others wait for their turn. Requests from pages of other
+//! origins are turned down, so that a web page can't play on a server on the same machine.
+//!
+//! On the WebSocket the solver thinks out loud: sending `guess` answers with a
+//! `candidates` event, a `best` event for every better guess it finds while scoring and a
+//! `guess` event with the guess it chose. Any other message is read as feedback and
+//! answered like `POST /games/ID/feedback`, as a `feedback` or an `error` event.
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    history,
+    json::Json,
+    metrics::Metrics,
+    palette::color_names,
+    rooms::{Room, Sender},
+    websocket::{self, Opcode},
+    Entry, Rules, SimpleGuesser, Solver, SolverOptions,
+};
+
+/// At most this many candidates are listed, the count is always given.
+const LIST_CANDIDATES: usize = 100;
+/// Requests with larger bodies are turned down.
+const MAX_BODY: usize = 1 << 16;
+/// Requests with a longer request line and headers are turned down.
+const MAX_HEAD: u64 = 1 << 13;
+/// The files of the page the server shows browsers, with their content types.
+const PAGE: [(&str, &str, &str); 3] = [
+    (
+        "/",
+        "text/html; charset=utf-8",
+        include_str!("../web/index.html"),
+    ),
+    (
+        "/app.js",
+        "text/javascript; charset=utf-8",
+        include_str!("../web/app.js"),
+    ),
+    (
+        "/style.css",
+        "text/css; charset=utf-8",
+        include_str!("../web/style.css"),
+    ),
+];
+/// Games nobody asked about for this long are ended.
+const IDLE: Duration = Duration::from_secs(30 * 60);
+/// Clients which send or read nothing for this long are hung up on, unless they opened a
+/// WebSocket, which may wait for `IDLE`.
+const TIMEOUT: Duration = Duration::from_secs(30);
+/// Further connections wait until one of these is closed.
+const MAX_CONNECTIONS: usize = 1024;
+/// Starting more games is turned down.
+const MAX_SESSIONS: usize = 10_000;
+/// Rooms have names of at most this many letters, digits, `-` and `_`.
+const MAX_ROOM_NAME: usize = 64;
+
+struct Session<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    guesser: SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+    history: Vec<Entry<FIELDS>>,
+}
+
+type Shared<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> =
+    Arc<Mutex<Session<FIELDS, COLORS, PARTITIONS>>>;
+
+/// A game and when it was last asked about.
+struct Slot<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    session: Shared<FIELDS, COLORS, PARTITIONS>,
+    used: Instant,
+}
+
+type Sessions<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> =
+    HashMap<u64, Slot<FIELDS, COLORS, PARTITIONS>>;
+
+struct Server<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    rules: Rules,
+    /// How the solvers of the games and rooms are set up.
+    options: SolverOptions,
+    sessions: Mutex<Sessions<FIELDS, COLORS, PARTITIONS>>,
+    next_id: AtomicU64,
+    /// How long a game is kept without requests, `IDLE` outside of tests.
+    idle: Duration,
+    /// Guesses which may be computed at once.
+    guesses: Limit,
+    metrics: Mutex<Metrics>,
+    rooms: Mutex<HashMap<String, Arc<Mutex<Room<FIELDS, COLORS, PARTITIONS>>>>>,
+    /// The id of the next member of a room.
+    next_member: AtomicU64,
+}
+
+/// Lets at most a number of threads in at once, the others wait.
+pub struct Limit {
+    free: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl Limit {
+    pub fn new(count: usize) -> Self {
+        Self {
+            free: Mutex::new(count),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Waits until fewer than the limit are in, and lets the caller in until the guard is
+    /// dropped.
+    pub fn enter(&self) -> impl Drop + '_ {
+        let mut free = self.free.lock().unwrap_or_else(|err| err.into_inner());
+        while *free == 0 {
+            free = self.freed.wait(free).unwrap_or_else(|err| err.into_inner());
+        }
+        *free -= 1;
+        Entered(self)
+    }
+}
+
+struct Entered<'a>(&'a Limit);
+
+impl Drop for Entered<'_> {
+    fn drop(&mut self) {
+        *self.0.free.lock().unwrap_or_else(|err| err.into_inner()) += 1;
+        self.0.freed.notify_one();
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// An answer with its HTTP status code.
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: Json,
+}
+
+impl Response {
+    fn ok(body: Json) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: Json::object([("error", Json::String(message.into()))]),
+        }
+    }
+}
+
+/// Answers requests on `port` of the local machine until the process is stopped.
+pub fn serve<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    port: u16,
+) -> Result<(), String> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|err| format!("can't listen on port {port}: {err}"))?;
+    info!("listening on http://{}:{port}", Ipv4Addr::LOCALHOST);
+    let server = Server::<FIELDS, COLORS, PARTITIONS>::new(rules, options.clone());
+    let connections = Limit::new(MAX_CONNECTIONS);
+    std::thread::scope(|scope| {
+        loop {
+            // waits before accepting, so the others are left queued with the system
+            let entered = connections.enter();
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(err) => {
+                    debug!("can't accept a connection: {err}");
+                    continue;
+                }
+            };
+            let timeouts = stream
+                .set_read_timeout(Some(TIMEOUT))
+                .and_then(|()| stream.set_write_timeout(Some(TIMEOUT)));
+            if let Err(err) = timeouts {
+                debug!("can't set the timeouts of a connection: {err}");
+                continue;
+            }
+            let server = &server;
+            scope.spawn(move || {
+                let _entered = entered;
+                if let Err(err) = server.answer(stream) {
+                    debug!("can't answer a request: {err}");
+                }
+            });
+        }
+    })
+}
+
+/// Reads a request, the body is as long as `Content-Length` says.
+fn read_request(reader: &mut impl BufRead) -> Result<Request, Response> {
+    let bad = |err: String| Response::error(400, err);
+    let mut head = reader.by_ref().take(MAX_HEAD);
+    let mut read_line = |line: &mut String| {
+        line.clear();
+        head.read_line(line).map_err(|err| bad(err.to_string()))?;
+        if !line.ends_with('\n') && head.limit() == 0 {
+            return Err(Response::error(
+                431,
+                format!("the request line and headers take more than {MAX_HEAD} bytes"),
+            ));
+        }
+        Ok(())
+    };
+    let mut line = String::new();
+    read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(bad(format!("invalid request line {line:?}")));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+    let mut headers = vec![];
+    loop {
+        read_line(&mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: String::new(),
+    };
+    let length: usize = match request.header("content-length") {
+        Some(length) => length
+            .parse()
+            .map_err(|_| bad(format!("invalid content length {length:?}")))?,
+        None => 0,
+    };
+    if length > MAX_BODY {
+        return Err(Response::error(
+            413,
+            format!("the body of {length} bytes is too large"),
+        ));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|err| bad(err.to_string()))?;
+    request.body = String::from_utf8(body).map_err(|_| bad("the body isn't UTF-8".to_string()))?;
+    Ok(request)
+}
+
+fn write_response(writer: &mut impl Write, response: &Response) -> std::io::Result<()> {
+    let body = response.body.to_string();
+    write_body(writer, response.status, "application/json", &body)
+}
+
+fn write_body(
+    writer: &mut impl Write,
+    status: u16,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Content Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "",
+    };
+    write!(
+        writer,
+        "HTTP/1.1 {status} {reason}\r\n\
+        Content-Type: {content_type}\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    writer.flush()
+}
+
+/// Whether the request doesn't come from a page of another origin. Browsers send the
+/// origin with WebSockets and requests which may change something, other clients usually
+/// don't send one.
+fn same_origin(request: &Request) -> bool {
+    let Some(origin) = request.header("origin") else {
+        return true;
+    };
+    let host = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"));
+    host.is_some() && host == request.header("host")
+}
+
+/// The key of the client, if the request opens a WebSocket.
+fn websocket_key(request: &Request) -> Option<String> {
+    let key = request.header("sec-websocket-key")?;
+    let upgrade = request.header("upgrade")?;
+    (request.method == "GET" && upgrade.eq_ignore_ascii_case("websocket")).then(|| key.to_string())
+}
+
+/// Accepts the WebSocket the client asked for with `key`, and sends events on it.
+fn accept(stream: &TcpStream, key: &str) -> io::Result<Sender> {
+    write!(
+        &mut &*stream,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+        Upgrade: websocket\r\n\
+        Connection: Upgrade\r\n\
+        Sec-WebSocket-Accept: {}\r\n\r\n",
+        websocket::accept_key(key)
+    )?;
+    // waiting for the next message, or for the others in a room, is fine
+    stream.set_read_timeout(Some(IDLE))?;
+    // the solver sends its events from the threads it scores on
+    let writer = Mutex::new(stream.try_clone()?);
+    Ok(Arc::new(move |event: &str, body: Json| {
+        let mut fields = vec![("event".to_string(), Json::String(event.to_string()))];
+        if let Json::Object(body) = body {
+            fields.extend(body);
+        }
+        let text = Json::Object(fields).to_string();
+        let mut writer = writer.lock().unwrap_or_else(|err| err.into_inner());
+        websocket::write_frame(&mut *writer, Opcode::Text, text.as_bytes())
+    }))
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>
+    Server<FIELDS, COLORS, PARTITIONS>
+{
+    fn new(rules: Rules, options: SolverOptions) -> Self {
+        Self {
+            rules,
+            options,
+            sessions: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            idle: IDLE,
+            guesses: Limit::new(
+                std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
+            ),
+            metrics: Mutex::new(Metrics::default()),
+            rooms: Mutex::new(HashMap::new()),
+            next_member: AtomicU64::new(1),
+        }
+    }
+
+    fn answer(&self, stream: TcpStream) -> std::io::Result<()> {
+        let mut reader = BufReader::new(&stream);
+        let response = match read_request(&mut reader) {
+            Ok(request) if !same_origin(&request) => {
+                Response::error(403, "requests from other origins are not answered")
+            }
+            Ok(request) => {
+                debug!("{} {}", request.method, request.path);
+                if let Some((key, session)) = self.upgrade(&request) {
+                    return self.events(reader, &stream, &key, session);
+                }
+                if let Some((key, name)) = self.upgrade_room(&request) {
+                    return self.room_events(reader, &stream, &key, &name);
+                }
+                let file = PAGE.iter().find(|(path, ..)| *path == request.path);
+                if let (Some((_, content_type, body)), "GET") = (file, request.method.as_str()) {
+                    return write_body(&mut &stream, 200, content_type, body);
+                }
+                if request.method == "GET" && request.path == "/metrics" {
+                    let metrics = self.metrics();
+                    let content_type = "text/plain; version=0.0.4";
+                    return write_body(&mut &stream, 200, content_type, &metrics);
+                }
+                self.route(&request)
+            }
+            Err(response) => response,
+        };
+        write_response(&mut &stream, &response)
+    }
+
+    /// The key of the client and the session, if the request opens the WebSocket of a game.
+    fn upgrade(&self, request: &Request) -> Option<(String, Shared<FIELDS, COLORS, PARTITIONS>)> {
+        let key = websocket_key(request)?;
+        let id = request
+            .path
+            .strip_prefix("/games/")?
+            .strip_suffix("/events")?
+            .parse()
+            .ok()?;
+        Some((key, self.session(id)?))
+    }
+
+    /// The key of the client and the name of the room, if the request opens the WebSocket
+    /// of a room.
+    fn upgrade_room(&self, request: &Request) -> Option<(String, String)> {
+        let key = websocket_key(request)?;
+        let name = request
+            .path
+            .strip_prefix("/rooms/")?
+            .strip_suffix("/events")?;
+        let valid = name.len() <= MAX_ROOM_NAME
+            && !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        valid.then(|| (key, name.to_string()))
+    }
+
+    /// Plays in the room `name` until the client closes the WebSocket.
+    fn room_events(
+        &self,
+        mut reader: impl BufRead,
+        stream: &TcpStream,
+        key: &str,
+        name: &str,
+    ) -> io::Result<()> {
+        let send = accept(stream, key)?;
+        let id = self.next_member.fetch_add(1, Ordering::Relaxed);
+        let room = {
+            // joins while holding the rooms, so the last member can't close the room first
+            let mut rooms = self.rooms.lock().unwrap_or_else(|err| err.into_inner());
+            let room = rooms.entry(name.to_string()).or_insert_with(|| {
+                Arc::new(Mutex::new(Room::new(self.rules, self.options.clone())))
+            });
+            room.lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .join(id, send);
+            Arc::clone(room)
+        };
+        let lock = || room.lock().unwrap_or_else(|err| err.into_inner());
+        let result = loop {
+            let frame = match websocket::read_frame(&mut reader) {
+                Ok(frame) => frame,
+                Err(err) => break Err(err),
+            };
+            match frame.opcode {
+                Opcode::Close => break websocket::write_frame(&mut &*stream, Opcode::Close, &[]),
+                Opcode::Ping => {
+                    if let Err(err) =
+                        websocket::write_frame(&mut &*stream, Opcode::Pong, &frame.payload)
+                    {
+                        break Err(err);
+                    }
+                }
+                Opcode::Pong => {}
+                Opcode::Text => {
+                    let text = String::from_utf8_lossy(&frame.payload);
+                    lock().message(id, &text, &self.guesses);
+                }
+            }
+        };
+        let mut rooms = self.rooms.lock().unwrap_or_else(|err| err.into_inner());
+        let mut room = lock();
+        room.leave(id);
+        if room.is_empty() {
+            rooms.remove(name);
+        }
+        result
+    }
+
+    /// Answers the messages on the WebSocket of a game until the client closes it.
+    fn events(
+        &self,
+        mut reader: impl BufRead,
+        stream: &TcpStream,
+        key: &str,
+        session: Shared<FIELDS, COLORS, PARTITIONS>,
+    ) -> io::Result<()> {
+        let send = accept(stream, key)?;
+        loop {
+            let frame = websocket::read_frame(&mut reader)?;
+            match frame.opcode {
+                Opcode::Close => {
+                    return websocket::write_frame(&mut &*stream, Opcode::Close, &[]);
+                }
+                Opcode::Ping => {
+                    websocket::write_frame(&mut &*stream, Opcode::Pong, &frame.payload)?
+                }
+                Opcode::Pong => {}
+                Opcode::Text => {
+                    let text = String::from_utf8_lossy(&frame.payload);
+                    let mut session = session.lock().unwrap_or_else(|err| err.into_inner());
+                    if text.trim() == "guess" {
+                        let send = Arc::clone(&send);
+                        self.think(&mut session, move |event, body| send(event, body))?;
+                    } else {
+                        let response = self.feedback(&mut session, &text);
+                        match response.status {
+                            200 => send("feedback", response.body)?,
+                            _ => send("error", response.body)?,
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Guesses and sends the events of it.
+    fn think(
+        &self,
+        session: &mut Session<FIELDS, COLORS, PARTITIONS>,
+        send: impl Fn(&str, Json) -> io::Result<()> + Clone + Send + Sync + 'static,
+    ) -> io::Result<()> {
+        self.update_candidates(session);
+        let count = session.guesser.candidates().len();
+        send("candidates", Json::object([("count", count.into())]))?;
+        let observe = send.clone();
+        session.guesser.observer = Some(Arc::new(move |guess, score| {
+            let best = Json::object([("guess", guess.into()), ("score", score.into())]);
+            // a client which went away is noticed when the guess is sent
+            let _ = observe("best", best);
+        }));
+        let response = self.guess(session);
+        session.guesser.observer = None;
+        send("guess", response.body)
+    }
+
+    fn route(&self, request: &Request) -> Response {
+        let path = request.path.split('?').next().unwrap_or_default();
+        let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let method = request.method.as_str();
+        match segments[..] {
+            ["games"] => match method {
+                "POST" => self.create(),
+                _ => Response::error(405, "use POST to start a game"),
+            },
+            ["games", id, ref rest @ ..] => {
+                let Some((id, session)) =
+                    id.parse().ok().and_then(|id| Some((id, self.session(id)?)))
+                else {
+                    return Response::error(404, format!("there is no game {id}"));
+                };
+                let mut session = session.lock().unwrap_or_else(|err| err.into_inner());
+                match (method, rest) {
+                    ("GET", []) => self.status(&mut session),
+                    ("DELETE", []) => {
+                        self.lock().remove(&id);
+                        Response::ok(Json::object([]))
+                    }
+                    ("GET", ["guess"]) => self.guess(&mut session),
+                    ("POST", ["feedback"]) => self.feedback(&mut session, &request.body),
+                    ("GET", ["candidates"]) => self.candidates(&mut session),
+                    (_, [] | ["guess" | "feedback" | "candidates"]) => {
+                        Response::error(405, format!("{method} isn't supported here"))
+                    }
+                    _ => Response::error(404, format!("{path} doesn't exist")),
+                }
+            }
+            _ => Response::error(404, format!("{path} doesn't exist")),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Sessions<FIELDS, COLORS, PARTITIONS>> {
+        self.sessions.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn metrics(&self) -> String {
+        let sessions = self.lock().len();
+        self.record().render(sessions)
+    }
+
+    fn record(&self) -> std::sync::MutexGuard<'_, Metrics> {
+        self.metrics.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// The game `id`, which counts as used now.
+    fn session(&self, id: u64) -> Option<Shared<FIELDS, COLORS, PARTITIONS>> {
+        let mut sessions = self.lock();
+        self.expire(&mut sessions);
+        let slot = sessions.get_mut(&id)?;
+        slot.used = Instant::now();
+        Some(Arc::clone(&slot.session))
+    }
+
+    /// Ends the games which were idle for too long and aren't in use.
+    fn expire(&self, sessions: &mut Sessions<FIELDS, COLORS, PARTITIONS>) {
+        let now = Instant::now();
+        sessions.retain(|id, slot| {
+            let keep = now - slot.used < self.idle || Arc::strong_count(&slot.session) > 1;
+            if !keep {
+                debug!("game {id} expired");
+                self.record().expired += 1;
+            }
+            keep
+        });
+    }
+
+    fn create(&self) -> Response {
+        let mut sessions = self.lock();
+        self.expire(&mut sessions);
+        if sessions.len() >= MAX_SESSIONS {
+            return Response::error(503, "too many games are running, try again later");
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let session = Session {
+            guesser: SimpleGuesser::with_options(self.rules, &self.options),
+            history: vec![],
+        };
+        sessions.insert(
+            id,
+            Slot {
+                session: Arc::new(Mutex::new(session)),
+                used: Instant::now(),
+            },
+        );
+        self.record().started += 1;
+        Response {
+            status: 201,
+            body: Json::object([
+                ("id", (id as usize).into()),
+                ("fields", FIELDS.into()),
+                ("colors", COLORS.into()),
+                ("duplicates", self.rules.duplicates.into()),
+                ("palette", color_names()[..COLORS as usize].to_vec().into()),
+            ]),
+        }
+    }
+
+    fn status(&self, session: &mut Session<FIELDS, COLORS, PARTITIONS>) -> Response {
+        let turns: Vec<_> = session
+            .history
+            .iter()
+            .map(|entry| {
+                Json::object([
+                    ("guess", entry.guess.into()),
+                    ("feedback", entry.evaluation.into()),
+                ])
+            })
+            .collect();
+        self.update_candidates(session);
+        Response::ok(Json::object([
+            ("turns", turns.into()),
+            ("candidates", session.guesser.candidates().len().into()),
+        ]))
+    }
+
+    /// Filters the candidates by the feedback of the session, which takes as long as a guess
+    /// early in a large game and so waits for the same limit.
+    fn update_candidates(&self, session: &mut Session<FIELDS, COLORS, PARTITIONS>) {
+        let _entered = self.guesses.enter();
+        session.guesser.update_candidates(&session.history);
+    }
+
+    fn guess(&self, session: &mut Session<FIELDS, COLORS, PARTITIONS>) -> Response {
+        let entered = self.guesses.enter();
+        let report = session.guesser.guess(&session.history);
+        drop(entered);
+        let report = match report {
+            Ok(report) => report,
+            Err(err) => return Response::error(409, err.to_string()),
+        };
+        self.record()
+            .record_guess(report.elapsed, report.candidates);
+        Response::ok(Json::object([
+            ("guess", report.guess.into()),
+            ("score", report.score.into()),
+            ("candidates", report.candidates.into()),
+            ("information", report.information.into()),
+            ("expected_guesses", report.expected_guesses.into()),
+            ("elapsed", report.elapsed.into()),
+        ]))
+    }
+
+    fn feedback(&self, session: &mut Session<FIELDS, COLORS, PARTITIONS>, body: &str) -> Response {
+        let entries = match history::parse::<FIELDS, COLORS>(self.rules, body) {
+            Ok(entries) if entries.is_empty() => {
+                return Response::error(400, "expected `guess | feedback`")
+            }
+            Ok(entries) => entries,
+            Err(err) => return Response::error(400, err.to_string()),
+        };
+        let turns = session.history.len();
+        session.history.extend(entries);
+        self.update_candidates(session);
+        let candidates = session.guesser.candidates().len();
+        if candidates == 0 {
+            session.history.truncate(turns);
+            return Response::error(409, "no code fits all the feedback, it was left out");
+        }
+        Response::ok(Json::object([("candidates", candidates.into())]))
+    }
+
+    fn candidates(&self, session: &mut Session<FIELDS, COLORS, PARTITIONS>) -> Response {
+        self.update_candidates(session);
+        let candidates = session.guesser.candidates();
+        let listed: Vec<_> = candidates.iter().take(LIST_CANDIDATES).copied().collect();
+        Response::ok(Json::object([
+            ("count", candidates.len().into()),
+            ("codes", listed.into()),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::max_gauss;
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: vec![],
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn read_and_write_http() {
+        let text = "POST /games/1/feedback HTTP/1.1\r\nHost: localhost\r\n\
+            content-length: 20\r\n\r\nrot rot blau blau | 1 0";
+        let read = read_request(&mut text.as_bytes()).unwrap();
+        assert_eq!(
+            (read.method.as_str(), read.path.as_str()),
+            ("POST", "/games/1/feedback")
+        );
+        assert_eq!(read.header("Content-Length"), Some("20"));
+        assert_eq!(read.body, "rot rot blau blau | ");
+        assert!(read_request(&mut "\r\n".as_bytes()).is_err());
+        let status = |text: &str| read_request(&mut text.as_bytes()).map_err(|err| err.status);
+        let long = format!(
+            "GET / HTTP/1.1\r\nX: {}\r\n\r\n",
+            "a".repeat(MAX_HEAD as usize)
+        );
+        assert_eq!(status(&long).err(), Some(431));
+        let many = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X: a\r\n".repeat(MAX_HEAD as usize)
+        );
+        assert_eq!(status(&many).err(), Some(431));
+        assert_eq!(
+            status("POST / HTTP/1.1\r\ncontent-length: 100000\r\n\r\n").err(),
+            Some(413)
+        );
+        let origin = |origin: &str| {
+            let text = format!("POST /games HTTP/1.1\r\nHost: localhost:8080\r\n{origin}\r\n");
+            same_origin(&read_request(&mut text.as_bytes()).unwrap())
+        };
+        assert!(origin(""));
+        assert!(origin("Origin: http://localhost:8080\r\n"));
+        assert!(!origin("Origin: http://localhost:8081\r\n"));
+        assert!(!origin("Origin: https://example.com\r\n"));
+        assert!(!origin("Origin: null\r\n"));
+        let mut written = vec![];
+        write_response(&mut written, &Response::error(404, "gone")).unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert!(written.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(!written.contains("Access-Control-Allow-Origin"));
+        assert!(written.contains("\r\nContent-Length: 16\r\n"));
+        assert!(written.ends_with("\r\n\r\n{\"error\":\"gone\"}"));
+    }
+
+    #[test]
+    fn page_links_its_files() {
+        let (_, content_type, index) = PAGE[0];
+        assert!(content_type.starts_with("text/html"));
+        for (path, _, body) in &PAGE[1..] {
+            assert!(index.contains(&format!("\"{path}\"")), "{path}");
+            assert!(!body.is_empty());
+        }
+    }
+
+    #[test]
+    fn play_a_session() {
+        let server = Server::<4, 6, { max_gauss(4) }>::new(
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+            SolverOptions::default(),
+        );
+        let created = server.route(&request("POST", "/games", ""));
+        assert_eq!(created.status, 201);
+        assert!(created.body.to_string().starts_with("{\"id\":1,"));
+        assert!(created
+            .body
+            .to_string()
+            .ends_with("\"palette\":[\"rot\",\"grün\",\"gelb\",\"blau\",\"orange\",\"pink\"]}"));
+        let guess = server.route(&request("GET", "/games/1/guess", ""));
+        assert_eq!(guess.status, 200);
+        assert!(guess.body.to_string().contains("\"candidates\":1296"));
+        let metrics = server.metrics();
+        assert!(metrics.contains("\nmastermind_guesses_total 1\n"));
+        assert!(metrics.contains("\nmastermind_guess_candidates_bucket{le=\"1000\"} 0\n"));
+        let feedback = server.route(&request("POST", "/games/1/feedback", "0 0 1 2 | 0 0"));
+        assert_eq!(
+            feedback.body,
+            Json::object([("candidates", 81usize.into())])
+        );
+        // the feedback is kept in the session
+        let status = server.route(&request("GET", "/games/1", ""));
+        assert!(status.body.to_string().ends_with("\"candidates\":81}"));
+        let candidates = server.route(&request("GET", "/games/1/candidates", ""));
+        assert!(candidates.body.to_string().starts_with("{\"count\":81,"));
+        // no code has none of these colors and all of them
+        let conflict = server.route(&request("POST", "/games/1/feedback", "0 0 1 2 | 4 0"));
+        assert_eq!(conflict.status, 409);
+        assert_eq!(
+            server
+                .route(&request("POST", "/games/1/feedback", "0 0 1 | 1 0"))
+                .status,
+            400
+        );
+        assert_eq!(server.route(&request("GET", "/games", "")).status, 405);
+        assert_eq!(
+            server.route(&request("PUT", "/games/1/guess", "")).status,
+            405
+        );
+        assert_eq!(server.route(&request("DELETE", "/games/1", "")).status, 200);
+        assert_eq!(server.route(&request("GET", "/games/1", "")).status, 404);
+        assert_eq!(
+            server.route(&request("GET", "/games/x/guess", "")).status,
+            404
+        );
+        assert_eq!(server.route(&request("GET", "/", "")).status, 404);
+    }
+
+    #[test]
+    fn think_out_loud() {
+        let server = Server::<4, 6, { max_gauss(4) }>::new(
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+            SolverOptions::default(),
+        );
+        server.route(&request("POST", "/games", ""));
+        let mut upgrade = request("GET", "/games/1/events", "");
+        assert!(server.upgrade(&upgrade).is_none());
+        upgrade.headers = vec![
+            ("Upgrade".to_string(), "websocket".to_string()),
+            ("Sec-WebSocket-Key".to_string(), "key".to_string()),
+        ];
+        let (key, session) = server.upgrade(&upgrade).unwrap();
+        assert_eq!(key, "key");
+        let events = Arc::new(Mutex::new(vec![]));
+        let sent = Arc::clone(&events);
+        let send = move |event: &str, _: Json| {
+            sent.lock().unwrap().push(event.to_string());
+            Ok(())
+        };
+        let mut session = session.lock().unwrap();
+        session.history = history::parse::<4, 6>(server.rules, "0 0 1 2 | 1 0").unwrap();
+        server.think(&mut session, send).unwrap();
+        assert!(session.guesser.observer.is_none());
+        let events = events.lock().unwrap();
+        assert_eq!(events.first().map(String::as_str), Some("candidates"));
+        assert!(events[1..events.len() - 1]
+            .iter()
+            .all(|event| event == "best"));
+        assert!(events.len() > 2);
+        assert_eq!(events.last().map(String::as_str), Some("guess"));
+    }
+
+    #[test]
+    fn open_rooms() {
+        let server = Server::<4, 6, { max_gauss(4) }>::new(
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+            SolverOptions::default(),
+        );
+        let upgrade = |path| {
+            server.upgrade_room(&Request {
+                headers: vec![
+                    ("Upgrade".to_string(), "websocket".to_string()),
+                    ("Sec-WebSocket-Key".to_string(), "key".to_string()),
+                ],
+                ..request("GET", path, "")
+            })
+        };
+        assert_eq!(
+            upgrade("/rooms/game-night_2/events"),
+            Some(("key".to_string(), "game-night_2".to_string()))
+        );
+        assert_eq!(upgrade("/rooms//events"), None);
+        assert_eq!(upgrade("/rooms/a%20b/events"), None);
+        assert_eq!(upgrade(&format!("/rooms/{}/events", "a".repeat(65))), None);
+        assert!(server
+            .upgrade_room(&request("GET", "/rooms/a/events", ""))
+            .is_none());
+    }
+
+    #[test]
+    fn expire_idle_sessions() {
+        let mut server = Server::<4, 6, { max_gauss(4) }>::new(
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+            SolverOptions::default(),
+        );
+        server.route(&request("POST", "/games", ""));
+        server.route(&request("POST", "/games", ""));
+        // a game in use is kept however long it was idle
+        let (_, used) = server
+            .upgrade(&Request {
+                headers: vec![
+                    ("Upgrade".to_string(), "websocket".to_string()),
+                    ("Sec-WebSocket-Key".to_string(), "key".to_string()),
+                ],
+                ..request("GET", "/games/2/events", "")
+            })
+            .unwrap();
+        server.idle = Duration::ZERO;
+        server.route(&request("POST", "/games", ""));
+        assert_eq!(server.route(&request("GET", "/games/1", "")).status, 404);
+        assert_eq!(server.route(&request("GET", "/games/2", "")).status, 200);
+        drop(used);
+        assert_eq!(server.route(&request("GET", "/games/2", "")).status, 404);
+        // ids aren't handed out again
+        let created = server.route(&request("POST", "/games", ""));
+        assert!(created.body.to_string().starts_with("{\"id\":4,"));
+        let metrics = server.metrics();
+        assert!(metrics.contains("\nmastermind_sessions_active 1\n"));
+        assert!(metrics.contains("\nmastermind_sessions_started_total 4\n"));
+        assert!(metrics.contains("\nmastermind_sessions_expired_total 3\n"));
+    }
+
+    #[test]
+    fn limit_guesses() {
+        let limit = Arc::new(Limit::new(1));
+        let entered = limit.enter();
+        let waiting = Arc::clone(&limit);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            let _entered = waiting.enter();
+            sender.send(()).unwrap();
+        });
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+        drop(entered);
+        receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        thread.join().unwrap();
+        assert_eq!(*limit.free.lock().unwrap(), 1);
+
+        // filtering the candidates by feedback waits for the limit as well
+        let mut server = Server::<4, 6, { max_gauss(4) }>::new(
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+            SolverOptions::default(),
+        );
+        server.guesses = Limit::new(1);
+        server.route(&request("POST", "/games", ""));
+        let entered = server.guesses.enter();
+        std::thread::scope(|scope| {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let server = &server;
+            scope.spawn(move || {
+                let feedback = request("POST", "/games/1/feedback", "0 0 1 2 | 0 0");
+                sender.send(server.route(&feedback).status).unwrap();
+            });
+            assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+            drop(entered);
+            assert_eq!(receiver.recv_timeout(Duration::from_secs(10)), Ok(200));
+        });
+    }
+}
@@ -0,0 +1,72 @@
+//! Scoring a guess against many codes at once with portable SIMD, enabled by the `simd`
+//! feature on nightly. Only the distinct-color rules are vectorized, each lane holds one
+//! packed code. Without AVX2 (e.g. `RUSTFLAGS="-C target-cpu=native"`) the vectors are
+//! split into many small operations and this is no faster than the scalar code.
+
+use std::simd::{cmp::SimdPartialEq, num::SimdUint, prelude::Select, u64x8};
+
+use crate::{max_gauss, Guess, BITS_PER_FIELD};
+
+const LANES: usize = 8;
+
+/// Counts how many of the `codes` would give each possible feedback to `guess` under the
+/// distinct-color rules, like `evaluate` does for a single code.
+pub fn partition<const FIELDS: usize, const PARTITIONS: usize>(
+    codes: &[Guess<FIELDS>],
+    guess: Guess<FIELDS>,
+) -> [u32; PARTITIONS] {
+    let mut counts = [0; PARTITIONS];
+    let chunks = codes.chunks_exact(LANES);
+    for code in chunks.remainder() {
+        counts[crate::evaluate(*code, guess).to_u32() as usize] += 1;
+    }
+    let one = u64x8::splat(1);
+    let zero = u64x8::splat(0);
+    // the lowest bit of every nibble which holds a field
+    let low_bits = (0..FIELDS).fold(0, |bits, field| bits | 1 << (field * BITS_PER_FIELD));
+    let low = u64x8::splat(low_bits);
+    // keeps the lowest bit of every field which is not zero, all shifts are constant
+    let nonzero_fields = |x: u64x8| (x | x >> 1 | x >> 2 | x >> 3) & low;
+    let guess_packed = u64x8::splat(guess.packed());
+    let guess_colors = guess.colors();
+    for chunk in chunks {
+        let packed = u64x8::from_array(std::array::from_fn(|lane| chunk[lane].packed()));
+        let exact =
+            u64x8::splat(FIELDS as u64) - nonzero_fields(packed ^ guess_packed).count_ones();
+        let mut matches = zero;
+        for guess_color in guess_colors {
+            // some field of the code has the color if xor-ing it into every field leaves a zero
+            let spread = u64x8::splat(low_bits * guess_color as u64);
+            matches += nonzero_fields(packed ^ spread)
+                .simd_ne(low)
+                .select(one, zero);
+        }
+        // `Evaluation::to_u32` for all lanes
+        let fields = u64x8::splat(FIELDS as u64);
+        let remaining = fields - (matches - exact);
+        let index = u64x8::splat(max_gauss(FIELDS) as u64) + exact
+            - (remaining + u64x8::splat(2)) * (remaining + one) / u64x8::splat(2);
+        for index in index.to_array() {
+            counts[index as usize] += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CodeIterator, GuessIterator};
+
+    #[test]
+    fn matches_scalar_partition() {
+        let codes: Vec<_> = CodeIterator::<4, 6>::default().collect();
+        for guess in GuessIterator::<4, 6>::default().step_by(7) {
+            let mut expected = [0; max_gauss(4)];
+            for code in &codes {
+                expected[crate::evaluate(*code, guess).to_u32() as usize] += 1;
+            }
+            assert_eq!(partition::<4, { max_gauss(4) }>(&codes, guess), expected);
+        }
+    }
+}
@@ -0,0 +1,522 @@
+//! Statistics over the games of a simulation.
+
+use std::time::Duration;
+
+use crate::json::Json;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub games: usize,
+    pub total_turns: usize,
+    /// The number of games which took as many turns as the index.
+    pub histogram: Vec<usize>,
+    pub elapsed: Duration,
+}
+
+impl Statistics {
+    pub fn new(turns: impl IntoIterator<Item = usize>, elapsed: Duration) -> Self {
+        let mut statistics = Self {
+            games: 0,
+            total_turns: 0,
+            histogram: Vec::new(),
+            elapsed,
+        };
+        for turns in turns {
+            statistics.games += 1;
+            statistics.total_turns += turns;
+            if statistics.histogram.len() <= turns {
+                statistics.histogram.resize(turns + 1, 0);
+            }
+            statistics.histogram[turns] += 1;
+        }
+        statistics
+    }
+
+    pub fn average(&self) -> f64 {
+        self.total_turns as f64 / self.games as f64
+    }
+
+    /// The middle of the sorted turns, or the mean of the two in the middle.
+    pub fn median(&self) -> f64 {
+        let nth = |n: usize| {
+            let mut games = 0;
+            self.histogram
+                .iter()
+                .position(|count| {
+                    games += count;
+                    games > n
+                })
+                .unwrap_or(0)
+        };
+        if self.games == 0 {
+            return 0.;
+        }
+        (nth((self.games - 1) / 2) + nth(self.games / 2)) as f64 / 2.
+    }
+
+    /// The sample variance of the turns.
+    pub fn variance(&self) -> f64 {
+        let average = self.average();
+        let squares: f64 = self
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(turns, games)| *games as f64 * (turns as f64 - average).powi(2))
+            .sum();
+        squares / self.games.saturating_sub(1).max(1) as f64
+    }
+
+    /// Half the width of the 95% confidence interval of the average by Student's
+    /// t-distribution, if the games were played against codes drawn at random. It is
+    /// infinite for a single game.
+    pub fn confidence(&self) -> f64 {
+        if self.games < 2 {
+            return f64::INFINITY;
+        }
+        t_95(self.games as f64 - 1.) * (self.variance() / self.games.max(1) as f64).sqrt()
+    }
+
+    /// The time per guess, as the guesses of all games were made in `elapsed`.
+    pub fn time_per_move(&self) -> Duration {
+        self.elapsed.div_f64(self.total_turns.max(1) as f64)
+    }
+
+    /// The most turns any game took.
+    pub fn worst_case(&self) -> usize {
+        self.histogram.len().saturating_sub(1)
+    }
+
+    /// The histogram as an ASCII bar chart with one line per number of turns from one to the
+    /// worst case, the longest bar `width` characters long.
+    pub fn chart(&self, width: usize) -> String {
+        let most = self.histogram.iter().copied().max().unwrap_or(0).max(1);
+        let mut chart = String::new();
+        for (turns, games) in self.histogram.iter().enumerate().skip(1) {
+            let share = 100. * *games as f64 / self.games.max(1) as f64;
+            let bar = "#".repeat((games * width).div_ceil(most));
+            chart += &format!("{turns:>3} | {bar:<width$} {games} ({share:.1}%)\n");
+        }
+        chart
+    }
+
+    /// Prints the summary with a chart of the histogram.
+    pub fn print(&self) {
+        println!(
+            "average: {:.3} turns over {} codes",
+            self.average(),
+            self.games
+        );
+        println!("worst case: {} turns", self.worst_case());
+        print!("{}", self.chart(40));
+        println!("total time: {:.1?}", self.elapsed);
+    }
+}
+
+impl From<&Statistics> for Json {
+    fn from(statistics: &Statistics) -> Self {
+        Json::object([
+            ("codes", statistics.games.into()),
+            ("average", statistics.average().into()),
+            ("median", statistics.median().into()),
+            ("worst_case", statistics.worst_case().into()),
+            (
+                "histogram",
+                Json::Array(
+                    statistics
+                        .histogram
+                        .iter()
+                        .map(|games| (*games).into())
+                        .collect(),
+                ),
+            ),
+            ("elapsed", statistics.elapsed.into()),
+            ("time_per_move", statistics.time_per_move().into()),
+        ])
+    }
+}
+
+/// Whether one solver takes fewer turns than another, from the games both played against the
+/// same codes drawn at random. The differences of the turns per code are tested against
+/// zero with Student's t-test, so that a small sample isn't taken for more than it shows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PairedTest {
+    /// The average number of turns the second solver took more.
+    pub difference: f64,
+    /// Half the width of the 95% confidence interval of `difference`.
+    pub confidence: f64,
+    /// The probability of a difference at least this large if the solvers were equally
+    /// good.
+    pub p_value: f64,
+}
+
+impl PairedTest {
+    /// Tests the turns of two solvers, which played the same codes in the same order.
+    pub fn new(first: &[usize], second: &[usize]) -> Self {
+        assert_eq!(first.len(), second.len(), "the games have to be paired");
+        let n = first.len().max(1) as f64;
+        let differences = || {
+            first
+                .iter()
+                .zip(second)
+                .map(|(first, second)| *second as f64 - *first as f64)
+        };
+        let difference = differences().sum::<f64>() / n;
+        let variance =
+            differences().map(|d| (d - difference).powi(2)).sum::<f64>() / (n - 1.).max(1.);
+        let error = (variance / n).sqrt();
+        // a single game says nothing about the spread of the differences
+        let p_value = if first.len() < 2 || difference == 0. {
+            1.
+        } else if error > 0. {
+            t_p_value(difference / error, n - 1.)
+        } else {
+            0.
+        };
+        Self {
+            difference,
+            confidence: if first.len() < 2 {
+                f64::INFINITY
+            } else {
+                t_95(n - 1.) * error
+            },
+            p_value,
+        }
+    }
+
+    /// Whether the difference is significant at the 5% level.
+    pub fn is_significant(&self) -> bool {
+        self.p_value < 0.05
+    }
+}
+
+impl From<&PairedTest> for Json {
+    fn from(test: &PairedTest) -> Self {
+        Json::object([
+            ("difference", test.difference.into()),
+            ("confidence", test.confidence.into()),
+            ("p_value", test.p_value.into()),
+        ])
+    }
+}
+
+/// The two-sided p-value of `t` under Student's t-distribution with `df` degrees of
+/// freedom.
+fn t_p_value(t: f64, df: f64) -> f64 {
+    incomplete_beta(df / 2., 0.5, df / (df + t * t))
+}
+
+/// The quantile of Student's t-distribution with `df` degrees of freedom for two-sided 95%
+/// confidence, infinite without any degree of freedom.
+fn t_95(df: f64) -> f64 {
+    if df < 1. {
+        return f64::INFINITY;
+    }
+    // the p-value falls with `t`, and one degree of freedom needs the largest, about 12.7
+    let (mut low, mut high) = (0., 16.);
+    for _ in 0..60 {
+        let t = (low + high) / 2.;
+        if t_p_value(t, df) > 0.05 {
+            low = t;
+        } else {
+            high = t;
+        }
+    }
+    (low + high) / 2.
+}
+
+/// The regularized incomplete beta function `I_x(a, b)` (Numerical Recipes, `betai`).
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+    if x >= 1. {
+        return 1.;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1. - x).ln()).exp();
+    // the continued fraction converges quickly on this side of the mean
+    if x < (a + 1.) / (a + b + 2.) {
+        front * beta_fraction(a, b, x) / a
+    } else {
+        1. - front * beta_fraction(b, a, 1. - x) / b
+    }
+}
+
+/// The continued fraction of the incomplete beta function by Lentz's method (Numerical
+/// Recipes, `betacf`).
+fn beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let nonzero = |value: f64| if value.abs() < TINY { TINY } else { value };
+    let mut c = 1.;
+    let mut d = 1. / nonzero(1. - (a + b) * x / (a + 1.));
+    let mut fraction = d;
+    for m in 1..=300 {
+        let m = m as f64;
+        let even = m * (b - m) * x / ((a + 2. * m - 1.) * (a + 2. * m));
+        d = 1. / nonzero(1. + even * d);
+        c = nonzero(1. + even / c);
+        fraction *= d * c;
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2. * m) * (a + 2. * m + 1.));
+        d = 1. / nonzero(1. + odd * d);
+        c = nonzero(1. + odd / c);
+        fraction *= d * c;
+        if (d * c - 1.).abs() < 1e-15 {
+            break;
+        }
+    }
+    fraction
+}
+
+/// The logarithm of the gamma function for positive `x` by Lanczos' approximation
+/// (Numerical Recipes, `gammln`).
+fn ln_gamma(x: f64) -> f64 {
+    let coefficients = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+    let tmp = x + 5.5;
+    let series = coefficients
+        .iter()
+        .enumerate()
+        .fold(1.000_000_000_190_015, |sum, (index, c)| {
+            sum + c / (x + 1. + index as f64)
+        });
+    (2.506_628_274_631_000_5 * series / x).ln() - tmp + (x + 0.5) * tmp.ln()
+}
+
+/// How well the information the guesses were expected to give predicted the information
+/// they gave, by turn. If the secrets are drawn uniformly, both agree on average.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Calibration {
+    /// The number of guesses and the sums of their expected and gained information in bit,
+    /// for every turn.
+    turns: Vec<(usize, f64, f64)>,
+}
+
+impl Calibration {
+    /// Adds a guess of the turn with zero-based index `turn`.
+    pub fn add(&mut self, turn: usize, expected: f64, gained: f64) {
+        if self.turns.len() <= turn {
+            self.turns.resize(turn + 1, (0, 0., 0.));
+        }
+        let (guesses, expected_sum, gained_sum) = &mut self.turns[turn];
+        *guesses += 1;
+        *expected_sum += expected;
+        *gained_sum += gained;
+    }
+
+    /// The number of guesses and the average expected and gained information, for every
+    /// turn.
+    pub fn averages(&self) -> impl Iterator<Item = (usize, f64, f64)> + '_ {
+        self.turns.iter().map(|(guesses, expected, gained)| {
+            let average = |sum: f64| sum / (*guesses).max(1) as f64;
+            (*guesses, average(*expected), average(*gained))
+        })
+    }
+
+    pub fn print(&self) {
+        println!("turn  guesses  expected    gained       gap");
+        for (turn, (guesses, expected, gained)) in self.averages().enumerate() {
+            println!(
+                "{:>4} {guesses:>8} {expected:>9.3} {gained:>9.3} {:>+9.3}",
+                turn + 1,
+                gained - expected
+            );
+        }
+    }
+}
+
+impl From<&Calibration> for Json {
+    fn from(calibration: &Calibration) -> Self {
+        Json::Array(
+            calibration
+                .averages()
+                .map(|(guesses, expected, gained)| {
+                    Json::object([
+                        ("guesses", guesses.into()),
+                        ("expected", expected.into()),
+                        ("gained", gained.into()),
+                        ("gap", (gained - expected).into()),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The fewest turns any solver can take to find every one of `codes` codes, if a guess gets
+/// one of `feedbacks` different feedbacks. Only one code is found in the first turn, as a
+/// guess gives the winning feedback for one code only, and every other feedback leads to
+/// one guess of the next turn, so at most `(feedbacks - 1)^(n - 1)` codes are found in turn
+/// `n`. The worst case is at least about `log(codes) / log(feedbacks - 1)` turns then.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LowerBound {
+    pub average: f64,
+    pub worst_case: usize,
+}
+
+impl LowerBound {
+    pub fn new(codes: usize, feedbacks: usize) -> Self {
+        let (mut turns, mut found, mut total_turns) = (0, 0, 0);
+        // the most guesses which can be made in the turn
+        let mut guesses = 1usize;
+        while found < codes {
+            turns += 1;
+            let found_now = guesses.min(codes - found);
+            found += found_now;
+            total_turns += found_now * turns;
+            guesses = guesses.saturating_mul(feedbacks.saturating_sub(1).max(1));
+        }
+        Self {
+            average: total_turns as f64 / codes.max(1) as f64,
+            worst_case: turns,
+        }
+    }
+
+    /// Prints the bound and how far the `statistics` are from it.
+    pub fn print(&self, statistics: &Statistics) {
+        println!(
+            "lower bound: {:.3} turns on average ({:+.3}), {} in the worst case ({:+})",
+            self.average,
+            statistics.average() - self.average,
+            self.worst_case,
+            statistics.worst_case() as isize - self.worst_case as isize
+        );
+    }
+}
+
+impl From<&LowerBound> for Json {
+    fn from(bound: &LowerBound) -> Self {
+        Json::object([
+            ("average", bound.average.into()),
+            ("worst_case", bound.worst_case.into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summarize_turns() {
+        let statistics = Statistics::new([3, 4, 4, 6, 1], Duration::ZERO);
+        assert_eq!(statistics.games, 5);
+        assert_eq!(statistics.average(), 3.6);
+        assert_eq!(statistics.worst_case(), 6);
+        assert_eq!(statistics.median(), 4.);
+        assert_eq!(Statistics::new([3, 4, 5, 6], Duration::ZERO).median(), 4.5);
+        assert_eq!(statistics.histogram, [0, 1, 0, 1, 2, 0, 1]);
+        assert_eq!(
+            Json::from(&statistics).to_string(),
+            "{\"codes\":5,\"average\":3.6,\"median\":4,\"worst_case\":6,\
+            \"histogram\":[0,1,0,1,2,0,1],\"elapsed\":0,\"time_per_move\":0}"
+        );
+    }
+
+    #[test]
+    fn calibrate_by_turn() {
+        let mut calibration = Calibration::default();
+        calibration.add(0, 2., 1.);
+        calibration.add(0, 2., 2.);
+        calibration.add(2, 0.5, 0.25);
+        assert_eq!(
+            calibration.averages().collect::<Vec<_>>(),
+            [(2, 2., 1.5), (0, 0., 0.), (1, 0.5, 0.25)]
+        );
+        assert_eq!(
+            Json::from(&calibration).to_string(),
+            "[{\"guesses\":2,\"expected\":2,\"gained\":1.5,\"gap\":-0.5},\
+            {\"guesses\":0,\"expected\":0,\"gained\":0,\"gap\":0},\
+            {\"guesses\":1,\"expected\":0.5,\"gained\":0.25,\"gap\":-0.25}]"
+        );
+    }
+
+    #[test]
+    fn bound_turns() {
+        // classic Mastermind: 1 + 13 + 169 codes in the first three turns, the rest in the
+        // fourth
+        let bound = LowerBound::new(1296, 14);
+        assert_eq!(bound.worst_case, 4);
+        assert_eq!(
+            bound.average,
+            (1 + 2 * 13 + 3 * 169 + 4 * 1113) as f64 / 1296.
+        );
+        assert_eq!(LowerBound::new(1, 14).average, 1.);
+        assert_eq!(LowerBound::new(3, 2).worst_case, 3);
+    }
+
+    #[test]
+    fn confidence_of_the_average() {
+        let statistics = Statistics::new([3, 4, 4, 5], Duration::ZERO);
+        assert!((statistics.variance() - 2. / 3.).abs() < 1e-12);
+        // the quantile of Student's t-distribution for 3 degrees of freedom
+        assert!((statistics.confidence() - 3.182_446 * (2f64 / 3. / 4.).sqrt()).abs() < 1e-6);
+        assert_eq!(
+            Statistics::new([4], Duration::ZERO).confidence(),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn student_t_distribution() {
+        for (df, quantile) in [
+            (1., 12.706_205),
+            (2., 4.302_653),
+            (9., 2.262_157),
+            (1e6, 1.959_966),
+        ] {
+            assert!((t_95(df) - quantile).abs() < 1e-5, "{df}");
+            assert!((t_p_value(quantile, df) - 0.05).abs() < 1e-6, "{df}");
+        }
+        assert!((t_p_value(1., 1.) - 0.5).abs() < 1e-12);
+        assert_eq!(t_p_value(0., 5.), 1.);
+        assert!((ln_gamma(5.) - 24f64.ln()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_paired_differences() {
+        // the second solver is one turn slower on every code
+        let first = [4, 5, 3, 4];
+        let test = PairedTest::new(&first, &[5, 6, 4, 5]);
+        assert_eq!(
+            (test.difference, test.confidence, test.p_value),
+            (1., 0., 0.)
+        );
+        assert!(test.is_significant());
+        let test = PairedTest::new(&first, &first);
+        assert_eq!(test.p_value, 1.);
+        // a difference of about half a standard error
+        let test = PairedTest::new(&[4, 4, 4, 4], &[5, 3, 5, 4]);
+        assert_eq!(test.difference, 0.25);
+        assert!((test.p_value - 0.6376).abs() < 1e-4);
+        assert!(!test.is_significant());
+        // about 2.08 standard errors from ten games, significant by the normal approximation
+        // with p = 0.038 but not by Student's t
+        let test = PairedTest::new(&[4; 10], &[3, 3, 3, 5, 5, 6, 6, 6, 6, 6]);
+        assert!((test.difference - 0.9).abs() < 1e-12);
+        assert!((test.p_value - 0.0676).abs() < 1e-3, "{}", test.p_value);
+        assert!(test.confidence > test.difference);
+        assert!(!test.is_significant());
+        let single = PairedTest::new(&[4], &[6]);
+        assert_eq!((single.p_value, single.confidence), (1., f64::INFINITY));
+    }
+
+    #[test]
+    fn chart_histogram() {
+        let statistics = Statistics::new([3, 4, 4, 6, 1], Duration::ZERO);
+        assert_eq!(
+            statistics.chart(4),
+            "  1 | ##   1 (20.0%)\n\
+            \x20 2 |      0 (0.0%)\n\
+            \x20 3 | ##   1 (20.0%)\n\
+            \x20 4 | #### 2 (40.0%)\n\
+            \x20 5 |      0 (0.0%)\n\
+            \x20 6 | ##   1 (20.0%)\n"
+        );
+    }
+}
@@ -0,0 +1,590 @@
+//! A log of every played and simulated game in a SQLite database, summarized by `stats`.
+//! Games are only logged with the `sqlite` feature, which links the SQLite library of the
+//! system through the few functions declared here.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use once_cell::sync::OnceCell;
+
+use crate::{cli::Output, json::Json, preset::Preset, Rules};
+
+#[cfg(feature = "sqlite")]
+use sqlite::{insert, summarize};
+
+static PATH: OnceCell<Option<PathBuf>> = OnceCell::new();
+
+#[cfg(feature = "sqlite")]
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS games (
+    id INTEGER PRIMARY KEY,
+    played_at INTEGER NOT NULL,
+    preset TEXT NOT NULL,
+    mode TEXT NOT NULL,
+    breaker TEXT NOT NULL,
+    turns INTEGER NOT NULL,
+    solved INTEGER NOT NULL
+)";
+
+/// Sets the database games are logged to, `None` turns logging off. Until this is called
+/// nothing is logged.
+pub fn set_path(path: Option<PathBuf>) {
+    let _ = PATH.set(path);
+}
+
+/// The database for `stats`, set by `set_path`.
+pub fn path() -> Option<&'static Path> {
+    PATH.get()?.as_deref()
+}
+
+/// The database in the user's data directory, if a home directory is known and games can
+/// be logged.
+pub fn default_path() -> Option<PathBuf> {
+    if !cfg!(feature = "sqlite") {
+        return None;
+    }
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+        })?;
+    Some(data_dir.join("mastermind_solver").join("games.sqlite"))
+}
+
+/// Who guessed the code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Breaker {
+    User,
+    Solver,
+}
+
+impl Breaker {
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    fn name(&self) -> &'static str {
+        match self {
+            Breaker::User => "you",
+            Breaker::Solver => "solver",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Game {
+    pub turns: usize,
+    pub solved: bool,
+}
+
+/// Logs the games of one command, e.g. `simulate`, to the database set by `set_path`.
+/// Failing to do so doesn't fail the command, it is only reported as a diagnostic.
+pub fn record<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    mode: &str,
+    breaker: Breaker,
+    games: impl IntoIterator<Item = Game>,
+) {
+    let Some(path) = path() else {
+        return;
+    };
+    let preset = Preset::find(FIELDS, COLORS, rules)
+        .expect("games are played with the dimensions of a preset");
+    if let Err(err) = insert(path, preset, mode, breaker, games) {
+        debug!("can't log the games to {}: {err}", path.display());
+    }
+}
+
+/// The games of one preset, mode and codebreaker.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Summary {
+    games: usize,
+    solved: usize,
+    /// Of the solved games.
+    total_turns: usize,
+    fewest_turns: Option<usize>,
+    /// The most games solved in a row.
+    best_streak: usize,
+    streak: usize,
+}
+
+impl Summary {
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    fn add(&mut self, game: Game) {
+        self.games += 1;
+        if game.solved {
+            self.solved += 1;
+            self.total_turns += game.turns;
+            self.fewest_turns = Some(self.fewest_turns.map_or(game.turns, |t| t.min(game.turns)));
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+    }
+
+    /// The average turns of the solved games, NaN if there are none.
+    fn average(&self) -> f64 {
+        self.total_turns as f64 / self.solved as f64
+    }
+}
+
+/// The summaries by preset, mode and codebreaker.
+type Summaries = BTreeMap<(String, String, String), Summary>;
+
+/// The games of the user in one month.
+struct Month {
+    month: String,
+    games: usize,
+    solved: usize,
+    average: f64,
+}
+
+fn format_average(average: f64) -> String {
+    if average.is_nan() {
+        "-".to_string()
+    } else {
+        format!("{average:.2}")
+    }
+}
+
+/// Prints how the games logged to `path` went, by preset, mode and codebreaker, and the
+/// games of the user by month.
+pub fn stats(path: Option<&Path>, output: Output) -> Result<(), String> {
+    let path = path.ok_or(if cfg!(feature = "sqlite") {
+        "games aren't logged as no home directory is known"
+    } else {
+        "games are only logged when built with the sqlite feature"
+    })?;
+    let (summaries, months) = if path.exists() {
+        summarize(path)?
+    } else {
+        Default::default()
+    };
+    match output {
+        Output::Text | Output::Porcelain => {
+            if summaries.is_empty() {
+                println!("no games were logged to {} yet", path.display());
+                return Ok(());
+            }
+            println!(
+                "{:<10} {:<12} {:<7} {:>7} {:>7} {:>8} {:>5} {:>7}",
+                "preset", "mode", "breaker", "games", "solved", "average", "best", "streak"
+            );
+            for ((preset, mode, breaker), summary) in &summaries {
+                println!(
+                    "{preset:<10} {mode:<12} {breaker:<7} {:>7} {:>7} {:>8} {:>5} {:>7}",
+                    summary.games,
+                    summary.solved,
+                    format_average(summary.average()),
+                    summary
+                        .fewest_turns
+                        .map_or("-".to_string(), |turns| turns.to_string()),
+                    summary.best_streak
+                );
+            }
+            if !months.is_empty() {
+                println!("\nyour games by month:");
+                println!(
+                    "{:<10} {:>7} {:>7} {:>8}",
+                    "month", "games", "solved", "average"
+                );
+                for month in &months {
+                    println!(
+                        "{:<10} {:>7} {:>7} {:>8}",
+                        month.month,
+                        month.games,
+                        month.solved,
+                        format_average(month.average)
+                    );
+                }
+            }
+        }
+        Output::Json => {
+            let summaries = summaries
+                .into_iter()
+                .map(|((preset, mode, breaker), summary)| {
+                    Json::object([
+                        ("preset", preset.into()),
+                        ("mode", mode.into()),
+                        ("breaker", breaker.into()),
+                        ("games", summary.games.into()),
+                        ("solved", summary.solved.into()),
+                        ("average", summary.average().into()),
+                        (
+                            "best",
+                            summary
+                                .fewest_turns
+                                .map_or(f64::NAN, |turns| turns as f64)
+                                .into(),
+                        ),
+                        ("streak", summary.best_streak.into()),
+                    ])
+                })
+                .collect::<Vec<_>>();
+            let months = months
+                .into_iter()
+                .map(|month| {
+                    Json::object([
+                        ("month", month.month.into()),
+                        ("games", month.games.into()),
+                        ("solved", month.solved.into()),
+                        ("average", month.average.into()),
+                    ])
+                })
+                .collect::<Vec<_>>();
+            let json = Json::object([("games", summaries.into()), ("months", months.into())]);
+            println!("{json}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn insert(
+    _: &Path,
+    _: Preset,
+    _: &str,
+    _: Breaker,
+    _: impl IntoIterator<Item = Game>,
+) -> Result<(), String> {
+    Err("games are only logged when built with the sqlite feature".to_string())
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn summarize(_: &Path) -> Result<(Summaries, Vec<Month>), String> {
+    Err("games are only logged when built with the sqlite feature".to_string())
+}
+
+/// Writing and reading the database through the C API of SQLite.
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use std::{
+        ffi::{c_char, c_int, CStr, CString},
+        time::SystemTime,
+    };
+
+    use super::*;
+
+    pub fn insert(
+        path: &Path,
+        preset: Preset,
+        mode: &str,
+        breaker: Breaker,
+        games: impl IntoIterator<Item = Game>,
+    ) -> Result<(), String> {
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory).map_err(|err| err.to_string())?;
+        }
+        let played_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        let database = Database::open(path)?;
+        database.execute(SCHEMA)?;
+        // a transaction makes logging thousands of simulated games fast
+        database.execute("BEGIN")?;
+        let mut statement = database.prepare(
+            "INSERT INTO games (played_at, preset, mode, breaker, turns, solved) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+        )?;
+        for game in games {
+            statement.bind_int(1, played_at)?;
+            statement.bind_text(2, preset.name())?;
+            statement.bind_text(3, mode)?;
+            statement.bind_text(4, breaker.name())?;
+            statement.bind_int(5, game.turns as i64)?;
+            statement.bind_int(6, game.solved as i64)?;
+            statement.step()?;
+            statement.reset();
+        }
+        drop(statement);
+        database.execute("COMMIT")
+    }
+
+    pub fn summarize(path: &Path) -> Result<(Summaries, Vec<Month>), String> {
+        let database = Database::open(path)?;
+        let mut summaries = BTreeMap::<_, Summary>::new();
+        let mut statement = database
+            .prepare("SELECT preset, mode, breaker, turns, solved FROM games ORDER BY id")?;
+        while statement.step()? {
+            let key = (statement.text(0), statement.text(1), statement.text(2));
+            summaries.entry(key).or_default().add(Game {
+                turns: statement.int(3) as usize,
+                solved: statement.int(4) != 0,
+            });
+        }
+        let mut months = vec![];
+        let mut statement = database.prepare(
+            "SELECT strftime('%Y-%m', played_at, 'unixepoch') AS month, COUNT(*), SUM(solved), \
+         AVG(CASE WHEN solved THEN turns END) FROM games WHERE breaker = 'you' \
+         GROUP BY month ORDER BY month",
+        )?;
+        while statement.step()? {
+            months.push(Month {
+                month: statement.text(0),
+                games: statement.int(1) as usize,
+                solved: statement.int(2) as usize,
+                average: statement.double(3).unwrap_or(f64::NAN),
+            });
+        }
+        Ok((summaries, months))
+    }
+
+    #[repr(C)]
+    struct Sqlite3 {
+        _private: [u8; 0],
+    }
+
+    #[repr(C)]
+    struct Sqlite3Stmt {
+        _private: [u8; 0],
+    }
+
+    const SQLITE_OK: c_int = 0;
+    const SQLITE_ROW: c_int = 100;
+    const SQLITE_DONE: c_int = 101;
+    const SQLITE_NULL: c_int = 5;
+    /// Makes SQLite copy bound strings, which have to live only for the call then.
+    const SQLITE_TRANSIENT: isize = -1;
+
+    #[link(name = "sqlite3")]
+    extern "C" {
+        fn sqlite3_open(filename: *const c_char, db: *mut *mut Sqlite3) -> c_int;
+        fn sqlite3_close(db: *mut Sqlite3) -> c_int;
+        fn sqlite3_errmsg(db: *mut Sqlite3) -> *const c_char;
+        fn sqlite3_prepare_v2(
+            db: *mut Sqlite3,
+            sql: *const c_char,
+            bytes: c_int,
+            statement: *mut *mut Sqlite3Stmt,
+            tail: *mut *const c_char,
+        ) -> c_int;
+        fn sqlite3_bind_int64(statement: *mut Sqlite3Stmt, index: c_int, value: i64) -> c_int;
+        fn sqlite3_bind_text(
+            statement: *mut Sqlite3Stmt,
+            index: c_int,
+            value: *const c_char,
+            bytes: c_int,
+            destructor: isize,
+        ) -> c_int;
+        fn sqlite3_step(statement: *mut Sqlite3Stmt) -> c_int;
+        fn sqlite3_reset(statement: *mut Sqlite3Stmt) -> c_int;
+        fn sqlite3_column_type(statement: *mut Sqlite3Stmt, column: c_int) -> c_int;
+        fn sqlite3_column_int64(statement: *mut Sqlite3Stmt, column: c_int) -> i64;
+        fn sqlite3_column_double(statement: *mut Sqlite3Stmt, column: c_int) -> f64;
+        fn sqlite3_column_text(statement: *mut Sqlite3Stmt, column: c_int) -> *const c_char;
+        fn sqlite3_finalize(statement: *mut Sqlite3Stmt) -> c_int;
+    }
+
+    struct Database(*mut Sqlite3);
+
+    impl Database {
+        fn open(path: &Path) -> Result<Self, String> {
+            let name =
+                CString::new(path.to_string_lossy().as_bytes()).map_err(|err| err.to_string())?;
+            let mut db = std::ptr::null_mut();
+            // SAFETY: the name is null-terminated.
+            let result = unsafe { sqlite3_open(name.as_ptr(), &mut db) };
+            if db.is_null() {
+                return Err("out of memory".to_string());
+            }
+            // the handle has to be closed even if opening failed
+            let database = Database(db);
+            if result != SQLITE_OK {
+                return Err(database.error());
+            }
+            Ok(database)
+        }
+
+        fn error(&self) -> String {
+            // SAFETY: the handle is valid until drop, and the message lives until the next call.
+            unsafe {
+                CStr::from_ptr(sqlite3_errmsg(self.0))
+                    .to_string_lossy()
+                    .into_owned()
+            }
+        }
+
+        fn prepare(&self, sql: &str) -> Result<Statement<'_>, String> {
+            let mut statement = std::ptr::null_mut();
+            // SAFETY: the length of the SQL is given, so it needn't be null-terminated.
+            let result = unsafe {
+                sqlite3_prepare_v2(
+                    self.0,
+                    sql.as_ptr().cast(),
+                    sql.len() as c_int,
+                    &mut statement,
+                    std::ptr::null_mut(),
+                )
+            };
+            if result != SQLITE_OK {
+                return Err(self.error());
+            }
+            Ok(Statement {
+                database: self,
+                statement,
+            })
+        }
+
+        fn execute(&self, sql: &str) -> Result<(), String> {
+            self.prepare(sql)?.step().map(|_| ())
+        }
+    }
+
+    impl Drop for Database {
+        fn drop(&mut self) {
+            // SAFETY: every statement borrows the database, so they are finalized already.
+            unsafe {
+                sqlite3_close(self.0);
+            }
+        }
+    }
+
+    struct Statement<'a> {
+        database: &'a Database,
+        statement: *mut Sqlite3Stmt,
+    }
+
+    impl Statement<'_> {
+        fn check(&self, result: c_int) -> Result<(), String> {
+            if result == SQLITE_OK {
+                Ok(())
+            } else {
+                Err(self.database.error())
+            }
+        }
+
+        fn bind_int(&mut self, index: c_int, value: i64) -> Result<(), String> {
+            // SAFETY: the statement is valid until drop.
+            self.check(unsafe { sqlite3_bind_int64(self.statement, index, value) })
+        }
+
+        fn bind_text(&mut self, index: c_int, value: &str) -> Result<(), String> {
+            // SAFETY: SQLite copies the string as it is bound with `SQLITE_TRANSIENT`.
+            self.check(unsafe {
+                sqlite3_bind_text(
+                    self.statement,
+                    index,
+                    value.as_ptr().cast(),
+                    value.len() as c_int,
+                    SQLITE_TRANSIENT,
+                )
+            })
+        }
+
+        /// Runs the statement until the next row, returns whether there is one.
+        fn step(&mut self) -> Result<bool, String> {
+            // SAFETY: the statement is valid until drop.
+            match unsafe { sqlite3_step(self.statement) } {
+                SQLITE_ROW => Ok(true),
+                SQLITE_DONE => Ok(false),
+                _ => Err(self.database.error()),
+            }
+        }
+
+        /// Makes the statement ready to run again, keeping its bindings.
+        fn reset(&mut self) {
+            // SAFETY: the statement is valid until drop.
+            unsafe {
+                sqlite3_reset(self.statement);
+            }
+        }
+
+        fn int(&self, column: c_int) -> i64 {
+            // SAFETY: the statement is valid until drop, columns out of range give 0.
+            unsafe { sqlite3_column_int64(self.statement, column) }
+        }
+
+        fn double(&self, column: c_int) -> Option<f64> {
+            // SAFETY: the statement is valid until drop, columns out of range are NULL.
+            unsafe {
+                (sqlite3_column_type(self.statement, column) != SQLITE_NULL)
+                    .then(|| sqlite3_column_double(self.statement, column))
+            }
+        }
+
+        fn text(&self, column: c_int) -> String {
+            // SAFETY: the text is null-terminated and lives until the next step, it is copied.
+            unsafe {
+                let text = sqlite3_column_text(self.statement, column);
+                if text.is_null() {
+                    return String::new();
+                }
+                CStr::from_ptr(text).to_string_lossy().into_owned()
+            }
+        }
+    }
+
+    impl Drop for Statement<'_> {
+        fn drop(&mut self) {
+            // SAFETY: the statement is finalized only here.
+            unsafe {
+                sqlite3_finalize(self.statement);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn streaks() {
+        let mut summary = Summary::default();
+        for (turns, solved) in [(5, true), (4, true), (10, false), (6, true), (3, true)] {
+            summary.add(Game { turns, solved });
+        }
+        assert_eq!(summary.games, 5);
+        assert_eq!(summary.solved, 4);
+        assert_eq!(summary.average(), 4.5);
+        assert_eq!(summary.fewest_turns, Some(3));
+        assert_eq!(summary.best_streak, 2);
+        assert!(Summary::default().average().is_nan());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn log_games() {
+        let path =
+            std::env::temp_dir().join(format!("mastermind-store-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let games = [
+            Game {
+                turns: 5,
+                solved: true,
+            },
+            Game {
+                turns: 10,
+                solved: false,
+            },
+            Game {
+                turns: 4,
+                solved: true,
+            },
+        ];
+        insert(&path, Preset::Classic, "codemaker", Breaker::User, games).unwrap();
+        insert(
+            &path,
+            Preset::Classic,
+            "simulate",
+            Breaker::Solver,
+            [games[0]],
+        )
+        .unwrap();
+        let (summaries, months) = summarize(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(summaries.len(), 2);
+        let user = &summaries[&(
+            "classic".to_string(),
+            "codemaker".to_string(),
+            "you".to_string(),
+        )];
+        assert_eq!((user.games, user.solved, user.best_streak), (3, 2, 1));
+        assert_eq!(user.average(), 4.5);
+        assert_eq!(months.len(), 1);
+        assert_eq!(
+            (months[0].games, months[0].solved, months[0].average),
+            (3, 2, 4.5)
+        );
+        assert!(summarize(Path::new("/nonexistent/directory/games.sqlite")).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use std::{fmt::Display, str::FromStr, sync::Arc};
+
+use crate::{compute_n_log2_n, POSSIBLE_BONUS};
+
+/// How the solver ranks guesses by the partition of the candidates they give.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Strategy {
+    /// The most expected information.
+    #[default]
+    Entropy,
+    /// The smallest largest partition, as proposed by Knuth.
+    Knuth,
+    /// The most partitions.
+    MostParts,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Entropy, Strategy::Knuth, Strategy::MostParts];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Strategy::Entropy => "entropy",
+            Strategy::Knuth => "knuth",
+            Strategy::MostParts => "mostparts",
+        }
+    }
+
+    /// The measure of the strategy as a scoring function. It scores guesses exactly like
+    /// the solver does with the strategy, which computes the scores faster on its own.
+    pub fn scoring<const FIELDS: usize, const PARTITIONS: usize>(&self) -> ScoringFn<PARTITIONS> {
+        match self {
+            Strategy::Entropy => Arc::new(entropy::<FIELDS, PARTITIONS>),
+            Strategy::Knuth => Arc::new(minimax::<FIELDS, PARTITIONS>),
+            Strategy::MostParts => Arc::new(most_parts::<FIELDS, PARTITIONS>),
+        }
+    }
+}
+
+/// Scores a guess by its partition of the candidates instead of a strategy, see
+/// `SimpleGuesser::scoring`: it is given the number of candidates which give each feedback,
+/// indexed by `Evaluation::to_u32`, and the number of candidates. Higher scores are better,
+/// they may be negative and NaN is the worst. As nothing is known about the score of a guess before all
+/// candidates are partitioned, every guess is scored in full.
+pub type ScoringFn<const PARTITIONS: usize> =
+    Arc<dyn Fn(&[u32; PARTITIONS], usize) -> f64 + Send + Sync>;
+
+/// The expected information in bit, with a guess which is certain to win preferred over
+/// every other guess.
+pub fn entropy<const FIELDS: usize, const PARTITIONS: usize>(
+    counts: &[u32; PARTITIONS],
+    total: usize,
+) -> f64 {
+    if total == 0 {
+        return 0.;
+    }
+    let partitions: f64 = counts
+        .iter()
+        .map(|count| compute_n_log2_n(*count as usize))
+        .sum();
+    let mut information = (compute_n_log2_n(total) - partitions) / total as f64;
+    if counts[FIELDS] == 1 && total == 1 {
+        information += PARTITIONS as f64 - 1.;
+    }
+    information
+}
+
+/// The number of candidates which are ruled out at least, with a bonus for guesses which
+/// may win.
+pub fn minimax<const FIELDS: usize, const PARTITIONS: usize>(
+    counts: &[u32; PARTITIONS],
+    total: usize,
+) -> f64 {
+    (total - counts.iter().max().copied().unwrap_or(0) as usize) as f64 + bonus::<FIELDS>(counts)
+}
+
+/// The number of partitions, with a bonus for guesses which may win.
+pub fn most_parts<const FIELDS: usize, const PARTITIONS: usize>(
+    counts: &[u32; PARTITIONS],
+    _total: usize,
+) -> f64 {
+    counts.iter().filter(|count| **count > 0).count() as f64 + bonus::<FIELDS>(counts)
+}
+
+fn bonus<const FIELDS: usize>(counts: &[u32]) -> f64 {
+    if counts[FIELDS] > 0 {
+        POSSIBLE_BONUS
+    } else {
+        0.
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Strategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!("unknown strategy {s:?}, expected \"entropy\", \"knuth\" or \"mostparts\"")
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, CodeIterator, GuessIterator, SimpleGuesser};
+
+    #[test]
+    fn parse_strategy_names() {
+        for strategy in Strategy::ALL {
+            assert_eq!(strategy.name().parse(), Ok(strategy));
+        }
+        assert_eq!("MostParts".parse(), Ok(Strategy::MostParts));
+        assert!("random".parse::<Strategy>().is_err());
+    }
+
+    #[test]
+    fn scoring_functions_match_the_solver() {
+        let codes: Vec<_> = CodeIterator::<4, 6>::default().collect();
+        for strategy in Strategy::ALL {
+            let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }> {
+                strategy,
+                ..Default::default()
+            };
+            guesser.update_candidates(&[]);
+            guesser.extend_n_log2_n();
+            let scoring = strategy.scoring::<4, { max_gauss(4) }>();
+            for guess in GuessIterator::<4, 6>::default().step_by(7) {
+                // a single candidate gets the bonus for winning
+                for total in [1, 2, codes.len()] {
+                    let counts = guesser.partition(&codes[..total], guess);
+                    assert_eq!(guesser.score(guess, &counts).1, scoring(&counts, total));
+                }
+            }
+        }
+    }
+}
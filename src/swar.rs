@@ -0,0 +1,127 @@
+//! Evaluating one guess against many codes with bit tricks on whole words (SWAR, SIMD
+//! within a register): several packed codes share one `u64` and are compared with the
+//! guess field by field in parallel. Only the distinct-color rules are supported.
+
+use crate::{Evaluation, Guess, BITS_PER_FIELD, MAX_COLORS};
+
+/// Codes packed side by side into words, `per_word` codes in every word.
+pub struct PackedCodes<const FIELDS: usize> {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl<const FIELDS: usize> PackedCodes<FIELDS> {
+    const BITS: usize = FIELDS * BITS_PER_FIELD;
+    /// How many codes fit into one word.
+    const PER_WORD: usize = u64::BITS as usize / Self::BITS;
+
+    pub fn new(codes: &[Guess<FIELDS>]) -> Self {
+        let mut packed = Self {
+            words: Vec::new(),
+            len: 0,
+        };
+        packed.repack(codes);
+        packed
+    }
+
+    /// Replaces the packed codes with `codes`, reusing the memory of the old ones.
+    pub fn repack(&mut self, codes: &[Guess<FIELDS>]) {
+        self.words.clear();
+        self.words.extend(codes.chunks(Self::PER_WORD).map(|chunk| {
+            chunk.iter().enumerate().fold(0, |word, (slot, code)| {
+                word | code.packed() << (slot * Self::BITS)
+            })
+        }));
+        self.len = codes.len();
+    }
+
+    /// `value` repeated in every slot of a word.
+    fn spread(value: u64) -> u64 {
+        (0..Self::PER_WORD).fold(0, |word, slot| word | value << (slot * Self::BITS))
+    }
+}
+
+/// Counts how many of the `codes` would give each possible feedback to `guess` under the
+/// distinct-color rules, like `evaluate` does for a single code.
+pub fn evaluate_batch<const FIELDS: usize, const PARTITIONS: usize>(
+    guess: Guess<FIELDS>,
+    codes: &PackedCodes<FIELDS>,
+) -> [u32; PARTITIONS] {
+    // the sums of the fields below must fit into a field
+    const { assert!(FIELDS < MAX_COLORS as usize) };
+    let code_bits = PackedCodes::<FIELDS>::BITS;
+    let per_word = PackedCodes::<FIELDS>::PER_WORD;
+    // the lowest bit of every field of a single code
+    let low_bits = (0..FIELDS).fold(0u64, |bits, field| bits | 1 << (field * BITS_PER_FIELD));
+    let low = PackedCodes::<FIELDS>::spread(low_bits);
+    // keeps the lowest bit of every field which is not zero
+    let nonzero_fields = |x: u64| (x | x >> 1 | x >> 2 | x >> 3) & low;
+    let guess_word = PackedCodes::<FIELDS>::spread(guess.packed());
+    // for every color of the guess, the color in every field of every slot
+    let colors = guess
+        .colors()
+        .map(|color| PackedCodes::<FIELDS>::spread(low_bits * color as u64));
+
+    // all fields of a single code
+    let field_mask = low_bits * (MAX_COLORS as u64 - 1);
+    // multiplying adds up all fields of a code in its last field
+    let sum_fields = |fields: u64| {
+        (fields.wrapping_mul(low_bits) >> ((FIELDS - 1) * BITS_PER_FIELD)) as usize
+            & (MAX_COLORS as usize - 1)
+    };
+    // the partition index by exact matches and matching colors
+    let mut indices = [[0u8; MAX_COLORS as usize]; MAX_COLORS as usize];
+    for (exact, row) in indices.iter_mut().enumerate().take(FIELDS + 1) {
+        for (matches, index) in row.iter_mut().enumerate().take(FIELDS + 1).skip(exact) {
+            let evaluation = Evaluation::<FIELDS> {
+                correct_color: (matches - exact) as u32,
+                exact: exact as u32,
+            };
+            *index = evaluation.to_u32() as u8;
+        }
+    }
+
+    let mut counts = [0; PARTITIONS];
+    for (index, word) in codes.words.iter().enumerate() {
+        let slots = per_word.min(codes.len - index * per_word);
+        // the fields which match the guess exactly
+        let exact = !nonzero_fields(word ^ guess_word) & low;
+        // for every field, the number of guess pegs with its color, the codes have distinct
+        // colors so these add up to the number of guess pegs whose color is in the code
+        let matches = colors
+            .iter()
+            .map(|color| !nonzero_fields(word ^ color) & low)
+            .sum::<u64>();
+        for slot in 0..slots {
+            let shift = slot * code_bits;
+            let exact = sum_fields((exact >> shift) & field_mask);
+            let matches = sum_fields((matches >> shift) & field_mask);
+            counts[indices[exact][matches] as usize] += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, CodeIterator, GuessIterator};
+
+    #[test]
+    fn matches_evaluate() {
+        // 4 codes per word, and a last word which is not full
+        let codes: Vec<_> = CodeIterator::<4, 6>::default().skip(1).collect();
+        let packed = PackedCodes::new(&codes);
+        for guess in GuessIterator::<4, 6>::default().step_by(7) {
+            let mut expected = [0; max_gauss(4)];
+            for code in &codes {
+                expected[crate::evaluate(*code, guess).to_u32() as usize] += 1;
+            }
+            assert_eq!(evaluate_batch(guess, &packed), expected);
+        }
+        let mut repacked = PackedCodes::new(&codes[..3]);
+        repacked.repack(&codes);
+        assert_eq!(repacked.words, packed.words);
+        assert_eq!(repacked.len, packed.len);
+    }
+}
@@ -0,0 +1,152 @@
+//! The feedback of every guess against every code, computed once for small games so
+//! that the solver can look it up instead of evaluating the same pairs every turn.
+
+use std::{
+    any::Any,
+    sync::{Arc, Mutex, Weak},
+};
+
+use rayon::prelude::*;
+
+use crate::{Guess, Rules};
+
+/// Tables with more entries are not built, one entry takes one byte.
+pub const MAX_ENTRIES: usize = 1 << 26;
+
+/// The fields, colors and rules a table is for.
+type Key = (usize, u32, Rules);
+/// A table in use, which is dropped with the last solver using it.
+type Shared = (Key, Weak<dyn Any + Send + Sync>);
+/// The tables in use, see `EvaluationTable::shared`.
+static TABLES: Mutex<Vec<Shared>> = Mutex::new(Vec::new());
+
+pub struct EvaluationTable<const FIELDS: usize> {
+    codes: Vec<Guess<FIELDS>>,
+    guesses: Vec<Guess<FIELDS>>,
+    /// The partition index (`Evaluation::to_u32`) of every code, one row per guess.
+    feedback: Vec<u8>,
+}
+
+impl<const FIELDS: usize> EvaluationTable<FIELDS> {
+    /// Builds the table for all codes and guesses allowed by the rules,
+    /// or returns `None` if it would have more than `MAX_ENTRIES` entries.
+    pub fn build<const COLORS: u32>(rules: Rules) -> Option<Self> {
+        let size = rules
+            .code_count::<FIELDS, COLORS>()
+            .checked_mul(rules.guess_count::<FIELDS, COLORS>())?;
+        if size > MAX_ENTRIES {
+            return None;
+        }
+        let codes: Vec<_> = rules.codes::<FIELDS, COLORS>().collect();
+        let guesses: Vec<_> = rules.guesses::<FIELDS, COLORS>().collect();
+        let feedback = guesses
+            .par_iter()
+            .flat_map_iter(|guess| {
+                codes
+                    .iter()
+                    .map(move |code| rules.evaluate(*code, *guess).to_u32() as u8)
+            })
+            .collect();
+        debug!(
+            "built evaluation table for {} guesses and {} codes",
+            guesses.len(),
+            codes.len()
+        );
+        Some(Self {
+            codes,
+            guesses,
+            feedback,
+        })
+    }
+
+    /// The table for the rules like `build`, shared with every solver which uses it already.
+    pub fn shared<const COLORS: u32>(rules: Rules) -> Option<Arc<Self>> {
+        let key = (FIELDS, COLORS, rules);
+        let find = |tables: &[Shared]| {
+            tables
+                .iter()
+                .filter(|(other, _)| *other == key)
+                .find_map(|(_, table)| table.upgrade()?.downcast().ok())
+        };
+        if let Some(table) = find(&TABLES.lock().unwrap()) {
+            return Some(table);
+        }
+        // built without the lock, as building waits for other tasks on the thread pool,
+        // which may need a table themselves
+        let table = Arc::new(Self::build::<COLORS>(rules)?);
+        let mut tables = TABLES.lock().unwrap();
+        if let Some(table) = find(&tables) {
+            return Some(table);
+        }
+        tables.retain(|(_, table)| table.strong_count() > 0);
+        let shared: Arc<dyn Any + Send + Sync> = table.clone();
+        tables.push((key, Arc::downgrade(&shared)));
+        Some(table)
+    }
+
+    pub fn codes(&self) -> &[Guess<FIELDS>] {
+        &self.codes
+    }
+
+    pub fn guesses(&self) -> &[Guess<FIELDS>] {
+        &self.guesses
+    }
+
+    pub fn guess_index(&self, guess: &Guess<FIELDS>) -> Option<usize> {
+        // the guesses are sorted, they are enumerated in the order of their packed representation
+        self.guesses
+            .binary_search_by_key(&guess.packed(), Guess::packed)
+            .ok()
+    }
+
+    /// The partition index of every code for the guess with the given index.
+    pub fn row(&self, guess_index: usize) -> &[u8] {
+        let start = guess_index * self.codes.len();
+        &self.feedback[start..start + self.codes.len()]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, SimpleGuesser};
+
+    #[test]
+    fn lookup_matches_evaluate() {
+        for rules in [
+            Rules {
+                duplicates: false,
+                missing_colors: 0,
+            },
+            Rules {
+                duplicates: true,
+                missing_colors: 0,
+            },
+        ] {
+            let table = EvaluationTable::<3>::build::<4>(rules).unwrap();
+            assert_eq!(table.codes().len(), rules.code_count::<3, 4>());
+            for guess in table.guesses() {
+                let row = table.row(table.guess_index(guess).unwrap());
+                for (code, feedback) in table.codes().iter().zip(row) {
+                    assert_eq!(*feedback as u32, rules.evaluate(*code, *guess).to_u32());
+                }
+            }
+        }
+        assert!(EvaluationTable::<6>::build::<10>(Rules::default()).is_none());
+    }
+
+    #[test]
+    fn share_tables() {
+        let rules = Rules::default();
+        let first = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let second = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let (first, second) = (first.table.unwrap(), second.table.unwrap());
+        assert!(Arc::ptr_eq(&first, &second));
+        let other = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(Rules {
+            missing_colors: 1,
+            ..rules
+        });
+        assert!(!Arc::ptr_eq(&first, &other.table.unwrap()));
+        assert!(EvaluationTable::<6>::shared::<10>(rules).is_none());
+    }
+}
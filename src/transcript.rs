@@ -0,0 +1,181 @@
+//! Transcripts of finished games, to share them or to see whether the solver still plays
+//! them the same way. A transcript is a saved game, see `save`, with the secret code if it
+//! is known and what the solver knew and did for every guess in the comment after it:
+//!
+//! ```text
+//! # preset: classic
+//! # palette: rot, grün, gelb, blau, orange, pink
+//! # secret: 3 1 1 5
+//! 0 0 1 1 | 1 1  # 1296 candidates, score 1.527, 1.527 bit expected, 1.379 bit gained, 2.1ms
+//! ```
+
+use std::path::Path;
+
+use crate::{
+    commands::{information_gained, Turn},
+    palette::Pegs,
+    readline::read_line,
+    save::{self, SavedGame},
+    Entry, Guess, Rules, SimpleGuesser, Solver, SolverOptions,
+};
+
+/// The comment for a guess of the solver.
+pub fn annotate<const FIELDS: usize>(turn: &Turn<FIELDS>) -> String {
+    format!(
+        "{} candidates, score {:.3}, {:.3} bit expected, {:.3} bit gained, {:.1?}",
+        turn.candidates, turn.score, turn.information, turn.gained, turn.elapsed
+    )
+}
+
+/// The comment for a guess the user played instead of the one of the solver.
+pub fn annotate_own(candidates: usize, gained: f64) -> String {
+    format!("{candidates} candidates, own guess, {gained:.3} bit gained")
+}
+
+/// Writes the game to `path`, with one comment per turn, which may be empty.
+pub fn write<const FIELDS: usize, const COLORS: u32>(
+    path: &Path,
+    rules: Rules,
+    secret: Option<Guess<FIELDS>>,
+    history: &[Entry<FIELDS>],
+    comments: &[String],
+) -> Result<(), String> {
+    std::fs::write(
+        path,
+        format::<FIELDS, COLORS>(rules, secret, history, comments),
+    )
+    .map_err(|err| format!("can't write {}: {err}", path.display()))
+}
+
+fn format<const FIELDS: usize, const COLORS: u32>(
+    rules: Rules,
+    secret: Option<Guess<FIELDS>>,
+    history: &[Entry<FIELDS>],
+    comments: &[String],
+) -> String {
+    let mut text = save::header::<FIELDS, COLORS>(rules);
+    if let Some(secret) = secret {
+        text.push_str(&format!("# secret: {}\n", secret.indices()));
+    }
+    for (entry, comment) in history.iter().zip(comments) {
+        text.push_str(&save::turn(entry));
+        if !comment.is_empty() {
+            text.push_str("  # ");
+            text.push_str(comment);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Steps through a transcript turn by turn if `step` is set, or prints it all at once
+/// otherwise. Every turn is annotated with what it narrowed the codes down to, and the
+/// recorded comment, and checked against the guess the solver would play now and the
+/// secret code.
+pub fn replay<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+    transcript: SavedGame,
+    step: bool,
+) -> Result<(), String> {
+    let history = transcript.history::<FIELDS, COLORS>()?;
+    let comments = transcript.comments();
+    let secret = match transcript.value("secret") {
+        Some(secret) => match secret.parse::<Guess<FIELDS>>() {
+            Ok(code) if rules.is_legal_code::<FIELDS, COLORS>(&code) => Some(code),
+            _ => return Err(format!("{secret:?} is not a secret of this game")),
+        },
+        None => None,
+    };
+    let mut guesser = SimpleGuesser::<FIELDS, COLORS, PARTITIONS>::with_options(rules, options);
+    match secret {
+        Some(secret) => println!("The secret is {}.", Pegs(&secret)),
+        None => println!("The secret isn't known."),
+    }
+    for (turn, entry) in history.iter().enumerate() {
+        if step && turn > 0 && read_line("press enter for the next turn").is_none() {
+            return Ok(());
+        }
+        // there is no guess once no code matches the feedback
+        let report = guesser.guess(&history[..turn]).ok();
+        let candidates = guesser.candidates().len();
+        let remaining = guesser
+            .candidates()
+            .iter()
+            .filter(|candidate| rules.evaluate(**candidate, entry.guess) == entry.evaluation)
+            .count();
+        println!(
+            "\nturn {}: {}  {}",
+            turn + 1,
+            entry.evaluation,
+            Pegs(&entry.guess)
+        );
+        if !comments[turn].is_empty() {
+            println!("  recorded: {}", comments[turn]);
+        }
+        println!(
+            "  {remaining} of {candidates} codes remain, {:.3} bit gained",
+            information_gained(candidates, remaining)
+        );
+        if let Some(report) = report.filter(|report| report.guess != entry.guess) {
+            println!(
+                "  the solver now plays {} ({:.3} bit expected)",
+                Pegs(&report.guess),
+                report.information
+            );
+        }
+        if let Some(secret) = secret {
+            let evaluation = rules.evaluate(secret, entry.guess);
+            if evaluation != entry.evaluation {
+                println!("  the feedback for the secret is {evaluation}");
+            }
+        }
+        if remaining == 0 {
+            println!("  no code matches all of the feedback");
+        }
+    }
+    match history.last() {
+        Some(entry) if entry.evaluation.exact == FIELDS as u32 => {
+            println!("\nThe code was found in {} turns.", history.len())
+        }
+        _ => println!(
+            "\nThe game ends after {} turns without finding the code.",
+            history.len()
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{commands::play, max_gauss, Preset};
+
+    #[test]
+    fn record_a_game() {
+        let rules = Preset::Classic.rules();
+        let code = Guess::new([3, 1, 1, 5]);
+        let mut guesser = SimpleGuesser::<4, 6, { max_gauss(4) }>::new(rules);
+        let turns = play(&mut guesser, code, usize::MAX, |_| {});
+        let history: Vec<_> = turns
+            .iter()
+            .map(|turn| Entry {
+                guess: turn.guess,
+                evaluation: turn.evaluation,
+            })
+            .collect();
+        let mut comments: Vec<_> = turns.iter().map(annotate).collect();
+        comments[1] = annotate_own(turns[1].candidates, turns[1].gained);
+        comments[2].clear();
+
+        let text = format::<4, 6>(rules, Some(code), &history, &comments);
+        assert!(text.contains("\n# secret: 3 1 1 5\n"));
+        assert!(text.contains(" # 1296 candidates, score "));
+        assert!(text.contains(" candidates, own guess, "));
+        let transcript = SavedGame::parse(text).unwrap();
+        assert_eq!(transcript.history::<4, 6>(), Ok(history));
+        assert_eq!(transcript.comments(), comments);
+        replay::<4, 6, { max_gauss(4) }>(rules, &SolverOptions::default(), transcript, false)
+            .unwrap();
+    }
+}
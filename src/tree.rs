@@ -0,0 +1,718 @@
+//! The complete decision tree of the solver: the guess to make, and for every feedback it
+//! can get the tree to continue with, until every code is found.
+//!
+//! As text, every node is a line with the feedback leading to it and its guess, indented by
+//! one space per turn, e.g. ` 1 2: 3 2 1 0` for the guess `3 2 1 0` after one exact match and
+//! two correct colors. The root has no feedback and the winning feedback is left out.
+//! Empty lines and comments starting with `#` are ignored when a tree is read.
+//!
+//! The binary format is a fraction of the size and safe to hand between versions: the magic
+//! bytes `MMTREE`, the version of the format, the compression of the nodes, the fields,
+//! colors and whether duplicates are allowed, the missing colors as a 32 bit and the number
+//! of nodes and a checksum of them as 64 bit little endian integers. The nodes follow in the order of the lines of the text,
+//! each as LEB128 numbers: the guess as a number in base `COLORS`, the number of codes, the
+//! number of children and in front of every child its feedback, `exact * (FIELDS + 1) +
+//! correct_color`. Trees of another version or game are rejected instead of misread.
+
+use std::{fmt::Display, iter::Peekable, ops::Range, path::Path};
+
+use crate::{
+    arena::Arena, compute_n_log2_n, json::Json, max_gauss, progress::Progress, Entry, Evaluation,
+    Guess, Rules, SimpleGuesser, Solver,
+};
+
+const MAGIC: &[u8] = b"MMTREE";
+/// Increase it whenever the binary format changes.
+const FORMAT_VERSION: u8 = 2;
+/// The nodes are stored as they are. zstd would shrink them further, but isn't available
+/// to this build, the number leaves room for it.
+const STORED: u8 = 0;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TreeError {
+    line: usize,
+    message: String,
+}
+
+impl Display for TreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// A line of the text format: its number, depth, feedback and guess.
+type Line<const FIELDS: usize> = (usize, usize, Option<Evaluation<FIELDS>>, Guess<FIELDS>);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node<const FIELDS: usize> {
+    pub guess: Guess<FIELDS>,
+    /// The number of codes which lead to this node.
+    pub codes: usize,
+    /// The tree for every other feedback than the winning one, in the order of
+    /// `Evaluation::to_u32`.
+    pub children: Vec<(Evaluation<FIELDS>, Node<FIELDS>)>,
+}
+
+impl<const FIELDS: usize> Node<FIELDS> {
+    /// Expands the solver into the tree which finds every one of the `codes`.
+    pub fn build<const COLORS: u32, const PARTITIONS: usize>(
+        guesser: &mut SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+        codes: impl IntoIterator<Item = Guess<FIELDS>>,
+    ) -> Self {
+        let mut arena = Arena::new();
+        let codes = arena.alloc(codes);
+        let progress = Progress::new("expanding", codes.len());
+        Self::expand(guesser, &mut arena, codes, &mut vec![], &progress)
+    }
+
+    fn expand<const COLORS: u32, const PARTITIONS: usize>(
+        guesser: &mut SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+        arena: &mut Arena<Guess<FIELDS>>,
+        codes: Range<usize>,
+        history: &mut Vec<Entry<FIELDS>>,
+        progress: &Progress,
+    ) -> Self {
+        let guess = guesser
+            .guess(history)
+            .expect("the codes fit the feedback they gave")
+            .guess;
+        let code_count = codes.len();
+        let mark = arena.mark();
+        let parts: [_; PARTITIONS] = arena.split(guesser.rules, codes, guess);
+        let mut children = vec![];
+        for part in parts {
+            let Some(code) = arena.get(part.clone()).first().copied() else {
+                continue;
+            };
+            if code == guess {
+                progress.inc(1);
+                continue;
+            }
+            let evaluation = guesser.rules.evaluate(code, guess);
+            history.push(Entry { guess, evaluation });
+            let child = Self::expand(guesser, arena, part, history, progress);
+            children.push((evaluation, child));
+            history.pop();
+        }
+        arena.release(mark);
+        Self {
+            guess,
+            codes: code_count,
+            children,
+        }
+    }
+
+    /// Whether the guess can be the code.
+    pub fn found(&self) -> bool {
+        self.codes > self.children.iter().map(|(_, child)| child.codes).sum()
+    }
+
+    /// The information the guess is expected to give in bit, the entropy of the partition
+    /// of the codes by their feedback.
+    pub fn information(&self) -> f64 {
+        let partitions: f64 = self
+            .children
+            .iter()
+            .map(|(_, child)| compute_n_log2_n(child.codes))
+            .sum();
+        (compute_n_log2_n(self.codes) - partitions) / self.codes.max(1) as f64
+    }
+
+    /// The number of nodes in the tree.
+    pub fn nodes(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(|(_, child)| child.nodes())
+            .sum::<usize>()
+    }
+
+    /// Reads a tree written as text, checking every guess and feedback against the rules.
+    pub fn parse<const COLORS: u32>(rules: Rules, text: &str) -> Result<Self, TreeError> {
+        let mut lines = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| TreeError {
+                line: index + 1,
+                message,
+            };
+            let content = line.split('#').next().unwrap_or_default().trim_end();
+            if content.trim().is_empty() {
+                continue;
+            }
+            let depth = content.len() - content.trim_start_matches(' ').len();
+            let (evaluation, guess) = match content.split_once(':') {
+                Some((evaluation, guess)) => {
+                    let evaluation: Evaluation<FIELDS> =
+                        evaluation.parse().map_err(|err| error(format!("{err}")))?;
+                    (Some(evaluation), guess)
+                }
+                None => (None, content),
+            };
+            let guess = guess.parse().map_err(|err| error(format!("{err}")))?;
+            if !rules.is_legal_turn::<FIELDS, COLORS>(&guess) {
+                return Err(error(format!("[{guess}] can't be played in this game")));
+            }
+            lines.push((index + 1, depth, evaluation, guess));
+        }
+        let mut lines = lines.into_iter().peekable();
+        let tree = match lines.next() {
+            Some((_, 0, None, guess)) => {
+                Self::parse_children::<COLORS>(rules, guess, &mut vec![], &mut lines)?
+            }
+            Some((line, ..)) => {
+                return Err(TreeError {
+                    line,
+                    message: "expected the first guess without indentation or feedback".to_string(),
+                })
+            }
+            None => {
+                return Err(TreeError {
+                    line: 0,
+                    message: "the tree is empty".to_string(),
+                })
+            }
+        };
+        match lines.next() {
+            Some((line, ..)) => Err(TreeError {
+                line,
+                message: "expected only one first guess".to_string(),
+            }),
+            None => Ok(tree),
+        }
+    }
+
+    /// Reads a tree from a file in either format.
+    pub fn read<const COLORS: u32>(rules: Rules, path: &Path) -> Result<Self, String> {
+        let error = |err: &dyn Display| format!("{}: {err}", path.display());
+        let bytes = std::fs::read(path).map_err(|err| error(&err))?;
+        if bytes.starts_with(MAGIC) {
+            return Self::from_bytes::<COLORS>(rules, &bytes).map_err(|err| error(&err));
+        }
+        let text = String::from_utf8(bytes).map_err(|err| error(&err))?;
+        Self::parse::<COLORS>(rules, &text).map_err(|err| error(&err))
+    }
+
+    /// The tree in the binary format.
+    pub fn to_bytes<const COLORS: u32>(&self, rules: Rules) -> Vec<u8> {
+        let mut nodes = vec![];
+        self.encode::<COLORS>(&mut nodes);
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend([
+            FORMAT_VERSION,
+            STORED,
+            FIELDS as u8,
+            COLORS as u8,
+            rules.duplicates as u8,
+        ]);
+        bytes.extend(rules.missing_colors.to_le_bytes());
+        bytes.extend((self.nodes() as u64).to_le_bytes());
+        bytes.extend(checksum(&nodes).to_le_bytes());
+        bytes.extend(nodes);
+        bytes
+    }
+
+    fn encode<const COLORS: u32>(&self, bytes: &mut Vec<u8>) {
+        let guess = self
+            .guess
+            .colors()
+            .iter()
+            .rev()
+            .fold(0, |index, color| index * COLORS as u64 + *color as u64);
+        write_number(bytes, guess);
+        write_number(bytes, self.codes as u64);
+        write_number(bytes, self.children.len() as u64);
+        for (evaluation, child) in &self.children {
+            let feedback = evaluation.exact * (FIELDS as u32 + 1) + evaluation.correct_color;
+            write_number(bytes, feedback.into());
+            child.encode::<COLORS>(bytes);
+        }
+    }
+
+    /// Reads a tree in the binary format, checking that it was written for this game.
+    pub fn from_bytes<const COLORS: u32>(rules: Rules, bytes: &[u8]) -> Result<Self, String> {
+        let cut_off = || "the tree is cut off".to_string();
+        let header = bytes
+            .strip_prefix(MAGIC)
+            .ok_or("not a tree in the binary format")?;
+        let [version, compression, fields, colors, duplicates, rest @ ..] = header else {
+            return Err(cut_off());
+        };
+        if *version != FORMAT_VERSION {
+            return Err(format!(
+                "the tree is in version {version} of the binary format, \
+                 only version {FORMAT_VERSION} can be read"
+            ));
+        }
+        if *compression != STORED {
+            return Err(format!("unknown compression {compression}"));
+        }
+        if (*fields as usize, *colors as u32, *duplicates != 0)
+            != (FIELDS, COLORS, rules.duplicates)
+        {
+            let duplicates = if *duplicates != 0 { "with" } else { "without" };
+            return Err(format!(
+                "the tree is for {fields} fields and {colors} colors {duplicates} duplicates"
+            ));
+        }
+        let (missing, rest) = rest.split_first_chunk().ok_or_else(cut_off)?;
+        if u32::from_le_bytes(*missing) != rules.missing_colors {
+            return Err("the tree is for a game with other colors missing".to_string());
+        }
+        let (count, rest) = rest.split_first_chunk().ok_or_else(cut_off)?;
+        let (sum, mut nodes) = rest.split_first_chunk().ok_or_else(cut_off)?;
+        if checksum(nodes) != u64::from_le_bytes(*sum) {
+            return Err("the tree is damaged".to_string());
+        }
+        let tree = Self::decode::<COLORS>(rules, &mut nodes)?;
+        if !nodes.is_empty() || tree.nodes() as u64 != u64::from_le_bytes(*count) {
+            return Err("the number of nodes doesn't match".to_string());
+        }
+        Ok(tree)
+    }
+
+    /// Reads the nodes in order, keeping the ones whose children are still being read on a
+    /// stack instead of recursing. The tree can't be deeper than the codes of its root, as
+    /// every guess which isn't the last has to rule out a code.
+    fn decode<const COLORS: u32>(rules: Rules, bytes: &mut &[u8]) -> Result<Self, String> {
+        // every open node with the number of children left to read and the feedback
+        // leading to the child being read
+        let mut open: Vec<(Self, usize, Option<Evaluation<FIELDS>>)> = vec![];
+        loop {
+            let (node, count) = Self::decode_node::<COLORS>(rules, bytes)?;
+            if open
+                .first()
+                .is_some_and(|(root, ..)| open.len() >= root.codes)
+            {
+                return Err("the tree is deeper than it has codes".to_string());
+            }
+            open.push((node, count, None));
+            while let Some((_, 0, _)) = open.last() {
+                let (node, _, _) = open.pop().unwrap();
+                let found = node
+                    .children
+                    .iter()
+                    .map(|(_, child)| child.codes)
+                    .sum::<usize>();
+                if node.codes != found && node.codes != found + 1 {
+                    return Err(format!(
+                        "[{}] can't lead to {} codes",
+                        node.guess, node.codes
+                    ));
+                }
+                let Some((parent, count, evaluation)) = open.last_mut() else {
+                    return Ok(node);
+                };
+                parent.children.push((evaluation.take().unwrap(), node));
+                *count -= 1;
+            }
+            let (parent, _, evaluation) = open.last_mut().unwrap();
+            let feedback = read_number(bytes)?;
+            let child = Evaluation {
+                exact: (feedback / (FIELDS as u64 + 1)) as u32,
+                correct_color: (feedback % (FIELDS as u64 + 1)) as u32,
+            };
+            if !child.is_valid() || feedback > (FIELDS * (FIELDS + 1)) as u64 {
+                return Err(format!("invalid feedback {feedback}"));
+            }
+            if let Some((previous, _)) = parent.children.last() {
+                if previous.to_u32() >= child.to_u32() {
+                    return Err("the feedback isn't in order".to_string());
+                }
+            }
+            *evaluation = Some(child);
+        }
+    }
+
+    /// Reads the guess and the number of codes of a node and how many children follow it.
+    fn decode_node<const COLORS: u32>(
+        rules: Rules,
+        bytes: &mut &[u8],
+    ) -> Result<(Self, usize), String> {
+        let mut index = read_number(bytes)?;
+        let guess = Guess::new(std::array::from_fn(|_| {
+            let color = index % COLORS as u64;
+            index /= COLORS as u64;
+            color as u32
+        }));
+        if index != 0 || !rules.is_legal_turn::<FIELDS, COLORS>(&guess) {
+            return Err(format!("[{guess}] can't be played in this game"));
+        }
+        let codes = read_number(bytes)? as usize;
+        if codes > (COLORS as usize).saturating_pow(FIELDS as u32) {
+            return Err(format!("[{guess}] can't lead to {codes} codes"));
+        }
+        let count = read_number(bytes)? as usize;
+        if count > max_gauss(FIELDS) {
+            return Err(format!("a guess can't have {count} kinds of feedback"));
+        }
+        let node = Self {
+            guess,
+            codes,
+            children: Vec::with_capacity(count),
+        };
+        Ok((node, count))
+    }
+
+    /// Builds the node of `guess` after `history` from the lines of its children.
+    fn parse_children<const COLORS: u32>(
+        rules: Rules,
+        guess: Guess<FIELDS>,
+        history: &mut Vec<Entry<FIELDS>>,
+        lines: &mut Peekable<impl Iterator<Item = Line<FIELDS>>>,
+    ) -> Result<Self, TreeError> {
+        let depth = history.len();
+        let mut children: Vec<(Evaluation<FIELDS>, Self)> = vec![];
+        while let Some((line, child_depth, evaluation, child)) =
+            lines.next_if(|(_, child_depth, ..)| *child_depth > depth)
+        {
+            let error = |message: &str| TreeError {
+                line,
+                message: message.to_string(),
+            };
+            let Some(evaluation) = evaluation.filter(|_| child_depth == depth + 1) else {
+                return Err(error("expected feedback indented by one more space"));
+            };
+            if children.iter().any(|(other, _)| *other == evaluation) {
+                return Err(error("the feedback appears twice"));
+            }
+            if let Err(err) = rules.check_feedback(guess, evaluation) {
+                return Err(error(&err.to_string()));
+            }
+            history.push(Entry { guess, evaluation });
+            let child = Self::parse_children::<COLORS>(rules, child, history, lines);
+            history.pop();
+            children.push((evaluation, child?));
+        }
+        children.sort_by_key(|(evaluation, _)| evaluation.to_u32());
+        // the guess is found if it could be the code
+        let found = rules.is_legal_code::<FIELDS, COLORS>(&guess)
+            && history
+                .iter()
+                .all(|entry| rules.evaluate(guess, entry.guess) == entry.evaluation);
+        let codes = children.iter().map(|(_, child)| child.codes).sum::<usize>() + found as usize;
+        Ok(Self {
+            guess,
+            codes,
+            children,
+        })
+    }
+
+    /// The first `depth` turns of the tree in the DOT language of Graphviz, nodes labeled with
+    /// the guess and the number of codes which lead to it, edges with the feedback. Guesses
+    /// which can be the code are drawn with a double border.
+    pub fn dot(&self, depth: usize) -> String {
+        let mut dot = String::from("digraph strategy {\n    node [shape=box];\n");
+        self.write_dot(&mut dot, &mut 0, depth);
+        dot += "}\n";
+        dot
+    }
+
+    /// Appends the node and its children up to `depth` more turns, numbering them from `id`.
+    fn write_dot(&self, dot: &mut String, id: &mut usize, depth: usize) {
+        let node = *id;
+        *id += 1;
+        let codes = match self.codes {
+            1 => "1 code".to_string(),
+            codes => format!("{codes} codes"),
+        };
+        let label = format!("{}\\n{codes}", escape(&self.guess.to_string()));
+        let border = if self.found() { ", peripheries=2" } else { "" };
+        *dot += &format!("    n{node} [label=\"{label}\"{border}];\n");
+        if depth <= 1 {
+            return;
+        }
+        for (evaluation, child) in &self.children {
+            let child_node = *id;
+            child.write_dot(dot, id, depth - 1);
+            *dot += &format!("    n{node} -> n{child_node} [label=\"{evaluation}\"];\n");
+        }
+    }
+
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        writeln!(f, "{}", self.guess.indices())?;
+        for (evaluation, child) in &self.children {
+            write!(
+                f,
+                "{:depth$}{} {}: ",
+                "",
+                evaluation.exact,
+                evaluation.correct_color,
+                depth = depth + 1
+            )?;
+            child.write(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Appends `value` in LEB128, seven bits per byte with the highest bit set on all but the
+/// last byte.
+fn write_number(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Takes a number written by `write_number` off the front of `bytes`.
+fn read_number(bytes: &mut &[u8]) -> Result<u64, String> {
+    let mut value = 0;
+    for shift in (0..u64::BITS).step_by(7) {
+        let (&byte, rest) = bytes
+            .split_first()
+            .ok_or_else(|| "the tree is cut off".to_string())?;
+        *bytes = rest;
+        if shift == 63 && byte > 1 {
+            return Err("a number is too large".to_string());
+        }
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("a number is too large".to_string())
+}
+
+/// The FNV-1a hash of the nodes, to notice a damaged file before reading it.
+pub(crate) fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Escapes a string for a quoted DOT label.
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl<const FIELDS: usize> Display for Node<FIELDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl<const FIELDS: usize> From<&Node<FIELDS>> for Json {
+    fn from(node: &Node<FIELDS>) -> Self {
+        let children = node
+            .children
+            .iter()
+            .map(|(evaluation, child)| {
+                Json::object([("feedback", (*evaluation).into()), ("node", child.into())])
+            })
+            .collect::<Vec<_>>();
+        Json::object([
+            ("guess", node.guess.into()),
+            ("codes", node.codes.into()),
+            ("children", children.into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{commands, max_gauss, Rules};
+
+    /// The guesses the tree makes until it finds `code`, if it finds it at all.
+    fn play<const FIELDS: usize>(
+        tree: &Node<FIELDS>,
+        rules: Rules,
+        code: Guess<FIELDS>,
+    ) -> Option<Vec<Guess<FIELDS>>> {
+        let mut guesses = vec![];
+        let mut node = tree;
+        loop {
+            guesses.push(node.guess);
+            if node.guess == code {
+                return Some(guesses);
+            }
+            let evaluation = rules.evaluate(code, node.guess);
+            node = &node
+                .children
+                .iter()
+                .find(|(feedback, _)| *feedback == evaluation)?
+                .1;
+        }
+    }
+
+    #[test]
+    fn tree_finds_every_code() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::new(rules);
+        let tree = Node::build(&mut guesser, rules.codes::<3, 4>());
+        for game in commands::play_all(&mut guesser, rules.codes::<3, 4>()) {
+            let guesses = play(&tree, rules, game.code);
+            let expected: Vec<_> = game.turns.iter().map(|turn| turn.guess).collect();
+            assert_eq!(guesses, Some(expected));
+        }
+        let text = tree.to_string();
+        assert_eq!(text.lines().count(), tree.nodes());
+        assert_eq!(text.lines().next(), Some(tree.guess.indices().as_str()));
+        assert!(text.lines().skip(1).all(|line| line.starts_with(' ')));
+        assert_eq!(Node::parse::<4>(rules, &text), Ok(tree));
+    }
+
+    #[test]
+    fn binary_format() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let mut guesser = SimpleGuesser::<3, 4, { max_gauss(3) }>::new(rules);
+        let tree = Node::build(&mut guesser, rules.codes::<3, 4>());
+        let bytes = tree.to_bytes::<4>(rules);
+        assert!(bytes.len() < tree.to_string().len() / 2);
+        assert_eq!(Node::from_bytes::<4>(rules, &bytes), Ok(tree.clone()));
+
+        let path = std::env::temp_dir().join(format!("mastermind_tree_{}", std::process::id()));
+        std::fs::write(&path, &bytes).unwrap();
+        assert_eq!(Node::read::<4>(rules, &path), Ok(tree.clone()));
+        std::fs::write(&path, tree.to_string()).unwrap();
+        assert_eq!(Node::read::<4>(rules, &path), Ok(tree));
+        std::fs::remove_file(path).unwrap();
+
+        // trees of other versions or games are rejected
+        let error = |bytes: &[u8]| Node::<3>::from_bytes::<4>(rules, bytes).unwrap_err();
+        let mut other_version = bytes.clone();
+        other_version[MAGIC.len()] += 1;
+        assert!(error(&other_version).contains("version 2"));
+        let distinct = Node::<3>::from_bytes::<4>(
+            Rules {
+                duplicates: false,
+                missing_colors: 0,
+            },
+            &bytes,
+        );
+        assert_eq!(
+            distinct,
+            Err("the tree is for 3 fields and 4 colors with duplicates".to_string())
+        );
+        assert!(Node::<3>::from_bytes::<5>(rules, &bytes).is_err());
+        let missing = Rules {
+            duplicates: true,
+            missing_colors: 0b10,
+        };
+        assert_eq!(
+            Node::<3>::from_bytes::<4>(missing, &bytes),
+            Err("the tree is for a game with other colors missing".to_string())
+        );
+        let mut damaged = bytes.clone();
+        *damaged.last_mut().unwrap() ^= 1;
+        assert_eq!(error(&damaged), "the tree is damaged");
+        assert_eq!(error(&bytes[..bytes.len() - 1]), "the tree is damaged");
+        assert_eq!(error(&bytes[..10]), "the tree is cut off");
+        assert_eq!(error(b"0 0 1\n"), "not a tree in the binary format");
+    }
+
+    #[test]
+    fn deep_binary_tree() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        // a chain of the same guess with a single child each, for no feedback at all
+        let chain = |codes: &[u64]| {
+            let mut nodes = vec![];
+            for (index, count) in codes.iter().enumerate() {
+                let last = index + 1 == codes.len();
+                nodes.extend([0, *count as u8, !last as u8]);
+                if !last {
+                    nodes.push(0);
+                }
+            }
+            let mut bytes = MAGIC.to_vec();
+            bytes.extend([FORMAT_VERSION, STORED, 3, 4, 1]);
+            bytes.extend(0u32.to_le_bytes());
+            bytes.extend((codes.len() as u64).to_le_bytes());
+            bytes.extend(checksum(&nodes).to_le_bytes());
+            bytes.extend(nodes);
+            Node::<3>::from_bytes::<4>(rules, &bytes)
+        };
+        let codes: Vec<u64> = (1..=64).rev().collect();
+        assert_eq!(chain(&codes).unwrap().nodes(), 64);
+        assert_eq!(
+            chain(&[1; 100_000]),
+            Err("the tree is deeper than it has codes".to_string())
+        );
+        assert_eq!(
+            chain(&[65]),
+            Err("[rot, rot, rot] can't lead to 65 codes".to_string())
+        );
+    }
+
+    #[test]
+    fn number_encoding() {
+        for value in [0, 1, 127, 128, 300, u64::MAX] {
+            let mut bytes = vec![];
+            write_number(&mut bytes, value);
+            assert_eq!(
+                bytes.len(),
+                (64 - value.leading_zeros()).max(1).div_ceil(7) as usize
+            );
+            let mut rest = &bytes[..];
+            assert_eq!(read_number(&mut rest), Ok(value));
+            assert!(rest.is_empty());
+        }
+        assert!(read_number(&mut &[0x80][..]).is_err());
+        assert!(read_number(&mut &[0xff; 10][..]).is_err());
+        // the tenth byte only has room for the highest bit
+        let mut too_large = [0xff; 10];
+        too_large[9] = 0x02;
+        assert_eq!(
+            read_number(&mut &too_large[..]),
+            Err("a number is too large".to_string())
+        );
+    }
+
+    #[test]
+    fn dot_graph() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let tree = Node::<3>::parse::<4>(rules, "0 0 1\n 1 0: 2 3 3\n  0 3: 3 2 1\n 0 0: 2 2 3\n")
+            .unwrap();
+        assert_eq!(
+            tree.dot(2),
+            "digraph strategy {
+    node [shape=box];
+    n0 [label=\"rot, rot, grün\\n2 codes\", peripheries=2];
+    n1 [label=\"gelb, gelb, blau\\n1 code\", peripheries=2];
+    n0 -> n1 [label=\"···\"];
+    n2 [label=\"gelb, blau, blau\\n0 codes\"];
+    n0 -> n2 [label=\"●··\"];
+}
+"
+        );
+        assert_eq!(tree.dot(1).lines().count(), 4);
+        assert_eq!(escape("say \"hi\" \\o/"), "say \\\"hi\\\" \\\\o/");
+    }
+
+    #[test]
+    fn parse_tree() {
+        let rules = Rules {
+            duplicates: true,
+            missing_colors: 0,
+        };
+        let tree = Node::<3>::parse::<4>(
+            rules,
+            "# book\n0 0 1\n 1 0: 2 3 3 # comment\n\n  0 3: 3 2 1\n 0 0: 2 2 3\n",
+        );
+        assert_eq!(
+            tree.map(|tree| tree.to_string()),
+            Ok("0 0 1\n 0 0: 2 2 3\n 1 0: 2 3 3\n  0 3: 3 2 1\n".to_string())
+        );
+        let error = |text| Node::<3>::parse::<4>(rules, text).map_err(|err| err.line);
+        assert_eq!(error(""), Err(0));
+        assert_eq!(error(" 1 0: 0 0 1"), Err(1));
+        assert_eq!(error("0 0 1\n1 1 1"), Err(2));
+        assert_eq!(error("0 0 1\n  1 0: 2 3 3"), Err(2));
+        assert_eq!(error("0 0 1\n 1 0: 2 3 3\n 1 0: 2 3 3"), Err(3));
+        assert_eq!(error("0 0 1\n 1 0: 2 3 7"), Err(2));
+        assert_eq!(error("0 0 1\n 4 0: 2 3 3"), Err(2));
+    }
+}
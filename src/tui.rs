@@ -0,0 +1,387 @@
+//! A full screen terminal interface for letting the solver guess a code the user keeps
+//! secret: the board with every guess and its feedback, the number of codes left and the
+//! best guesses, with the feedback for the current guess entered by the keyboard.
+
+use std::io::{IsTerminal, Write};
+
+use crate::{
+    palette::Pegs,
+    readline::{read_key, Key, TerminalMode},
+    store::{self, Breaker},
+    Entry, Evaluation, Guess, Rules, SimpleGuesser, Solver, SolverOptions, Suggestion,
+};
+
+/// The number of guesses in the suggestion panel.
+const SUGGESTIONS: usize = 5;
+/// Ranking every guess against more candidates than this would keep the user waiting.
+const MAX_RANKED_CANDIDATES: usize = 2000;
+const HELP: &str = "←/→ exact or color  ↑/↓ or digits count  enter submit  \
+                    tab other guess  backspace undo  n new game  q quit";
+
+/// Switches to the alternate screen with a hidden cursor and back when dropped, so that
+/// the terminal looks like before afterwards.
+struct Screen;
+
+impl Screen {
+    fn enter() -> Self {
+        print!("\x1b[?1049h\x1b[?25l");
+        Screen
+    }
+
+    fn draw(&self, lines: &[String]) {
+        let mut stdout = std::io::stdout().lock();
+        let mut screen = "\x1b[H".to_string();
+        for line in lines {
+            screen.push_str(line);
+            screen.push_str("\x1b[K\r\n");
+        }
+        screen.push_str("\x1b[J");
+        let _ = stdout.write_all(screen.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Count {
+    Exact,
+    CorrectColor,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Continue,
+    Quit,
+}
+
+/// The state of a game on the screen.
+struct Board<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize> {
+    guesser: SimpleGuesser<FIELDS, COLORS, PARTITIONS>,
+    /// How the solver is set up again for a new game.
+    options: SolverOptions,
+    history: Vec<Entry<FIELDS>>,
+    /// The guess to enter the feedback for, `None` once the code is found.
+    guess: Option<Guess<FIELDS>>,
+    /// The guess of the solver, played unless another one is picked with tab.
+    best: Guess<FIELDS>,
+    suggestions: Vec<Suggestion<FIELDS>>,
+    /// The suggestion played instead of the guess of the solver.
+    picked: Option<usize>,
+    feedback: Evaluation<FIELDS>,
+    /// The count changed by the arrow and digit keys.
+    selected: Count,
+    message: String,
+}
+
+impl<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>
+    Board<FIELDS, COLORS, PARTITIONS>
+{
+    fn new(rules: Rules, options: SolverOptions) -> Self {
+        let mut board = Self {
+            guesser: SimpleGuesser::with_options(rules, &options),
+            options,
+            history: vec![],
+            guess: None,
+            best: Guess::new([0; FIELDS]),
+            suggestions: vec![],
+            picked: None,
+            feedback: Evaluation {
+                correct_color: 0,
+                exact: 0,
+            },
+            selected: Count::Exact,
+            message: String::new(),
+        };
+        board.next_turn();
+        board
+    }
+
+    /// Lets the solver pick the next guess for the history.
+    fn next_turn(&mut self) {
+        let report = self
+            .guesser
+            .guess(&self.history)
+            .expect("feedback no code fits isn't submitted");
+        self.best = report.guess;
+        self.guess = Some(report.guess);
+        self.suggestions = if self.guesser.candidates().len() <= MAX_RANKED_CANDIDATES {
+            self.guesser.suggestions(SUGGESTIONS)
+        } else {
+            vec![]
+        };
+        self.picked = None;
+        self.feedback = Evaluation {
+            correct_color: 0,
+            exact: 0,
+        };
+        self.selected = Count::Exact;
+    }
+
+    fn count(&mut self) -> &mut u32 {
+        match self.selected {
+            Count::Exact => &mut self.feedback.exact,
+            Count::CorrectColor => &mut self.feedback.correct_color,
+        }
+    }
+
+    /// Sets the selected count if the feedback stays valid.
+    fn set_count(&mut self, count: u32) {
+        let previous = std::mem::replace(self.count(), count);
+        if let Err(err) = self.feedback.validate() {
+            *self.count() = previous;
+            self.message = err.to_string();
+        }
+    }
+
+    fn handle(&mut self, key: Key) -> Action {
+        self.message.clear();
+        match key {
+            Key::Char('q') | Key::EndOfInput => return Action::Quit,
+            Key::Char('n') => *self = Self::new(self.guesser.rules, self.options.clone()),
+            Key::Backspace if !self.history.is_empty() => {
+                self.history.pop();
+                self.next_turn();
+            }
+            _ if self.guess.is_none() => {}
+            Key::Left => self.selected = Count::Exact,
+            Key::Right => self.selected = Count::CorrectColor,
+            Key::Up => {
+                let count = *self.count() + 1;
+                self.set_count(count);
+            }
+            Key::Down => {
+                let count = self.count().saturating_sub(1);
+                self.set_count(count);
+            }
+            Key::Char(digit) if digit.is_ascii_digit() => {
+                self.set_count(digit as u32 - '0' as u32);
+            }
+            Key::Tab => {
+                self.picked = match self.picked {
+                    None if !self.suggestions.is_empty() => Some(0),
+                    Some(index) if index + 1 < self.suggestions.len() => Some(index + 1),
+                    _ => None,
+                };
+                self.guess = Some(
+                    self.picked
+                        .map_or(self.best, |index| self.suggestions[index].guess),
+                );
+            }
+            Key::Enter => self.submit(),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Adds the feedback for the current guess to the history, unless no code fits it.
+    fn submit(&mut self) {
+        let Some(guess) = self.guess else {
+            return;
+        };
+        let evaluation = self.feedback;
+        if let Err(err) = self.guesser.rules.check_feedback(guess, evaluation) {
+            self.message = err.to_string();
+            return;
+        }
+        self.history.push(Entry { guess, evaluation });
+        if evaluation.exact == FIELDS as u32 {
+            let game = store::Game {
+                turns: self.history.len(),
+                solved: true,
+            };
+            store::record::<FIELDS, COLORS>(self.guesser.rules, "tui", Breaker::Solver, [game]);
+            self.guess = None;
+            self.message = format!(
+                "found the code in {} turns, n for a new game",
+                self.history.len()
+            );
+            return;
+        }
+        self.guesser.update_candidates(&self.history);
+        if self.guesser.candidates().is_empty() {
+            self.history.pop();
+            self.guesser.update_candidates(&self.history);
+            self.message = "no code matches this feedback, some of it must be wrong".to_string();
+            return;
+        }
+        self.next_turn();
+    }
+
+    /// The lines of the screen.
+    fn render(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("mastermind: {FIELDS} fields, {COLORS} colors"),
+            String::new(),
+        ];
+        for (turn, entry) in self.history.iter().enumerate() {
+            lines.push(format!(
+                "{:>3}  {}  {}",
+                turn + 1,
+                entry.evaluation,
+                Pegs(&entry.guess)
+            ));
+        }
+        if let Some(guess) = &self.guess {
+            let highlight = |count: Count, value: u32| {
+                if count == self.selected {
+                    format!("\x1b[7m {value} \x1b[0m")
+                } else {
+                    format!(" {value} ")
+                }
+            };
+            lines.push(format!(
+                "{:>3}  {}  {}",
+                self.history.len() + 1,
+                self.feedback,
+                Pegs(guess)
+            ));
+            lines.push(format!(
+                "     exact{}  color{}",
+                highlight(Count::Exact, self.feedback.exact),
+                highlight(Count::CorrectColor, self.feedback.correct_color)
+            ));
+        }
+        lines.push(String::new());
+        lines.push(match self.guesser.candidates().len() {
+            1 => "1 code remains consistent".to_string(),
+            n => format!("{n} codes remain consistent"),
+        });
+        lines.push(String::new());
+        if self.suggestions.is_empty() {
+            lines.push("too many codes remain to rank the guesses".to_string());
+        } else {
+            lines.push(format!("{:<34} bit   worst case", "best guesses"));
+        }
+        for (rank, suggestion) in self.suggestions.iter().enumerate() {
+            lines.push(format!(
+                "{} {}. {:<28} {:>7.3} {:>12}{}",
+                if self.picked == Some(rank) { '>' } else { ' ' },
+                rank + 1,
+                suggestion.guess.to_string(),
+                suggestion.score,
+                suggestion.worst_case,
+                if suggestion.possible {
+                    "  possible secret"
+                } else {
+                    ""
+                }
+            ));
+        }
+        lines.push(String::new());
+        lines.push(HELP.to_string());
+        lines.push(self.message.clone());
+        lines
+    }
+}
+
+/// Plays like `interactive::run` on the whole terminal until the user quits.
+pub fn run<const FIELDS: usize, const COLORS: u32, const PARTITIONS: usize>(
+    rules: Rules,
+    options: &SolverOptions,
+) -> Result<(), String> {
+    if !std::io::stdout().is_terminal() {
+        return Err("the terminal interface needs a terminal".to_string());
+    }
+    let Some(_raw_mode) = TerminalMode::without(libc::ICANON | libc::ECHO) else {
+        return Err("the terminal interface needs a terminal".to_string());
+    };
+    let mut board = Board::<FIELDS, COLORS, PARTITIONS>::new(rules, options.clone());
+    let screen = Screen::enter();
+    let mut stdin = std::io::stdin().lock();
+    loop {
+        screen.draw(&board.render());
+        let Some(key) = read_key(&mut stdin) else {
+            return Ok(());
+        };
+        if board.handle(key) == Action::Quit {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{max_gauss, Preset};
+
+    fn digit(count: u32) -> Key {
+        Key::Char(char::from_digit(count, 10).unwrap())
+    }
+
+    #[test]
+    fn play_with_the_keyboard() {
+        let rules = Preset::Classic.rules();
+        let mut board = Board::<4, 6, { max_gauss(4) }>::new(rules, SolverOptions::default());
+        assert!(board
+            .render()
+            .contains(&"1296 codes remain consistent".to_string()));
+        assert_eq!(board.suggestions.len(), SUGGESTIONS);
+
+        // feedback is kept valid
+        board.handle(digit(3));
+        board.handle(Key::Right);
+        board.handle(Key::Up);
+        board.handle(Key::Up);
+        assert_eq!((board.feedback.exact, board.feedback.correct_color), (3, 1));
+        assert!(board.message.starts_with("there are only 4 pegs"));
+        // but no code gives it
+        board.handle(Key::Enter);
+        assert!(board.history.is_empty());
+        assert!(board
+            .message
+            .starts_with("if all other fields match exactly"));
+
+        // another guess can be picked
+        board.handle(Key::Tab);
+        assert_eq!(board.guess, Some(board.suggestions[0].guess));
+        board.handle(Key::Tab);
+        assert_eq!(board.guess, Some(board.suggestions[1].guess));
+
+        let code = Guess::new([3, 1, 1, 5]);
+        while let Some(guess) = board.guess {
+            let evaluation = rules.evaluate(code, guess);
+            for key in [
+                Key::Left,
+                digit(0),
+                Key::Right,
+                digit(evaluation.correct_color),
+                Key::Left,
+                digit(evaluation.exact),
+                Key::Enter,
+            ] {
+                assert_eq!(board.handle(key), Action::Continue);
+            }
+            assert_eq!(board.message.is_empty(), guess != code);
+            assert!(board.history.len() <= 6);
+        }
+        assert_eq!(board.history.last().unwrap().guess, code);
+        assert_eq!(
+            board.render().last().unwrap(),
+            &format!(
+                "found the code in {} turns, n for a new game",
+                board.history.len()
+            )
+        );
+
+        // taking back the winning turn
+        let turns = board.history.len();
+        board.handle(Key::Backspace);
+        assert_eq!(board.history.len(), turns - 1);
+        assert_eq!(board.guesser.candidates(), [code]);
+        // no other code fits
+        board.handle(digit(0));
+        board.handle(Key::Enter);
+        assert_eq!(board.history.len(), turns - 1);
+        assert!(board.message.starts_with("no code matches"));
+
+        board.handle(Key::Char('n'));
+        assert!(board.history.is_empty());
+        assert_eq!(board.handle(Key::Char('q')), Action::Quit);
+    }
+}
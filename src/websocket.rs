@@ -0,0 +1,195 @@
+//! Just enough of the WebSocket protocol (RFC 6455) for the server to push events: the
+//! handshake, and unfragmented frames of at most `MAX_PAYLOAD` bytes.
+
+use std::io::{self, Read, Write};
+
+/// Appended to the key of the client to prove that the server speaks WebSocket.
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// Messages from clients are short commands, longer ones are turned down.
+const MAX_PAYLOAD: u64 = 1 << 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    Text = 1,
+    Close = 8,
+    Ping = 9,
+    Pong = 10,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub opcode: Opcode,
+    pub payload: Vec<u8>,
+}
+
+/// The `Sec-WebSocket-Accept` header answering the `Sec-WebSocket-Key` of a client.
+pub fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{GUID}").as_bytes()))
+}
+
+/// Writes an unmasked frame, as servers send them.
+pub fn write_frame(writer: &mut impl Write, opcode: Opcode, payload: &[u8]) -> io::Result<()> {
+    let mut header = vec![0x80 | opcode as u8];
+    match payload.len() {
+        length @ 0..=125 => header.push(length as u8),
+        length @ 126..=0xffff => {
+            header.push(126);
+            header.extend((length as u16).to_be_bytes());
+        }
+        length => {
+            header.push(127);
+            header.extend((length as u64).to_be_bytes());
+        }
+    }
+    writer.write_all(&header)?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+/// Reads a frame of a client, which are masked. Frames with other opcodes than `Opcode`
+/// and fragmented messages are errors.
+pub fn read_frame(reader: &mut impl Read) -> io::Result<Frame> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut header = [0; 2];
+    reader.read_exact(&mut header)?;
+    if header[0] & 0x80 == 0 {
+        return Err(invalid("fragmented messages aren't supported"));
+    }
+    let opcode = match header[0] & 0x0f {
+        1 => Opcode::Text,
+        8 => Opcode::Close,
+        9 => Opcode::Ping,
+        10 => Opcode::Pong,
+        _ => return Err(invalid("only text messages are supported")),
+    };
+    let length = match header[1] & 0x7f {
+        126 => {
+            let mut length = [0; 2];
+            reader.read_exact(&mut length)?;
+            u16::from_be_bytes(length) as u64
+        }
+        127 => {
+            let mut length = [0; 8];
+            reader.read_exact(&mut length)?;
+            u64::from_be_bytes(length)
+        }
+        length => length as u64,
+    };
+    if length > MAX_PAYLOAD {
+        return Err(invalid("the message is too long"));
+    }
+    let mut mask = [0; 4];
+    if header[1] & 0x80 != 0 {
+        reader.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0; length as usize];
+    reader.read_exact(&mut payload)?;
+    for (index, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[index % 4];
+    }
+    Ok(Frame { opcode, payload })
+}
+
+fn sha1(message: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend((message.len() as u64 * 8).to_be_bytes());
+    for block in padded.chunks(64) {
+        let mut words = [0u32; 80];
+        for (word, bytes) in words.iter_mut().zip(block.chunks(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().expect("blocks are whole words"));
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a827999),
+                20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            (e, d, c, b, a) = (d, c, b.rotate_left(30), a, temp);
+        }
+        for (state, value) in state.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+    let mut digest = [0; 20];
+    for (bytes, word) in digest.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, byte)| {
+            bits | (*byte as u32) << (16 - 8 * index)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answer_the_handshake() {
+        assert_eq!(
+            sha1(b"abc").map(|byte| format!("{byte:02x}")).concat(),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(base64(b"ab"), "YWI=");
+        assert_eq!(base64(b"abcd"), "YWJjZA==");
+        // the example of RFC 6455
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn read_and_write_frames() {
+        let mut written = vec![];
+        write_frame(&mut written, Opcode::Text, b"Hello").unwrap();
+        assert_eq!(written, b"\x81\x05Hello");
+        written.clear();
+        write_frame(&mut written, Opcode::Text, &[b'x'; 300]).unwrap();
+        assert_eq!(written[..4], [0x81, 126, 1, 44]);
+        // the masked example of RFC 6455
+        let masked = [
+            0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+        ];
+        assert_eq!(
+            read_frame(&mut &masked[..]).unwrap(),
+            Frame {
+                opcode: Opcode::Text,
+                payload: b"Hello".to_vec()
+            }
+        );
+        assert!(read_frame(&mut &[0x01, 0x00][..]).is_err());
+        assert!(read_frame(&mut &[0x82, 0x00][..]).is_err());
+    }
+}
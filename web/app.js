@@ -0,0 +1,203 @@
+// The board of the page served by `mastermind_solver serve`, playing through the API of
+// src/server.rs. Breaking the code, the page keeps the secret and evaluates the guesses,
+// and tells the server the feedback so the solver can give hints. Making the code, the
+// solver guesses and the user answers.
+
+"use strict";
+
+// the colors of the built-in color names, more colors are spread over the hues
+const COLORS = [
+  "#e53935", "#43a047", "#fdd835", "#1e88e5", "#fb8c00",
+  "#f06292", "#fafafa", "#9e9e9e", "#212121", "#795548",
+];
+
+const $ = (id) => document.getElementById(id);
+
+let game = null;
+
+function color(index) {
+  return COLORS[index] ?? `hsl(${(index * 137) % 360}, 70%, 55%)`;
+}
+
+async function request(method, path, body) {
+  const response = await fetch(path, { method, body });
+  const json = await response.json();
+  if (!response.ok) {
+    throw new Error(json.error);
+  }
+  return json;
+}
+
+function status(text) {
+  $("status").textContent = text;
+}
+
+function peg(index, onclick) {
+  const element = document.createElement(onclick ? "button" : "span");
+  element.className = index === null ? "peg empty" : "peg";
+  if (index !== null) {
+    element.style.background = color(index);
+    element.title = game.palette[index];
+  }
+  if (onclick) {
+    element.type = "button";
+    element.onclick = onclick;
+  }
+  return element;
+}
+
+function keys(exact, white) {
+  const element = document.createElement("span");
+  element.className = "keys";
+  for (let i = 0; i < game.fields; i++) {
+    const key = document.createElement("span");
+    key.className = i < exact ? "key exact" : i < exact + white ? "key white" : "key";
+    element.append(key);
+  }
+  return element;
+}
+
+function render() {
+  const board = $("board");
+  board.replaceChildren();
+  for (const turn of game.turns) {
+    const row = document.createElement("li");
+    row.className = "row";
+    row.append(...turn.guess.map((index) => peg(index)));
+    if (turn.exact !== undefined) {
+      row.append(keys(turn.exact, turn.white));
+    }
+    board.append(row);
+  }
+  const breaking = game.mode === "break" && !game.over;
+  if (breaking) {
+    const row = document.createElement("li");
+    row.className = "row";
+    row.append(...game.current.map((index, field) => peg(index, () => {
+      game.current[field] = null;
+      render();
+    })));
+    board.append(row);
+  }
+  $("picker").hidden = !breaking;
+  $("guess").disabled = game.current.includes(null);
+  const last = game.turns[game.turns.length - 1];
+  $("feedback").hidden = game.mode !== "make" || game.over || !last || last.exact !== undefined;
+}
+
+function evaluate(secret, guess) {
+  let exact = 0;
+  const left = new Map();
+  for (let field = 0; field < game.fields; field++) {
+    if (secret[field] === guess[field]) {
+      exact++;
+    }
+    left.set(secret[field], (left.get(secret[field]) ?? 0) + 1);
+  }
+  let common = 0;
+  for (const index of guess) {
+    if (left.get(index) > 0) {
+      left.set(index, left.get(index) - 1);
+      common++;
+    }
+  }
+  return { exact, white: common - exact };
+}
+
+function randomCode() {
+  const colors = [...Array(game.colors).keys()];
+  return Array.from({ length: game.fields }, () => {
+    const index = Math.floor(Math.random() * colors.length);
+    return game.duplicates ? colors[index] : colors.splice(index, 1)[0];
+  });
+}
+
+function line(turn) {
+  return `${turn.guess.join(" ")} | ${turn.exact} ${turn.white}`;
+}
+
+async function solverGuess() {
+  status("The solver is thinking…");
+  const report = await request("GET", `/games/${game.id}/guess`);
+  return report.guess.map((name) => game.palette.indexOf(name));
+}
+
+async function start() {
+  if (game) {
+    request("DELETE", `/games/${game.id}`).catch(() => {});
+  }
+  const created = await request("POST", "/games");
+  game = {
+    ...created,
+    mode: $("mode").value,
+    turns: [],
+    current: Array(created.fields).fill(null),
+    over: false,
+  };
+  $("palette").replaceChildren(...game.palette.map((_, index) => peg(index, () => {
+    const field = game.current.indexOf(null);
+    if (field >= 0) {
+      game.current[field] = index;
+      render();
+    }
+  })));
+  if (game.mode === "break") {
+    game.secret = randomCode();
+    status(`Find the code of ${game.fields} pegs in ${game.colors} colors.`);
+  } else {
+    status(`Think of a code of ${game.fields} pegs in ${game.colors} colors.`);
+    render();
+    game.turns.push({ guess: await solverGuess() });
+    status("How good is the guess?");
+  }
+  render();
+}
+
+async function guess() {
+  const turn = { guess: game.current, ...evaluate(game.secret, game.current) };
+  game.turns.push(turn);
+  game.current = Array(game.fields).fill(null);
+  if (turn.exact === game.fields) {
+    game.over = true;
+    status(`Solved in ${game.turns.length} guesses!`);
+  } else {
+    status("");
+    await request("POST", `/games/${game.id}/feedback`, line(turn));
+  }
+  render();
+}
+
+async function hint() {
+  game.current = await solverGuess();
+  status("The solver would guess this.");
+  render();
+}
+
+async function answer(event) {
+  event.preventDefault();
+  const turn = game.turns[game.turns.length - 1];
+  const exact = Number($("exact").value);
+  const white = Number($("white").value);
+  await request("POST", `/games/${game.id}/feedback`, line({ ...turn, exact, white }));
+  Object.assign(turn, { exact, white });
+  if (exact === game.fields) {
+    game.over = true;
+    status(`The solver found your code in ${game.turns.length} guesses.`);
+  } else {
+    render();
+    game.turns.push({ guess: await solverGuess() });
+    status("How good is the guess?");
+  }
+  render();
+}
+
+function report(action) {
+  return (...args) => action(...args).catch((err) => status(err.message));
+}
+
+$("new").onclick = report(start);
+$("mode").onchange = report(start);
+$("guess").onclick = report(guess);
+$("hint").onclick = report(hint);
+$("feedback").onsubmit = report(answer);
+report(start)();